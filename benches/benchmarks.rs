@@ -1,6 +1,8 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::convert::TryFrom;
 
-use sharks::{Share, Sharks};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use sharks::{FieldShare, Share, Sharks, GF2_128, GF2_64};
 
 fn dealer(c: &mut Criterion) {
     let sharks = Sharks(255);
@@ -21,13 +23,62 @@ fn recover(c: &mut Criterion) {
     });
 }
 
+fn bulk(c: &mut Criterion) {
+    let sharks = Sharks(10);
+    let secret = vec![42u8; 1 << 20];
+    let shares: Vec<Share> = sharks.dealer(&secret).take(10).collect();
+
+    c.bench_function("split_megabyte_secret", |b| {
+        b.iter(|| sharks.dealer(black_box(&secret)).take(10).count())
+    });
+    c.bench_function("recover_megabyte_secret", |b| {
+        b.iter(|| sharks.recover(black_box(shares.as_slice())))
+    });
+
+    let wide: Vec<FieldShare<GF2_64>> = sharks.dealer_in(&secret).take(10).collect();
+    c.bench_function("split_megabyte_secret_wide", |b| {
+        b.iter(|| {
+            sharks
+                .dealer_in::<GF2_64>(black_box(&secret))
+                .take(10)
+                .count()
+        })
+    });
+    c.bench_function("recover_megabyte_secret_wide", |b| {
+        b.iter(|| sharks.recover_in(black_box(wide.as_slice())))
+    });
+
+    let clmul: Vec<FieldShare<GF2_128>> = sharks.dealer_in(&secret).take(10).collect();
+    c.bench_function("split_megabyte_secret_clmul", |b| {
+        b.iter(|| {
+            sharks
+                .dealer_in::<GF2_128>(black_box(&secret))
+                .take(10)
+                .count()
+        })
+    });
+    c.bench_function("recover_megabyte_secret_clmul", |b| {
+        b.iter(|| sharks.recover_in(black_box(clmul.as_slice())))
+    });
+}
+
+fn batch(c: &mut Criterion) {
+    let sharks = Sharks(3);
+    let secrets: Vec<[u8; 32]> = (0..10000).map(|i| [i as u8; 32]).collect();
+    let refs: Vec<&[u8]> = secrets.iter().map(|s| &s[..]).collect();
+
+    c.bench_function("split_batch_secrets", |b| {
+        b.iter(|| sharks.dealer_batch(black_box(&refs)).take(5).count())
+    });
+}
+
 fn share(c: &mut Criterion) {
     let bytes_vec = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
     let bytes = bytes_vec.as_slice();
-    let share = Share::from(bytes);
+    let share = Share::try_from(bytes).unwrap();
 
     c.bench_function("share_from_bytes", |b| {
-        b.iter(|| Share::from(black_box(bytes)))
+        b.iter(|| Share::try_from(black_box(bytes)))
     });
 
     c.bench_function("share_to_bytes", |b| {
@@ -35,5 +86,41 @@ fn share(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, dealer, recover, share);
+fn unchecked(c: &mut Criterion) {
+    // A million recoveries of small secrets, cycling through a thousand share sets
+    const RECOVERIES: usize = 1 << 20;
+    let sharks = Sharks(3);
+    let sets: Vec<Vec<Share>> = (0..1024)
+        .map(|i| sharks.dealer(&[i as u8; 16]).take(3).collect())
+        .collect();
+
+    let mut group = c.benchmark_group("recover_small_secrets");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(RECOVERIES as u64));
+    group.bench_function("checked", |b| {
+        b.iter(|| {
+            sets.iter()
+                .cycle()
+                .take(RECOVERIES)
+                .map(|s| {
+                    sharks
+                        .recover(black_box(s.as_slice()))
+                        .map_or(0, |s| s.len())
+                })
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("unchecked", |b| {
+        b.iter(|| {
+            sets.iter()
+                .cycle()
+                .take(RECOVERIES)
+                .map(|s| sharks.recover_unchecked(black_box(s.as_slice())).len())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, dealer, recover, bulk, batch, share, unchecked);
 criterion_main!(benches);
@@ -0,0 +1,73 @@
+#ifndef SHARKS_H
+#define SHARKS_H
+
+/* Generated with cbindgen from src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of the library functions, `SHARKS_STATUS_OK` on success.
+typedef enum SharksStatus {
+  SHARKS_STATUS_OK = 0,
+  // A required pointer is null.
+  SHARKS_STATUS_NULL_POINTER = 1,
+  // Fewer distinct shares than the threshold were provided.
+  SHARKS_STATUS_NOT_ENOUGH_SHARES = 2,
+  // Several shares have the same `x` coordinate and different values.
+  SHARKS_STATUS_DUPLICATE_SHARE = 3,
+  // Share bytes are empty, have a zero `x` coordinate or shares have different lengths.
+  SHARKS_STATUS_INVALID_SHARE = 4,
+  // The threshold embedded in the shares does not match, or exceeds the number of shares requested.
+  SHARKS_STATUS_INVALID_THRESHOLD = 5,
+  // Any other error.
+  SHARKS_STATUS_ERROR = 6,
+} SharksStatus;
+
+// A byte buffer allocated by the library, to be released with `sharks_buffer_free`.
+typedef struct SharksBuffer {
+  uint8_t *data;
+  size_t len;
+} SharksBuffer;
+
+// Returns the length in bytes of each share of a secret of `secret_len` bytes.
+size_t sharks_share_len(size_t secret_len);
+
+// Splits the `secret_len` bytes at `secret` into `count` shares, any `threshold` of them recovering it.
+// On success `out` holds the shares one after another, each `sharks_share_len(secret_len)` bytes long,
+// otherwise it is left empty. Any buffer previously held by `out` must be released first.
+//
+// # Safety
+//
+// `secret` must point to `secret_len` readable bytes, and `out` to a writable `SharksBuffer`.
+enum SharksStatus sharks_split(const uint8_t *secret,
+                               size_t secret_len,
+                               uint8_t threshold,
+                               uint8_t count,
+                               struct SharksBuffer *out);
+
+// Recovers the secret from `count` shares of `share_len` bytes each, stored one after another at `shares`.
+// On success `out` holds the secret, `share_len - 1` bytes long, otherwise it is left empty.
+//
+// # Safety
+//
+// `shares` must point to `count * share_len` readable bytes, and `out` to a writable `SharksBuffer`.
+enum SharksStatus sharks_recover(const uint8_t *shares,
+                                 size_t share_len,
+                                 size_t count,
+                                 uint8_t threshold,
+                                 struct SharksBuffer *out);
+
+// Wipes and releases a buffer returned by the library, leaving it empty. Does nothing if it is already empty.
+//
+// # Safety
+//
+// `buffer` must be null or point to a `SharksBuffer` obtained from the library and not modified since.
+void sharks_buffer_free(struct SharksBuffer *buffer);
+
+// Returns a static, null terminated description of the `status`.
+const char *sharks_status_message(enum SharksStatus status);
+
+#endif  /* SHARKS_H */
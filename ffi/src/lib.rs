@@ -0,0 +1,263 @@
+//! C bindings of the [sharks](https://crates.io/crates/sharks) crate, built as a shared and a static library.
+//! The `include/sharks.h` header is generated with `cbindgen --config cbindgen.toml --output include/sharks.h`.
+//!
+//! Shares are exchanged as contiguous byte buffers, each share of a secret of `n` bytes taking `n + 1` bytes.
+//! Buffers returned by the library must be released with `sharks_buffer_free`, which wipes them from memory.
+
+use std::convert::TryFrom;
+use std::ffi::c_char;
+use std::{ptr, slice};
+
+use sharks::zeroize::Zeroize;
+use sharks::{Share, Sharks, SharksError};
+
+/// Result of the library functions, `SHARKS_STATUS_OK` on success.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SharksStatus {
+    Ok = 0,
+    /// A required pointer is null.
+    NullPointer = 1,
+    /// Fewer distinct shares than the threshold were provided.
+    NotEnoughShares = 2,
+    /// Several shares have the same `x` coordinate and different values.
+    DuplicateShare = 3,
+    /// Share bytes are empty, have a zero `x` coordinate or shares have different lengths.
+    InvalidShare = 4,
+    /// The threshold embedded in the shares does not match, or exceeds the number of shares requested.
+    InvalidThreshold = 5,
+    /// Any other error.
+    Error = 6,
+}
+
+impl From<SharksError> for SharksStatus {
+    fn from(e: SharksError) -> Self {
+        match e {
+            SharksError::NotEnoughShares { .. } => SharksStatus::NotEnoughShares,
+            SharksError::DuplicateShareX => SharksStatus::DuplicateShare,
+            SharksError::EmptyShare
+            | SharksError::ZeroShareX
+            | SharksError::InvalidLength
+            | SharksError::InvalidElement
+            | SharksError::InconsistentLengths => SharksStatus::InvalidShare,
+            SharksError::InconsistentThresholds
+            | SharksError::ThresholdMismatch { .. }
+            | SharksError::InvalidThreshold
+            | SharksError::TooFewShares { .. } => SharksStatus::InvalidThreshold,
+            _ => SharksStatus::Error,
+        }
+    }
+}
+
+/// A byte buffer allocated by the library, to be released with `sharks_buffer_free`.
+#[repr(C)]
+pub struct SharksBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl SharksBuffer {
+    fn new(bytes: Vec<u8>) -> Self {
+        let mut bytes = bytes.into_boxed_slice();
+        let buffer = SharksBuffer {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+        };
+        std::mem::forget(bytes);
+        buffer
+    }
+}
+
+// Obtains a slice from a C pointer and length, which may be null if the length is zero
+unsafe fn input<'a>(data: *const u8, len: usize) -> Result<&'a [u8], SharksStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(SharksStatus::NullPointer)
+    } else {
+        Ok(slice::from_raw_parts(data, len))
+    }
+}
+
+// Writes the result to `out`, returning the status
+unsafe fn output(result: Result<Vec<u8>, SharksStatus>, out: *mut SharksBuffer) -> SharksStatus {
+    if out.is_null() {
+        return SharksStatus::NullPointer;
+    }
+    match result {
+        Ok(bytes) => {
+            *out = SharksBuffer::new(bytes);
+            SharksStatus::Ok
+        }
+        Err(status) => {
+            *out = SharksBuffer {
+                data: ptr::null_mut(),
+                len: 0,
+            };
+            status
+        }
+    }
+}
+
+/// Returns the length in bytes of each share of a secret of `secret_len` bytes.
+#[no_mangle]
+pub extern "C" fn sharks_share_len(secret_len: usize) -> usize {
+    secret_len + 1
+}
+
+/// Splits the `secret_len` bytes at `secret` into `count` shares, any `threshold` of them recovering it.
+/// On success `out` holds the shares one after another, each `sharks_share_len(secret_len)` bytes long,
+/// otherwise it is left empty. Any buffer previously held by `out` must be released first.
+///
+/// # Safety
+///
+/// `secret` must point to `secret_len` readable bytes, and `out` to a writable `SharksBuffer`.
+#[no_mangle]
+pub unsafe extern "C" fn sharks_split(
+    secret: *const u8,
+    secret_len: usize,
+    threshold: u8,
+    count: u8,
+    out: *mut SharksBuffer,
+) -> SharksStatus {
+    let result = input(secret, secret_len).and_then(|secret| {
+        if count < threshold {
+            return Err(SharksStatus::InvalidThreshold);
+        }
+        let mut bytes = Vec::with_capacity(count as usize * sharks_share_len(secret_len));
+        for share in Sharks(threshold).dealer(secret).take(count as usize) {
+            let mut share = Vec::from(&share);
+            bytes.extend_from_slice(&share);
+            share.zeroize();
+        }
+        Ok(bytes)
+    });
+    output(result, out)
+}
+
+/// Recovers the secret from `count` shares of `share_len` bytes each, stored one after another at `shares`.
+/// On success `out` holds the secret, `share_len - 1` bytes long, otherwise it is left empty.
+///
+/// # Safety
+///
+/// `shares` must point to `count * share_len` readable bytes, and `out` to a writable `SharksBuffer`.
+#[no_mangle]
+pub unsafe extern "C" fn sharks_recover(
+    shares: *const u8,
+    share_len: usize,
+    count: usize,
+    threshold: u8,
+    out: *mut SharksBuffer,
+) -> SharksStatus {
+    let len = match share_len.checked_mul(count) {
+        Some(len) => len,
+        None => return output(Err(SharksStatus::InvalidShare), out),
+    };
+    let result = input(shares, len).and_then(|bytes| {
+        if share_len == 0 {
+            return Err(SharksStatus::InvalidShare);
+        }
+        let shares = bytes
+            .chunks(share_len)
+            .map(Share::try_from)
+            .collect::<Result<Vec<Share>, SharksError>>()?;
+        Ok(Sharks(threshold).recover(&shares)?)
+    });
+    output(result, out)
+}
+
+/// Wipes and releases a buffer returned by the library, leaving it empty. Does nothing if it is already empty.
+///
+/// # Safety
+///
+/// `buffer` must be null or point to a `SharksBuffer` obtained from the library and not modified since.
+#[no_mangle]
+pub unsafe extern "C" fn sharks_buffer_free(buffer: *mut SharksBuffer) {
+    if buffer.is_null() || (*buffer).data.is_null() {
+        return;
+    }
+    let mut bytes = Box::from_raw(ptr::slice_from_raw_parts_mut((*buffer).data, (*buffer).len));
+    bytes.zeroize();
+    drop(bytes);
+    (*buffer).data = ptr::null_mut();
+    (*buffer).len = 0;
+}
+
+/// Returns a static, null terminated description of the `status`.
+#[no_mangle]
+pub extern "C" fn sharks_status_message(status: SharksStatus) -> *const c_char {
+    let message: &'static [u8] = match status {
+        SharksStatus::Ok => b"Success\0",
+        SharksStatus::NullPointer => b"A required pointer is null\0",
+        SharksStatus::NotEnoughShares => b"Not enough shares to recover original secret\0",
+        SharksStatus::DuplicateShare => b"Shares with the same x coordinate differ\0",
+        SharksStatus::InvalidShare => b"Share bytes are invalid\0",
+        SharksStatus::InvalidThreshold => b"Share threshold is invalid\0",
+        SharksStatus::Error => b"Shares cannot be used to recover the secret\0",
+    };
+    message.as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        sharks_buffer_free, sharks_recover, sharks_share_len, sharks_split, SharksBuffer,
+        SharksStatus,
+    };
+    use std::ptr;
+
+    fn empty() -> SharksBuffer {
+        SharksBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    #[test]
+    fn split_recover_works() {
+        let secret = [1u8, 2, 3, 4];
+        let mut shares = empty();
+        let mut recovered = empty();
+        unsafe {
+            let status = sharks_split(secret.as_ptr(), secret.len(), 3, 5, &mut shares);
+            assert_eq!(status, SharksStatus::Ok);
+            assert_eq!(shares.len, 5 * sharks_share_len(4));
+
+            let share_len = sharks_share_len(4);
+            let status = sharks_recover(shares.data, share_len, 3, 3, &mut recovered);
+            assert_eq!(status, SharksStatus::Ok);
+            assert_eq!(
+                std::slice::from_raw_parts(recovered.data, recovered.len),
+                secret
+            );
+            sharks_buffer_free(&mut recovered);
+
+            let status = sharks_recover(shares.data, share_len, 2, 3, &mut recovered);
+            assert_eq!(status, SharksStatus::NotEnoughShares);
+
+            assert!(recovered.data.is_null());
+            sharks_buffer_free(&mut shares);
+            assert!(shares.data.is_null());
+        }
+    }
+
+    #[test]
+    fn invalid_input_err() {
+        let mut out = empty();
+        unsafe {
+            assert_eq!(
+                sharks_split(ptr::null(), 1, 2, 3, &mut out),
+                SharksStatus::NullPointer
+            );
+            assert_eq!(
+                sharks_split([1].as_ptr(), 1, 3, 2, &mut out),
+                SharksStatus::InvalidThreshold
+            );
+            assert_eq!(
+                sharks_recover([0, 1, 2, 3].as_ptr(), 2, 2, 2, &mut out),
+                SharksStatus::InvalidShare
+            );
+            assert!(out.data.is_null());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Online recovery, shares being added one at a time as custodians provide them.
+// Each share is checked against the ones received so far when it is added, so that a wrong share is reported
+// to its custodian right away. Once the threshold is reached, further shares must lie on the same polynomials.
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+/// Accumulates shares until the threshold is reached and the secret can be recovered, see `Sharks::accumulator`.
+#[derive(Clone, Debug)]
+pub struct RecoveryAccumulator {
+    threshold: u8,
+    shares: Vec<Share>,
+}
+
+impl RecoveryAccumulator {
+    /// Adds a share, returning the number of shares still needed to recover the secret.
+    /// An `Err` is returned, and the share discarded, if a share was already added at its `x` coordinate,
+    /// it embeds another threshold or group identifier, its length differs from the ones already added or,
+    /// once the threshold is reached, it does not lie on the polynomials of the others.
+    pub fn add<S: AsShareRef>(&mut self, share: &S) -> Result<usize, SharksError> {
+        let share = share.as_share_ref();
+        if let Some(found) = share.threshold().filter(|t| *t != self.threshold) {
+            return Err(SharksError::ThresholdMismatch {
+                expected: self.threshold,
+                found,
+            });
+        } else if share.group().is_some_and(|g| {
+            self.shares
+                .iter()
+                .any(|s| s.group.is_some_and(|other| other != g))
+        }) {
+            return Err(SharksError::MixedGroups);
+        } else if self.shares.iter().any(|s| s.x == share.x) {
+            return Err(SharksError::DuplicateShareX);
+        } else if self
+            .shares
+            .first()
+            .is_some_and(|s| s.y.len() != share.y.len())
+        {
+            return Err(SharksError::InconsistentLengths);
+        } else if self.remaining() == 0 {
+            let (xs, ys) = self.points();
+            if math::interpolate_points(&xs, &ys, share.x) != GF256::as_elements(share.y) {
+                return Err(SharksError::InconsistentShares);
+            }
+        }
+
+        self.shares.push(Share {
+            x: share.x,
+            y: GF256::as_elements(share.y).to_vec(),
+            threshold: Some(self.threshold),
+            group: share.group(),
+        });
+        Ok(self.remaining())
+    }
+
+    /// Returns the number of shares added so far.
+    pub fn received(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Returns the number of shares still needed to recover the secret.
+    pub fn remaining(&self) -> usize {
+        (self.threshold as usize)
+            .max(1)
+            .saturating_sub(self.shares.len())
+    }
+
+    /// Returns the `x` coordinates of the shares added so far.
+    pub fn xs(&self) -> Vec<GF256> {
+        self.shares.iter().map(|s| s.x).collect()
+    }
+
+    /// Recovers the secret once enough shares were added, otherwise an `Err` is returned.
+    pub fn secret(&self) -> Result<Vec<u8>, SharksError> {
+        if self.remaining() > 0 {
+            return Err(SharksError::NotEnoughShares {
+                needed: (self.threshold as usize).max(1),
+                got: self.shares.len(),
+                provided: self.shares.len(),
+            });
+        }
+        let (xs, ys) = self.points();
+        math::decode_secret(&math::interpolate_points(&xs, &ys, GF256::ZERO))
+    }
+
+    // Returns the points of the first threshold shares, which define the polynomials
+    fn points(&self) -> (Vec<GF256>, Vec<&[GF256]>) {
+        let used = &self.shares[..(self.threshold as usize).max(1)];
+        (
+            used.iter().map(|s| s.x).collect(),
+            used.iter().map(|s| s.y.as_slice()).collect(),
+        )
+    }
+}
+
+impl Sharks {
+    /// Returns an empty `RecoveryAccumulator`, to which shares are added one at a time
+    /// until the secret can be recovered.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// let sharks = Sharks(3);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(4).collect();
+    /// let mut accumulator = sharks.accumulator();
+    /// assert_eq!(accumulator.add(&shares[3]), Ok(2));
+    /// assert_eq!(accumulator.add(&shares[3]), Err(SharksError::DuplicateShareX));
+    /// assert_eq!(accumulator.add(&shares[0]), Ok(1));
+    /// assert_eq!(accumulator.add(&shares[1]), Ok(0));
+    /// assert_eq!(accumulator.secret().unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn accumulator(&self) -> RecoveryAccumulator {
+        RecoveryAccumulator {
+            threshold: self.0,
+            shares: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn accumulator_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let mut accumulator = sharks.accumulator();
+        assert_eq!(accumulator.remaining(), 3);
+        assert!(accumulator.secret().is_err());
+
+        // Shares parsed from bytes are accepted as well
+        let bytes = Vec::from(&shares[4]);
+        let parsed = Share::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(accumulator.add(&parsed), Ok(2));
+        assert_eq!(accumulator.add(&shares[2]), Ok(1));
+        assert_eq!(accumulator.add(&shares[0]), Ok(0));
+        assert_eq!(accumulator.secret(), Ok(vec![1, 2, 3, 4]));
+
+        // Extra shares are checked against the others
+        assert_eq!(accumulator.add(&shares[1]), Ok(0));
+        assert_eq!(accumulator.received(), 4);
+        assert_eq!(
+            accumulator.xs(),
+            vec![GF256(5), GF256(3), GF256(1), GF256(2)]
+        );
+        assert_eq!(accumulator.secret(), Ok(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn accumulator_err() {
+        let sharks = Sharks(2);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(4).collect();
+        let mut accumulator = sharks.accumulator();
+        accumulator.add(&shares[0]).unwrap();
+
+        let other: Vec<Share> = Sharks(3).dealer(&[1, 2, 3]).take(2).collect();
+        assert_eq!(
+            accumulator.add(&other[1]),
+            Err(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+        let short: Vec<Share> = sharks.dealer(&[1, 2]).take(2).collect();
+        assert_eq!(
+            accumulator.add(&short[1]),
+            Err(SharksError::InconsistentLengths)
+        );
+
+        accumulator.add(&shares[1]).unwrap();
+        let mut corrupted = shares[2].clone();
+        corrupted.y[0] = corrupted.y[0] + GF256(1);
+        assert_eq!(
+            accumulator.add(&corrupted),
+            Err(SharksError::InconsistentShares)
+        );
+        assert_eq!(accumulator.received(), 2);
+        assert_eq!(accumulator.secret(), Ok(vec![1, 2, 3]));
+
+        // Shares of different splits are rejected below the threshold already
+        let a: Vec<Share> = Sharks(3).dealer_grouped(&[1, 2]).take(3).collect();
+        let b: Vec<Share> = Sharks(3).dealer_grouped(&[1, 2]).take(3).collect();
+        let mut accumulator = Sharks(3).accumulator();
+        assert_eq!(accumulator.add(&a[0]), Ok(2));
+        assert_eq!(accumulator.add(&b[1]), Err(SharksError::MixedGroups));
+        assert_eq!(accumulator.add(&a[1]), Ok(1));
+        let mut plain = a[2].clone();
+        plain.group = None;
+        assert_eq!(accumulator.add(&plain), Ok(0));
+        assert_eq!(accumulator.secret(), Ok(vec![1, 2]));
+    }
+}
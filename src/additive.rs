@@ -0,0 +1,286 @@
+// Conversion between Shamir shares and additive shares, so that shares can feed multi-party computation
+// frameworks working on additive sharings, and be converted back afterwards.
+// A quorum of Shamir shares is turned into additive shares of the same secret by weighting each of them by its
+// Lagrange coefficient at zero for the quorum, as the secret is the sum of the weighted `y` values.
+// Back, each additive share is split with Shamir among the participants, who add up the shares they received,
+// the sum of polynomials being a polynomial of the same degree going through the sum of the additive shares.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::Field;
+use super::math;
+use super::share::FieldShare;
+use super::Sharks;
+
+/// Additive share of a secret over the field `F`: the chunks of the secret are the sums of the `value`s
+/// of the shares of all the parties, with no threshold, every party being needed.
+/// The values are wiped from memory once dropped.
+#[derive(Clone)]
+pub struct AdditiveShare<F: Field> {
+    /// Identifier of the party holding the share, the `x` coordinate of the Shamir share it was converted from.
+    pub party: F,
+    /// The share of each secret chunk.
+    pub value: Vec<F>,
+}
+
+impl<F: Field> Zeroize for AdditiveShare<F> {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+/// Prints the share party and length, the values being redacted so that shares do not leak into logs.
+impl<F: Field> fmt::Debug for AdditiveShare<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AdditiveShare")
+            .field("party", &self.party)
+            .field("value", &format_args!("<{} redacted>", self.value.len()))
+            .finish()
+    }
+}
+
+impl<F: Field> Drop for AdditiveShare<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Sharks {
+    /// Converts the Shamir `share` into an additive share among the `participants`, given by their `x` coordinates,
+    /// which must include the share one. The share `y` values are multiplied by its Lagrange coefficient for them,
+    /// so that the secret is the sum of the additive shares of all the participants.
+    /// An `Err` is returned if there are fewer participants than the threshold, some of them are zero or repeated,
+    /// or the share is not one of them.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, AdditiveShare, FieldShare, GF256 };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+    /// let participants = [shares[0].x, shares[2].x];
+    /// let additive: Vec<AdditiveShare<GF256>> = [&shares[0], &shares[2]]
+    ///     .iter()
+    ///     .map(|s| sharks.to_additive(s, &participants).unwrap())
+    ///     .collect();
+    /// assert_eq!(Sharks::recover_additive(&additive).unwrap(), vec![1, 2, 3]);
+    ///
+    /// // Back to Shamir shares, each party splitting its additive share among the participants
+    /// let dealt: Vec<Vec<FieldShare<GF256>>> = additive
+    ///     .iter()
+    ///     .map(|a| sharks.deal_additive(a, &participants).unwrap())
+    ///     .collect();
+    /// let shares: Vec<Share> = (0..2)
+    ///     .map(|i| sharks.combine_additive(dealt.iter().map(|d| &d[i])).unwrap())
+    ///     .collect();
+    /// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn to_additive<F: Field>(
+        &self,
+        share: &FieldShare<F>,
+        participants: &[F],
+    ) -> Result<AdditiveShare<F>, SharksError> {
+        let weights = self.lagrange_coefficients(participants)?;
+        let weight = participants
+            .iter()
+            .position(|x| *x == share.x)
+            .map(|i| weights[i])
+            .ok_or(SharksError::MixedShares)?;
+        Ok(AdditiveShare {
+            party: share.x,
+            value: share.y.iter().map(|y| *y * weight).collect(),
+        })
+    }
+
+    /// Recovers the secret from the additive shares of all the parties, summing them.
+    /// An `Err` is returned if there are no shares, some parties are repeated, the shares have different lengths
+    /// or the sum is not a correctly padded secret.
+    pub fn recover_additive<F: Field>(shares: &[AdditiveShare<F>]) -> Result<Vec<u8>, SharksError> {
+        let first = shares.first().ok_or(SharksError::NotEnoughShares {
+            needed: 1,
+            got: 0,
+            provided: 0,
+        })?;
+        let mut parties = HashSet::new();
+        if !shares.iter().all(|s| parties.insert(s.party)) {
+            return Err(SharksError::DuplicateShareX);
+        } else if shares.iter().any(|s| s.value.len() != first.value.len()) {
+            return Err(SharksError::InconsistentLengths);
+        }
+
+        let mut sum = vec![F::ZERO; first.value.len()];
+        for s in shares {
+            sum.iter_mut()
+                .zip(s.value.iter())
+                .for_each(|(a, v)| *a = *a + *v);
+        }
+        let secret = math::decode_secret(&sum);
+        sum.zeroize();
+        secret
+    }
+
+    /// Run by each party, splits its additive `share` into Shamir shares at the `participants` `x` coordinates,
+    /// in their order, to be sent to each of them and combined with `combine_additive`.
+    /// An `Err` is returned if there are fewer participants than the threshold, or some of them are zero or repeated.
+    pub fn deal_additive<F: Field>(
+        &self,
+        share: &AdditiveShare<F>,
+        participants: &[F],
+    ) -> Result<Vec<FieldShare<F>>, SharksError> {
+        self.lagrange_coefficients(participants)?;
+        let mut coeffs =
+            math::random_coefficients(share.value.clone(), self.0, &mut rand::thread_rng());
+        let shares = participants
+            .iter()
+            .map(|x| FieldShare {
+                x: *x,
+                y: math::evaluate_coefficients(&coeffs, *x),
+                threshold: Some(self.0),
+                group: None,
+            })
+            .collect();
+        coeffs.zeroize();
+        Ok(shares)
+    }
+
+    /// Run by each participant, adds up the Shamir shares dealt to it by every party with `deal_additive`
+    /// into its Shamir share of the secret.
+    /// An `Err` is returned if there are no shares, they are for different participants, have different lengths
+    /// or were dealt with another threshold.
+    pub fn combine_additive<'a, F, T>(&self, shares: T) -> Result<FieldShare<F>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        let shares: Vec<&FieldShare<F>> = shares.into_iter().collect();
+        let first = shares.first().ok_or(SharksError::NotEnoughShares {
+            needed: 1,
+            got: 0,
+            provided: 0,
+        })?;
+        if shares.iter().any(|s| s.x != first.x) {
+            return Err(SharksError::MixedShares);
+        } else if shares.iter().any(|s| s.y.len() != first.y.len()) {
+            return Err(SharksError::InconsistentLengths);
+        } else if let Some(found) = shares
+            .iter()
+            .find_map(|s| s.threshold.filter(|t| *t != self.0))
+        {
+            return Err(SharksError::ThresholdMismatch {
+                expected: self.0,
+                found,
+            });
+        }
+
+        let mut y = vec![F::ZERO; first.y.len()];
+        for s in shares.iter() {
+            y.iter_mut().zip(s.y.iter()).for_each(|(a, v)| *a = *a + *v);
+        }
+        Ok(FieldShare {
+            x: first.x,
+            y,
+            threshold: Some(self.0),
+            group: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdditiveShare;
+    use crate::{FieldShare, Sharks, SharksError, GF65536};
+
+    #[test]
+    fn additive_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3, 4, 5]).take(5).collect();
+        let quorum = [&shares[4], &shares[0], &shares[2]];
+        let participants: Vec<GF65536> = quorum.iter().map(|s| s.x).collect();
+        let additive: Vec<AdditiveShare<GF65536>> = quorum
+            .iter()
+            .map(|s| sharks.to_additive(s, &participants).unwrap())
+            .collect();
+        assert_eq!(
+            Sharks::recover_additive(&additive).unwrap(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            format!("{:?}", additive[0]),
+            format!(
+                "AdditiveShare {{ party: {:?}, value: <3 redacted> }}",
+                additive[0].party
+            )
+        );
+
+        // Back to Shamir among a larger set of participants
+        let targets: Vec<GF65536> = (10..15).map(GF65536).collect();
+        let dealt: Vec<Vec<FieldShare<GF65536>>> = additive
+            .iter()
+            .map(|a| sharks.deal_additive(a, &targets).unwrap())
+            .collect();
+        let converted: Vec<FieldShare<GF65536>> = (0..targets.len())
+            .map(|i| {
+                sharks
+                    .combine_additive(dealt.iter().map(|d| &d[i]))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(converted[3].x, GF65536(13));
+        assert_eq!(
+            sharks.recover_in(&converted[1..4]).unwrap(),
+            vec![1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn additive_err() {
+        let sharks = Sharks(2);
+        let shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1]).take(3).collect();
+        assert_eq!(
+            sharks
+                .to_additive(&shares[0], &[shares[1].x, shares[2].x])
+                .err(),
+            Some(SharksError::MixedShares)
+        );
+        assert_eq!(
+            sharks.to_additive(&shares[0], &[shares[0].x]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+        assert_eq!(
+            sharks
+                .to_additive(&shares[0], &[shares[0].x, shares[0].x])
+                .err(),
+            Some(SharksError::DuplicateShareX)
+        );
+
+        let additive = sharks
+            .to_additive(&shares[0], &[shares[0].x, shares[1].x])
+            .unwrap();
+        assert_eq!(
+            Sharks::recover_additive(&[additive.clone(), additive.clone()]).err(),
+            Some(SharksError::DuplicateShareX)
+        );
+        let dealt = sharks
+            .deal_additive(&additive, &[GF65536(1), GF65536(2)])
+            .unwrap();
+        assert_eq!(
+            sharks.combine_additive(&dealt).err(),
+            Some(SharksError::MixedShares)
+        );
+        assert_eq!(
+            Sharks(3).combine_additive(&dealt[..1]).err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,364 @@
+// Fixed size shares which need no allocator, allowing to use the crate without `std`.
+// Instead of storing the polynomials, the dealer derives their coefficients from a random seed
+// every time a share is evaluated, so its memory usage only depends on the secret size.
+
+use core::fmt;
+
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::Sharks;
+
+/// A share of a secret of `N` bytes over `GF256`, backed by a fixed size array so it needs no allocator.
+/// See `Sharks::dealer_array`.
+#[derive(Clone)]
+pub struct ArrayShare<const N: usize> {
+    pub x: GF256,
+    pub y: [GF256; N],
+}
+
+/// Compares the share `x` and `y` values in constant time, so that checking shares does not leak them through timing.
+impl<const N: usize> ConstantTimeEq for ArrayShare<N> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.y
+            .iter()
+            .zip(other.y.iter())
+            .fold(self.x.0.ct_eq(&other.x.0), |eq, (a, b)| {
+                eq & a.0.ct_eq(&b.0)
+            })
+    }
+}
+
+/// Constant time, see the `ConstantTimeEq` implementation.
+impl<const N: usize> PartialEq for ArrayShare<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const N: usize> Eq for ArrayShare<N> {}
+
+/// Prints the share `x` coordinate, the `y` values being redacted so that shares do not leak into logs.
+impl<const N: usize> fmt::Debug for ArrayShare<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayShare")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", N))
+            .finish()
+    }
+}
+
+/// Wipes the share `x` and `y` values from memory.
+/// This is done automatically when the share is dropped.
+impl<const N: usize> Zeroize for ArrayShare<N> {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<const N: usize> Drop for ArrayShare<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+// Checks at compile time that `M` byte arrays hold shares of `N` byte secrets, their `x` coordinate included
+struct ByteLength<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> ByteLength<N, M> {
+    const CHECK: () = assert!(
+        M == N + 1,
+        "share byte arrays must be one byte longer than the secret"
+    );
+}
+
+impl<const N: usize> ArrayShare<N> {
+    /// Serializes the share as an `x || y` byte array, without any allocation.
+    /// The array length `M` must be `N + 1`, which is checked at compile time.
+    ///
+    /// ```compile_fail
+    /// # use sharks::{ Sharks, ArrayShare };
+    /// let share = Sharks(1).dealer_array(&mut rand::rngs::OsRng, &[1, 2]).next().unwrap();
+    /// let bytes: [u8; 2] = share.to_bytes();
+    /// ```
+    pub fn to_bytes<const M: usize>(&self) -> [u8; M] {
+        #[allow(clippy::let_unit_value)]
+        let () = ByteLength::<N, M>::CHECK;
+        let mut bytes = [0u8; M];
+        bytes[0] = self.x.0;
+        for (b, y) in bytes[1..].iter_mut().zip(self.y.iter()) {
+            *b = y.0;
+        }
+        bytes
+    }
+
+    /// Parses a share from an `x || y` byte array of length `M = N + 1`, checked at compile time.
+    /// An `Err` is returned if `x` is zero.
+    pub fn from_bytes<const M: usize>(bytes: &[u8; M]) -> Result<ArrayShare<N>, SharksError> {
+        #[allow(clippy::let_unit_value)]
+        let () = ByteLength::<N, M>::CHECK;
+        if bytes[0] == 0 {
+            return Err(SharksError::ZeroShareX);
+        }
+        let mut y = [GF256::ZERO; N];
+        for (y, b) in y.iter_mut().zip(bytes[1..].iter()) {
+            *y = GF256(*b);
+        }
+        Ok(ArrayShare {
+            x: GF256(bytes[0]),
+            y,
+        })
+    }
+}
+
+/// Iterator along the fixed size shares of a secret, returned by `Sharks::dealer_array`.
+/// The secret and coefficients seed are wiped from memory once dropped.
+pub struct ArrayDealer<const N: usize> {
+    secret: [GF256; N],
+    seed: [u8; 32],
+    threshold: u8,
+    index: usize,
+}
+
+impl<const N: usize> Iterator for ArrayDealer<N> {
+    type Item = ArrayShare<N>;
+
+    fn next(&mut self) -> Option<ArrayShare<N>> {
+        let x = GF256::from_index(self.index)?;
+        self.index += 1;
+
+        // Coefficients are drawn in the same order for every share, degrees going from higher to lower
+        let mut rng = ChaCha20Rng::from_seed(self.seed);
+        let mut y = [GF256::ZERO; N];
+        for (y, s) in y.iter_mut().zip(self.secret.iter()) {
+            let acc =
+                (1..self.threshold).fold(GF256::ZERO, |acc, _| acc * x + GF256::random(&mut rng));
+            *y = acc * x + *s;
+        }
+        Some(ArrayShare { x, y })
+    }
+}
+
+impl<const N: usize> Drop for ArrayDealer<N> {
+    fn drop(&mut self) {
+        self.secret.iter_mut().for_each(Zeroize::zeroize);
+        self.seed.zeroize();
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte array and a random number generator, returns an `Iterator` along new
+    /// fixed size shares, without any allocation. The polynomial coefficients are derived from
+    /// a seed drawn from `rng`, using the ChaCha20 stream cipher. The maximum number of shares
+    /// that can be generated is 255.
+    ///
+    /// Example:
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// # use sharks::{ Sharks, ArrayShare };
+    /// # let sharks = Sharks(3);
+    /// let mut rng = rand::rngs::OsRng;
+    /// let mut dealer = sharks.dealer_array(&mut rng, &[1, 2, 3, 4]);
+    /// let shares: [ArrayShare<4>; 3] = [dealer.next().unwrap(), dealer.next().unwrap(), dealer.next().unwrap()];
+    /// assert_eq!(sharks.recover_array(&shares).unwrap(), [1, 2, 3, 4]);
+    pub fn dealer_array<R: CryptoRng + RngCore, const N: usize>(
+        &self,
+        rng: &mut R,
+        secret: &[u8; N],
+    ) -> ArrayDealer<N> {
+        let mut dealer = ArrayDealer {
+            secret: [GF256::ZERO; N],
+            seed: [0; 32],
+            threshold: self.0,
+            index: 1,
+        };
+        rng.fill_bytes(&mut dealer.seed);
+        for (e, b) in dealer.secret.iter_mut().zip(secret.iter()) {
+            *e = GF256(*b);
+        }
+        dealer
+    }
+
+    /// Given a slice of fixed size shares, recovers the original secret without any allocation.
+    /// If the number of shares is less than the minimum threshold, some of them are repeated
+    /// or their `x` is zero, an `Err` is returned.
+    pub fn recover_array<const N: usize>(
+        &self,
+        shares: &[ArrayShare<N>],
+    ) -> Result<[u8; N], SharksError> {
+        self.recover_points(shares.len(), |i| shares[i].x, |i, j| shares[i].y[j])
+    }
+
+    /// Given a `secret` byte array and a random number generator, returns an `Iterator` along new shares
+    /// serialized as `x || y` byte arrays, without any allocation. The array length `M` must be `N + 1`,
+    /// which is checked at compile time. See `Sharks::dealer_array`.
+    ///
+    /// Example:
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// # use sharks::Sharks;
+    /// let sharks = Sharks(2);
+    /// let key = [7u8; 32];
+    /// let mut dealer = sharks.split_array(&mut rand::rngs::OsRng, &key);
+    /// let shares: [[u8; 33]; 2] = [dealer.next().unwrap(), dealer.next().unwrap()];
+    /// assert_eq!(sharks.recover_split_array(&shares).unwrap(), key);
+    /// ```
+    pub fn split_array<R: CryptoRng + RngCore, const N: usize, const M: usize>(
+        &self,
+        rng: &mut R,
+        secret: &[u8; N],
+    ) -> impl Iterator<Item = [u8; M]> {
+        self.dealer_array(rng, secret).map(|s| s.to_bytes())
+    }
+
+    /// Given a slice of shares serialized by `split_array`, recovers the original secret without any allocation.
+    /// An `Err` is returned in the same cases as `recover_array`.
+    pub fn recover_split_array<const N: usize, const M: usize>(
+        &self,
+        shares: &[[u8; M]],
+    ) -> Result<[u8; N], SharksError> {
+        #[allow(clippy::let_unit_value)]
+        let () = ByteLength::<N, M>::CHECK;
+        self.recover_points(
+            shares.len(),
+            |i| GF256(shares[i][0]),
+            |i, j| GF256(shares[i][j + 1]),
+        )
+    }
+
+    // Recovers a secret of `N` bytes from `count` points, given by their `x` coordinate and `y` values
+    fn recover_points<const N: usize>(
+        &self,
+        count: usize,
+        x: impl Fn(usize) -> GF256,
+        y: impl Fn(usize, usize) -> GF256,
+    ) -> Result<[u8; N], SharksError> {
+        if count < (self.0 as usize).max(1) {
+            return Err(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: count,
+                provided: count,
+            });
+        }
+        for i in 0..count {
+            if x(i) == GF256::ZERO {
+                return Err(SharksError::ZeroShareX);
+            } else if (0..i).any(|j| x(j) == x(i)) {
+                return Err(SharksError::DuplicateShareX);
+            }
+        }
+
+        let mut secret = [GF256::ZERO; N];
+        for i in 0..count {
+            let (num, den) = (0..count)
+                .filter(|j| x(*j) != x(i))
+                .fold((GF256::ONE, GF256::ONE), |(num, den), j| {
+                    (num * x(j), den * (x(j) - x(i)))
+                });
+            let weight = num * den.inverse();
+            for (j, s) in secret.iter_mut().enumerate() {
+                *s = *s + weight * y(i, j);
+            }
+        }
+
+        let mut bytes = [0; N];
+        for (b, s) in bytes.iter_mut().zip(secret.iter_mut()) {
+            *b = s.0;
+            s.zeroize();
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayShare;
+    #[cfg(feature = "std")]
+    use crate::Share;
+    use crate::{Sharks, SharksError, GF256};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn array_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<ArrayShare<4>> = sharks
+            .dealer_array(&mut ChaCha8Rng::from_seed([1; 32]), &[1, 2, 3, 4])
+            .collect();
+        assert_eq!(shares.len(), 255);
+        assert_eq!(shares[254].x, GF256(255));
+        assert_eq!(
+            sharks.recover_array(&shares[100..103]).unwrap(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(sharks.recover_array(&shares).unwrap(), [1, 2, 3, 4]);
+
+        // Array shares are regular shares
+        #[cfg(feature = "std")]
+        {
+            let shares: Vec<Share> = shares[..3]
+                .iter()
+                .map(|s| Share {
+                    x: s.x,
+                    y: s.y.to_vec(),
+                    threshold: None,
+                    group: None,
+                })
+                .collect();
+            assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn split_array_integration_works() {
+        let sharks = Sharks(3);
+        let secret = [42u8; 32];
+        let shares: Vec<[u8; 33]> = sharks
+            .split_array(&mut ChaCha8Rng::from_seed([2; 32]), &secret)
+            .collect();
+        assert_eq!(shares.len(), 255);
+        assert_eq!(shares[9][0], 10);
+        assert_eq!(sharks.recover_split_array(&shares[7..10]), Ok(secret));
+
+        let share = ArrayShare::<32>::from_bytes(&shares[3]).unwrap();
+        assert_eq!(share.x, GF256(4));
+        assert_eq!(share.to_bytes(), shares[3]);
+        assert_eq!(
+            ArrayShare::<32>::from_bytes(&[0; 33]),
+            Err(SharksError::ZeroShareX)
+        );
+        assert_eq!(
+            sharks.recover_split_array::<32, 33>(&[shares[0], shares[0], shares[1]]),
+            Err(SharksError::DuplicateShareX)
+        );
+    }
+
+    #[test]
+    fn recover_array_err() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<ArrayShare<2>> = sharks
+            .dealer_array(&mut ChaCha8Rng::from_seed([3; 32]), &[1, 2])
+            .take(3)
+            .collect();
+        assert_eq!(
+            sharks.recover_array(&shares[1..]),
+            Err(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        shares[1].x = shares[0].x;
+        assert_eq!(
+            sharks.recover_array(&shares),
+            Err(SharksError::DuplicateShareX)
+        );
+        shares[1].x = GF256(0);
+        assert_eq!(sharks.recover_array(&shares), Err(SharksError::ZeroShareX));
+    }
+}
@@ -0,0 +1,202 @@
+// Asynchronous counterpart of the streaming split and recovery, over the `futures` `AsyncRead` and `AsyncWrite` traits,
+// so that shares can be dealt to remote custodians over network connections without blocking a thread.
+// Streams have the same layout as the ones of `split_stream`: `x || y..`. Tokio types implement these traits
+// through the `compat` adapters of the `tokio-util` crate.
+
+use std::io;
+
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::Sharks;
+
+const BUFFER_SIZE: usize = 8192;
+
+fn invalid_input(error: SharksError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+// Reads into `buf` until it is full or the reader reaches its end, returning the number of bytes read
+async fn fill<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]).await {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read)
+}
+
+impl Sharks {
+    /// Same as `split_stream`, reading the secret from an asynchronous `reader` and writing the shares
+    /// to asynchronous `writers`, which are flushed once the whole secret was split.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # futures::executor::block_on(async {
+    /// let sharks = Sharks(2);
+    /// let mut writers = vec![Vec::new(); 3];
+    /// sharks.split_async(&[1, 2, 3, 4][..], &mut writers).await.unwrap();
+    ///
+    /// let mut secret = Vec::new();
+    /// sharks.recover_async(&mut [&writers[0][..], &writers[2][..]], &mut secret).await.unwrap();
+    /// assert_eq!(secret, vec![1, 2, 3, 4]);
+    /// # });
+    /// ```
+    pub async fn split_async<R, W>(&self, mut reader: R, writers: &mut [W]) -> io::Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        if writers.len() < self.0 as usize || writers.is_empty() {
+            return Err(invalid_input(SharksError::TooFewShares {
+                min: (self.0 as usize).max(1),
+            }));
+        } else if writers.len() > 255 {
+            return Err(invalid_input(SharksError::TooManyShares { max: 255 }));
+        }
+
+        for (x, writer) in (1..=255).zip(writers.iter_mut()) {
+            writer.write_all(&[x]).await?;
+        }
+
+        let mut buf = vec![0u8; BUFFER_SIZE];
+        let result = async {
+            loop {
+                let read = fill(&mut reader, &mut buf).await?;
+                if read == 0 {
+                    break;
+                }
+                let shares: Vec<Vec<u8>> = self
+                    .dealer(&buf[..read])
+                    .take(writers.len())
+                    .map(|s| GF256::as_bytes(&s.y).to_vec())
+                    .collect();
+                for (share, writer) in shares.iter().zip(writers.iter_mut()) {
+                    writer.write_all(share).await?;
+                }
+            }
+            for writer in writers.iter_mut() {
+                writer.flush().await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        buf.zeroize();
+        result
+    }
+
+    /// Same as `recover_stream`, reading the shares from asynchronous `readers` and writing the secret
+    /// to an asynchronous `writer`, which is flushed once the whole secret was recovered.
+    pub async fn recover_async<R, W>(&self, readers: &mut [R], mut writer: W) -> io::Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut x = Vec::with_capacity(readers.len());
+        for reader in readers.iter_mut() {
+            let mut byte = [0u8];
+            if fill(reader, &mut byte).await? == 0 {
+                return Err(invalid_input(SharksError::EmptyShare));
+            } else if byte[0] == 0 {
+                return Err(invalid_input(SharksError::ZeroShareX));
+            } else if x.contains(&GF256(byte[0])) {
+                return Err(invalid_input(SharksError::DuplicateShareX));
+            }
+            x.push(GF256(byte[0]));
+        }
+        if x.len() < self.0 as usize || x.is_empty() {
+            return Err(invalid_input(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: x.len(),
+                provided: x.len(),
+            }));
+        }
+
+        let mut bufs = vec![vec![0u8; BUFFER_SIZE]; readers.len()];
+        let result = async {
+            loop {
+                let mut read = Vec::with_capacity(readers.len());
+                for (reader, buf) in readers.iter_mut().zip(bufs.iter_mut()) {
+                    read.push(fill(reader, buf).await?);
+                }
+                if read.iter().any(|r| *r != read[0]) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        SharksError::InconsistentLengths,
+                    ));
+                } else if read[0] == 0 {
+                    break;
+                }
+
+                let ys: Vec<&[GF256]> = bufs
+                    .iter()
+                    .map(|b| GF256::as_elements(&b[..read[0]]))
+                    .collect();
+                let mut chunk = math::interpolate_points(&x, &ys, GF256::ZERO);
+                let written = writer.write_all(GF256::as_bytes(&chunk)).await;
+                chunk.zeroize();
+                written?;
+            }
+            writer.flush().await
+        }
+        .await;
+
+        bufs.zeroize();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BUFFER_SIZE;
+    use crate::{Share, Sharks};
+    use futures::executor::block_on;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn async_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..3 * BUFFER_SIZE + 10).map(|i| i as u8).collect();
+        let mut writers = vec![Vec::new(); 5];
+        block_on(sharks.split_async(secret.as_slice(), &mut writers)).unwrap();
+        assert!(writers.iter().all(|w| w.len() == secret.len() + 1));
+
+        let mut recovered = Vec::new();
+        let mut readers: Vec<&[u8]> = writers[2..].iter().map(|w| w.as_slice()).collect();
+        block_on(sharks.recover_async(&mut readers, &mut recovered)).unwrap();
+        assert_eq!(recovered, secret);
+
+        // Streams are regular shares
+        let shares: Vec<Share> = writers[..3]
+            .iter()
+            .map(|w| Share::try_from(w.as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn async_err() {
+        let sharks = Sharks(2);
+        let mut writers = vec![Vec::new(); 1];
+        assert!(block_on(sharks.split_async(&[1, 2][..], &mut writers)).is_err());
+        let mut writers = vec![Vec::new(); 3];
+        block_on(sharks.split_async(&[1, 2, 3][..], &mut writers)).unwrap();
+
+        let mut recovered = Vec::new();
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &writers[0]];
+        assert!(block_on(sharks.recover_async(&mut readers, &mut recovered)).is_err());
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &writers[1][..3]];
+        assert!(block_on(sharks.recover_async(&mut readers, &mut recovered)).is_err());
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &[]];
+        assert!(block_on(sharks.recover_async(&mut readers, &mut recovered)).is_err());
+    }
+}
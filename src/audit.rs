@@ -0,0 +1,153 @@
+// Hooks notified when shares are issued and secrets recovered, allowing to keep an audit trail of them.
+// Observers only receive public metadata: thresholds, `x` coordinates, errors and the time of the event,
+// never the secret nor the shares `y` values.
+
+use std::time::SystemTime;
+
+use super::error::SharksError;
+use super::field::GF256;
+use super::share::{AsShareRef, Share, ShareRef};
+use super::Sharks;
+
+/// Receives the events of `Sharks::dealer_observed` and `Sharks::recover_observed`.
+/// All methods do nothing by default, so that observers only implement the ones they are interested in.
+pub trait Observer {
+    /// Called when the share at the `x` coordinate of a secret split with `threshold` is issued.
+    fn share_issued(&self, _threshold: u8, _x: GF256, _at: SystemTime) {}
+
+    /// Called before a secret is recovered at `threshold` from the shares at the `xs` coordinates.
+    fn recovery_attempted(&self, _threshold: u8, _xs: &[GF256], _at: SystemTime) {}
+
+    /// Called once the secret was recovered from the shares at the `xs` coordinates.
+    fn recovery_succeeded(&self, _threshold: u8, _xs: &[GF256], _at: SystemTime) {}
+
+    /// Called when the secret could not be recovered from the shares at the `xs` coordinates.
+    fn recovery_failed(&self, _threshold: u8, _xs: &[GF256], _error: SharksError, _at: SystemTime) {
+    }
+}
+
+impl Sharks {
+    /// Same as `dealer`, notifying the `observer` of every share issued by the returned `Iterator`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, Observer, GF256 };
+    /// # use std::time::SystemTime;
+    /// struct Log;
+    ///
+    /// impl Observer for Log {
+    ///     fn share_issued(&self, threshold: u8, x: GF256, _at: SystemTime) {
+    ///         println!("issued share {} of a {}-of-n secret", x.0, threshold);
+    ///     }
+    ///
+    ///     fn recovery_succeeded(&self, _threshold: u8, xs: &[GF256], _at: SystemTime) {
+    ///         println!("secret recovered by {} participants", xs.len());
+    ///     }
+    /// }
+    ///
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer_observed(&[1, 2, 3], &Log).take(3).collect();
+    /// assert_eq!(sharks.recover_observed(&shares[1..], &Log).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn dealer_observed<'a, O: Observer + ?Sized>(
+        &self,
+        secret: &[u8],
+        observer: &'a O,
+    ) -> impl Iterator<Item = Share> + 'a {
+        self.dealer(secret).inspect(move |s| {
+            observer.share_issued(s.threshold().unwrap_or(0), s.x, SystemTime::now())
+        })
+    }
+
+    /// Same as `recover`, notifying the `observer` of the recovery attempt, and then of its success or failure.
+    pub fn recover_observed<'a, S, T, O>(
+        &self,
+        shares: T,
+        observer: &O,
+    ) -> Result<Vec<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+        O: Observer + ?Sized,
+    {
+        let shares: Vec<ShareRef> = shares.into_iter().map(AsShareRef::as_share_ref).collect();
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        observer.recovery_attempted(self.0, &xs, SystemTime::now());
+
+        let result = self.recover(&shares);
+        match &result {
+            Ok(_) => observer.recovery_succeeded(self.0, &xs, SystemTime::now()),
+            Err(e) => observer.recovery_failed(self.0, &xs, *e, SystemTime::now()),
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Observer;
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::cell::RefCell;
+    use std::time::SystemTime;
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<String>>);
+
+    impl Observer for Recorder {
+        fn share_issued(&self, threshold: u8, x: GF256, _at: SystemTime) {
+            self.0
+                .borrow_mut()
+                .push(format!("issued {} {}", threshold, x.0));
+        }
+
+        fn recovery_attempted(&self, threshold: u8, xs: &[GF256], _at: SystemTime) {
+            self.0
+                .borrow_mut()
+                .push(format!("attempted {} {}", threshold, xs.len()));
+        }
+
+        fn recovery_succeeded(&self, _threshold: u8, xs: &[GF256], _at: SystemTime) {
+            let xs: Vec<u8> = xs.iter().map(|x| x.0).collect();
+            self.0.borrow_mut().push(format!("succeeded {:?}", xs));
+        }
+
+        fn recovery_failed(
+            &self,
+            _threshold: u8,
+            _xs: &[GF256],
+            error: SharksError,
+            _at: SystemTime,
+        ) {
+            self.0.borrow_mut().push(format!("failed {}", error));
+        }
+    }
+
+    #[test]
+    fn observer_integration_works() {
+        let sharks = Sharks(2);
+        let recorder = Recorder::default();
+        let shares: Vec<Share> = sharks.dealer_observed(&[1, 2], &recorder).take(3).collect();
+        assert_eq!(
+            sharks.recover_observed(&shares[1..], &recorder),
+            Ok(vec![1, 2])
+        );
+        assert!(sharks.recover_observed(&shares[..1], &recorder).is_err());
+
+        let events = recorder.0.borrow();
+        assert_eq!(events[..3], ["issued 2 1", "issued 2 2", "issued 2 3"]);
+        assert_eq!(events[3..5], ["attempted 2 2", "succeeded [2, 3]"]);
+        assert_eq!(events[5], "attempted 2 1");
+        assert!(events[6].starts_with("failed "));
+    }
+
+    #[test]
+    fn observer_defaults_works() {
+        struct Silent;
+        impl Observer for Silent {}
+
+        let sharks = Sharks(1);
+        let shares: Vec<Share> = sharks.dealer_observed(&[1], &Silent).take(1).collect();
+        assert_eq!(sharks.recover_observed(&shares, &Silent), Ok(vec![1]));
+    }
+}
@@ -0,0 +1,187 @@
+// Beaver multiplication triples, the correlated randomness multiplying Shamir-shared values in MPC protocols.
+// A trusted dealer draws uniformly random `a` and `b` and shares them along with their product `c = a * b`,
+// with the same threshold and at the same `x` coordinates, so that each participant holds one share of each.
+// To multiply shared `x` and `y`, the participants open `d = x - a` and `e = y - b`, which leak nothing as
+// `a` and `b` are uniform, and compute their shares of `x * y = c + d * b + e * a + d * e` locally.
+// Each triple is used for a single multiplication: opening two masks of the same triple reveals their relation.
+
+use rand::{CryptoRng, RngCore};
+
+use super::error::SharksError;
+use super::field::Field;
+use super::math::random_element;
+use super::share::FieldShare;
+use super::Sharks;
+
+/// Share of a participant of multiplication triples: the `y` values of `a`, `b` and `c` are shares of
+/// as many triples, each one `c = a * b` for uniformly random `a` and `b`. The three shares have the same `x` coordinate.
+#[derive(Clone, Debug)]
+pub struct BeaverTriple<F: Field> {
+    /// Share of the random `a` factors.
+    pub a: FieldShare<F>,
+    /// Share of the random `b` factors.
+    pub b: FieldShare<F>,
+    /// Share of the products `c = a * b`.
+    pub c: FieldShare<F>,
+}
+
+impl Sharks {
+    /// Returns an `Iterator` along the shares of `count` new multiplication triples over the field `F`,
+    /// one for each participant as with `dealer_in`. Any threshold of them can multiply as many pairs of values
+    /// shared with the same threshold and `x` coordinates, see `BeaverTriple::mask`.
+    /// The dealer learns the triples, and must be trusted not to reveal them.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, BeaverTriple, FieldShare, GF65536 };
+    /// let sharks = Sharks(2);
+    /// let xs: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[6]).take(3).collect();
+    /// let ys: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[7]).take(3).collect();
+    /// let triples: Vec<BeaverTriple<GF65536>> = sharks.triple_dealer(1).take(3).collect();
+    ///
+    /// // Each participant masks its shares with its triple, and the masks are opened
+    /// let (ds, es): (Vec<_>, Vec<_>) = (0..3).map(|i| triples[i].mask(&xs[i], &ys[i]).unwrap()).unzip();
+    /// let d = sharks.open(&ds[..2]).unwrap();
+    /// let e = sharks.open(&es[1..]).unwrap();
+    ///
+    /// // Each participant then computes its share of the product
+    /// let products: Vec<FieldShare<GF65536>> = triples.iter().map(|t| t.multiply(&d, &e).unwrap()).collect();
+    /// let x = sharks.open(&xs[..2]).unwrap();
+    /// let y = sharks.open(&ys[..2]).unwrap();
+    /// assert_eq!(sharks.open(&products[1..]).unwrap(), vec![x[0] * y[0]]);
+    /// ```
+    pub fn triple_dealer<F: Field>(&self, count: usize) -> impl Iterator<Item = BeaverTriple<F>> {
+        self.triple_dealer_with_rng(&mut rand::thread_rng(), count)
+    }
+
+    /// Same as `triple_dealer`, generating the triples and the coefficients of their shares with the `rng`
+    /// random number generator instead of the thread local one.
+    pub fn triple_dealer_with_rng<F: Field, R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        count: usize,
+    ) -> impl Iterator<Item = BeaverTriple<F>> {
+        let a: Vec<F> = (0..count).map(|_| random_element(rng)).collect();
+        let b: Vec<F> = (0..count).map(|_| random_element(rng)).collect();
+        let c: Vec<F> = a.iter().zip(b.iter()).map(|(a, b)| *a * *b).collect();
+        let a = self.deal_with_rng(a, rng);
+        let b = self.deal_with_rng(b, rng);
+        let c = self.deal_with_rng(c, rng);
+        a.zip(b).zip(c).map(|((a, b), c)| BeaverTriple { a, b, c })
+    }
+
+    /// Given an iterable collection of shares over the field `F`, recovers the shared values as field elements,
+    /// without decoding them into a secret. Used to open the values masked by `BeaverTriple::mask`
+    /// and the products of `BeaverTriple::multiply`.
+    /// An `Err` is returned for the same reasons as `recover`.
+    pub fn open<'a, F, T>(&self, shares: T) -> Result<Vec<F>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+    {
+        self.interpolate(shares)
+    }
+}
+
+impl<F: Field> BeaverTriple<F> {
+    /// Masks the participant shares of the `x` and `y` values to be multiplied, returning the shares of
+    /// `d = x - a` and `e = y - b`, to be opened with `Sharks::open` and passed to `multiply`.
+    /// An `Err` is returned if the shares are at another `x` coordinate than the triple, or their lengths differ.
+    pub fn mask(
+        &self,
+        x: &FieldShare<F>,
+        y: &FieldShare<F>,
+    ) -> Result<(FieldShare<F>, FieldShare<F>), SharksError> {
+        if x.x != self.a.x || y.x != self.a.x {
+            return Err(SharksError::MixedShares);
+        } else if x.y.len() != self.a.y.len() || y.y.len() != self.a.y.len() {
+            return Err(SharksError::InconsistentLengths);
+        }
+        let sub = |s: &FieldShare<F>, t: &FieldShare<F>| FieldShare {
+            x: s.x,
+            y: s.y.iter().zip(t.y.iter()).map(|(s, t)| *s - *t).collect(),
+            threshold: s.threshold,
+            group: s.group,
+        };
+        Ok((sub(x, &self.a), sub(y, &self.b)))
+    }
+
+    /// Returns the participant share of the products `x * y`, given the opened masks `d` and `e`.
+    /// An `Err` is returned if the masks are not as long as the triple.
+    pub fn multiply(&self, d: &[F], e: &[F]) -> Result<FieldShare<F>, SharksError> {
+        if d.len() != self.c.y.len() || e.len() != self.c.y.len() {
+            return Err(SharksError::InconsistentLengths);
+        }
+        let y = (0..d.len())
+            .map(|i| self.c.y[i] + d[i] * self.b.y[i] + e[i] * self.a.y[i] + d[i] * e[i])
+            .collect();
+        Ok(FieldShare {
+            x: self.c.x,
+            y,
+            threshold: self.c.threshold,
+            group: self.c.group,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BeaverTriple;
+    use crate::{FieldShare, Sharks, SharksError, GF256};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn triples_work() {
+        let sharks = Sharks(3);
+        let triples: Vec<BeaverTriple<GF256>> = sharks
+            .triple_dealer_with_rng(&mut ChaCha8Rng::from_seed([3; 32]), 50)
+            .take(5)
+            .collect();
+        let a = sharks.open(triples.iter().map(|t| &t.a)).unwrap();
+        let b = sharks.open(triples[2..].iter().map(|t| &t.b)).unwrap();
+        let c = sharks.open(triples[..3].iter().map(|t| &t.c)).unwrap();
+        assert_eq!(c.len(), 50);
+        for i in 0..50 {
+            assert_eq!(c[i], a[i] * b[i]);
+        }
+
+        let xs: Vec<FieldShare<GF256>> = sharks.dealer_in(&[3; 50]).take(5).collect();
+        let ys: Vec<FieldShare<GF256>> = sharks.dealer_in(&[5; 50]).take(5).collect();
+        let masks: Vec<(FieldShare<GF256>, FieldShare<GF256>)> = (0..5)
+            .map(|i| triples[i].mask(&xs[i], &ys[i]).unwrap())
+            .collect();
+        let d = sharks.open(masks[..3].iter().map(|m| &m.0)).unwrap();
+        let e = sharks.open(masks[2..].iter().map(|m| &m.1)).unwrap();
+        let products: Vec<FieldShare<GF256>> = triples
+            .iter()
+            .map(|t| t.multiply(&d, &e).unwrap())
+            .collect();
+        assert_eq!(products[0].threshold, Some(3));
+        let x = sharks.open(&xs[1..4]).unwrap();
+        let y = sharks.open(&ys[1..4]).unwrap();
+        let z = sharks.open(&products[..3]).unwrap();
+        for i in 0..50 {
+            assert_eq!(z[i], x[i] * y[i]);
+        }
+    }
+
+    #[test]
+    fn triples_err() {
+        let sharks = Sharks(2);
+        let triples: Vec<BeaverTriple<GF256>> = sharks.triple_dealer(2).take(2).collect();
+        let xs: Vec<FieldShare<GF256>> = sharks.dealer_in(&[1]).take(2).collect();
+        assert_eq!(
+            triples[0].mask(&xs[1], &xs[0]).err(),
+            Some(SharksError::MixedShares)
+        );
+        assert_eq!(
+            triples[0].mask(&xs[0], &xs[0]).err(),
+            Some(SharksError::InconsistentLengths)
+        );
+        assert_eq!(
+            triples[0].multiply(&[GF256(1)], &[GF256(1)]).err(),
+            Some(SharksError::InconsistentLengths)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+// Command line interface splitting files into shares and recovering them, built with the `cli` feature.
+//
+//     sharks split --threshold K --shares N [--format hex|base64|mnemonic] FILE
+//     sharks recover [--threshold K] [--format hex|base64|mnemonic] [--output FILE] SHARE...
+//
+// Shares are printed one per line, `-` reads the secret from the standard input.
+// Without `--threshold`, recovery expects all the given shares to be needed.
+
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+use sharks::zeroize::Zeroize;
+use sharks::{Share, Sharks, SharksError};
+
+const USAGE: &str = "Usage:
+    sharks split --threshold K --shares N [--format hex|base64|mnemonic] FILE
+    sharks recover [--threshold K] [--format hex|base64|mnemonic] [--output FILE] SHARE...
+
+Splits FILE (`-` for the standard input) into N shares, printed one per line, any K of them recovering it.
+Recovers the secret from the SHARE arguments, written to the standard output unless `--output` is given.";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Hex,
+    Base64,
+    Mnemonic,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Format, String> {
+        match s {
+            "hex" => Ok(Format::Hex),
+            "base64" => Ok(Format::Base64),
+            "mnemonic" => Ok(Format::Mnemonic),
+            _ => Err(format!("unknown format `{}`", s)),
+        }
+    }
+
+    fn encode(self, share: &Share) -> String {
+        match self {
+            Format::Hex => share.to_hex(),
+            Format::Base64 => share.to_base64(),
+            Format::Mnemonic => share.to_mnemonic(),
+        }
+    }
+
+    fn decode(self, s: &str) -> Result<Share, SharksError> {
+        match self {
+            Format::Hex => Share::from_hex(s),
+            Format::Base64 => Share::from_base64(s),
+            Format::Mnemonic => Share::from_mnemonic(s),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Split {
+        threshold: u8,
+        shares: u8,
+        format: Format,
+        file: String,
+    },
+    Recover {
+        threshold: Option<u8>,
+        format: Format,
+        output: Option<String>,
+        shares: Vec<String>,
+    },
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, args) = args.split_first().ok_or("missing command")?;
+    let mut threshold = None;
+    let mut shares = None;
+    let mut format = Format::Hex;
+    let mut output = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value of `{}`", arg));
+        let number = |s: &String| {
+            s.parse::<u8>()
+                .map_err(|_| format!("invalid number `{}`", s))
+        };
+        match arg.as_str() {
+            "-k" | "--threshold" => threshold = Some(number(value()?)?),
+            "-n" | "--shares" => shares = Some(number(value()?)?),
+            "-f" | "--format" => format = Format::parse(value()?)?,
+            "-o" | "--output" => output = Some(value()?.clone()),
+            "-" => positional.push(arg.clone()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    match command.as_str() {
+        "split" => {
+            if output.is_some() {
+                return Err("`--output` is only supported by `recover`".into());
+            }
+            let file = match positional.as_slice() {
+                [file] => file.clone(),
+                _ => return Err("`split` expects a single FILE".into()),
+            };
+            Ok(Command::Split {
+                threshold: threshold.ok_or("missing `--threshold`")?,
+                shares: shares.ok_or("missing `--shares`")?,
+                format,
+                file,
+            })
+        }
+        "recover" => {
+            if shares.is_some() {
+                return Err("`--shares` is only supported by `split`".into());
+            }
+            if positional.is_empty() {
+                return Err("`recover` expects at least one SHARE".into());
+            }
+            Ok(Command::Recover {
+                threshold,
+                format,
+                output,
+                shares: positional,
+            })
+        }
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+fn split(secret: &[u8], threshold: u8, shares: u8, format: Format) -> Result<Vec<String>, String> {
+    if threshold == 0 {
+        return Err("the threshold must be at least 1".into());
+    }
+    if shares < threshold {
+        return Err(SharksError::NotEnoughShares {
+            needed: threshold as usize,
+            got: shares as usize,
+            provided: shares as usize,
+        }
+        .to_string());
+    }
+    Ok(Sharks(threshold)
+        .dealer(secret)
+        .take(shares as usize)
+        .map(|s| format.encode(&s))
+        .collect())
+}
+
+fn recover(shares: &[String], threshold: Option<u8>, format: Format) -> Result<Vec<u8>, String> {
+    let shares = shares
+        .iter()
+        .map(|s| format.decode(s.trim()))
+        .collect::<Result<Vec<Share>, SharksError>>()
+        .map_err(|e| format!("invalid share: {}", e))?;
+    let threshold = match threshold {
+        Some(threshold) => threshold,
+        None => u8::try_from(shares.len()).map_err(|_| "too many shares given")?,
+    };
+    Sharks(threshold)
+        .recover(&shares)
+        .map_err(|e| e.to_string())
+}
+
+fn read_secret(file: &str) -> io::Result<Vec<u8>> {
+    if file == "-" {
+        let mut secret = Vec::new();
+        io::stdin().read_to_end(&mut secret)?;
+        Ok(secret)
+    } else {
+        fs::read(file)
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match parse_args(args)? {
+        Command::Split {
+            threshold,
+            shares,
+            format,
+            file,
+        } => {
+            let mut secret = read_secret(&file).map_err(|e| format!("{}: {}", file, e))?;
+            let shares = split(&secret, threshold, shares, format);
+            secret.zeroize();
+            shares?.iter().for_each(|s| println!("{}", s));
+            Ok(())
+        }
+        Command::Recover {
+            threshold,
+            format,
+            output,
+            shares,
+        } => {
+            let mut secret = recover(&shares, threshold, format)?;
+            let written = match &output {
+                Some(file) => fs::write(file, &secret).map_err(|e| format!("{}: {}", file, e)),
+                None => io::stdout()
+                    .write_all(&secret)
+                    .and_then(|_| io::stdout().flush())
+                    .map_err(|e| e.to_string()),
+            };
+            secret.zeroize();
+            written
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_args, recover, split, Command, Format};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_args_works() {
+        assert_eq!(
+            parse_args(&args("split --threshold 3 -n 5 secret.txt")),
+            Ok(Command::Split {
+                threshold: 3,
+                shares: 5,
+                format: Format::Hex,
+                file: "secret.txt".into(),
+            })
+        );
+        assert_eq!(
+            parse_args(&args("recover -f base64 -o out aa bb")),
+            Ok(Command::Recover {
+                threshold: None,
+                format: Format::Base64,
+                output: Some("out".into()),
+                shares: vec!["aa".into(), "bb".into()],
+            })
+        );
+        assert!(parse_args(&args("split -k 3 secret.txt")).is_err());
+        assert!(parse_args(&args("split -k 3 -n 5")).is_err());
+        assert!(parse_args(&args("recover -n 5 aa")).is_err());
+        assert!(parse_args(&args("recover -f base32 aa")).is_err());
+        assert!(parse_args(&args("join aa")).is_err());
+    }
+
+    #[test]
+    fn split_recover_works() {
+        let secret = b"a secret file";
+        for format in [Format::Hex, Format::Base64, Format::Mnemonic].iter() {
+            let shares = split(secret, 3, 5, *format).unwrap();
+            assert_eq!(shares.len(), 5);
+            assert_eq!(recover(&shares[1..4], None, *format).unwrap(), secret);
+            assert_eq!(recover(&shares, Some(3), *format).unwrap(), secret);
+            assert!(recover(&shares[..2], Some(3), *format).is_err());
+        }
+        assert!(split(secret, 3, 2, Format::Hex).is_err());
+        assert!(recover(&["0g".into()], None, Format::Hex).is_err());
+    }
+}
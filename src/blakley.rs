@@ -0,0 +1,220 @@
+// Blakley's threshold scheme, where the secret is a coordinate of a point and each share a hyperplane through it.
+// Each secret chunk is the first coordinate of a point of a `k` dimensional space, the others being random,
+// and each share holds the constant terms `b = a · p` of the hyperplanes of normal `a` through the points.
+// Normals are the rows of a Cauchy matrix `1 / (x - c_j)` derived from the share `x` coordinate, all of whose
+// square submatrices are invertible, so that any `k` hyperplanes intersect at a single point which fewer do not
+// determine. Shares thus keep the regular `x || y` format, and are recovered by solving a `k` by `k` linear system.
+
+use rand::Rng;
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::{FieldShare, Share};
+use super::Sharks;
+
+// Columns `c_j` of the Cauchy matrix: zero followed by the highest `x` coordinates, which shares do not use
+fn column<F: Field>(j: usize) -> F {
+    match j {
+        0 => F::ZERO,
+        _ => F::from_index(256 - j).expect("thresholds are lower than 256"),
+    }
+}
+
+// Normal of the hyperplanes of the share at `x`, `None` if `x` is one of the columns
+fn normal<F: Field>(x: F, k: usize) -> Option<Vec<F>> {
+    (0..k)
+        .map(|j| {
+            let d = x - column(j);
+            Some(d).filter(|d| *d != F::ZERO).map(F::inverse)
+        })
+        .collect()
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns exactly `n` new shares of Blakley's scheme, any threshold of them
+    /// recovering it with `recover_blakley`. Shares have the same format and size as regular ones,
+    /// but hyperplanes use the highest `x` coordinates as columns, so that at most `256 - threshold` shares are dealt.
+    /// An `Err` is returned if the threshold is zero, or `n` is lower than it or higher than `256 - threshold`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, SharksError };
+    /// let sharks = Sharks(3);
+    /// let shares = sharks.dealer_blakley(&[1, 2, 3], 5).unwrap();
+    /// assert_eq!(sharks.recover_blakley(&shares[2..]).unwrap(), vec![1, 2, 3]);
+    /// assert!(sharks.recover_blakley(&shares[3..]).is_err());
+    /// assert_eq!(sharks.dealer_blakley(&[1], 254).err(), Some(SharksError::TooManyShares { max: 253 }));
+    /// ```
+    pub fn dealer_blakley(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        self.deal_blakley(
+            math::encode_secret::<GF256>(secret),
+            n,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Given an iterable collection of shares generated by `dealer_blakley`, recovers the original secret
+    /// from the intersection of their hyperplanes. An `Err` is returned for the same reasons as `recover`,
+    /// or if a share is at an `x` coordinate used as a column.
+    pub fn recover_blakley<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        self.recover_blakley_in(shares)
+    }
+
+    // Deals `n` shares of the `chunks` over any field `F`, see `dealer_blakley`
+    pub(crate) fn deal_blakley<F: Field, R: Rng + ?Sized>(
+        &self,
+        chunks: Vec<F>,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<FieldShare<F>>, SharksError> {
+        let k = self.0 as usize;
+        if k == 0 {
+            return Err(SharksError::InvalidThreshold);
+        } else if n > 256 - k {
+            return Err(SharksError::TooManyShares { max: 256 - k });
+        } else if n < k {
+            return Err(SharksError::TooFewShares { min: k });
+        }
+
+        // One point per chunk, structure-of-arrays: one vector per coordinate
+        let mut points: Vec<Vec<F>> = (1..k)
+            .map(|_| {
+                (0..chunks.len())
+                    .map(|_| math::random_element(rng))
+                    .collect()
+            })
+            .collect();
+        points.insert(0, chunks);
+
+        let shares = (1..=n)
+            .map(|i| {
+                let x = F::from_index(i).expect("up to 255 shares are dealt");
+                let a = normal(x, k).expect("shares do not use the columns coordinates");
+                let mut y = vec![F::ZERO; points[0].len()];
+                a.iter()
+                    .zip(points.iter())
+                    .for_each(|(a, p)| F::add_scaled_slice(&mut y, *a, p));
+                FieldShare {
+                    x,
+                    y,
+                    threshold: Some(self.0),
+                    group: None,
+                }
+            })
+            .collect();
+
+        points.zeroize();
+        Ok(shares)
+    }
+
+    // Recovers a secret split by `deal_blakley` over any field `F`
+    pub(crate) fn recover_blakley_in<'a, F, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+    {
+        let k = self.0 as usize;
+        let shares = self.distinct(shares, k)?;
+        let shares = &shares[..k];
+        let normals = shares
+            .iter()
+            .map(|s| normal(s.x, k))
+            .collect::<Option<Vec<Vec<F>>>>()
+            .ok_or(SharksError::InvalidIndex)?;
+
+        // The first coordinate is `w · b`, for the weights `w` solving `Aᵀ w = e_1`
+        let rows = (0..k)
+            .map(|r| {
+                let mut row: Vec<F> = normals.iter().map(|a| a[r]).collect();
+                row.push(if r == 0 { F::ONE } else { F::ZERO });
+                row
+            })
+            .collect();
+        let weights = math::solve(rows).expect("Cauchy matrices are invertible");
+
+        let mut chunks = vec![F::ZERO; shares[0].y.len()];
+        weights
+            .iter()
+            .zip(shares.iter())
+            .for_each(|(w, s)| F::add_scaled_slice(&mut chunks, *w, &s.y));
+        let secret = math::decode_secret(&chunks);
+        chunks.zeroize();
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldShare, Share, Sharks, SharksError, GF256, GF2_64};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn blakley_integration_works() {
+        for threshold in 1..6 {
+            let sharks = Sharks(threshold);
+            let secret: Vec<u8> = (0..40).collect();
+            let shares = sharks.dealer_blakley(&secret, 8).unwrap();
+            assert_eq!(shares.len(), 8);
+            assert_eq!(shares[0].y.len(), 40);
+            assert_eq!(shares[7].x, GF256(8));
+            assert_eq!(sharks.recover_blakley(&shares).unwrap(), secret);
+            assert_eq!(sharks.recover_blakley(shares.iter().rev()).unwrap(), secret);
+            assert_eq!(
+                sharks
+                    .recover_blakley(&shares[8 - threshold as usize..])
+                    .unwrap(),
+                secret
+            );
+        }
+        assert_eq!(
+            Sharks(255).dealer_blakley(&[1, 2], 1).err(),
+            Some(SharksError::TooFewShares { min: 255 })
+        );
+        let shares = Sharks(2).dealer_blakley(&[1, 2], 254).unwrap();
+        assert_eq!(
+            Sharks(2).recover_blakley(&shares[252..]).unwrap(),
+            vec![1, 2]
+        );
+
+        let sharks = Sharks(3);
+        let mut rng = ChaCha8Rng::from_seed([5; 32]);
+        let wide: Vec<FieldShare<GF2_64>> = sharks
+            .deal_blakley(crate::math::encode_secret(&[9; 20]), 4, &mut rng)
+            .unwrap();
+        assert_eq!(sharks.recover_blakley_in(&wide[1..]).unwrap(), vec![9; 20]);
+    }
+
+    #[test]
+    fn blakley_err() {
+        let sharks = Sharks(3);
+        assert_eq!(
+            Sharks(0).dealer_blakley(&[1], 1).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            sharks.dealer_blakley(&[1], 2).err(),
+            Some(SharksError::TooFewShares { min: 3 })
+        );
+        let mut shares: Vec<Share> = sharks.dealer_blakley(&[1, 2], 3).unwrap();
+        assert_eq!(
+            sharks.recover_blakley(&shares[1..]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        shares[0].x = GF256(255);
+        assert_eq!(
+            sharks.recover_blakley(&shares).err(),
+            Some(SharksError::InvalidIndex)
+        );
+    }
+}
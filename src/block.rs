@@ -0,0 +1,228 @@
+// Block oriented sharing of large secrets, where each fixed size block is shared independently.
+// Block shares know their index and the total number of blocks, so they can be processed
+// in any order or in parallel, and recovery detects missing blocks.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// A share of a single block of a secret split in blocks, see `Sharks::dealer_blocks`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct BlockShare {
+    /// Index of the block within the secret.
+    pub index: u32,
+    /// Total number of blocks of the secret.
+    pub count: u32,
+    /// Share of the block.
+    pub share: Share,
+}
+
+/// Obtains a byte vector from a `BlockShare` instance: `index || count || share`,
+/// being the index and count 4 bytes big endian integers.
+impl From<&BlockShare> for Vec<u8> {
+    fn from(s: &BlockShare) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(s.share.y.len() + 9);
+        bytes.extend_from_slice(&s.index.to_be_bytes());
+        bytes.extend_from_slice(&s.count.to_be_bytes());
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `BlockShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for BlockShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<BlockShare, Self::Error> {
+        if s.len() < 8 {
+            return Err(SharksError::InvalidLength);
+        }
+        let index = u32::from_be_bytes([s[0], s[1], s[2], s[3]]);
+        let count = u32::from_be_bytes([s[4], s[5], s[6], s[7]]);
+        if index >= count {
+            return Err(SharksError::InvalidIndex);
+        }
+        Ok(BlockShare {
+            index,
+            count,
+            share: Share::try_from(&s[8..])?,
+        })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, splits it in blocks of `block_size` bytes, the last one possibly shorter,
+    /// and returns an `Iterator` over the blocks, each item being an `Iterator` along new shares of the block.
+    /// Blocks are shared lazily and independently, so memory usage only depends on the block size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero, or the secret has more than `u32::MAX` blocks.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, BlockShare };
+    /// # let sharks = Sharks(3);
+    /// let secret: Vec<u8> = (0..100).collect();
+    /// // Get 5 shares of each 32 bytes block
+    /// let shares: Vec<BlockShare> = sharks
+    ///     .dealer_blocks(&secret, 32)
+    ///     .flat_map(|block| block.take(5))
+    ///     .collect();
+    /// assert_eq!(shares.len(), 4 * 5);
+    /// assert_eq!(sharks.recover_blocks(&shares).unwrap(), secret);
+    pub fn dealer_blocks<'a>(
+        &'a self,
+        secret: &'a [u8],
+        block_size: usize,
+    ) -> impl Iterator<Item = impl Iterator<Item = BlockShare>> + 'a {
+        assert!(block_size > 0, "Block size cannot be zero");
+        let count = u32::try_from(secret.len().div_ceil(block_size)).expect("Too many blocks");
+        (0..count)
+            .zip(secret.chunks(block_size))
+            .map(move |(index, block)| {
+                self.dealer(block).map(move |share| BlockShare {
+                    index,
+                    count,
+                    share,
+                })
+            })
+    }
+
+    /// Given an iterable collection of shares of a single block, recovers the block contents and its index.
+    /// An `Err` is returned if the shares belong to different blocks, or would fail to `recover`.
+    pub fn recover_block<'a, T>(&self, shares: T) -> Result<(u32, Vec<u8>), SharksError>
+    where
+        T: IntoIterator<Item = &'a BlockShare>,
+        T::IntoIter: Iterator<Item = &'a BlockShare>,
+    {
+        let shares: Vec<&BlockShare> = shares.into_iter().collect();
+        let first = shares.first().ok_or(SharksError::NotEnoughShares {
+            needed: self.0 as usize,
+            got: 0,
+            provided: 0,
+        })?;
+        if shares
+            .iter()
+            .any(|s| s.index != first.index || s.count != first.count)
+        {
+            return Err(SharksError::MixedShares);
+        }
+        Ok((first.index, self.recover(shares.iter().map(|s| &s.share))?))
+    }
+
+    /// Given an iterable collection of shares of all the blocks of a secret, in any order,
+    /// recovers the original secret. An `Err` is returned if any block is missing or cannot be recovered.
+    pub fn recover_blocks<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a BlockShare>,
+        T::IntoIter: Iterator<Item = &'a BlockShare>,
+    {
+        let mut blocks: BTreeMap<u32, Vec<&BlockShare>> = BTreeMap::new();
+        let mut counts = Vec::new();
+        for share in shares {
+            blocks.entry(share.index).or_default().push(share);
+            if !counts.contains(&share.count) {
+                counts.push(share.count);
+            }
+        }
+
+        match counts.as_slice() {
+            [] => {
+                return Err(SharksError::NotEnoughShares {
+                    needed: self.0 as usize,
+                    got: 0,
+                    provided: 0,
+                })
+            }
+            [count] if blocks.len() == *count as usize => {}
+            [_] => return Err(SharksError::MissingBlocks),
+            _ => return Err(SharksError::MixedShares),
+        }
+
+        let mut secret = Vec::new();
+        for shares in blocks.values() {
+            secret.extend(self.recover_block(shares.iter().copied())?.1);
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockShare;
+    use crate::{Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn blocks_integration_works() {
+        let sharks = Sharks(2);
+        let secret: Vec<u8> = (0..=255).collect();
+        let mut shares: Vec<BlockShare> = sharks
+            .dealer_blocks(&secret, 50)
+            .flat_map(|b| b.take(3))
+            .collect();
+        assert_eq!(shares.len(), 6 * 3);
+        assert_eq!(shares[17].share.y.len(), 6);
+
+        shares.reverse();
+        assert_eq!(sharks.recover_blocks(&shares[1..]).unwrap(), secret);
+        assert_eq!(
+            sharks.recover_block(&shares[..2]).unwrap(),
+            (5, secret[250..].to_vec())
+        );
+
+        let shares: Vec<BlockShare> = shares
+            .iter()
+            .map(|s| BlockShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_blocks(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn missing_blocks_err() {
+        let sharks = Sharks(2);
+        let secret: Vec<u8> = (0..100).collect();
+        let shares: Vec<BlockShare> = sharks
+            .dealer_blocks(&secret, 10)
+            .flat_map(|b| b.take(2))
+            .collect();
+        assert_eq!(
+            sharks.recover_blocks(&shares[2..]),
+            Err(SharksError::MissingBlocks)
+        );
+        assert_eq!(
+            sharks.recover_blocks(&shares[..18]),
+            Err(SharksError::MissingBlocks)
+        );
+        assert_eq!(
+            sharks.recover_blocks(&shares[1..]),
+            Err(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+        assert_eq!(
+            sharks.recover_block(&shares[1..3]),
+            Err(SharksError::MixedShares)
+        );
+    }
+
+    #[test]
+    fn block_share_bytes_err() {
+        assert_eq!(
+            BlockShare::try_from(&[0, 0, 0, 0, 0, 0, 0][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            BlockShare::try_from(&[0, 0, 0, 1, 0, 0, 0, 1, 1, 2][..]).err(),
+            Some(SharksError::InvalidIndex)
+        );
+        assert!(BlockShare::try_from(&[0, 0, 0, 0, 0, 0, 0, 1, 1, 2][..]).is_ok());
+    }
+}
@@ -0,0 +1,520 @@
+// Builder validating all the parameters of a split up front, and the configured engine it produces.
+// `Sharks` only holds the threshold, the builder also sets the number of shares dealt, the random number
+// generator, the byte format shares are serialized to and the secrets padding, so that they are not repeated
+// at every call.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use rand::rngs::ThreadRng;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::entropy::{EntropyRng, EntropySource};
+use super::error::SharksError;
+use super::field::{Field, GF256, GF2_64};
+use super::limits::Limits;
+use super::math;
+use super::padding;
+use super::share::{FieldShare, Share};
+use super::Sharks;
+
+/// Byte format of the shares split and recovered by a `SharksEngine`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ShareFormat {
+    /// `x || y`, see `Vec::from(&Share)`.
+    #[default]
+    Bytes,
+    /// `version || threshold || x || y`, see `Share::to_versioned_bytes`.
+    Versioned,
+    /// `x || y || crc`, see `Share::to_checked_bytes`.
+    Checked,
+    /// `x || y` over `GF2_64` instead of `GF256`, each element being 8 bytes long, see `Vec::from(&FieldShare)`.
+    /// Large secrets are split and recovered with eight times fewer field operations.
+    Wide,
+}
+
+/// Threshold scheme the secrets are split with by a `SharksEngine`, all of them dealing shares in the same formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Scheme {
+    /// Shamir polynomials, see `Sharks::dealer`.
+    #[default]
+    Shamir,
+    /// XORs of random pads, faster when every share is needed, see `Sharks::dealer_xor`.
+    /// The number of shares must be the threshold.
+    Xor,
+    /// Blakley hyperplanes, see `Sharks::dealer_blakley`. At most `256 - threshold` shares can be dealt.
+    Blakley,
+}
+
+/// Builder of a `SharksEngine`, validating its parameters once in `build`.
+/// The threshold is required, the number of shares defaults to the threshold,
+/// the random number generator to the thread local one, the scheme to `Scheme::Shamir` and the format to `ShareFormat::Bytes`,
+/// secrets not being padded and recovered shares not being limited.
+///
+/// Example:
+/// ```
+/// # use sharks::{ SharksBuilder, ShareFormat };
+/// let mut engine = SharksBuilder::new()
+///     .threshold(3)
+///     .total_shares(5)
+///     .rng(rand::rngs::OsRng)
+///     .format(ShareFormat::Checked)
+///     .build()
+///     .unwrap();
+/// let shares = engine.split(&[1, 2, 3]);
+/// assert_eq!(shares.len(), 5);
+/// assert_eq!(engine.recover(&shares[2..]).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone)]
+pub struct SharksBuilder<R = ThreadRng> {
+    threshold: Option<u8>,
+    total_shares: Option<usize>,
+    rng: R,
+    format: ShareFormat,
+    padding: Option<usize>,
+    limits: Option<Limits>,
+    scheme: Scheme,
+}
+
+impl SharksBuilder {
+    /// Returns a builder with the default parameters and no threshold.
+    pub fn new() -> SharksBuilder {
+        SharksBuilder {
+            threshold: None,
+            total_shares: None,
+            rng: rand::thread_rng(),
+            format: ShareFormat::default(),
+            padding: None,
+            limits: None,
+            scheme: Scheme::default(),
+        }
+    }
+}
+
+impl Default for SharksBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CryptoRng + RngCore> SharksBuilder<R> {
+    /// Sets the minimum number of shares needed to recover the secret.
+    pub fn threshold(mut self, threshold: u8) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the number of shares dealt by `SharksEngine::split`.
+    pub fn total_shares(mut self, total_shares: usize) -> Self {
+        self.total_shares = Some(total_shares);
+        self
+    }
+
+    /// Sets the random number generator the polynomials are generated with.
+    pub fn rng<Q: CryptoRng + RngCore>(self, rng: Q) -> SharksBuilder<Q> {
+        SharksBuilder {
+            threshold: self.threshold,
+            total_shares: self.total_shares,
+            rng,
+            format: self.format,
+            padding: self.padding,
+            limits: self.limits,
+            scheme: self.scheme,
+        }
+    }
+
+    /// Sets the entropy source the polynomials are generated from, on platforms without a `rand` generator.
+    pub fn entropy<E: EntropySource>(self, source: E) -> SharksBuilder<EntropyRng<E>> {
+        self.rng(EntropyRng(source))
+    }
+
+    /// Sets the byte format of the shares.
+    pub fn format(mut self, format: ShareFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Pads secrets to a multiple of `block` bytes before splitting them, so that the shares length does not
+    /// reveal their exact length, see `Sharks::dealer_padded`. The padding is removed by `SharksEngine::recover`.
+    pub fn padding(mut self, block: usize) -> Self {
+        self.padding = Some(block);
+        self
+    }
+
+    /// Checks the shares passed to `SharksEngine::recover` against the `limits` before parsing them,
+    /// for engines recovering untrusted shares, see `Limits::parse_all`.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Sets the threshold scheme secrets are split with.
+    pub fn scheme(mut self, scheme: Scheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Validates the parameters and returns the configured engine. An `Err` is returned if the threshold
+    /// is missing or zero, more than 255 shares are requested or fewer shares than the threshold,
+    /// another number of shares than the threshold is requested with `Scheme::Xor`, or more than `256 - threshold`
+    /// with `Scheme::Blakley`, or the padding block size is zero.
+    pub fn build(self) -> Result<SharksEngine<R>, SharksError> {
+        let threshold = match self.threshold {
+            Some(threshold) if threshold > 0 => threshold,
+            _ => return Err(SharksError::InvalidThreshold),
+        };
+        let total_shares = self.total_shares.unwrap_or(threshold as usize);
+        if total_shares > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        } else if total_shares < threshold as usize {
+            return Err(SharksError::TooFewShares {
+                min: threshold as usize,
+            });
+        } else if self.scheme == Scheme::Xor && total_shares != threshold as usize {
+            return Err(SharksError::InvalidThreshold);
+        } else if self.scheme == Scheme::Blakley && total_shares > 256 - threshold as usize {
+            return Err(SharksError::TooManyShares {
+                max: 256 - threshold as usize,
+            });
+        } else if self.padding == Some(0) {
+            return Err(SharksError::InvalidLength);
+        }
+        Ok(SharksEngine {
+            sharks: Sharks(threshold),
+            total_shares,
+            rng: self.rng,
+            format: self.format,
+            padding: self.padding,
+            limits: self.limits,
+            scheme: self.scheme,
+        })
+    }
+}
+
+/// Prints the parameters, the random number generator state being omitted.
+impl<R> fmt::Debug for SharksBuilder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharksBuilder")
+            .field("threshold", &self.threshold)
+            .field("total_shares", &self.total_shares)
+            .field("format", &self.format)
+            .field("padding", &self.padding)
+            .field("limits", &self.limits)
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Splits secrets into a fixed number of serialized shares and recovers them, returned by `SharksBuilder::build`.
+pub struct SharksEngine<R = ThreadRng> {
+    sharks: Sharks,
+    total_shares: usize,
+    rng: R,
+    format: ShareFormat,
+    padding: Option<usize>,
+    limits: Option<Limits>,
+    scheme: Scheme,
+}
+
+/// Prints the parameters, the random number generator state being omitted.
+impl<R> fmt::Debug for SharksEngine<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharksEngine")
+            .field("threshold", &self.sharks.0)
+            .field("total_shares", &self.total_shares)
+            .field("format", &self.format)
+            .field("padding", &self.padding)
+            .field("limits", &self.limits)
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: CryptoRng + RngCore> SharksEngine<R> {
+    /// Returns the `Sharks` instance with the configured threshold.
+    pub fn sharks(&self) -> &Sharks {
+        &self.sharks
+    }
+
+    /// Returns the number of shares dealt by `split`.
+    pub fn total_shares(&self) -> usize {
+        self.total_shares
+    }
+
+    /// Returns the byte format of the shares.
+    pub fn format(&self) -> ShareFormat {
+        self.format
+    }
+
+    /// Returns the block size secrets are padded to, if any.
+    pub fn padding(&self) -> Option<usize> {
+        self.padding
+    }
+
+    /// Returns the limits recovered shares are checked against, if any.
+    pub fn limits(&self) -> Option<Limits> {
+        self.limits
+    }
+
+    /// Returns the threshold scheme secrets are split with.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    /// Given a `secret` byte slice, returns the configured number of new shares over `GF256`, whatever the format.
+    pub fn deal(&mut self, secret: &[u8]) -> Vec<Share> {
+        self.padded(secret, |engine, secret| engine.deal_raw(secret))
+    }
+
+    /// Given a `secret` byte slice, returns the configured number of new shares serialized in the configured format.
+    pub fn split(&mut self, secret: &[u8]) -> Vec<Vec<u8>> {
+        self.padded(secret, |engine, secret| engine.split_raw(secret))
+    }
+
+    // Calls `f` with the secret padded as configured
+    fn padded<T>(&mut self, secret: &[u8], f: impl FnOnce(&mut Self, &[u8]) -> T) -> T {
+        match self.padding {
+            Some(block) => {
+                // The block size was checked by the builder
+                let mut padded = padding::pad(secret, block).unwrap();
+                let result = f(self, &padded);
+                padded.zeroize();
+                result
+            }
+            None => f(self, secret),
+        }
+    }
+
+    fn deal_raw(&mut self, secret: &[u8]) -> Vec<Share> {
+        self.deal_in(math::encode_secret::<GF256>(secret))
+    }
+
+    // Deals the configured number of shares of the `chunks` with the configured scheme
+    fn deal_in<F: Field>(&mut self, chunks: Vec<F>) -> Vec<FieldShare<F>> {
+        match self.scheme {
+            Scheme::Shamir => self
+                .sharks
+                .deal_with_rng(chunks, &mut self.rng)
+                .take(self.total_shares)
+                .collect(),
+            Scheme::Xor => self.sharks.deal_xor(chunks, &mut self.rng),
+            // The number of shares was checked by the builder
+            Scheme::Blakley => self
+                .sharks
+                .deal_blakley(chunks, self.total_shares, &mut self.rng)
+                .unwrap(),
+        }
+    }
+
+    fn split_raw(&mut self, secret: &[u8]) -> Vec<Vec<u8>> {
+        let format = self.format;
+        if format == ShareFormat::Wide {
+            return self
+                .deal_in(math::encode_secret::<GF2_64>(secret))
+                .iter()
+                .map(Vec::from)
+                .collect();
+        }
+        self.deal_raw(secret)
+            .iter()
+            .map(|s| match format {
+                ShareFormat::Bytes => Vec::from(s),
+                // Dealt shares always know their threshold
+                ShareFormat::Versioned => s.to_versioned_bytes().unwrap(),
+                ShareFormat::Checked => s.to_checked_bytes(),
+                ShareFormat::Wide => unreachable!(),
+            })
+            .collect()
+    }
+
+    /// Given shares serialized in the configured format, recovers the original secret, removing its padding.
+    /// An `Err` is returned if there are more shares or longer ones than the limits allow, any of them cannot
+    /// be parsed, in the same cases as `Sharks::recover`, or if the secret is not correctly padded.
+    pub fn recover<T: AsRef<[u8]>>(&self, shares: &[T]) -> Result<Vec<u8>, SharksError> {
+        let secret = self.recover_raw(shares)?;
+        match self.padding {
+            Some(_) => padding::unpad(secret),
+            None => Ok(secret),
+        }
+    }
+
+    fn recover_raw<T: AsRef<[u8]>>(&self, shares: &[T]) -> Result<Vec<u8>, SharksError> {
+        if let Some(limits) = self.limits {
+            if shares.len() > limits.max_shares {
+                return Err(SharksError::TooManyShares {
+                    max: limits.max_shares,
+                });
+            }
+            shares
+                .iter()
+                .try_for_each(|s| limits.check_share(s.as_ref()))?;
+        }
+        if self.format == ShareFormat::Wide {
+            let shares = shares
+                .iter()
+                .map(|s| FieldShare::<GF2_64>::try_from(s.as_ref()))
+                .collect::<Result<Vec<_>, SharksError>>()?;
+            return self.recover_in(&shares);
+        }
+        let shares = shares
+            .iter()
+            .map(|s| match self.format {
+                ShareFormat::Bytes => Share::try_from(s.as_ref()),
+                ShareFormat::Versioned => Share::from_versioned_bytes(s.as_ref()),
+                ShareFormat::Checked => Share::from_checked_bytes(s.as_ref()),
+                ShareFormat::Wide => unreachable!(),
+            })
+            .collect::<Result<Vec<Share>, SharksError>>()?;
+        self.recover_in(&shares)
+    }
+
+    // Recovers the secret from the parsed `shares` with the configured scheme
+    fn recover_in<F: Field>(&self, shares: &[FieldShare<F>]) -> Result<Vec<u8>, SharksError> {
+        match self.scheme {
+            Scheme::Shamir => self.sharks.recover_in(shares),
+            Scheme::Xor => self.sharks.recover_xor_in(shares),
+            Scheme::Blakley => self.sharks.recover_blakley_in(shares),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scheme, ShareFormat, SharksBuilder};
+    use crate::{Limits, SharksError};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn builder_integration_works() {
+        for format in [
+            ShareFormat::Bytes,
+            ShareFormat::Versioned,
+            ShareFormat::Checked,
+            ShareFormat::Wide,
+        ] {
+            let mut engine = SharksBuilder::new()
+                .threshold(3)
+                .total_shares(5)
+                .format(format)
+                .build()
+                .unwrap();
+            assert_eq!(engine.sharks().0, 3);
+            assert_eq!(engine.format(), format);
+            let shares = engine.split(&[1, 2, 3, 4]);
+            assert_eq!(shares.len(), 5);
+            assert_eq!(engine.recover(&shares[1..4]), Ok(vec![1, 2, 3, 4]));
+
+            for scheme in [Scheme::Xor, Scheme::Blakley] {
+                let mut engine = SharksBuilder::new()
+                    .threshold(4)
+                    .format(format)
+                    .scheme(scheme)
+                    .build()
+                    .unwrap();
+                assert_eq!(engine.scheme(), scheme);
+                let shares = engine.split(&[1, 2, 3, 4]);
+                assert_eq!(shares.len(), 4);
+                assert_eq!(engine.recover(&shares), Ok(vec![1, 2, 3, 4]));
+                assert!(engine.recover(&shares[1..]).is_err());
+            }
+        }
+
+        // Seeded generators deal the same shares
+        let deal = |seed| {
+            SharksBuilder::new()
+                .threshold(2)
+                .rng(ChaCha20Rng::from_seed(seed))
+                .build()
+                .unwrap()
+                .split(&[42])
+        };
+        assert_eq!(deal([1; 32]), deal([1; 32]));
+        assert_eq!(deal([1; 32]).len(), 2);
+
+        // Wide shares hold 8 bytes coordinates and elements
+        let mut engine = SharksBuilder::new()
+            .threshold(2)
+            .format(ShareFormat::Wide)
+            .build()
+            .unwrap();
+        let shares = engine.split(&[7; 20]);
+        assert_eq!(shares[0].len(), 8 + 24);
+        assert_eq!(engine.recover(&shares), Ok(vec![7; 20]));
+
+        // Padded secrets of different lengths result in shares of the same length
+        let mut engine = SharksBuilder::new()
+            .threshold(2)
+            .padding(16)
+            .build()
+            .unwrap();
+        let short = engine.split(&[1; 3]);
+        let long = engine.split(&[1; 15]);
+        assert_eq!(short[0].len(), 17);
+        assert_eq!(long[1].len(), 17);
+        assert_eq!(engine.recover(&short), Ok(vec![1; 3]));
+        assert_eq!(engine.deal(&[1; 16])[0].y.len(), 32);
+    }
+
+    #[test]
+    fn builder_err() {
+        let cases = [
+            (SharksBuilder::new(), SharksError::InvalidThreshold),
+            (
+                SharksBuilder::new().threshold(0),
+                SharksError::InvalidThreshold,
+            ),
+            (
+                SharksBuilder::new().threshold(2).total_shares(256),
+                SharksError::TooManyShares { max: 255 },
+            ),
+            (
+                SharksBuilder::new().threshold(1).padding(0),
+                SharksError::InvalidLength,
+            ),
+            (
+                SharksBuilder::new()
+                    .threshold(2)
+                    .total_shares(3)
+                    .scheme(Scheme::Xor),
+                SharksError::InvalidThreshold,
+            ),
+            (
+                SharksBuilder::new()
+                    .threshold(2)
+                    .total_shares(255)
+                    .scheme(Scheme::Blakley),
+                SharksError::TooManyShares { max: 254 },
+            ),
+            (
+                SharksBuilder::new().threshold(3).total_shares(2),
+                SharksError::TooFewShares { min: 3 },
+            ),
+        ];
+        for (builder, err) in cases {
+            assert_eq!(builder.build().err(), Some(err));
+        }
+
+        let mut engine = SharksBuilder::new()
+            .threshold(2)
+            .total_shares(4)
+            .limits(Limits {
+                max_share_bytes: 4,
+                max_shares: 3,
+            })
+            .build()
+            .unwrap();
+        let shares = engine.split(&[1, 2, 3]);
+        assert_eq!(engine.recover(&shares[1..]), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            engine.recover(&shares).err(),
+            Some(SharksError::TooManyShares { max: 3 })
+        );
+        let shares = engine.split(&[1, 2, 3, 4]);
+        assert_eq!(
+            engine.recover(&shares[..2]).err(),
+            Some(SharksError::ShareTooLong { max: 4 })
+        );
+    }
+}
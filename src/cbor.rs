@@ -0,0 +1,377 @@
+// Canonical CBOR (RFC 8949) encoding of shares and bundles of shares, self-describing for long-term storage.
+// A share is a map with unsigned integer keys: `0` the schema version, `1` the size in bytes of the field elements,
+// `2` the threshold if known, `3` the `x` coordinate and `4` the `y` values, both as byte strings of big endian
+// elements, and `5` the group identifier if known. A bundle is an array of shares. Encoding follows the core deterministic rules, integers and lengths
+// having their shortest form and keys being sorted, and decoding rejects any other encoding of the same shares,
+// so that each set of shares has exactly one valid encoding.
+
+use std::convert::TryFrom;
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::Field;
+use super::share::FieldShare;
+
+/// Schema version of the CBOR encoded shares, see `FieldShare::to_cbor`.
+pub const CBOR_VERSION: u8 = 1;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+
+const KEY_VERSION: u64 = 0;
+const KEY_ELEMENT_BYTES: u64 = 1;
+const KEY_THRESHOLD: u64 = 2;
+const KEY_X: u64 = 3;
+const KEY_Y: u64 = 4;
+const KEY_GROUP: u64 = 5;
+
+// Writes the head of a data item in its shortest form
+fn push_head(bytes: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    if n < 24 {
+        bytes.push(major | n as u8);
+    } else if n <= u8::MAX as u64 {
+        bytes.extend_from_slice(&[major | 24, n as u8]);
+    } else if n <= u16::MAX as u64 {
+        bytes.push(major | 25);
+        bytes.extend_from_slice(&(n as u16).to_be_bytes());
+    } else if n <= u32::MAX as u64 {
+        bytes.push(major | 26);
+        bytes.extend_from_slice(&(n as u32).to_be_bytes());
+    } else {
+        bytes.push(major | 27);
+        bytes.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn push_uint(bytes: &mut Vec<u8>, key: u64, value: u64) {
+    push_head(bytes, MAJOR_UNSIGNED, key);
+    push_head(bytes, MAJOR_UNSIGNED, value);
+}
+
+fn push_bytes(bytes: &mut Vec<u8>, key: u64, value: &[u8]) {
+    push_head(bytes, MAJOR_UNSIGNED, key);
+    push_head(bytes, MAJOR_BYTES, value.len() as u64);
+    bytes.extend_from_slice(value);
+}
+
+fn push_share<F: Field>(bytes: &mut Vec<u8>, share: &FieldShare<F>) {
+    let mut elements = Vec::from(share);
+    push_head(
+        bytes,
+        MAJOR_MAP,
+        4 + share.threshold().is_some() as u64 + share.group().is_some() as u64,
+    );
+    push_uint(bytes, KEY_VERSION, CBOR_VERSION as u64);
+    push_uint(bytes, KEY_ELEMENT_BYTES, F::BYTES as u64);
+    if let Some(threshold) = share.threshold() {
+        push_uint(bytes, KEY_THRESHOLD, threshold as u64);
+    }
+    push_bytes(bytes, KEY_X, &elements[..F::BYTES]);
+    push_bytes(bytes, KEY_Y, &elements[F::BYTES..]);
+    if let Some(group) = share.group() {
+        push_uint(bytes, KEY_GROUP, group);
+    }
+    elements.zeroize();
+}
+
+// Strict reader of canonical CBOR data items
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SharksError> {
+        if n > self.bytes.len() {
+            return Err(SharksError::InvalidEncoding);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    // Reads the head of a data item of the `major` type, rejecting indefinite lengths and non shortest forms
+    fn head(&mut self, major: u8) -> Result<u64, SharksError> {
+        let initial = self.take(1)?[0];
+        if initial >> 5 != major {
+            return Err(SharksError::InvalidEncoding);
+        }
+        let (n, min) = match initial & 0x1f {
+            info @ 0..=23 => return Ok(info as u64),
+            24 => (self.take(1)?[0] as u64, 24),
+            25 => {
+                let b = self.take(2)?;
+                (u16::from_be_bytes([b[0], b[1]]) as u64, u8::MAX as u64 + 1)
+            }
+            26 => {
+                let b = self.take(4)?;
+                (
+                    u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64,
+                    u16::MAX as u64 + 1,
+                )
+            }
+            27 => {
+                let mut b = [0u8; 8];
+                b.copy_from_slice(self.take(8)?);
+                (u64::from_be_bytes(b), u32::MAX as u64 + 1)
+            }
+            _ => return Err(SharksError::InvalidEncoding),
+        };
+        if n < min {
+            return Err(SharksError::InvalidEncoding);
+        }
+        Ok(n)
+    }
+
+    fn uint(&mut self) -> Result<u64, SharksError> {
+        self.head(MAJOR_UNSIGNED)
+    }
+
+    fn byte_string(&mut self) -> Result<&'a [u8], SharksError> {
+        let len = self.head(MAJOR_BYTES)?;
+        self.take(usize::try_from(len).map_err(|_| SharksError::InvalidEncoding)?)
+    }
+
+    fn share<F: Field>(&mut self) -> Result<FieldShare<F>, SharksError> {
+        let entries = self.head(MAJOR_MAP)?;
+        let (mut version, mut element_bytes, mut threshold, mut x, mut y, mut group) =
+            (None, None, None, None, None, None);
+        let mut previous = None;
+        for _ in 0..entries {
+            let key = self.uint()?;
+            // Keys are sorted, which also rules out duplicates
+            if previous.is_some_and(|p| key <= p) {
+                return Err(SharksError::InvalidEncoding);
+            }
+            previous = Some(key);
+            match key {
+                KEY_VERSION => version = Some(self.uint()?),
+                KEY_ELEMENT_BYTES => element_bytes = Some(self.uint()?),
+                KEY_THRESHOLD => threshold = Some(self.uint()?),
+                KEY_X => x = Some(self.byte_string()?),
+                KEY_Y => y = Some(self.byte_string()?),
+                KEY_GROUP => group = Some(self.uint()?),
+                _ => return Err(SharksError::InvalidEncoding),
+            }
+        }
+
+        match version {
+            Some(v) if v == CBOR_VERSION as u64 => {}
+            Some(v) => return Err(SharksError::UnknownVersion(v.min(u8::MAX as u64) as u8)),
+            None => return Err(SharksError::InvalidEncoding),
+        }
+        if element_bytes.ok_or(SharksError::InvalidEncoding)? != F::BYTES as u64 {
+            return Err(SharksError::InvalidElement);
+        }
+        let threshold = match threshold {
+            Some(t) if t == 0 || t > u8::MAX as u64 => return Err(SharksError::InvalidThreshold),
+            t => t.map(|t| t as u8),
+        };
+        let (x, y) = x.zip(y).ok_or(SharksError::InvalidEncoding)?;
+        if x.len() != F::BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+        let mut elements = x.to_vec();
+        elements.extend_from_slice(y);
+        let share = FieldShare::try_from(elements.as_slice());
+        elements.zeroize();
+        let mut share = share?;
+        share.threshold = threshold;
+        share.group = group;
+        Ok(share)
+    }
+
+    fn finish(&self) -> Result<(), SharksError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(SharksError::InvalidEncoding)
+        }
+    }
+}
+
+impl<F: Field> FieldShare<F> {
+    /// Serializes the share as canonical CBOR, a map holding the schema version, the size of the field elements,
+    /// the threshold if known, the `x` and `y` values and the group identifier if known. Unlike the byte formats, the encoding describes itself,
+    /// so that stored shares remain readable across versions.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+    /// let encoded = shares[0].to_cbor();
+    /// let decoded = Share::from_cbor(&encoded).unwrap();
+    /// assert_eq!(decoded.threshold(), Some(2));
+    ///
+    /// let bundle = Share::bundle_to_cbor(&shares[1..]);
+    /// let mut decoded = Share::bundle_from_cbor(&bundle).unwrap();
+    /// decoded.push(Share::from_cbor(&encoded).unwrap());
+    /// assert_eq!(sharks.recover(&decoded).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + (self.y.len() + 1) * F::BYTES);
+        push_share(&mut bytes, self);
+        bytes
+    }
+
+    /// Parses a share serialized with `to_cbor`. An `Err` is returned if the bytes are not the canonical encoding
+    /// of a share, the schema version is unknown, the elements are of another field or the share is invalid.
+    pub fn from_cbor(s: &[u8]) -> Result<FieldShare<F>, SharksError> {
+        let mut reader = Reader { bytes: s };
+        let share = reader.share()?;
+        reader.finish()?;
+        Ok(share)
+    }
+
+    /// Serializes a bundle of shares as a canonical CBOR array of shares, each one encoded as with `to_cbor`.
+    pub fn bundle_to_cbor(shares: &[FieldShare<F>]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        push_head(&mut bytes, MAJOR_ARRAY, shares.len() as u64);
+        shares.iter().for_each(|s| push_share(&mut bytes, s));
+        bytes
+    }
+
+    /// Parses a bundle of shares serialized with `bundle_to_cbor`, validating each share as `from_cbor`.
+    pub fn bundle_from_cbor(s: &[u8]) -> Result<Vec<FieldShare<F>>, SharksError> {
+        let mut reader = Reader { bytes: s };
+        let len = reader.head(MAJOR_ARRAY)?;
+        // Each share takes at least 11 bytes, bounding the allocation with the input length
+        if len > (s.len() / 11) as u64 {
+            return Err(SharksError::InvalidEncoding);
+        }
+        let shares = (0..len)
+            .map(|_| reader.share())
+            .collect::<Result<Vec<FieldShare<F>>, SharksError>>()?;
+        reader.finish()?;
+        Ok(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldShare, Share, Sharks, SharksError, GF65536};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn cbor_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let encoded = shares[2].to_cbor();
+        // {0: 1, 1: 1, 2: 3, 3: h'03', 4: h'....'}
+        assert_eq!(
+            encoded[..12],
+            [0xa5, 0x00, 0x01, 0x01, 0x01, 0x02, 0x03, 0x03, 0x41, 0x03, 0x04, 0x44]
+        );
+        assert_eq!(encoded.len(), 16);
+        let decoded = Share::from_cbor(&encoded).unwrap();
+        assert!(decoded == shares[2]);
+        assert_eq!(decoded.threshold(), Some(3));
+        assert_eq!(decoded.to_cbor(), encoded);
+
+        let bundle = Share::bundle_to_cbor(&shares[..3]);
+        assert_eq!(bundle[0], 0x83);
+        let decoded = Share::bundle_from_cbor(&bundle).unwrap();
+        assert_eq!(sharks.recover(&decoded).unwrap(), vec![1, 2, 3, 4]);
+        assert!(Share::bundle_from_cbor(&[0x80]).unwrap().is_empty());
+
+        // Shares parsed from bytes have no threshold, and other fields are recorded
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(
+            share.to_cbor(),
+            vec![0xa4, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03]
+        );
+        let wide: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).take(3).collect();
+        let bundle = FieldShare::bundle_to_cbor(&wide);
+        let decoded: Vec<FieldShare<GF65536>> = FieldShare::bundle_from_cbor(&bundle).unwrap();
+        assert_eq!(sharks.recover_in(&decoded).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            Share::bundle_from_cbor(&bundle).err(),
+            Some(SharksError::InvalidElement)
+        );
+
+        // Group identifiers are kept, so that shares of different splits are not mixed
+        let mut share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        share.group = Some(0x0102);
+        let encoded = share.to_cbor();
+        assert_eq!(encoded[12..], [0x05, 0x19, 0x01, 0x02]);
+        assert_eq!(Share::from_cbor(&encoded).unwrap().group(), Some(0x0102));
+        let a: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(3).collect();
+        let b: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(3).collect();
+        let bundle = Share::bundle_to_cbor(&[a[0].clone(), a[1].clone(), b[2].clone()]);
+        assert_eq!(
+            sharks
+                .recover(&Share::bundle_from_cbor(&bundle).unwrap())
+                .err(),
+            Some(SharksError::MixedGroups)
+        );
+    }
+
+    #[test]
+    fn cbor_strict_decoding_err() {
+        let valid = [
+            0xa4, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03,
+        ];
+        assert!(Share::from_cbor(&valid).is_ok());
+
+        let invalid: [&[u8]; 7] = [
+            // Trailing bytes
+            &[
+                0xa4, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03, 0x00,
+            ],
+            // Truncated
+            &valid[..11],
+            // Non shortest integer
+            &[
+                0xa4, 0x00, 0x18, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03,
+            ],
+            // Unsorted keys
+            &[
+                0xa4, 0x01, 0x01, 0x00, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03,
+            ],
+            // Indefinite length map
+            &[
+                0xbf, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03, 0xff,
+            ],
+            // Unknown key
+            &[
+                0xa5, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03, 0x06, 0x00,
+            ],
+            // Missing `y` values
+            &[0xa3, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x01],
+        ];
+        for bytes in invalid.iter() {
+            assert_eq!(
+                Share::from_cbor(bytes).err(),
+                Some(SharksError::InvalidEncoding)
+            );
+        }
+
+        assert_eq!(
+            Share::from_cbor(&[
+                0xa4, 0x00, 0x02, 0x01, 0x01, 0x03, 0x41, 0x01, 0x04, 0x42, 0x02, 0x03
+            ])
+            .err(),
+            Some(SharksError::UnknownVersion(2))
+        );
+        assert_eq!(
+            Share::from_cbor(&[
+                0xa5, 0x00, 0x01, 0x01, 0x01, 0x02, 0x00, 0x03, 0x41, 0x01, 0x04, 0x41, 0x02
+            ])
+            .err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            Share::from_cbor(&[0xa4, 0x00, 0x01, 0x01, 0x01, 0x03, 0x41, 0x00, 0x04, 0x41, 0x02])
+                .err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert_eq!(
+            Share::bundle_from_cbor(&[0x9a, 0xff, 0xff, 0xff, 0xff]).err(),
+            Some(SharksError::InvalidEncoding)
+        );
+    }
+}
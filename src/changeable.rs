@@ -0,0 +1,300 @@
+// Shares recoverable at one of several thresholds, chosen after they were distributed.
+// Each share holds one layer per threshold, all of them at the same `x` coordinate. The layer of the
+// primary threshold, the `Sharks` one, shares the secret itself, while each alternative threshold layer
+// shares the secret masked with the ChaCha20 keystream of an activation key. Alternative thresholds
+// are thus unusable until their activation key, kept apart by the dealer or an escrow, is released.
+
+use std::convert::TryFrom;
+
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// Key releasing an alternative threshold of changeable shares, see `Sharks::dealer_changeable`.
+pub type ActivationKey = [u8; 32];
+
+// Masks the secret with the keystream of the activation key, or unmasks it
+fn mask(secret: &mut [u8], key: &ActivationKey) {
+    let mut pad = vec![0u8; secret.len()];
+    ChaCha20Rng::from_seed(*key).fill_bytes(&mut pad);
+    secret.iter_mut().zip(pad.iter()).for_each(|(s, p)| *s ^= p);
+    pad.zeroize();
+}
+
+/// A share recoverable at one of several thresholds, see `Sharks::dealer_changeable`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct ChangeableShare {
+    /// One share per threshold, all at the same `x` coordinate, the primary threshold one first.
+    /// Each of them embeds its threshold.
+    pub layers: Vec<Share>,
+}
+
+impl ChangeableShare {
+    /// Returns the thresholds the share can be recovered at, the primary one first.
+    pub fn thresholds(&self) -> Vec<u8> {
+        self.layers.iter().filter_map(|l| l.threshold()).collect()
+    }
+}
+
+/// Wipes the layers from memory.
+/// This is done automatically when the share is dropped.
+impl Zeroize for ChangeableShare {
+    fn zeroize(&mut self) {
+        self.layers.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl Drop for ChangeableShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ChangeableShare {}
+
+/// Obtains a byte vector from a `ChangeableShare` instance: `count || thresholds || x || y`,
+/// the `y` values of the layers following each other.
+impl From<&ChangeableShare> for Vec<u8> {
+    fn from(s: &ChangeableShare) -> Vec<u8> {
+        let mut bytes = vec![s.layers.len() as u8];
+        bytes.extend(s.thresholds());
+        if let Some(first) = s.layers.first() {
+            bytes.push(first.x.0);
+        }
+        for layer in s.layers.iter() {
+            bytes.extend(layer.y.iter().map(|y| y.0));
+        }
+        bytes
+    }
+}
+
+/// Obtains a `ChangeableShare` instance from a byte slice, validating its contents.
+/// An `Err` is returned if there are no layers, the thresholds are zero or repeated,
+/// or the remaining bytes cannot be split in as many valid shares.
+impl TryFrom<&[u8]> for ChangeableShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<ChangeableShare, Self::Error> {
+        let (count, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        let count = *count as usize;
+        if count == 0 || s.len() <= count + 1 {
+            return Err(SharksError::InvalidLength);
+        }
+        let (thresholds, s) = s.split_at(count);
+        if thresholds
+            .iter()
+            .enumerate()
+            .any(|(i, t)| *t == 0 || thresholds[..i].contains(t))
+        {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let (x, y) = s.split_at(1);
+        if y.is_empty() || !y.len().is_multiple_of(count) {
+            return Err(SharksError::InvalidLength);
+        }
+        let layers = thresholds
+            .iter()
+            .zip(y.chunks(y.len() / count))
+            .map(|(t, y)| {
+                let mut layer = Share::try_from([x, y].concat().as_slice())?;
+                layer.threshold = Some(*t);
+                Ok(layer)
+            })
+            .collect::<Result<Vec<Share>, SharksError>>()?;
+        Ok(ChangeableShare { layers })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice and alternative `thresholds`, returns an activation key for each of them,
+    /// in the same order, and an `Iterator` along new shares recoverable at any of the thresholds,
+    /// for instance at the `Sharks` primary threshold normally and at a lower one in emergencies.
+    /// Recovering at an alternative threshold requires its activation key, which must be kept apart,
+    /// by the dealer or an escrow, until the threshold is to be used. The maximum number of shares is 255,
+    /// each of them being as large as a regular share per threshold.
+    /// An `Err` is returned if an alternative threshold is zero, repeated or equal to the primary one.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, ChangeableShare };
+    /// let sharks = Sharks(4);
+    /// let (keys, dealer) = sharks.dealer_changeable(&mut rand::thread_rng(), &[1, 2, 3], &[2]).unwrap();
+    /// let shares: Vec<ChangeableShare> = dealer.take(5).collect();
+    /// assert_eq!(sharks.recover_changeable(&shares[..4], None).unwrap(), vec![1, 2, 3]);
+    ///
+    /// // In an emergency the 2-of-5 threshold is activated
+    /// let emergency = Sharks(2);
+    /// assert!(emergency.recover_changeable(&shares[..2], None).is_err());
+    /// assert_eq!(emergency.recover_changeable(&shares[..2], Some(&keys[0])).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn dealer_changeable<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+        thresholds: &[u8],
+    ) -> Result<(Vec<ActivationKey>, impl Iterator<Item = ChangeableShare>), SharksError> {
+        if self.0 == 0
+            || thresholds
+                .iter()
+                .enumerate()
+                .any(|(i, t)| *t == 0 || *t == self.0 || thresholds[..i].contains(t))
+        {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let mut keys = vec![ActivationKey::default(); thresholds.len()];
+        keys.iter_mut().for_each(|k| rng.fill_bytes(k));
+        let mut dealers = vec![self.dealer_with_rng(rng, secret)];
+        for (t, key) in thresholds.iter().zip(keys.iter()) {
+            let mut masked = secret.to_vec();
+            mask(&mut masked, key);
+            dealers.push(Sharks(*t).dealer_with_rng(rng, &masked));
+            masked.zeroize();
+        }
+
+        let shares = std::iter::from_fn(move || {
+            let layers = dealers
+                .iter_mut()
+                .map(|d| d.next())
+                .collect::<Option<Vec<Share>>>()?;
+            Some(ChangeableShare { layers })
+        });
+        Ok((keys, shares))
+    }
+
+    /// Given an iterable collection of changeable shares, recovers the original secret using their layers
+    /// of the `Sharks` threshold. Alternative thresholds require their activation `key`, which is ignored
+    /// for the primary one. An `Err` is returned if the shares cannot be recovered at this threshold,
+    /// the activation key is missing or `recover` fails. A wrong activation key recovers a wrong secret.
+    pub fn recover_changeable<'a, T>(
+        &self,
+        shares: T,
+        key: Option<&ActivationKey>,
+    ) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a ChangeableShare>,
+        T::IntoIter: Iterator<Item = &'a ChangeableShare>,
+    {
+        let mut primary = None;
+        let layers = shares
+            .into_iter()
+            .map(|s| {
+                let i = s
+                    .layers
+                    .iter()
+                    .position(|l| l.threshold() == Some(self.0))
+                    .ok_or(SharksError::ThresholdMismatch {
+                        expected: self.0,
+                        found: s.layers.first().and_then(|l| l.threshold()).unwrap_or(0),
+                    })?;
+                if *primary.get_or_insert(i == 0) != (i == 0) {
+                    return Err(SharksError::MixedShares);
+                }
+                Ok(&s.layers[i])
+            })
+            .collect::<Result<Vec<&Share>, SharksError>>()?;
+
+        let mut secret = self.recover(layers)?;
+        if primary == Some(false) {
+            mask(&mut secret, key.ok_or(SharksError::UnauthorizedShares)?);
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChangeableShare;
+    use crate::{Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn changeable_integration_works() {
+        let sharks = Sharks(4);
+        let (keys, dealer) = sharks
+            .dealer_changeable(&mut rand::thread_rng(), &[1, 2, 3, 4], &[2, 6])
+            .unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_ne!(keys[0], keys[1]);
+        let shares: Vec<ChangeableShare> = dealer
+            .map(|s| ChangeableShare::try_from(Vec::from(&s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(shares.len(), 255);
+        assert_eq!(shares[254].layers[2].x, GF256(255));
+        assert_eq!(shares[0].thresholds(), vec![4, 2, 6]);
+
+        assert_eq!(
+            sharks.recover_changeable(&shares[10..14], None),
+            Ok(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            Sharks(2).recover_changeable(&shares[..2], Some(&keys[0])),
+            Ok(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            Sharks(6).recover_changeable(&shares[..6], Some(&keys[1])),
+            Ok(vec![1, 2, 3, 4])
+        );
+        assert_ne!(
+            Sharks(2).recover_changeable(&shares[..2], Some(&keys[1])),
+            Ok(vec![1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn changeable_recover_err() {
+        let sharks = Sharks(3);
+        assert!(sharks
+            .dealer_changeable(&mut rand::thread_rng(), &[1], &[3])
+            .is_err());
+        assert!(sharks
+            .dealer_changeable(&mut rand::thread_rng(), &[1], &[2, 2])
+            .is_err());
+
+        let (_, dealer) = sharks
+            .dealer_changeable(&mut rand::thread_rng(), &[1, 2], &[2])
+            .unwrap();
+        let shares: Vec<ChangeableShare> = dealer.take(3).collect();
+        assert_eq!(
+            Sharks(2).recover_changeable(&shares, None),
+            Err(SharksError::UnauthorizedShares)
+        );
+        assert_eq!(
+            Sharks(4).recover_changeable(&shares, None),
+            Err(SharksError::ThresholdMismatch {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            sharks.recover_changeable(&shares[..2], None),
+            Err(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+    }
+
+    #[test]
+    fn changeable_bytes_err() {
+        let cases: [(&[u8], SharksError); 6] = [
+            (&[], SharksError::EmptyShare),
+            (&[0, 1], SharksError::InvalidLength),
+            (&[1, 2, 1], SharksError::InvalidLength),
+            (&[2, 2, 2, 1, 5, 6], SharksError::InvalidThreshold),
+            (&[2, 2, 3, 1, 5, 6, 7], SharksError::InvalidLength),
+            (&[1, 2, 0, 5], SharksError::ZeroShareX),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ChangeableShare::try_from(*bytes).err(), Some(*err));
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// Hash commitments to each share of a split, letting custodians check their own share without any other one.
+// The dealer publishes, along with the shares, the SHA-256 digest of each of them salted with a random value
+// common to the whole set: `SHA-256(domain || salt || x || y)`. Commitments are serialized as
+// `version || salt || (x || digest)..`. Unlike the `vss` schemes they do not prove that the shares are consistent
+// with each other, only that a share is the one the dealer issued, which detects silent corruption of backups.
+
+use std::convert::TryFrom;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use super::error::SharksError;
+use super::share::Share;
+
+/// Version byte prefixed to serialized `ShareCommitments`.
+pub const COMMITMENTS_VERSION: u8 = 1;
+
+const SALT_BYTES: usize = 32;
+const DIGEST_BYTES: usize = 32;
+
+// Domain separation of the share digests
+const DOMAIN: &[u8] = b"sharks share commitment";
+
+fn digest(salt: &[u8; SALT_BYTES], share: &Share) -> [u8; DIGEST_BYTES] {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update(salt);
+    hasher.update(Vec::from(share));
+    hasher.finalize().into()
+}
+
+/// Salted SHA-256 commitments to a set of shares, published by the dealer so that each custodian can check
+/// the share they received, and then periodically their backup of it. Can be serialized to and from a byte array.
+/// Shares of secrets shorter than 16 bytes can be brute forced from their digest,
+/// so their commitments should only be given to the custodians.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, Share, ShareCommitments };
+/// # use std::convert::TryFrom;
+/// let shares: Vec<Share> = Sharks(2).dealer(&[1; 32]).take(3).collect();
+/// let published = Vec::from(&ShareCommitments::new(&shares));
+///
+/// // A custodian checks their share against the published commitments
+/// let commitments = ShareCommitments::try_from(published.as_slice()).unwrap();
+/// assert!(commitments.verify_share(&shares[1]));
+/// let mut corrupted = shares[1].clone();
+/// corrupted.y[0].0 ^= 1;
+/// assert!(!commitments.verify_share(&corrupted));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareCommitments {
+    salt: [u8; SALT_BYTES],
+    digests: Vec<(u8, [u8; DIGEST_BYTES])>,
+}
+
+impl ShareCommitments {
+    /// Commits to each of the `shares`, with a random salt.
+    pub fn new<'a, T>(shares: T) -> ShareCommitments
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        let mut salt = [0u8; SALT_BYTES];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let digests = shares
+            .into_iter()
+            .map(|s| (s.x.0, digest(&salt, s)))
+            .collect();
+        ShareCommitments { salt, digests }
+    }
+
+    /// Returns the `x` coordinates of the committed shares.
+    pub fn xs(&self) -> Vec<u8> {
+        self.digests.iter().map(|(x, _)| *x).collect()
+    }
+
+    /// Checks the `share` is the committed one at its `x` coordinate, the digests being compared in constant time.
+    /// Returns `false` if no share was committed at this coordinate.
+    pub fn verify_share(&self, share: &Share) -> bool {
+        let expected = digest(&self.salt, share);
+        self.digests
+            .iter()
+            .any(|(x, d)| *x == share.x.0 && bool::from(d.ct_eq(&expected)))
+    }
+}
+
+/// Obtains a byte vector from a `ShareCommitments` instance: `version || salt || (x || digest)..`
+impl From<&ShareCommitments> for Vec<u8> {
+    fn from(c: &ShareCommitments) -> Vec<u8> {
+        let mut bytes = vec![COMMITMENTS_VERSION];
+        bytes.extend_from_slice(&c.salt);
+        for (x, d) in c.digests.iter() {
+            bytes.push(*x);
+            bytes.extend_from_slice(d);
+        }
+        bytes
+    }
+}
+
+/// Obtains a `ShareCommitments` instance from a byte slice, validating its contents.
+/// An `Err` is returned if the version is unknown, the length is invalid,
+/// or a commitment is at a zero or repeated `x` coordinate.
+impl TryFrom<&[u8]> for ShareCommitments {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<ShareCommitments, Self::Error> {
+        let (version, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        if *version != COMMITMENTS_VERSION {
+            return Err(SharksError::UnknownVersion(*version));
+        } else if s.len() < SALT_BYTES || !(s.len() - SALT_BYTES).is_multiple_of(1 + DIGEST_BYTES) {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let (salt, s) = s.split_at(SALT_BYTES);
+        let mut digests: Vec<(u8, [u8; DIGEST_BYTES])> = Vec::new();
+        for c in s.chunks(1 + DIGEST_BYTES) {
+            if c[0] == 0 {
+                return Err(SharksError::ZeroShareX);
+            } else if digests.iter().any(|(x, _)| *x == c[0]) {
+                return Err(SharksError::DuplicateShareX);
+            }
+            digests.push((c[0], <[u8; DIGEST_BYTES]>::try_from(&c[1..]).unwrap()));
+        }
+        Ok(ShareCommitments {
+            salt: <[u8; SALT_BYTES]>::try_from(salt).unwrap(),
+            digests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShareCommitments, COMMITMENTS_VERSION};
+    use crate::{Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn commitments_integration_works() {
+        let shares: Vec<Share> = Sharks(3).dealer(&[1, 2, 3, 4]).take(5).collect();
+        let commitments = ShareCommitments::new(&shares[..4]);
+        assert_eq!(commitments.xs(), vec![1, 2, 3, 4]);
+
+        let bytes = Vec::from(&commitments);
+        assert_eq!(bytes.len(), 1 + 32 + 4 * 33);
+        let commitments = ShareCommitments::try_from(bytes.as_slice()).unwrap();
+        assert!(shares[..4].iter().all(|s| commitments.verify_share(s)));
+        assert!(!commitments.verify_share(&shares[4]));
+
+        // Shares parsed from bytes, without a known threshold, verify as well
+        let share = Share::try_from(Vec::from(&shares[2]).as_slice()).unwrap();
+        assert!(commitments.verify_share(&share));
+        let mut moved = shares[2].clone();
+        moved.x.0 = 1;
+        assert!(!commitments.verify_share(&moved));
+        let mut truncated = shares[2].clone();
+        truncated.y.pop();
+        assert!(!commitments.verify_share(&truncated));
+
+        // Salts differ between commitment sets
+        assert_ne!(
+            ShareCommitments::new(&shares),
+            ShareCommitments::new(&shares)
+        );
+    }
+
+    #[test]
+    fn commitments_bytes_err() {
+        let shares: Vec<Share> = Sharks(2).dealer(&[1]).take(2).collect();
+        let bytes = Vec::from(&ShareCommitments::new(&shares));
+        let mut zero = bytes.clone();
+        zero[33] = 0;
+        let mut duplicate = bytes.clone();
+        duplicate[66] = bytes[33];
+
+        let cases: [(&[u8], SharksError); 6] = [
+            (&[], SharksError::EmptyShare),
+            (&[COMMITMENTS_VERSION + 1], SharksError::UnknownVersion(2)),
+            (&bytes[..32], SharksError::InvalidLength),
+            (&bytes[..bytes.len() - 1], SharksError::InvalidLength),
+            (&zero, SharksError::ZeroShareX),
+            (&duplicate, SharksError::DuplicateShareX),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ShareCommitments::try_from(*bytes).err(), Some(*err));
+        }
+    }
+}
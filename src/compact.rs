@@ -0,0 +1,227 @@
+// Computational secret sharing (Krawczyk), making shares about threshold times smaller than the secret.
+// The secret is encrypted with ChaCha20-Poly1305 under a random key used only once, and only the key is shared
+// with Shamir. The ciphertext is erasure coded instead, as a ramp scheme packing threshold bytes per polynomial,
+// so that any threshold fragments rebuild it although each one is threshold times smaller.
+// Fewer shares than the threshold reveal nothing about the key, and thus only the length of the secret,
+// as long as ChaCha20-Poly1305 is secure. Tampered fragments are rejected when decrypting.
+
+use std::convert::TryFrom;
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+// Length in bytes of the ChaCha20-Poly1305 keys
+const KEY_BYTES: usize = 32;
+
+/// A share of a secret split with `Sharks::dealer_compact`, holding a Shamir share of the encryption key
+/// and a fragment of the ciphertext. Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct CompactShare {
+    /// Shamir share of the encryption key, embedding the threshold.
+    pub key: Share,
+    /// Erasure coded fragment of the ciphertext, at the same `x` coordinate.
+    pub data: Share,
+}
+
+/// Wipes the key share and the fragment from memory.
+/// This is done automatically when the share is dropped.
+impl Zeroize for CompactShare {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.data.zeroize();
+    }
+}
+
+impl Drop for CompactShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for CompactShare {}
+
+/// Obtains a byte vector from a `CompactShare` instance: `threshold || x || key y || data y`,
+/// with a zero threshold if unknown.
+impl From<&CompactShare> for Vec<u8> {
+    fn from(s: &CompactShare) -> Vec<u8> {
+        let mut bytes = vec![s.key.threshold().unwrap_or(0), s.key.x.0];
+        bytes.extend(s.key.y.iter().map(|y| y.0));
+        bytes.extend(s.data.y.iter().map(|y| y.0));
+        bytes
+    }
+}
+
+/// Obtains a `CompactShare` instance from a byte slice, validating its contents.
+/// An `Err` is returned if it is too short to hold the key share and a fragment, or the `x` coordinate is zero.
+impl TryFrom<&[u8]> for CompactShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<CompactShare, Self::Error> {
+        let (threshold, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        if s.len() <= KEY_BYTES + 1 {
+            return Err(SharksError::InvalidLength);
+        }
+        let (key, data) = s.split_at(KEY_BYTES + 1);
+        let threshold = Some(*threshold).filter(|t| *t != 0);
+        let mut data = Share::try_from([&key[..1], data].concat().as_slice())?;
+        let mut key = Share::try_from(key)?;
+        key.threshold = threshold;
+        data.threshold = threshold;
+        Ok(CompactShare { key, data })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns an `Iterator` along new compact shares, each about `1 / threshold`
+    /// the size of the secret plus 34 bytes, instead of as large as it. Suits large secrets such as backups.
+    /// The secret is encrypted with a random key, shared with Shamir, and the ciphertext erasure coded,
+    /// so that secrecy relies on ChaCha20-Poly1305 instead of being unconditional. The maximum number of shares is 255.
+    /// An `Err` is returned if the threshold is zero.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, CompactShare };
+    /// let sharks = Sharks(4);
+    /// let secret = vec![7; 4000];
+    /// let shares: Vec<CompactShare> = sharks.dealer_compact(&mut rand::thread_rng(), &secret).unwrap().take(6).collect();
+    /// assert!(Vec::from(&shares[0]).len() < 1100);
+    /// assert_eq!(sharks.recover_compact(&shares[2..]).unwrap(), secret);
+    /// assert!(sharks.recover_compact(&shares[3..]).is_err());
+    /// ```
+    pub fn dealer_compact<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+    ) -> Result<impl Iterator<Item = CompactShare>, SharksError> {
+        if self.0 == 0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let mut key = [0u8; KEY_BYTES];
+        rng.fill_bytes(&mut key);
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(Nonce::from_slice(&[0; 12]), secret)
+            .expect("secrets are shorter than the ChaCha20-Poly1305 limit");
+        let keys: Vec<Share> = self.dealer_with_rng(rng, &key).take(255).collect();
+        key.zeroize();
+
+        let fragments = self.dealer_ramp(&ciphertext, self.0)?;
+        Ok(keys
+            .into_iter()
+            .zip(fragments)
+            .map(|(key, data)| CompactShare { key, data }))
+    }
+
+    /// Given an iterable collection of shares generated by `dealer_compact`, recovers the original secret.
+    /// An `Err` is returned for the same reasons as `recover`, if the key share and fragment of a share are
+    /// at different `x` coordinates, or if the fragments were tampered with or belong to another secret.
+    pub fn recover_compact<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a CompactShare>,
+        T::IntoIter: Iterator<Item = &'a CompactShare>,
+    {
+        let shares: Vec<&CompactShare> = shares.into_iter().collect();
+        if shares.iter().any(|s| s.key.x != s.data.x) {
+            return Err(SharksError::MixedShares);
+        }
+
+        let ciphertext = self.recover_ramp(shares.iter().map(|s| &s.data), self.0)?;
+        let mut key = self.recover(shares.iter().map(|s| &s.key))?;
+        if key.len() != KEY_BYTES {
+            key.zeroize();
+            return Err(SharksError::InvalidLength);
+        }
+        let secret = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(Nonce::from_slice(&[0; 12]), ciphertext.as_slice())
+            .map_err(|_| SharksError::InvalidCiphertext);
+        key.zeroize();
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactShare;
+    use crate::{Sharks, SharksError};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn compact_integration_works() {
+        let mut rng = ChaCha8Rng::from_seed([7; 32]);
+        for threshold in 1..5 {
+            let sharks = Sharks(threshold);
+            for len in [0usize, 1, 15, 1000].iter() {
+                let secret: Vec<u8> = (0..*len).map(|i| i as u8).collect();
+                let shares: Vec<CompactShare> = sharks
+                    .dealer_compact(&mut rng, &secret)
+                    .unwrap()
+                    .take(6)
+                    .collect();
+                assert_eq!(shares[0].key.threshold(), Some(threshold));
+                let padded = if threshold > 1 { len + 17 } else { len + 16 };
+                assert_eq!(shares[0].data.y.len(), padded.div_ceil(threshold as usize));
+                assert_eq!(sharks.recover_compact(&shares[1..]).unwrap(), secret);
+
+                let parsed: Vec<CompactShare> = shares[6 - threshold as usize..]
+                    .iter()
+                    .map(|s| CompactShare::try_from(Vec::from(s).as_slice()).unwrap())
+                    .collect();
+                assert_eq!(parsed[0].key.x, shares[6 - threshold as usize].key.x);
+                assert_eq!(sharks.recover_compact(&parsed).unwrap(), secret);
+            }
+        }
+    }
+
+    #[test]
+    fn compact_err() {
+        let sharks = Sharks(2);
+        let mut rng = ChaCha8Rng::from_seed([8; 32]);
+        assert_eq!(
+            Sharks(0).dealer_compact(&mut rng, &[1]).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        let mut shares: Vec<CompactShare> = sharks
+            .dealer_compact(&mut rng, &[1, 2, 3])
+            .unwrap()
+            .take(3)
+            .collect();
+        assert_eq!(
+            sharks.recover_compact(&shares[..1]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+
+        shares[0].data.y[0].0 ^= 1;
+        assert_eq!(
+            sharks.recover_compact(&shares[..2]).err(),
+            Some(SharksError::InvalidCiphertext)
+        );
+        assert_eq!(sharks.recover_compact(&shares[1..]).unwrap(), vec![1, 2, 3]);
+        shares[1].data.x = shares[2].data.x;
+        assert_eq!(
+            sharks.recover_compact(&shares[1..]).err(),
+            Some(SharksError::MixedShares)
+        );
+
+        assert_eq!(
+            CompactShare::try_from(&[][..]).err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            CompactShare::try_from(&[2; 34][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+    }
+}
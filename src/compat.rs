@@ -0,0 +1,531 @@
+// Interoperability with the classic `gfsplit`/`gfcombine` (libgfshare) and `ssss-split`/`ssss-combine` tools.
+//
+// libgfshare splits each byte over the same field as `GF256`, modulo x**8 + x**4 + x**3 + x**2 + 1, and evaluates
+// the usual polynomials with the secret as constant term, so its shares are regular `Share`s. Only the layout
+// differs: each share is a file holding the `y` bytes, whose name ends with the `x` coordinate as a 3 digits suffix.
+//
+// ssss instead handles the whole secret as a single element of the binary field GF(2**(8 * len)), modulo the
+// lexicographically first irreducible pentanomial of that degree. Shares are the points `x = 1, 2, ...` of the monic
+// polynomial `x**k + c[k - 1] * x**(k - 1) + ... + c[1] * x + secret`, written as `[token-]x-hex(y)`.
+// The optional diffusion layer ssss applies by default to secrets of 8 bytes or more is not supported,
+// so such shares must be generated and combined with the `-D` flag.
+
+use std::fmt;
+
+use rand::RngCore;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::encoding;
+use super::error::SharksError;
+use super::field::GF256;
+use super::share::Share;
+use super::Sharks;
+
+// Middle terms of the irreducible pentanomials `x**d + x**a + x**b + x**c + 1` used by ssss,
+// for every degree `d` multiple of 8, from 8 to 1024
+#[rustfmt::skip]
+const SSSS_POLYNOMIALS: [[u8; 3]; 128] = [
+    [4, 3, 1], [5, 3, 1], [4, 3, 1], [7, 3, 2], [5, 4, 3], [5, 3, 2], [7, 4, 2], [4, 3, 1],
+    [10, 9, 3], [9, 4, 2], [7, 6, 2], [10, 9, 6], [4, 3, 1], [5, 4, 3], [4, 3, 1], [7, 2, 1],
+    [5, 3, 2], [7, 4, 2], [6, 3, 2], [5, 3, 2], [15, 3, 2], [11, 3, 2], [9, 8, 7], [7, 2, 1],
+    [5, 3, 2], [9, 3, 1], [7, 3, 1], [9, 8, 3], [9, 4, 2], [8, 5, 3], [15, 14, 10], [10, 5, 2],
+    [9, 6, 2], [9, 3, 2], [9, 5, 2], [11, 10, 1], [7, 3, 2], [11, 2, 1], [9, 7, 4], [4, 3, 1],
+    [8, 3, 1], [7, 4, 1], [7, 2, 1], [13, 11, 6], [5, 3, 2], [7, 3, 2], [8, 7, 5], [12, 3, 2],
+    [13, 10, 6], [5, 3, 2], [5, 3, 2], [9, 5, 2], [9, 7, 2], [13, 4, 3], [4, 3, 1], [11, 6, 4],
+    [18, 9, 6], [19, 18, 13], [11, 3, 2], [15, 9, 6], [4, 3, 1], [16, 5, 2], [15, 14, 6], [8, 5, 2],
+    [15, 11, 2], [11, 6, 2], [7, 5, 3], [8, 3, 1], [19, 16, 9], [11, 9, 6], [15, 7, 6], [13, 4, 3],
+    [14, 13, 3], [13, 6, 3], [9, 5, 2], [19, 13, 6], [19, 10, 3], [11, 6, 5], [9, 2, 1], [14, 3, 2],
+    [13, 3, 1], [7, 5, 4], [11, 9, 8], [11, 6, 5], [23, 16, 9], [19, 14, 6], [23, 10, 2], [8, 3, 2],
+    [5, 4, 3], [9, 6, 4], [4, 3, 2], [13, 8, 6], [13, 11, 1], [13, 10, 3], [11, 6, 5], [19, 17, 4],
+    [15, 14, 7], [13, 9, 6], [9, 7, 3], [9, 7, 1], [14, 3, 2], [11, 8, 2], [11, 6, 4], [13, 5, 2],
+    [11, 5, 1], [11, 4, 1], [19, 10, 3], [21, 10, 6], [13, 3, 1], [15, 7, 5], [19, 18, 10], [7, 5, 3],
+    [12, 7, 2], [7, 5, 1], [14, 9, 6], [10, 3, 2], [15, 13, 12], [12, 11, 9], [16, 9, 7], [12, 9, 3],
+    [9, 5, 2], [17, 10, 6], [24, 9, 3], [17, 15, 13], [5, 4, 3], [19, 17, 8], [15, 6, 3], [19, 6, 1],
+];
+
+// Maximum secret length supported by ssss, in bytes
+const SSSS_MAX_BYTES: usize = SSSS_POLYNOMIALS.len();
+
+impl Share {
+    /// Parses a share written by `gfsplit`, from the `name` of its file and its `contents`.
+    /// The name must end with the `x` coordinate as a 3 digits suffix, like `secret.txt.042`.
+    /// An `Err` is returned if the suffix is missing, zero or out of range, or the contents are empty.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(2);
+    /// let files: Vec<(String, Vec<u8>)> = sharks
+    ///     .dealer(b"secret")
+    ///     .take(3)
+    ///     .map(|s| s.to_gfshare("secret.txt"))
+    ///     .collect();
+    /// assert_eq!(files[0].0, "secret.txt.001");
+    ///
+    /// // Files readable by `gfcombine secret.txt.001 secret.txt.003`, and the other way around
+    /// let shares: Vec<Share> = files.iter().map(|(n, c)| Share::from_gfshare(n, c).unwrap()).collect();
+    /// assert_eq!(sharks.recover(&shares[1..]).unwrap(), b"secret");
+    /// ```
+    pub fn from_gfshare(name: &str, contents: &[u8]) -> Result<Share, SharksError> {
+        let suffix = name
+            .rsplit_once('.')
+            .map(|(_, suffix)| suffix)
+            .filter(|s| s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or(SharksError::InvalidEncoding)?;
+        let x = match suffix.parse::<u8>() {
+            Ok(0) => return Err(SharksError::ZeroShareX),
+            Ok(x) => x,
+            Err(_) => return Err(SharksError::InvalidIndex),
+        };
+        if contents.is_empty() {
+            return Err(SharksError::EmptyShare);
+        }
+        Ok(Share {
+            x: GF256(x),
+            y: contents.iter().map(|b| GF256(*b)).collect(),
+            threshold: None,
+            group: None,
+        })
+    }
+
+    /// Returns the file name and contents `gfcombine` expects for the share, the name being
+    /// the `stem` followed by the `x` coordinate as a 3 digits suffix.
+    pub fn to_gfshare(&self, stem: &str) -> (String, Vec<u8>) {
+        (
+            format!("{}.{:03}", stem, self.x.0),
+            self.y.iter().map(|y| y.0).collect(),
+        )
+    }
+}
+
+// Binary field GF(2**degree) used by ssss, its elements stored as little endian 64 bits limbs
+struct BinaryField {
+    degree: usize,
+    modulus: Vec<u64>,
+}
+
+impl BinaryField {
+    fn new(degree: usize) -> BinaryField {
+        let [a, b, c] = SSSS_POLYNOMIALS[degree / 8 - 1];
+        let mut modulus = vec![0; degree / 64 + 1];
+        for bit in [degree, a as usize, b as usize, c as usize, 0].iter() {
+            modulus[bit / 64] |= 1 << (bit % 64);
+        }
+        BinaryField { degree, modulus }
+    }
+
+    fn zero(&self) -> Vec<u64> {
+        vec![0; self.modulus.len()]
+    }
+
+    fn element(&self, n: u8) -> Vec<u64> {
+        let mut e = self.zero();
+        e[0] = n as u64;
+        e
+    }
+
+    // Reads a big endian element of `degree / 8` bytes
+    fn decode(&self, bytes: &[u8]) -> Vec<u64> {
+        let mut e = self.zero();
+        for (i, b) in bytes.iter().rev().enumerate() {
+            e[i / 8] |= (*b as u64) << (8 * (i % 8));
+        }
+        e
+    }
+
+    fn encode(&self, e: &[u64]) -> Vec<u8> {
+        (0..self.degree / 8)
+            .rev()
+            .map(|i| (e[i / 8] >> (8 * (i % 8))) as u8)
+            .collect()
+    }
+
+    fn add(a: &mut [u64], b: &[u64]) {
+        a.iter_mut().zip(b).for_each(|(a, b)| *a ^= b);
+    }
+
+    // Shift-and-add multiplication, interleaving the reductions through masks so that it runs in constant time
+    fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let top = (self.degree / 64, self.degree % 64);
+        let mut r = self.zero();
+        for i in (0..self.degree).rev() {
+            let mut carry = 0;
+            for limb in r.iter_mut() {
+                let next = *limb >> 63;
+                *limb = *limb << 1 | carry;
+                carry = next;
+            }
+            let reduce = (r[top.0] >> top.1 & 1).wrapping_neg();
+            let add = (b[i / 64] >> (i % 64) & 1).wrapping_neg();
+            for ((r, m), a) in r.iter_mut().zip(&self.modulus).zip(a) {
+                *r ^= (m & reduce) ^ (a & add);
+            }
+        }
+        r
+    }
+
+    fn bits(e: &[u64]) -> usize {
+        e.iter()
+            .rposition(|l| *l != 0)
+            .map_or(0, |i| 64 * i + 64 - e[i].leading_zeros() as usize)
+    }
+
+    // Adds `b * x**shift` to `a`
+    fn add_shifted(a: &mut [u64], b: &[u64], shift: usize) {
+        let (limbs, bits) = (shift / 64, shift % 64);
+        for i in (limbs..a.len()).rev() {
+            let low = b[i - limbs] << bits;
+            let high = match (bits, i - limbs) {
+                (0, _) | (_, 0) => 0,
+                (_, j) => b[j - 1] >> (64 - bits),
+            };
+            a[i] ^= low | high;
+        }
+    }
+
+    // Binary extended Euclidean algorithm, `e` being non-zero
+    fn inverse(&self, e: &[u64]) -> Vec<u64> {
+        let (mut u, mut v) = (e.to_vec(), self.modulus.clone());
+        let (mut g1, mut g2) = (self.element(1), self.zero());
+        while BinaryField::bits(&u) > 1 {
+            let (bu, bv) = (BinaryField::bits(&u), BinaryField::bits(&v));
+            if bu < bv {
+                std::mem::swap(&mut u, &mut v);
+                std::mem::swap(&mut g1, &mut g2);
+            }
+            let shift = bu.max(bv) - bu.min(bv);
+            BinaryField::add_shifted(&mut u, &v, shift);
+            BinaryField::add_shifted(&mut g1, &g2, shift);
+        }
+        g1
+    }
+}
+
+/// A share generated by the `ssss` tool, or readable by it, see `Sharks::dealer_ssss`.
+/// Can be parsed from and written to the `ssss` text format.
+#[derive(Clone)]
+pub struct SsssShare {
+    /// Index of the share, its `x` coordinate.
+    pub x: u8,
+    /// Point of the polynomial at `x`, a big endian element of as many bytes as the secret.
+    pub y: Vec<u8>,
+}
+
+/// Prints the share `x` coordinate and length, the `y` value being redacted so that shares do not leak into logs.
+impl fmt::Debug for SsssShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SsssShare")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", self.y.len()))
+            .finish()
+    }
+}
+
+/// Wipes the share from memory.
+/// This is done automatically when the share is dropped.
+impl Zeroize for SsssShare {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.zeroize();
+    }
+}
+
+impl Drop for SsssShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SsssShare {}
+
+impl SsssShare {
+    /// Writes the share in the `ssss` text format, `x-hex(y)`, as printed by `ssss-split`.
+    pub fn to_text(&self) -> String {
+        format!("{}-{}", self.x, encoding::to_hex(&self.y))
+    }
+
+    /// Parses a share in the `ssss` text format, `[token-]x-hex(y)`, the token being ignored.
+    /// An `Err` is returned if the index is not a number between 1 and 255, or the hexadecimal `y` value
+    /// is not as long as a secret `ssss` supports, between 1 and 128 bytes.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, SsssShare };
+    /// // Second share of a 6 bytes secret, prefixed with the `backup` token
+    /// let share = SsssShare::from_text("backup-2-5e1e0aa199b1").unwrap();
+    /// assert_eq!((share.x, share.y.len()), (2, 6));
+    /// assert_eq!(share.to_text(), "2-5e1e0aa199b1");
+    /// ```
+    pub fn from_text(text: &str) -> Result<SsssShare, SharksError> {
+        let mut parts = text.trim().rsplitn(3, '-');
+        let (y, x) = match (parts.next(), parts.next()) {
+            (Some(y), Some(x)) => (y, x),
+            _ => return Err(SharksError::InvalidEncoding),
+        };
+        let x = match x.parse::<u8>() {
+            Ok(0) => return Err(SharksError::ZeroShareX),
+            Ok(x) => x,
+            Err(_) => return Err(SharksError::InvalidEncoding),
+        };
+        let y = encoding::from_hex(y).ok_or(SharksError::InvalidEncoding)?;
+        if y.is_empty() || y.len() > SSSS_MAX_BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+        Ok(SsssShare { x, y })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` of 1 to 128 bytes, returns `n` shares compatible with `ssss-combine -D`,
+    /// the threshold of which must be given to it.
+    /// An `Err` is returned if the secret length is not supported, the threshold is zero or `n` is lower than it.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, SsssShare };
+    /// let sharks = Sharks(3);
+    /// let shares = sharks.dealer_ssss(b"secret", 5).unwrap();
+    /// // Written for `ssss-combine -t 3 -D`
+    /// let text: Vec<String> = shares.iter().map(|s| s.to_text()).collect();
+    /// let shares: Vec<SsssShare> = text.iter().map(|t| SsssShare::from_text(t).unwrap()).collect();
+    /// assert_eq!(sharks.recover_ssss(&shares[2..]).unwrap(), b"secret");
+    /// ```
+    pub fn dealer_ssss(&self, secret: &[u8], n: u8) -> Result<Vec<SsssShare>, SharksError> {
+        if secret.is_empty() || secret.len() > SSSS_MAX_BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+        if self.0 == 0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+        if n < self.0 {
+            return Err(SharksError::TooFewShares {
+                min: self.0 as usize,
+            });
+        }
+
+        let field = BinaryField::new(secret.len() * 8);
+        let mut rng = rand::thread_rng();
+        let mut coeffs: Vec<Vec<u64>> = (1..self.0)
+            .map(|_| {
+                let mut bytes = vec![0u8; secret.len()];
+                rng.fill_bytes(&mut bytes);
+                let coeff = field.decode(&bytes);
+                bytes.zeroize();
+                coeff
+            })
+            .collect();
+        coeffs.insert(0, field.decode(secret));
+
+        let shares = (1..=n)
+            .map(|i| {
+                let x = field.element(i);
+                // Horner's rule, the leading coefficient being one
+                let mut y = x.clone();
+                for coeff in coeffs[1..].iter().rev() {
+                    BinaryField::add(&mut y, coeff);
+                    y = field.mul(&y, &x);
+                }
+                BinaryField::add(&mut y, &coeffs[0]);
+                SsssShare {
+                    x: i,
+                    y: field.encode(&y),
+                }
+            })
+            .collect();
+
+        coeffs.zeroize();
+        Ok(shares)
+    }
+
+    /// Given an iterable collection of shares, generated by `ssss-split -D` or `dealer_ssss`,
+    /// recovers the original secret. The threshold must be the `ssss-split` one, since it determines
+    /// the polynomial the shares are points of. Only the first threshold distinct shares are used.
+    /// An `Err` is returned if less than threshold distinct shares are given, or they are inconsistent.
+    pub fn recover_ssss<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a SsssShare>,
+        T::IntoIter: Iterator<Item = &'a SsssShare>,
+    {
+        let mut distinct: Vec<&SsssShare> = Vec::new();
+        let mut provided = 0;
+        for s in shares {
+            provided += 1;
+            match distinct.iter().find(|d| d.x == s.x) {
+                Some(d) if d.y != s.y => return Err(SharksError::DuplicateShareX),
+                Some(_) => {}
+                None => distinct.push(s),
+            }
+        }
+        if self.0 == 0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+        if distinct.len() < self.0 as usize {
+            return Err(SharksError::NotEnoughShares {
+                needed: self.0 as usize,
+                got: distinct.len(),
+                provided,
+            });
+        }
+        let len = distinct[0].y.len();
+        if len == 0 || len > SSSS_MAX_BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+        if distinct.iter().any(|s| s.y.len() != len) {
+            return Err(SharksError::InconsistentLengths);
+        }
+        if distinct.iter().any(|s| s.x == 0) {
+            return Err(SharksError::ZeroShareX);
+        }
+
+        let field = BinaryField::new(len * 8);
+        let points: Vec<(Vec<u64>, Vec<u64>)> = distinct[..self.0 as usize]
+            .iter()
+            .map(|s| {
+                let x = field.element(s.x);
+                // Removes the leading term, leaving a polynomial of degree `k - 1` to interpolate
+                let mut y = field.decode(&s.y);
+                let power = (0..self.0).fold(field.element(1), |p, _| field.mul(&p, &x));
+                BinaryField::add(&mut y, &power);
+                (x, y)
+            })
+            .collect();
+
+        // Lagrange interpolation at zero, subtraction being addition in binary fields
+        let mut secret = field.zero();
+        for (i, (xi, yi)) in points.iter().enumerate() {
+            let (mut num, mut den) = (field.element(1), field.element(1));
+            for (j, (xj, _)) in points.iter().enumerate() {
+                if i != j {
+                    num = field.mul(&num, xj);
+                    let mut diff = xj.clone();
+                    BinaryField::add(&mut diff, xi);
+                    den = field.mul(&den, &diff);
+                }
+            }
+            let term = field.mul(yi, &field.mul(&num, &field.inverse(&den)));
+            BinaryField::add(&mut secret, &term);
+        }
+        Ok(field.encode(&secret))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryField, SsssShare, SSSS_POLYNOMIALS};
+    use crate::{Share, Sharks, SharksError};
+
+    #[test]
+    fn gfshare_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..40).collect();
+        let files: Vec<(String, Vec<u8>)> = sharks
+            .dealer(&secret)
+            .take(5)
+            .map(|s| s.to_gfshare("key"))
+            .collect();
+        assert_eq!(files[4].0, "key.005");
+        assert_eq!(files[4].1.len(), secret.len());
+
+        let shares: Vec<Share> = files
+            .iter()
+            .map(|(name, contents)| Share::from_gfshare(name, contents).unwrap())
+            .collect();
+        assert_eq!(sharks.recover(&shares[2..]).unwrap(), secret);
+
+        assert_eq!(
+            Share::from_gfshare("key.000", &[1]).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert_eq!(
+            Share::from_gfshare("key.256", &[1]).err(),
+            Some(SharksError::InvalidIndex)
+        );
+        assert_eq!(
+            Share::from_gfshare("key.1", &[1]).err(),
+            Some(SharksError::InvalidEncoding)
+        );
+        assert_eq!(
+            Share::from_gfshare("key.001", &[]).err(),
+            Some(SharksError::EmptyShare)
+        );
+    }
+
+    #[test]
+    fn binary_field_works() {
+        for degree in [8, 16, 72, 1024].iter() {
+            let field = BinaryField::new(*degree);
+            let bytes: Vec<u8> = (0..*degree / 8).map(|i| (i * 37 + 11) as u8).collect();
+            let e = field.decode(&bytes);
+            assert_eq!(field.encode(&e), bytes);
+            assert_eq!(field.mul(&e, &field.inverse(&e)), field.element(1));
+        }
+        // AES field, modulo x**8 + x**4 + x**3 + x + 1
+        let field = BinaryField::new(8);
+        assert_eq!(
+            field.mul(&field.element(0x57), &field.element(0x83)),
+            field.element(0xc1)
+        );
+        assert_eq!(SSSS_POLYNOMIALS[127], [19, 6, 1]);
+    }
+
+    #[test]
+    fn ssss_integration_works() {
+        let sharks = Sharks(3);
+        for len in [1, 7, 8, 33, 128].iter() {
+            let secret: Vec<u8> = (0..*len).map(|i| i as u8 ^ 0xa5).collect();
+            let shares = sharks.dealer_ssss(&secret, 5).unwrap();
+            assert_eq!(shares[4].x, 5);
+            assert_eq!(sharks.recover_ssss(&shares[1..4]).unwrap(), secret);
+            assert_eq!(sharks.recover_ssss(&shares).unwrap(), secret);
+
+            let shares: Vec<SsssShare> = shares
+                .iter()
+                .map(|s| SsssShare::from_text(&format!("token-{}", s.to_text())).unwrap())
+                .collect();
+            assert_eq!(sharks.recover_ssss(&shares[2..]).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn ssss_errors_work() {
+        let sharks = Sharks(2);
+        assert_eq!(
+            sharks.dealer_ssss(&[], 3).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            sharks.dealer_ssss(&[0; 129], 3).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            sharks.dealer_ssss(&[1], 1).err(),
+            Some(SharksError::TooFewShares { min: 2 })
+        );
+
+        let shares = sharks.dealer_ssss(&[1, 2], 3).unwrap();
+        let mut conflicting = shares[0].clone();
+        conflicting.y[0] ^= 1;
+        assert_eq!(
+            sharks.recover_ssss(vec![&shares[0], &conflicting]).err(),
+            Some(SharksError::DuplicateShareX)
+        );
+        assert_eq!(
+            sharks.recover_ssss(vec![&shares[0], &shares[0]]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 2
+            })
+        );
+        let short = SsssShare { x: 3, y: vec![1] };
+        assert_eq!(
+            sharks.recover_ssss(vec![&shares[0], &short]).err(),
+            Some(SharksError::InconsistentLengths)
+        );
+
+        for text in ["1-", "0-ab", "x-ab", "1-abc", "ab"].iter() {
+            assert!(SsssShare::from_text(text).is_err());
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// Secret digest shared along with the secret, so that recovering it from wrong shares is detected.
+// The first bytes of `SHA-256(domain || secret)` are appended to the secret before splitting it: shares of
+// different secrets, corrupted shares or a threshold lower than the one the secret was split with all result
+// in a wrong secret, whose digest does not match anymore. The digest being shared too, it reveals nothing.
+
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+// A wrong secret goes unnoticed with a probability of 2**-32
+const DIGEST_BYTES: usize = 4;
+
+// Domain separation of the secret digest
+const DOMAIN: &[u8] = b"sharks secret digest";
+
+fn digest(secret: &[u8]) -> [u8; DIGEST_BYTES] {
+    let hash = Sha256::new()
+        .chain_update(DOMAIN)
+        .chain_update(secret)
+        .finalize();
+    let mut digest = [0u8; DIGEST_BYTES];
+    digest.copy_from_slice(&hash[..DIGEST_BYTES]);
+    digest
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares of it followed by its digest,
+    /// making each share 4 bytes longer. `recover_with_digest` checks the digest of the recovered secret,
+    /// so that shares of different secrets, corrupted shares or a threshold lower than the one used
+    /// to split the secret return an `Err` instead of a wrong secret.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// # use std::convert::TryFrom;
+    /// let bytes: Vec<Vec<u8>> = Sharks(3).dealer_with_digest(&[1, 2, 3]).take(3).map(|s| Vec::from(&s)).collect();
+    /// // Shares parsed from bytes do not know their threshold
+    /// let shares: Vec<Share> = bytes.iter().map(|b| Share::try_from(b.as_slice()).unwrap()).collect();
+    /// assert_eq!(Sharks(3).recover_with_digest(&shares).unwrap(), vec![1, 2, 3]);
+    /// // Recovering with the wrong threshold is detected nonetheless
+    /// assert_eq!(Sharks(2).recover_with_digest(&shares[..2]), Err(SharksError::InvalidDigest));
+    /// ```
+    pub fn dealer_with_digest(&self, secret: &[u8]) -> impl Iterator<Item = Share> {
+        let mut payload = Vec::with_capacity(secret.len() + DIGEST_BYTES);
+        payload.extend_from_slice(secret);
+        payload.extend_from_slice(&digest(secret));
+        let dealer = self.dealer(&payload);
+        payload.zeroize();
+        dealer
+    }
+
+    /// Given an iterable collection of shares dealt by `dealer_with_digest`, recovers the original secret
+    /// and checks its digest in constant time. An `Err` is returned in the same cases as `recover`,
+    /// or if the digest does not match, in which case the shares or the threshold are wrong.
+    pub fn recover_with_digest<'a, S, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut payload = self.recover(shares)?;
+        if payload.len() < DIGEST_BYTES {
+            return Err(SharksError::InvalidDigest);
+        }
+        let (secret, expected) = payload.split_at(payload.len() - DIGEST_BYTES);
+        if bool::from(digest(secret).ct_eq(expected)) {
+            payload.truncate(payload.len() - DIGEST_BYTES);
+            Ok(payload)
+        } else {
+            payload.zeroize();
+            Err(SharksError::InvalidDigest)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn digest_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer_with_digest(&[1, 2, 3, 4]).take(5).collect();
+        assert_eq!(shares[0].y.len(), 8);
+        assert_eq!(
+            sharks.recover_with_digest(&shares[2..]),
+            Ok(vec![1, 2, 3, 4])
+        );
+
+        let empty: Vec<Share> = sharks.dealer_with_digest(&[]).take(3).collect();
+        assert_eq!(sharks.recover_with_digest(&empty), Ok(vec![]));
+    }
+
+    #[test]
+    fn digest_err() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks
+            .dealer_with_digest(&[1, 2, 3, 4])
+            .take(4)
+            .map(|s| Share::try_from(Vec::from(&s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(
+            Sharks(2).recover_with_digest(&shares[..2]),
+            Err(SharksError::InvalidDigest)
+        );
+
+        let other: Vec<Share> = sharks
+            .dealer_with_digest(&[1, 2, 3, 4])
+            .take(3)
+            .map(|s| Share::try_from(Vec::from(&s).as_slice()).unwrap())
+            .collect();
+        let mixed = [&shares[0], &shares[1], &other[2]];
+        assert_eq!(
+            sharks.recover_with_digest(mixed),
+            Err(SharksError::InvalidDigest)
+        );
+
+        shares[0].y[1] = shares[0].y[1] + GF256(1);
+        assert_eq!(
+            sharks.recover_with_digest(&shares[..3]),
+            Err(SharksError::InvalidDigest)
+        );
+
+        let plain: Vec<Share> = sharks.dealer(&[1, 2]).take(3).collect();
+        assert_eq!(
+            sharks.recover_with_digest(&plain),
+            Err(SharksError::InvalidDigest)
+        );
+    }
+}
@@ -0,0 +1,217 @@
+// Building blocks of a joint-Feldman distributed key generation, in which no party ever knows the shared secret.
+// Each party deals a random scalar contribution to all the others with `dkg_contribution`, publishing its Feldman
+// commitments. Every participant then checks the shares it received against their commitments and sums them with
+// `dkg_aggregate`: the sums are shares of the sum of all contributions, which is the group secret key, while
+// summing the commitments gives the group commitments, and with them the group public key and verification keys.
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use super::error::SharksError;
+use super::field::{Field, ScalarCommitments};
+use super::share::FieldShare;
+use super::Sharks;
+
+/// Share of a distributed key, returned by `Sharks::dkg_aggregate`, along with the group commitments.
+/// The group secret key is recovered from threshold of them with `Sharks::recover_scalar`,
+/// though threshold schemes rather combine partial results computed with each share.
+#[derive(Clone, Debug)]
+pub struct DkgShare {
+    /// Share of the group secret key held by the participant.
+    pub share: FieldShare<Scalar>,
+    /// Sum of the commitments of all the contributions.
+    pub commitments: ScalarCommitments,
+}
+
+impl DkgShare {
+    /// Returns the group public key, `secret * G` for the group secret key.
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.commitments.public_key()
+    }
+
+    /// Returns the verification key of the participant at `x`, the commitment to its share.
+    pub fn verification_key(&self, x: Scalar) -> RistrettoPoint {
+        self.commitments.verification_key(x)
+    }
+}
+
+impl Sharks {
+    /// Returns the contribution of a party to a distributed key generation among `participants` parties:
+    /// the commitments to a random polynomial, to be broadcast to all the participants, and the shares of its
+    /// constant term, the share at `x = i` being sent privately to the participant `i`, the party included.
+    /// An `Err` is returned if there are fewer participants than the threshold.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, DkgShare, FieldShare };
+    /// use sharks::curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+    ///
+    /// let sharks = Sharks(2);
+    /// let contributions: Vec<_> = (0..3).map(|_| sharks.dkg_contribution(3).unwrap()).collect();
+    ///
+    /// // Each participant aggregates the shares it received from every party
+    /// let shares: Vec<DkgShare> = (0..3)
+    ///     .map(|i| {
+    ///         let received = contributions.iter().map(|(c, shares)| (c, &shares[i]));
+    ///         sharks.dkg_aggregate(Scalar::from(i as u64 + 1), received).unwrap()
+    ///     })
+    ///     .collect();
+    ///
+    /// let secret = sharks.recover_scalar(shares[1..].iter().map(|s| &s.share)).unwrap();
+    /// assert_eq!(RistrettoPoint::mul_base(&secret), shares[0].public_key());
+    /// ```
+    pub fn dkg_contribution(
+        &self,
+        participants: u8,
+    ) -> Result<(ScalarCommitments, Vec<FieldShare<Scalar>>), SharksError> {
+        if self.0 == 0 {
+            return Err(SharksError::InvalidThreshold);
+        } else if participants < self.0 {
+            return Err(SharksError::TooFewShares {
+                min: self.0 as usize,
+            });
+        }
+        let (commitments, dealer) =
+            self.dealer_scalar_feldman(Field::random(&mut rand::thread_rng()));
+        Ok((commitments, dealer.take(participants as usize).collect()))
+    }
+
+    /// Given the commitments of every contribution along with the share of it received by the participant at `x`,
+    /// checks each share and returns their sum, the participant share of the group secret key.
+    /// An `Err` is returned if there are no contributions, one of them was generated for another threshold,
+    /// a share is at another `x`, or it does not match its commitments, the index of the faulty
+    /// contribution being returned then so that its dealer can be excluded.
+    pub fn dkg_aggregate<'a, T>(&self, x: Scalar, contributions: T) -> Result<DkgShare, SharksError>
+    where
+        T: IntoIterator<Item = (&'a ScalarCommitments, &'a FieldShare<Scalar>)>,
+    {
+        let mut y = Scalar::ZERO;
+        let mut points = vec![RistrettoPoint::default(); self.0 as usize];
+        let mut count = 0;
+        for (index, (commitments, share)) in contributions.into_iter().enumerate() {
+            if commitments.threshold() != self.0 {
+                return Err(SharksError::ThresholdMismatch {
+                    expected: self.0,
+                    found: commitments.threshold(),
+                });
+            } else if share.x != x {
+                return Err(SharksError::MixedShares);
+            } else if !share.verify_feldman(commitments) {
+                return Err(SharksError::InvalidContribution { index });
+            }
+            y += share.y[0];
+            points
+                .iter_mut()
+                .zip(commitments.points.iter())
+                .for_each(|(p, c)| *p += c);
+            count += 1;
+        }
+        if count == 0 {
+            return Err(SharksError::NotEnoughShares {
+                needed: 1,
+                got: 0,
+                provided: 0,
+            });
+        }
+
+        Ok(DkgShare {
+            share: FieldShare {
+                x,
+                y: vec![y],
+                threshold: Some(self.0),
+                group: None,
+            },
+            commitments: ScalarCommitments { points },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DkgShare;
+    use crate::field::ScalarCommitments;
+    use crate::{FieldShare, Sharks, SharksError};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn dkg_integration_works() {
+        let sharks = Sharks(3);
+        let contributions: Vec<(ScalarCommitments, Vec<FieldShare<Scalar>>)> = (0..5)
+            .map(|_| sharks.dkg_contribution(5).unwrap())
+            .collect();
+        let shares: Vec<DkgShare> = (0..5)
+            .map(|i| {
+                let received = contributions.iter().map(|(c, s)| (c, &s[i]));
+                sharks
+                    .dkg_aggregate(Scalar::from(i as u64 + 1), received)
+                    .unwrap()
+            })
+            .collect();
+
+        // All participants agree on the group commitments
+        assert!(shares
+            .iter()
+            .all(|s| s.commitments == shares[0].commitments));
+        for s in shares.iter() {
+            assert!(s.share.verify_feldman(&s.commitments));
+            assert_eq!(
+                s.verification_key(s.share.x),
+                RistrettoPoint::mul_base(&s.share.y[0])
+            );
+        }
+
+        let public_key: RistrettoPoint = contributions.iter().map(|(c, _)| c.public_key()).sum();
+        assert_eq!(shares[0].public_key(), public_key);
+        let secret = sharks
+            .recover_scalar(shares[2..].iter().map(|s| &s.share))
+            .unwrap();
+        assert_eq!(RistrettoPoint::mul_base(&secret), public_key);
+    }
+
+    #[test]
+    fn dkg_err() {
+        let sharks = Sharks(2);
+        assert_eq!(
+            sharks.dkg_contribution(1).err(),
+            Some(SharksError::TooFewShares { min: 2 })
+        );
+        assert_eq!(
+            Sharks(0).dkg_contribution(1).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+
+        let (c0, s0) = sharks.dkg_contribution(3).unwrap();
+        let (c1, mut s1) = sharks.dkg_contribution(3).unwrap();
+        let (c2, s2) = Sharks(3).dkg_contribution(3).unwrap();
+        let x = Scalar::from(1u64);
+        assert_eq!(
+            sharks.dkg_aggregate(x, Vec::new()).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 1,
+                got: 0,
+                provided: 0
+            })
+        );
+        assert_eq!(
+            sharks
+                .dkg_aggregate(x, vec![(&c0, &s0[0]), (&c2, &s2[0])])
+                .err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            sharks.dkg_aggregate(x, vec![(&c0, &s0[1])]).err(),
+            Some(SharksError::MixedShares)
+        );
+        s1[0].y[0] += Scalar::ONE;
+        assert_eq!(
+            sharks
+                .dkg_aggregate(x, vec![(&c0, &s0[0]), (&c1, &s1[0])])
+                .err(),
+            Some(SharksError::InvalidContribution { index: 1 })
+        );
+    }
+}
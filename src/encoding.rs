@@ -0,0 +1,592 @@
+// Text encodings used to represent share bytes in human readable formats
+
+#[cfg(feature = "text")]
+use super::crc::crc32;
+#[cfg(feature = "text")]
+use super::error::SharksError;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Encodes `bytes` using the standard Base64 alphabet with padding, as defined in RFC 4648.
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+// Decodes a padded standard Base64 string. Returns `None` if the input is not canonically encoded.
+pub fn from_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    for (i, chunk) in s.chunks(4).enumerate() {
+        let last = i == s.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+
+        let mut n = 0u32;
+        for c in &chunk[..4 - padding] {
+            let v = BASE64_ALPHABET.iter().position(|a| a == c)? as u32;
+            n = n << 6 | v;
+        }
+        n <<= 6 * padding as u32;
+
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        // Reject non-zero trailing bits so each byte string has a single encoding
+        if decoded[3 - padding..].iter().any(|b| *b != 0) {
+            return None;
+        }
+        out.extend_from_slice(&decoded[..3 - padding]);
+    }
+
+    Some(out)
+}
+
+// Encodes `bytes` as lowercase hexadecimal digits.
+#[cfg(feature = "text")]
+pub fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+// Decodes a string of hexadecimal digits, either lowercase or uppercase.
+// Returns `None` if the input has an odd length or contains any other character.
+#[cfg(feature = "text")]
+pub fn from_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    s.chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+// Characters of the QR code alphanumeric mode, in the order of their values.
+#[cfg(feature = "text")]
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+// Encodes `bytes` in Base45, as defined in RFC 9285, which only uses QR code alphanumeric characters.
+// Each pair of bytes becomes three characters, a trailing byte two.
+#[cfg(feature = "text")]
+pub fn to_base45(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    for chunk in bytes.chunks(2) {
+        let mut n = chunk.iter().fold(0usize, |n, b| n << 8 | *b as usize);
+        for _ in 0..chunk.len() + 1 {
+            out.push(BASE45_ALPHABET[n % 45] as char);
+            n /= 45;
+        }
+    }
+    out
+}
+
+// Decodes a Base45 string. Returns `None` if it contains any character outside of the alphabet,
+// has a trailing single character, or any group of characters overflows the bytes it represents.
+#[cfg(feature = "text")]
+pub fn from_base45(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if s.len() % 3 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(s.len() / 3 * 2 + 1);
+    for chunk in s.chunks(3) {
+        let mut n = 0usize;
+        for c in chunk.iter().rev() {
+            n = n * 45 + BASE45_ALPHABET.iter().position(|a| a == c)?;
+        }
+        if chunk.len() == 3 {
+            if n > 0xffff {
+                return None;
+            }
+            out.extend_from_slice(&[(n >> 8) as u8, n as u8]);
+        } else if n > 0xff {
+            return None;
+        } else {
+            out.push(n as u8);
+        }
+    }
+    Some(out)
+}
+
+// Crockford Base32 alphabet, followed by the five extra symbols of its check characters.
+#[cfg(feature = "text")]
+const CROCKFORD_ALPHABET: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+// Number of data characters of each group, followed by its check character.
+#[cfg(feature = "text")]
+const CROCKFORD_GROUP: usize = 4;
+
+// Check character value of the group at `index` of data character `values`, their weighted sum modulo 37.
+// As 37 is prime and the weights are distinct, any single substitution or adjacent transposition changes it,
+// and the group index is added so that groups read in the wrong place are likely to be detected.
+#[cfg(feature = "text")]
+fn crockford_check(index: usize, values: &[u8]) -> u8 {
+    let sum = values
+        .iter()
+        .enumerate()
+        .fold(index % 37, |sum, (j, v)| sum + (j + 1) * *v as usize);
+    (sum % 37) as u8
+}
+
+// Value of a Crockford Base32 character, case insensitive, reading `I` and `L` as `1` and `O` as `0`.
+#[cfg(feature = "text")]
+fn crockford_value(c: u8) -> Option<u8> {
+    let c = match c.to_ascii_uppercase() {
+        b'I' | b'L' => b'1',
+        b'O' => b'0',
+        c => c,
+    };
+    CROCKFORD_ALPHABET
+        .iter()
+        .position(|a| *a == c)
+        .map(|v| v as u8)
+}
+
+// Splits `bytes` into 5 bits values, without padding, the last value holding zero trailing bits.
+#[cfg(feature = "text")]
+fn to_base32_values(bytes: &[u8]) -> Vec<u8> {
+    let mut values = Vec::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut acc, mut bits) = (0u32, 0);
+    for b in bytes {
+        acc = acc << 8 | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push((acc >> bits & 0x1f) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        values.push((acc << (5 - bits) & 0x1f) as u8);
+    }
+    values
+}
+
+// Joins 5 bits values split by `to_base32_values` back into bytes.
+// Returns `InvalidEncoding` if there is a whole trailing value or the trailing bits are not zero,
+// so that each byte string has a single encoding.
+#[cfg(feature = "text")]
+fn from_base32_values(values: &[u8]) -> Result<Vec<u8>, SharksError> {
+    let mut out = Vec::with_capacity(values.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for v in values {
+        acc = acc << 5 | *v as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 5 || acc != 0 {
+        return Err(SharksError::InvalidEncoding);
+    }
+    Ok(out)
+}
+
+// Crockford group of data character `values` at `index`, followed by its check character.
+#[cfg(feature = "text")]
+fn crockford_group(index: usize, values: &[u8]) -> String {
+    values
+        .iter()
+        .chain(core::iter::once(&crockford_check(index, values)))
+        .map(|v| CROCKFORD_ALPHABET[*v as usize] as char)
+        .collect()
+}
+
+// Decodes the data characters of a Crockford group at `index`, checking its check character.
+// Returns `None` if a character is invalid, the check character does not match or the group is too short or long.
+#[cfg(feature = "text")]
+fn crockford_group_values(index: usize, group: &[u8]) -> Option<Vec<u8>> {
+    if group.len() < 2 || group.len() > CROCKFORD_GROUP + 1 {
+        return None;
+    }
+    let (data, check) = group.split_at(group.len() - 1);
+    let data = data
+        .iter()
+        .map(|c| crockford_value(*c).filter(|v| *v < 32))
+        .collect::<Option<Vec<u8>>>()?;
+    Some(data).filter(|data| crockford_value(check[0]) == Some(crockford_check(index, data)))
+}
+
+// Encodes `bytes` in Crockford Base32, without padding, the last character holding zero trailing bits.
+// Characters are grouped by four, each group followed by a check character and separated by hyphens.
+#[cfg(feature = "text")]
+pub fn to_crockford(bytes: &[u8]) -> String {
+    let groups: Vec<String> = to_base32_values(bytes)
+        .chunks(CROCKFORD_GROUP)
+        .enumerate()
+        .map(|(i, group)| crockford_group(i, group))
+        .collect();
+    groups.join("-")
+}
+
+// Decodes a string written by `to_crockford`, case insensitive and reading `I`, `L` and `O` as `1`, `1` and `0`.
+// Returns `InvalidGroup` with the index of the first group holding an invalid character or check character,
+// and `InvalidEncoding` if the groups have invalid lengths or the trailing bits are not canonical.
+#[cfg(feature = "text")]
+pub fn from_crockford(s: &str) -> Result<Vec<u8>, SharksError> {
+    let groups: Vec<&[u8]> = s.split('-').map(str::as_bytes).collect();
+    let mut values = Vec::with_capacity(groups.len() * CROCKFORD_GROUP);
+    for (i, group) in groups.iter().enumerate() {
+        let last = i == groups.len() - 1;
+        if group.len() < 2
+            || group.len() > CROCKFORD_GROUP + 1
+            || (!last && group.len() != CROCKFORD_GROUP + 1)
+        {
+            return Err(SharksError::InvalidEncoding);
+        }
+        values.extend(
+            crockford_group_values(i, group).ok_or(SharksError::InvalidGroup { index: i })?,
+        );
+    }
+    from_base32_values(&values)
+}
+
+// Number of Crockford groups on each line of the paper format.
+#[cfg(feature = "text")]
+const PAPER_GROUPS: usize = 4;
+
+// Line check value of the data character `values` of the line at `index`, a 10 bits truncated CRC-32 of them,
+// the line index and whether it is the last one, so that swapped, repeated or missing lines are detected.
+#[cfg(feature = "text")]
+fn paper_check(index: usize, last: bool, values: &[u8]) -> [u8; 2] {
+    let mut bytes = (index as u32).to_be_bytes().to_vec();
+    bytes.push(last as u8);
+    bytes.extend_from_slice(values);
+    let crc = crc32(&bytes);
+    [(crc >> 5 & 0x1f) as u8, (crc & 0x1f) as u8]
+}
+
+// Encodes `bytes` for manual transcription on paper: Crockford groups with their check character, as with
+// `to_crockford`, by four on numbered lines, each line ending with two line check characters:
+// `01: GGGGC GGGGC GGGGC GGGGC LL`. Each line holds 10 bytes.
+#[cfg(feature = "text")]
+pub fn to_paper(bytes: &[u8]) -> String {
+    let values = to_base32_values(bytes);
+    let lines: Vec<&[u8]> = values.chunks(PAPER_GROUPS * CROCKFORD_GROUP).collect();
+    let lines: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .map(|(l, line)| {
+            let mut text = format!("{:02}:", l + 1);
+            for (g, group) in line.chunks(CROCKFORD_GROUP).enumerate() {
+                text.push(' ');
+                text.push_str(&crockford_group(l * PAPER_GROUPS + g, group));
+            }
+            text.push(' ');
+            paper_check(l, l == lines.len() - 1, line)
+                .iter()
+                .for_each(|v| text.push(CROCKFORD_ALPHABET[*v as usize] as char));
+            text
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// Decodes the data characters of each line of a text written by `to_paper`, case insensitive, reading `I`, `L`
+// and `O` as `1`, `1` and `0`, and ignoring blank lines and repeated spaces. Returns `InvalidLine` for each line
+// which is not numbered in order, holds an invalid group, whose index is reported, or an invalid line check.
+#[cfg(feature = "text")]
+pub fn paper_lines(s: &str) -> Vec<Result<Vec<u8>, SharksError>> {
+    let lines: Vec<&str> = s.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(l, line)| {
+            let invalid = |group| SharksError::InvalidLine { line: l, group };
+            let mut tokens = line.split_whitespace();
+            let number = tokens.next().and_then(|t| t.strip_suffix(':'));
+            if number.and_then(|n| n.parse::<usize>().ok()) != Some(l + 1) {
+                return Err(invalid(None));
+            }
+            let tokens: Vec<&[u8]> = tokens.map(str::as_bytes).collect();
+            let last = l == lines.len() - 1;
+            let (check, groups) = tokens.split_last().ok_or(invalid(None))?;
+            if groups.is_empty()
+                || groups.len() > PAPER_GROUPS
+                || (!last && groups.len() != PAPER_GROUPS)
+            {
+                return Err(invalid(None));
+            }
+
+            let mut values = Vec::with_capacity(PAPER_GROUPS * CROCKFORD_GROUP);
+            for (g, group) in groups.iter().enumerate() {
+                if g < groups.len() - 1 && group.len() != CROCKFORD_GROUP + 1 {
+                    return Err(invalid(Some(g)));
+                }
+                values.extend(
+                    crockford_group_values(l * PAPER_GROUPS + g, group).ok_or(invalid(Some(g)))?,
+                );
+            }
+            let check = check
+                .iter()
+                .map(|c| crockford_value(*c).filter(|v| *v < 32))
+                .collect::<Option<Vec<u8>>>();
+            if check.as_deref() != Some(&paper_check(l, last, &values)[..]) {
+                return Err(invalid(None));
+            }
+            Ok(values)
+        })
+        .collect()
+}
+
+// Decodes a text written by `to_paper`, returning the error of its first invalid line, see `paper_lines`,
+// or `InvalidEncoding` if it is empty or its trailing bits are not canonical.
+#[cfg(feature = "text")]
+pub fn from_paper(s: &str) -> Result<Vec<u8>, SharksError> {
+    let lines = paper_lines(s)
+        .into_iter()
+        .collect::<Result<Vec<Vec<u8>>, SharksError>>()?;
+    if lines.is_empty() {
+        return Err(SharksError::InvalidEncoding);
+    }
+    from_base32_values(&lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "text")]
+    use super::{
+        from_base45, from_crockford, from_hex, from_paper, paper_lines, to_base45, to_crockford,
+        to_hex, to_paper,
+    };
+    use super::{from_base64, to_base64};
+    #[cfg(feature = "text")]
+    use crate::SharksError;
+
+    #[test]
+    fn base64_roundtrip_works() {
+        let cases: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+
+        for (bytes, text) in cases.iter() {
+            assert_eq!(to_base64(bytes), *text);
+            assert_eq!(from_base64(text).unwrap(), *bytes);
+        }
+    }
+
+    #[test]
+    fn base64_invalid_input_err() {
+        assert!(from_base64("Zg=").is_none());
+        assert!(from_base64("Zh==").is_none());
+        assert!(from_base64("Zg==Zg==").is_none());
+        assert!(from_base64("Z!==").is_none());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn hex_roundtrip_works() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0x00, 0x1f, 0xab, 0xff]), "001fabff");
+        assert_eq!(from_hex("001fabff").unwrap(), vec![0x00, 0x1f, 0xab, 0xff]);
+        assert_eq!(from_hex("001FABFF").unwrap(), vec![0x00, 0x1f, 0xab, 0xff]);
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn hex_invalid_input_err() {
+        assert!(from_hex("abc").is_none());
+        assert!(from_hex("0g").is_none());
+        assert!(from_hex(" 00").is_none());
+        assert!(from_hex("+1").is_none());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn base45_roundtrip_works() {
+        // Test vectors from RFC 9285
+        let cases: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"AB", "BB8"),
+            (b"Hello!!", "%69 VD92EX0"),
+            (b"ietf!", "QED8WEX0"),
+        ];
+
+        for (bytes, text) in cases.iter() {
+            assert_eq!(to_base45(bytes), *text);
+            assert_eq!(from_base45(text).unwrap(), *bytes);
+        }
+        assert_eq!(
+            from_base45(&to_base45(&[0xff, 0xff, 0xff])).unwrap(),
+            vec![0xff; 3]
+        );
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn base45_invalid_input_err() {
+        assert!(from_base45("BB8B").is_none());
+        assert!(from_base45("GGW").is_none());
+        assert!(from_base45("U6").is_none());
+        assert!(from_base45("bb8").is_none());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn crockford_roundtrip_works() {
+        assert_eq!(to_crockford(&[]), "");
+        assert_eq!(to_crockford(&[0xff]), "ZWD");
+        assert_eq!(to_crockford(b"foobar"), "CSQP$-YRK1X-E8*");
+        for len in 1..12 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i as u8).wrapping_mul(97)).collect();
+            assert_eq!(from_crockford(&to_crockford(&bytes)).unwrap(), bytes);
+        }
+        assert_eq!(
+            from_crockford("csqp$-yrklx-e8*").unwrap(),
+            b"foobar".to_vec()
+        );
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn crockford_invalid_input_err() {
+        let text = to_crockford(b"foobar");
+        for i in 0..text.len() {
+            let mut mistyped = text.clone().into_bytes();
+            if mistyped[i] == b'-' {
+                continue;
+            }
+            mistyped[i] = if mistyped[i] == b'A' { b'B' } else { b'A' };
+            assert_eq!(
+                from_crockford(core::str::from_utf8(&mistyped).unwrap()),
+                Err(SharksError::InvalidGroup { index: i / 6 })
+            );
+        }
+        // Adjacent transposition and misplaced groups
+        assert!(from_crockford("CSPQ$-YRK1X-E8*").is_err());
+        assert!(from_crockford("YRK1X-CSQP$-E8*").is_err());
+        assert_eq!(
+            from_crockford("CSQP$-YRK1X-E8U"),
+            Err(SharksError::InvalidGroup { index: 2 })
+        );
+        assert_eq!(
+            from_crockford("CSQP$YRK1X-E8*"),
+            Err(SharksError::InvalidEncoding)
+        );
+        assert_eq!(
+            from_crockford("CSQ$-YRK1X-E8*"),
+            Err(SharksError::InvalidEncoding)
+        );
+        assert_eq!(from_crockford(""), Err(SharksError::InvalidEncoding));
+        assert_eq!(from_crockford("ZXF"), Err(SharksError::InvalidEncoding));
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn paper_roundtrip_works() {
+        let text = to_paper(b"foobar");
+        assert!(text.starts_with("01: CSQP$ YRK1X E8* "));
+        assert_eq!(text.lines().count(), 1);
+        for len in [1usize, 9, 10, 11, 25, 100] {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            let text = to_paper(&bytes);
+            assert_eq!(text.lines().count(), len.div_ceil(10));
+            assert_eq!(from_paper(&text).unwrap(), bytes);
+            // Sloppy but valid transcriptions
+            let sloppy = text.to_lowercase().replace(' ', "  ").replace('\n', "\n\n");
+            assert_eq!(from_paper(&sloppy).unwrap(), bytes);
+        }
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn paper_invalid_input_err() {
+        let bytes: Vec<u8> = (0..25).collect();
+        let text = to_paper(&bytes);
+        let lines: Vec<&str> = text.lines().collect();
+
+        // Every mistyped character is located, the other lines still decoding
+        for (l, line) in lines.iter().enumerate() {
+            let tokens: Vec<&str> = line.split(' ').collect();
+            for t in 1..tokens.len() {
+                for c in 0..tokens[t].len() {
+                    let mut mistyped: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+                    let replacement = if tokens[t].as_bytes()[c] == b'A' {
+                        "B"
+                    } else {
+                        "A"
+                    };
+                    mistyped[t].replace_range(c..c + 1, replacement);
+                    let mut mistyped_lines: Vec<String> =
+                        lines.iter().map(|l| l.to_string()).collect();
+                    mistyped_lines[l] = mistyped.join(" ");
+
+                    let decoded = paper_lines(&mistyped_lines.join("\n"));
+                    let group = Some(t - 1).filter(|_| t < tokens.len() - 1);
+                    assert_eq!(decoded[l], Err(SharksError::InvalidLine { line: l, group }));
+                    assert!(decoded.iter().enumerate().all(|(i, d)| i == l || d.is_ok()));
+                }
+            }
+        }
+
+        // Swapped, missing and misnumbered lines
+        let swapped = [lines[1], lines[0], lines[2]].join("\n");
+        assert_eq!(
+            from_paper(&swapped),
+            Err(SharksError::InvalidLine {
+                line: 0,
+                group: None
+            })
+        );
+        let renumbered = [
+            lines[1].replacen("02:", "01:", 1),
+            lines[0].replacen("01:", "02:", 1),
+        ];
+        assert!(from_paper(&renumbered.join("\n")).is_err());
+        assert_eq!(
+            from_paper(&lines[..2].join("\n")),
+            Err(SharksError::InvalidLine {
+                line: 1,
+                group: None
+            })
+        );
+        assert_eq!(from_paper(""), Err(SharksError::InvalidEncoding));
+        assert_eq!(
+            from_paper(&lines[0][4..]),
+            Err(SharksError::InvalidLine {
+                line: 0,
+                group: None
+            })
+        );
+    }
+}
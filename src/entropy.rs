@@ -0,0 +1,141 @@
+// Source of the randomness of the dealer polynomials, for platforms without a `rand` generator.
+// Dealers take any `CryptoRng + RngCore` generator, which `EntropyRng` provides on top of an `EntropySource`,
+// a single method trait simpler to implement over a hardware generator or an enclave interface.
+// With the `getrandom` feature, `OsEntropy` reads the operating system generator, even without `std`.
+
+use rand::{CryptoRng, Error, RngCore};
+
+/// Source of cryptographically secure random bytes, from which the dealer polynomials are generated.
+/// Implement it for a hardware random number generator, or any other source available on the platform,
+/// and deal shares with `EntropyRng` or `SharksBuilder::entropy`.
+///
+/// Example:
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// # use sharks::{ Sharks, Share, EntropySource, EntropyRng };
+/// struct Trng;
+///
+/// impl EntropySource for Trng {
+///     fn fill_bytes(&mut self, dest: &mut [u8]) {
+///         // Read from the hardware generator instead
+///         rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, dest)
+///     }
+/// }
+///
+/// let sharks = Sharks(2);
+/// let shares: Vec<Share> = sharks.dealer_with_rng(&mut EntropyRng(Trng), &[1, 2, 3]).take(2).collect();
+/// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+/// ```
+pub trait EntropySource {
+    /// Fills `dest` with random bytes. Must panic rather than return predictable bytes if the source fails.
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+}
+
+impl<E: EntropySource + ?Sized> EntropySource for &mut E {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        (**self).fill_bytes(dest)
+    }
+}
+
+/// Random number generator drawing its bytes from an `EntropySource`, to be given to the dealers.
+#[derive(Debug, Clone, Default)]
+pub struct EntropyRng<E>(pub E);
+
+impl<E: EntropySource> RngCore for EntropyRng<E> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.0.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.0.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Entropy sources are required to be cryptographically secure.
+impl<E: EntropySource> CryptoRng for EntropyRng<E> {}
+
+/// Entropy source reading the operating system generator through the `getrandom` crate.
+#[cfg(feature = "getrandom")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsEntropy;
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for OsEntropy {
+    /// Panics if the operating system generator is unavailable.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        getrandom::getrandom(dest).expect("the operating system random number generator failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntropyRng, EntropySource};
+    use crate::Sharks;
+
+    // Counter, only fit for testing
+    struct Counter(u8);
+
+    impl EntropySource for Counter {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for b in dest.iter_mut() {
+                self.0 = self.0.wrapping_add(1);
+                *b = self.0;
+            }
+        }
+    }
+
+    #[test]
+    fn entropy_rng_works() {
+        let sharks = Sharks(3);
+        let mut dealer = sharks.dealer_array(&mut EntropyRng(Counter(0)), &[1, 2, 3, 4]);
+        let shares = [
+            dealer.next().unwrap(),
+            dealer.next().unwrap(),
+            dealer.next().unwrap(),
+        ];
+        assert_eq!(sharks.recover_array(&shares), Ok([1, 2, 3, 4]));
+
+        // The same source state deals the same shares
+        let again = sharks
+            .dealer_array(&mut EntropyRng(Counter(0)), &[1, 2, 3, 4])
+            .next()
+            .unwrap();
+        assert_eq!(again, shares[0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn builder_entropy_works() {
+        let mut engine = crate::SharksBuilder::new()
+            .threshold(2)
+            .total_shares(3)
+            .entropy(Counter(7))
+            .build()
+            .unwrap();
+        let shares = engine.split(&[1, 2, 3]);
+        assert_eq!(engine.recover(&shares[1..]), Ok(vec![1, 2, 3]));
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn os_entropy_works() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        super::OsEntropy.fill_bytes(&mut a);
+        super::OsEntropy.fill_bytes(&mut b);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,287 @@
+// Envelopes wrapping shares with human oriented metadata, so that shares stored for years remain identifiable.
+// Metadata fields are serialized as tagged, length prefixed values, unknown tags being skipped when parsing,
+// so that fields added by future versions do not prevent older ones from reading the share.
+
+use std::convert::TryFrom;
+
+use super::error::SharksError;
+use super::share::Share;
+
+/// Version byte prefixed to serialized `ShareEnvelope`s.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+const TAG_END: u8 = 0;
+const TAG_LABEL: u8 = 1;
+const TAG_PARTICIPANT: u8 = 2;
+const TAG_TIMESTAMP: u8 = 3;
+const TAG_THRESHOLD: u8 = 4;
+const TAG_COUNT: u8 = 5;
+const TAG_GROUP: u8 = 6;
+
+/// A share along with optional metadata describing it, all of it being public.
+/// Can be serialized to and from a stable, versioned byte array.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, ShareEnvelope };
+/// # use std::convert::TryFrom;
+/// let sharks = Sharks(2);
+/// let envelopes: Vec<Vec<u8>> = sharks
+///     .dealer(&[1, 2, 3])
+///     .zip(["alice", "bob", "carol"].iter())
+///     .map(|(share, name)| {
+///         let mut envelope = ShareEnvelope::new(share);
+///         envelope.label = Some("backup server root key".into());
+///         envelope.participant = Some(name.to_string());
+///         envelope.count = Some(3);
+///         Vec::from(&envelope)
+///     })
+///     .collect();
+///
+/// let envelopes: Vec<ShareEnvelope> = envelopes
+///     .iter()
+///     .map(|e| ShareEnvelope::try_from(e.as_slice()).unwrap())
+///     .collect();
+/// assert_eq!(envelopes[1].participant.as_deref(), Some("bob"));
+/// assert_eq!(envelopes[1].threshold, Some(2));
+/// assert_eq!(sharks.recover(envelopes.iter().map(|e| &e.share)).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShareEnvelope {
+    /// Description of the secret.
+    pub label: Option<String>,
+    /// Name of the participant holding the share.
+    pub participant: Option<String>,
+    /// Time the secret was split at, in seconds since the Unix epoch.
+    pub timestamp: Option<u64>,
+    /// Minimum number of shares needed to recover the secret, also embedded in the parsed share.
+    pub threshold: Option<u8>,
+    /// Total number of shares of the secret.
+    pub count: Option<u8>,
+    /// The wrapped share.
+    pub share: Share,
+}
+
+impl ShareEnvelope {
+    /// Wraps a share without metadata, except for its threshold if known.
+    pub fn new(share: Share) -> ShareEnvelope {
+        ShareEnvelope {
+            label: None,
+            participant: None,
+            timestamp: None,
+            threshold: share.threshold(),
+            count: None,
+            share,
+        }
+    }
+}
+
+fn push_field(bytes: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    bytes.push(tag);
+    bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(value);
+}
+
+/// Obtains a byte vector from a `ShareEnvelope` instance: `version || fields || 0 || share`,
+/// each present field being serialized as `tag || length || value`, the length being a 4 bytes big endian integer.
+/// Tags are 1 for the label, 2 the participant, both UTF-8 encoded, 3 the timestamp, as an 8 bytes big endian integer,
+/// 4 the threshold and 5 the count, as single bytes, and 6 the group identifier of the share, if any,
+/// as an 8 bytes big endian integer.
+impl From<&ShareEnvelope> for Vec<u8> {
+    fn from(e: &ShareEnvelope) -> Vec<u8> {
+        let mut bytes = vec![ENVELOPE_VERSION];
+        if let Some(label) = &e.label {
+            push_field(&mut bytes, TAG_LABEL, label.as_bytes());
+        }
+        if let Some(participant) = &e.participant {
+            push_field(&mut bytes, TAG_PARTICIPANT, participant.as_bytes());
+        }
+        if let Some(timestamp) = e.timestamp {
+            push_field(&mut bytes, TAG_TIMESTAMP, &timestamp.to_be_bytes());
+        }
+        if let Some(threshold) = e.threshold {
+            push_field(&mut bytes, TAG_THRESHOLD, &[threshold]);
+        }
+        if let Some(count) = e.count {
+            push_field(&mut bytes, TAG_COUNT, &[count]);
+        }
+        if let Some(group) = e.share.group() {
+            push_field(&mut bytes, TAG_GROUP, &group.to_be_bytes());
+        }
+        bytes.push(TAG_END);
+        bytes.extend(Vec::from(&e.share));
+        bytes
+    }
+}
+
+/// Obtains a `ShareEnvelope` instance from a byte slice, validating its contents.
+/// Fields with unknown tags are skipped, while repeated or malformed ones are rejected.
+impl TryFrom<&[u8]> for ShareEnvelope {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<ShareEnvelope, Self::Error> {
+        let (version, mut s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        if *version != ENVELOPE_VERSION {
+            return Err(SharksError::UnknownVersion(*version));
+        }
+
+        let mut fields: [Option<&[u8]>; 7] = [None; 7];
+        loop {
+            let (tag, rest) = s.split_first().ok_or(SharksError::InvalidLength)?;
+            if *tag == TAG_END {
+                s = rest;
+                break;
+            }
+            if rest.len() < 4 {
+                return Err(SharksError::InvalidLength);
+            }
+            let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            if rest.len() - 4 < len {
+                return Err(SharksError::InvalidLength);
+            }
+            let (value, rest) = rest[4..].split_at(len);
+            if let Some(field) = fields.get_mut(*tag as usize) {
+                if field.replace(value).is_some() {
+                    return Err(SharksError::InvalidEncoding);
+                }
+            }
+            s = rest;
+        }
+
+        let text = |value: Option<&[u8]>| {
+            value
+                .map(|v| String::from_utf8(v.to_vec()).map_err(|_| SharksError::InvalidEncoding))
+                .transpose()
+        };
+        let byte = |value: Option<&[u8]>| match value {
+            Some([b]) => Ok(Some(*b)),
+            Some(_) => Err(SharksError::InvalidLength),
+            None => Ok(None),
+        };
+        let integer = |value: Option<&[u8]>| match value {
+            Some(v) => <[u8; 8]>::try_from(v)
+                .map(|v| Some(u64::from_be_bytes(v)))
+                .map_err(|_| SharksError::InvalidLength),
+            None => Ok(None),
+        };
+        let timestamp = integer(fields[TAG_TIMESTAMP as usize])?;
+        let threshold = byte(fields[TAG_THRESHOLD as usize])?;
+        if threshold == Some(0) {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let mut share = Share::try_from(s)?;
+        share.threshold = threshold;
+        share.group = integer(fields[TAG_GROUP as usize])?;
+        Ok(ShareEnvelope {
+            label: text(fields[TAG_LABEL as usize])?,
+            participant: text(fields[TAG_PARTICIPANT as usize])?,
+            timestamp,
+            threshold,
+            count: byte(fields[TAG_COUNT as usize])?,
+            share,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShareEnvelope;
+    use crate::{Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn envelope_integration_works() {
+        let sharks = Sharks(3);
+        let envelopes: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3, 4])
+            .take(4)
+            .enumerate()
+            .map(|(i, share)| {
+                let mut envelope = ShareEnvelope::new(share);
+                envelope.label = Some("vault".into());
+                envelope.participant = Some(format!("participant {}", i));
+                envelope.timestamp = Some(1_700_000_000);
+                envelope.count = Some(4);
+                Vec::from(&envelope)
+            })
+            .collect();
+
+        let envelopes: Vec<ShareEnvelope> = envelopes
+            .iter()
+            .map(|e| ShareEnvelope::try_from(e.as_slice()).unwrap())
+            .collect();
+        assert_eq!(envelopes[2].label.as_deref(), Some("vault"));
+        assert_eq!(envelopes[2].participant.as_deref(), Some("participant 2"));
+        assert_eq!(envelopes[2].timestamp, Some(1_700_000_000));
+        assert_eq!(envelopes[2].threshold, Some(3));
+        assert_eq!(envelopes[2].count, Some(4));
+        assert_eq!(envelopes[2].share.threshold(), Some(3));
+        assert_eq!(
+            sharks.recover(envelopes.iter().map(|e| &e.share)).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            Sharks(2).recover(envelopes.iter().map(|e| &e.share)).err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn envelope_bytes_works() {
+        let share = Share::try_from(&[1, 2][..]).unwrap();
+        let envelope = ShareEnvelope::new(share);
+        assert_eq!(Vec::from(&envelope), vec![1, 0, 1, 2]);
+
+        // Unknown fields are skipped
+        let envelope =
+            ShareEnvelope::try_from(&[1, 9, 0, 0, 0, 1, 7, 4, 0, 0, 0, 1, 2, 0, 1, 2][..]).unwrap();
+        assert_eq!(envelope.threshold, Some(2));
+        assert!(envelope.label.is_none());
+
+        // Group identifiers are kept, so that shares of different splits are not mixed
+        let sharks = Sharks(2);
+        let parse = |share: Share| {
+            let envelope =
+                ShareEnvelope::try_from(Vec::from(&ShareEnvelope::new(share)).as_slice());
+            envelope.unwrap().share
+        };
+        let a: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(2).map(parse).collect();
+        let b: Vec<Share> = sharks.dealer_grouped(&[3, 4]).take(2).map(parse).collect();
+        assert!(a[0].group().is_some() && a[0].group() == a[1].group());
+        assert_eq!(sharks.recover(&a).unwrap(), vec![1, 2]);
+        assert_eq!(
+            sharks.recover(vec![&a[0], &b[1]]).err(),
+            Some(SharksError::MixedGroups)
+        );
+        assert!(ShareEnvelope::try_from(&[1, 6, 0, 0, 0, 1, 7, 0, 1, 2][..]).is_err());
+    }
+
+    #[test]
+    fn envelope_bytes_err() {
+        let cases: [(&[u8], SharksError); 7] = [
+            (&[], SharksError::EmptyShare),
+            (&[2, 0, 1, 2], SharksError::UnknownVersion(2)),
+            (&[1, 1, 0, 0], SharksError::InvalidLength),
+            (&[1, 1, 0, 0, 0, 2, 0xff], SharksError::InvalidLength),
+            (
+                &[1, 1, 0, 0, 0, 1, 0xff, 0, 1, 2],
+                SharksError::InvalidEncoding,
+            ),
+            (
+                &[1, 4, 0, 0, 0, 1, 0, 0, 1, 2],
+                SharksError::InvalidThreshold,
+            ),
+            (
+                &[1, 5, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 1, 2],
+                SharksError::InvalidEncoding,
+            ),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ShareEnvelope::try_from(*bytes).err(), Some(*err));
+        }
+    }
+}
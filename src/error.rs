@@ -0,0 +1,248 @@
+// Error type returned by every fallible operation of the crate
+
+use core::fmt;
+
+/// Reason why shares could not be parsed, dealt or used to recover a secret.
+/// New variants may be added in future versions, so matches must include a wildcard arm.
+///
+/// Example:
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// # use sharks::{ Sharks, Share, SharksError };
+/// let sharks = Sharks(3);
+/// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(2).collect();
+/// match sharks.recover(&shares) {
+///     Err(SharksError::NotEnoughShares { needed, got, .. }) => assert_eq!((needed, got), (3, 2)),
+///     _ => unreachable!(),
+/// }
+/// let error = sharks.recover(&shares).unwrap_err();
+/// assert_eq!(error.missing_shares(), Some(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SharksError {
+    /// Fewer distinct shares than the threshold were provided: `needed` are required, `got` distinct valid ones
+    /// were seen out of the `provided` ones, which also count the identical copies ignored by `recover`.
+    NotEnoughShares {
+        needed: usize,
+        got: usize,
+        provided: usize,
+    },
+    /// Several shares have the same `x` coordinate, or member index.
+    /// Recovering from `Share`s only fails if their `y` values differ, identical copies being ignored.
+    DuplicateShareX,
+    /// Shares hold different numbers of `y` values.
+    InconsistentLengths,
+    /// A share holds no `y` values, or its bytes are empty.
+    EmptyShare,
+    /// A share has a zero `x` coordinate, which would be the secret itself.
+    ZeroShareX,
+    /// Shares embed different thresholds or format versions.
+    InconsistentThresholds,
+    /// The threshold embedded in the shares is not the expected one.
+    ThresholdMismatch { expected: u8, found: u8 },
+    /// A threshold is missing, zero or out of range.
+    InvalidThreshold,
+    /// Share bytes have an unknown format version.
+    UnknownVersion(u8),
+    /// Bytes, words or a secret have an invalid length.
+    InvalidLength,
+    /// Bytes do not represent an element of the field or group.
+    InvalidElement,
+    /// A block or member index, or a byte range, is out of range.
+    InvalidIndex,
+    /// A mnemonic contains a word which is not in the wordlist.
+    InvalidWord,
+    /// A share or mnemonic checksum does not match, usually because it was corrupted.
+    InvalidChecksum,
+    /// A mnemonic, or the recovered secret, is not correctly padded, usually because shares are corrupted.
+    InvalidPadding,
+    /// The digest of the recovered secret does not match, because shares are corrupted or the passphrase is wrong.
+    InvalidDigest,
+    /// Too many shares are corrupted for the errors to be corrected.
+    TooManyErrors,
+    /// More shares were requested than the field allows.
+    TooManyShares { max: usize },
+    /// Shares belong to different secrets, blocks or participants.
+    MixedShares,
+    /// Shares of some of the blocks are missing.
+    MissingBlocks,
+    /// The number of secret bytes packed per polynomial is zero or exceeds the threshold.
+    InvalidPacking,
+    /// Text is not correctly hexadecimal or Base64 encoded, or bytes are not canonical CBOR.
+    InvalidEncoding,
+    /// The shares do not satisfy the hierarchical access structure or policy, or their threshold was not activated.
+    UnauthorizedShares,
+    /// A sealed share cannot be opened, because it was sealed to another key or passphrase, or tampered with.
+    InvalidCiphertext,
+    /// The share of the distributed key generation contribution at `index` does not match its commitments.
+    InvalidContribution { index: usize },
+    /// Shares beyond the threshold do not lie on the polynomial of the others, so some of them are corrupted.
+    InconsistentShares,
+    /// The recovered secret is not valid UTF-8 text.
+    InvalidUtf8,
+    /// A share, or a bundle of shares, is longer than the `max` bytes allowed by the `Limits`.
+    ShareTooLong { max: usize },
+    /// The operation was cancelled by its progress callback.
+    Cancelled,
+    /// A value does not serialize, or the recovered secret does not deserialize into the expected type.
+    InvalidValue,
+    /// The group at `index` of a Crockford Base32 encoded share has an invalid character or check character,
+    /// usually because it was mistyped.
+    InvalidGroup { index: usize },
+    /// The recovered secret is `found` bytes long instead of the `expected` length.
+    UnexpectedLength { expected: usize, found: usize },
+    /// The shares or secret of a test vector differ from the ones this crate deals or recovers.
+    VectorMismatch,
+    /// The line at index `line` of a share written for paper, or its `group` if known, is mistyped or out of order.
+    InvalidLine { line: usize, group: Option<usize> },
+    /// Shares stamped with different group identifiers, dealt by different splits, were combined.
+    MixedGroups,
+    /// Fewer shares than the threshold were requested to be dealt, at least `min` being needed.
+    TooFewShares { min: usize },
+    /// A participant of a weighted split was given a zero weight.
+    InvalidWeight,
+}
+
+impl fmt::Display for SharksError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SharksError::NotEnoughShares {
+                needed,
+                got,
+                provided,
+            } if got != provided => write!(
+                f,
+                "Not enough shares to recover original secret, {} needed but {} distinct of {} given",
+                needed, got, provided
+            ),
+            SharksError::NotEnoughShares { needed, got, .. } => write!(
+                f,
+                "Not enough shares to recover original secret, {} needed but {} given",
+                needed, got
+            ),
+            SharksError::DuplicateShareX => write!(f, "Shares must be distinct"),
+            SharksError::InconsistentLengths => write!(f, "Shares have different lengths"),
+            SharksError::EmptyShare => write!(f, "Share contains no y values"),
+            SharksError::ZeroShareX => write!(f, "Share x coordinate cannot be zero"),
+            SharksError::InconsistentThresholds => {
+                write!(f, "Shares disagree on threshold or format version")
+            }
+            SharksError::ThresholdMismatch { expected, found } => write!(
+                f,
+                "Shares threshold is {} but {} was expected",
+                found, expected
+            ),
+            SharksError::InvalidThreshold => write!(f, "Threshold is missing or out of range"),
+            SharksError::UnknownVersion(version) => {
+                write!(f, "Unknown share format version {}", version)
+            }
+            SharksError::InvalidLength => write!(f, "Input has an invalid length"),
+            SharksError::InvalidElement => write!(f, "Input contains an invalid field element"),
+            SharksError::InvalidIndex => write!(f, "Index is out of range"),
+            SharksError::InvalidWord => write!(f, "Mnemonic contains an invalid word"),
+            SharksError::InvalidChecksum => write!(f, "Input has an invalid checksum"),
+            SharksError::InvalidPadding => write!(f, "Input has an invalid padding"),
+            SharksError::InvalidDigest => write!(f, "Recovered secret digest is invalid"),
+            SharksError::TooManyErrors => {
+                write!(f, "Too many corrupted shares to recover original secret")
+            }
+            SharksError::TooManyShares { max } => {
+                write!(f, "At most {} shares can be generated", max)
+            }
+            SharksError::MixedShares => {
+                write!(
+                    f,
+                    "Shares belong to different secrets, blocks or participants"
+                )
+            }
+            SharksError::MissingBlocks => write!(f, "Shares of some blocks are missing"),
+            SharksError::InvalidPacking => {
+                write!(f, "Packed bytes must be between one and the threshold")
+            }
+            SharksError::InvalidEncoding => write!(f, "Input is not correctly encoded"),
+            SharksError::UnauthorizedShares => {
+                write!(f, "Shares do not satisfy the access structure")
+            }
+            SharksError::InvalidCiphertext => {
+                write!(
+                    f,
+                    "Sealed share cannot be opened with this key or passphrase"
+                )
+            }
+            SharksError::InvalidContribution { index } => {
+                write!(f, "Contribution {} does not match its commitments", index)
+            }
+            SharksError::InconsistentShares => {
+                write!(f, "Shares do not lie on the same polynomial")
+            }
+            SharksError::InvalidUtf8 => write!(f, "Recovered secret is not valid UTF-8"),
+            SharksError::ShareTooLong { max } => {
+                write!(f, "Share is longer than the limit of {} bytes", max)
+            }
+            SharksError::Cancelled => write!(f, "Operation was cancelled"),
+            SharksError::InvalidValue => {
+                write!(f, "Value cannot be serialized or deserialized")
+            }
+            SharksError::InvalidGroup { index } => {
+                write!(f, "Group {} of the text is mistyped", index)
+            }
+            SharksError::UnexpectedLength { expected, found } => write!(
+                f,
+                "Recovered secret is {} bytes long but {} were expected",
+                found, expected
+            ),
+            SharksError::VectorMismatch => {
+                write!(f, "Test vector does not match the dealt or recovered values")
+            }
+            SharksError::InvalidLine {
+                line,
+                group: Some(group),
+            } => write!(f, "Group {} of line {} of the text is mistyped", group, line),
+            SharksError::InvalidLine { line, group: None } => {
+                write!(f, "Line {} of the text is mistyped or out of order", line)
+            }
+            SharksError::MixedGroups => write!(f, "Shares were dealt by different splits"),
+            SharksError::TooFewShares { min } => {
+                write!(f, "At least {} shares must be generated", min)
+            }
+            SharksError::InvalidWeight => write!(f, "Participant weights cannot be zero"),
+        }
+    }
+}
+
+impl SharksError {
+    /// Returns how many more distinct shares are needed to recover the secret,
+    /// or `None` if the error is not caused by missing shares.
+    pub fn missing_shares(&self) -> Option<usize> {
+        match self {
+            SharksError::NotEnoughShares { needed, got, .. } => Some(needed.saturating_sub(*got)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SharksError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SharksError;
+
+    #[test]
+    fn display_works() {
+        let error = SharksError::NotEnoughShares {
+            needed: 3,
+            got: 2,
+            provided: 2,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Not enough shares to recover original secret, 3 needed but 2 given"
+        );
+        assert_eq!(
+            SharksError::UnknownVersion(7).to_string(),
+            "Unknown share format version 7"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+// Bit-sliced GF256 bulk arithmetic, using only logical operations on 64 bits words.
+// Blocks of 64 bytes are transposed into 8 planes, the plane `k` holding the bit `k` of every byte, so that
+// a product by a constant is computed for the whole block at once: the planes of `a * x**i` are obtained by
+// rotating those of `a * x**(i-1)` and folding the top plane into the reduction ones, and are accumulated under
+// masks derived from the bits of the constant.
+// Nothing is indexed by the elements nor the constant, making it constant time on every target,
+// and faster than the scalar fallback on targets without vector instructions. Slices tails use the scalar product.
+
+use super::GF256;
+
+const BLOCK: usize = 64;
+
+// Transposes an 8x8 bits matrix, the byte `i` being its row `i` and the bit `j` its column `j`
+fn transpose8(mut x: u64) -> u64 {
+    let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+    x ^= t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+    x ^= t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+    x ^ t ^ (t << 28)
+}
+
+// Bit planes of a block, the bit `i` of the plane `k` being the bit `k` of the byte `i`
+fn slice(bytes: &[u8; BLOCK]) -> [u64; 8] {
+    let mut planes = [0u64; 8];
+    for (w, chunk) in bytes.chunks_exact(8).enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(chunk);
+        let t = transpose8(u64::from_le_bytes(word));
+        for (k, plane) in planes.iter_mut().enumerate() {
+            *plane |= ((t >> (8 * k)) & 0xff) << (8 * w);
+        }
+    }
+    planes
+}
+
+// Inverse of `slice`
+fn unslice(planes: &[u64; 8], bytes: &mut [u8; BLOCK]) {
+    for (w, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+        let mut t = 0u64;
+        for (k, plane) in planes.iter().enumerate() {
+            t |= ((plane >> (8 * w)) & 0xff) << (8 * k);
+        }
+        chunk.copy_from_slice(&transpose8(t).to_le_bytes());
+    }
+}
+
+// Multiplies the sliced elements by `c`, modulo `x**8 + x**4 + x**3 + x**2 + 1`
+fn mul(mut a: [u64; 8], c: u8) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    for i in 0..8 {
+        let mask = ((c >> i) & 1) as u64;
+        let mask = mask.wrapping_neg();
+        for (r, p) in result.iter_mut().zip(a.iter()) {
+            *r ^= p & mask;
+        }
+        // Doubles the elements, the carried out plane being reduced by the low 0x1d byte of the polynomial
+        let top = a[7];
+        a = [
+            a[7],
+            a[0],
+            a[1] ^ top,
+            a[2] ^ top,
+            a[3] ^ top,
+            a[4],
+            a[5],
+            a[6],
+        ];
+    }
+    result
+}
+
+// Computes `dst[i] = c * a[i] ^ b[i]` over `len` bytes.
+// `dst` may alias `a` or `b`, every block being read before being written.
+pub unsafe fn mul_xor(dst: *mut u8, a: *const u8, b: *const u8, len: usize, c: u8) {
+    let mut i = 0;
+    let mut block = [0u8; BLOCK];
+    while i + BLOCK <= len {
+        core::ptr::copy_nonoverlapping(a.add(i), block.as_mut_ptr(), BLOCK);
+        unslice(&mul(slice(&block), c), &mut block);
+        for (j, byte) in block.iter().enumerate() {
+            *dst.add(i + j) = byte ^ *b.add(i + j);
+        }
+        i += BLOCK;
+    }
+    let c = GF256(c);
+    while i < len {
+        *dst.add(i) = (c * GF256(*a.add(i))).0 ^ *b.add(i);
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mul, mul_xor, slice, unslice, BLOCK};
+    use crate::field::GF256;
+
+    fn block(seed: u8) -> [u8; BLOCK] {
+        let mut block = [0u8; BLOCK];
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(37).wrapping_add(seed);
+        }
+        block
+    }
+
+    #[test]
+    fn slice_works() {
+        let bytes = block(5);
+        let planes = slice(&bytes);
+        for (i, byte) in bytes.iter().enumerate() {
+            for (k, plane) in planes.iter().enumerate() {
+                assert_eq!((plane >> i) & 1, ((byte >> k) & 1) as u64);
+            }
+        }
+        let mut unsliced = [0u8; BLOCK];
+        unslice(&planes, &mut unsliced);
+        assert_eq!(unsliced[..], bytes[..]);
+    }
+
+    #[test]
+    fn mul_works() {
+        let bytes = block(11);
+        for c in 0..=255 {
+            let mut product = [0u8; BLOCK];
+            unslice(&mul(slice(&bytes), c), &mut product);
+            for (p, b) in product.iter().zip(bytes.iter()) {
+                assert_eq!(*p, (GF256(c) * GF256(*b)).0);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_xor_works() {
+        for len in [0, 63, 64, 65, 200].iter().copied() {
+            let a: Vec<u8> = (0..len).map(|i| (i as u8).wrapping_mul(13)).collect();
+            let mut acc: Vec<u8> = (0..len).map(|i| (i as u8) ^ 0x5a).collect();
+            let expected: Vec<u8> = a
+                .iter()
+                .zip(acc.iter())
+                .map(|(a, b)| (GF256(143) * GF256(*a) + GF256(*b)).0)
+                .collect();
+            let dst = acc.as_mut_ptr();
+            // SAFETY: both vectors are `len` long and `dst` aliases `b` only.
+            unsafe { mul_xor(dst, a.as_ptr(), dst, len, 143) };
+            assert_eq!(acc, expected);
+        }
+    }
+}
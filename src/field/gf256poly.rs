@@ -0,0 +1,196 @@
+// Galois Field 256 (2**8) modulo a polynomial of choice, for compatibility with other implementations
+// Operations run in constant time, like the `GF256` ones, but without SIMD nor table based multiplication
+
+use core::iter::{Product, Sum};
+use core::ops::{Add, Mul, Sub};
+
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+
+use super::gf256::mul;
+use super::Field;
+
+// Whether `polynomial` has degree 8 and no factor of degree 1 to 4, which would be needed by any reducible one
+const fn is_irreducible(polynomial: u16) -> bool {
+    if polynomial >> 8 != 1 {
+        return false;
+    }
+    let mut divisor = 2u16;
+    while divisor < 32 {
+        // Remainder of the carry-less division of the polynomial by the divisor
+        let degree = 15 - divisor.leading_zeros();
+        let mut rest = polynomial;
+        let mut bit = 8;
+        while bit >= degree {
+            if rest >> bit & 1 == 1 {
+                rest ^= divisor << (bit - degree);
+            }
+            bit -= 1;
+        }
+        if rest == 0 {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
+/// Element of the Galois Field 256 (2**8) modulo `POLYNOMIAL`, given with its degree 8 term, like `0x11b`
+/// for x**8 + x**4 + x**3 + x + 1. Other implementations of Shamir's Secret Sharing use different polynomials
+/// than the `GF256` one, `0x11d`, and recovering their shares over another field returns a wrong secret
+/// instead of failing, so shares must be split and recovered over the same one.
+/// Reducible polynomials do not define a field and are rejected at compile time once the type is used.
+///
+/// Example:
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// # use sharks::{ Sharks, FieldShare, GF256Poly };
+/// // The AES field
+/// assert_eq!(GF256Poly::<0x11b>(0x57) * GF256Poly(0x83), GF256Poly(0xc1));
+///
+/// let sharks = Sharks(3);
+/// let shares: Vec<FieldShare<GF256Poly<0x11b>>> = sharks.dealer_in(&[1, 2, 3]).take(3).collect();
+/// assert_eq!(sharks.recover_in(&shares).unwrap(), vec![1, 2, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use sharks::{ Sharks, FieldShare, GF256Poly };
+/// // x**8 + 1 = (x + 1)**8
+/// let shares: Vec<FieldShare<GF256Poly<0x101>>> = Sharks(3).dealer_in(&[1, 2, 3]).take(3).collect();
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[repr(transparent)]
+pub struct GF256Poly<const POLYNOMIAL: u16>(pub u8);
+
+impl<const POLYNOMIAL: u16> GF256Poly<POLYNOMIAL> {
+    const REDUCTION: u8 = {
+        assert!(
+            is_irreducible(POLYNOMIAL),
+            "the polynomial must be irreducible and of degree 8"
+        );
+        POLYNOMIAL as u8
+    };
+}
+
+impl<const POLYNOMIAL: u16> Field for GF256Poly<POLYNOMIAL> {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const BYTES: usize = 1;
+
+    fn from_index(n: usize) -> Option<Self> {
+        match n {
+            1..=255 => Some(Self(n as u8)),
+            _ => None,
+        }
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(Uniform::new_inclusive(1, 255).sample(rng))
+    }
+
+    // Computed as `self ** (2**8 - 2)`, the exponent being public the branches leak nothing
+    fn inverse(self) -> Self {
+        let mut result = Self(1);
+        let mut base = self;
+        let mut exp = 0xfeu8;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes[0] = self.0;
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(Self(bytes[0]))
+    }
+}
+
+impl<const POLYNOMIAL: u16> zeroize::Zeroize for GF256Poly<POLYNOMIAL> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const POLYNOMIAL: u16> Add for GF256Poly<POLYNOMIAL> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const POLYNOMIAL: u16> Sub for GF256Poly<POLYNOMIAL> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl<const POLYNOMIAL: u16> Mul for GF256Poly<POLYNOMIAL> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self(mul(self.0, other.0, Self::REDUCTION))
+    }
+}
+
+impl<const POLYNOMIAL: u16> Sum for GF256Poly<POLYNOMIAL> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |acc, x| acc + x)
+    }
+}
+
+impl<const POLYNOMIAL: u16> Product for GF256Poly<POLYNOMIAL> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_irreducible, Field, GF256Poly};
+    use crate::GF256;
+
+    #[test]
+    fn is_irreducible_works() {
+        // There are 30 irreducible polynomials of degree 8 over GF(2)
+        assert_eq!((0x100..0x200).filter(|p| is_irreducible(*p)).count(), 30);
+        assert!(is_irreducible(0x11b));
+        assert!(is_irreducible(0x11d));
+        assert!(!is_irreducible(0x101));
+        assert!(!is_irreducible(0x1b));
+        assert!(!is_irreducible(0x21b));
+    }
+
+    #[test]
+    fn mul_works() {
+        for a in 0..=255 {
+            for b in 0..=255 {
+                assert_eq!(
+                    (GF256Poly::<0x11d>(a) * GF256Poly(b)).0,
+                    (GF256(a) * GF256(b)).0
+                );
+            }
+        }
+        assert_eq!(GF256Poly::<0x11b>(0x57) * GF256Poly(0x13), GF256Poly(0xfe));
+    }
+
+    #[test]
+    fn inverse_works() {
+        for i in 1..=255 {
+            assert_eq!(GF256Poly::<0x11b>(i) * GF256Poly(i).inverse(), GF256Poly(1));
+        }
+    }
+}
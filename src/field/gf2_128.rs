@@ -0,0 +1,263 @@
+// Basic operations overrided for the Galois Field 2**128
+// Uses the x**128 + x**7 + x**2 + x + 1 irreducible polynomial, the one of GCM
+// Products are computed as four 64 bits carry-less multiplications, with the PCLMULQDQ instruction on x86_64
+// and PMULL on aarch64 when available, detected at runtime with `std`, and a masked software fallback otherwise.
+// Operations run in constant time, without branches depending on the elements values
+
+use core::iter::{Product, Sum};
+use core::ops::{Add, Div, Mul, Sub};
+
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+
+use super::Field;
+
+/// Element of the Galois Field 2**128, each one holding sixteen bytes of the secret.
+/// Products use carry-less multiplication instructions where available, so that large secrets
+/// such as bulk data keys are split and recovered with sixteen times fewer field operations than with `GF256`.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct GF2_128(pub u128);
+
+// Carry-less product of two 64 bits polynomials, shifting and masking `a` by each bit of `b`
+fn clmul_soft(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    (0..64).fold(0u128, |acc, i| {
+        acc ^ ((a << i) & (((b >> i) & 1) as u128).wrapping_neg())
+    })
+}
+
+// Carry-less product of two 64 bits polynomials with the fastest available implementation
+#[allow(unreachable_code)]
+fn clmul(a: u64, b: u64) -> u128 {
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    {
+        if is_x86_feature_detected!("pclmulqdq") {
+            // SAFETY: the instruction was detected
+            return unsafe { x86::clmul(a, b) };
+        }
+    }
+    #[cfg(all(
+        target_arch = "x86_64",
+        not(feature = "std"),
+        target_feature = "pclmulqdq"
+    ))]
+    {
+        // SAFETY: the instruction is enabled at compile time
+        return unsafe { x86::clmul(a, b) };
+    }
+    #[cfg(all(target_arch = "aarch64", feature = "std"))]
+    {
+        if std::arch::is_aarch64_feature_detected!("pmull") {
+            // SAFETY: the instruction was detected
+            return unsafe { arm::clmul(a, b) };
+        }
+    }
+    #[cfg(all(target_arch = "aarch64", not(feature = "std"), target_feature = "aes"))]
+    {
+        // SAFETY: the instruction is enabled at compile time
+        return unsafe { arm::clmul(a, b) };
+    }
+    clmul_soft(a, b)
+}
+
+#[cfg(all(
+    target_arch = "x86_64",
+    any(feature = "std", target_feature = "pclmulqdq")
+))]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    #[target_feature(enable = "pclmulqdq")]
+    pub unsafe fn clmul(a: u64, b: u64) -> u128 {
+        let product = _mm_clmulepi64_si128(
+            _mm_set_epi64x(0, a as i64),
+            _mm_set_epi64x(0, b as i64),
+            0x00,
+        );
+        let low = _mm_cvtsi128_si64(product) as u64;
+        let high = _mm_extract_epi64(product, 1) as u64;
+        (high as u128) << 64 | low as u128
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", any(feature = "std", target_feature = "aes")))]
+mod arm {
+    use core::arch::aarch64::*;
+
+    #[target_feature(enable = "aes")]
+    pub unsafe fn clmul(a: u64, b: u64) -> u128 {
+        vmull_p64(a, b)
+    }
+}
+
+impl Field for GF2_128 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const BYTES: usize = 16;
+
+    fn from_index(n: usize) -> Option<Self> {
+        match n {
+            0 => None,
+            _ => Some(Self(n as u128)),
+        }
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(Uniform::new_inclusive(1, u128::MAX).sample(rng))
+    }
+
+    // Computed as `self ** (2**128 - 2)`
+    fn inverse(self) -> Self {
+        let mut result = Self(1);
+        let mut base = self;
+        let mut exp = u128::MAX - 1;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut be = [0u8; 16];
+        be.copy_from_slice(bytes);
+        Some(Self(u128::from_be_bytes(be)))
+    }
+}
+
+impl zeroize::Zeroize for GF2_128 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF2_128 {
+    type Output = GF2_128;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for GF2_128 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for GF2_128 {
+    type Output = Self;
+
+    // Schoolbook product of the 64 bits halves into 256 bits, reduced modulo the field polynomial
+    // by folding the high half twice, `x**128` being `x**7 + x**2 + x + 1`
+    fn mul(self, other: Self) -> Self::Output {
+        let (a0, a1) = (self.0 as u64, (self.0 >> 64) as u64);
+        let (b0, b1) = (other.0 as u64, (other.0 >> 64) as u64);
+        let middle = clmul(a0, b1) ^ clmul(a1, b0);
+        let low = clmul(a0, b0) ^ (middle << 64);
+        let high = clmul(a1, b1) ^ (middle >> 64);
+
+        let carry = (high >> 127) ^ (high >> 126) ^ (high >> 121);
+        let high = high ^ carry;
+        Self(low ^ high ^ (high << 1) ^ (high << 2) ^ (high << 7))
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Div for GF2_128 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl Sum for GF2_128 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |acc, x| acc + x)
+    }
+}
+
+impl Product for GF2_128 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clmul, clmul_soft, Field, GF2_128};
+
+    #[test]
+    fn clmul_works() {
+        assert_eq!(clmul_soft(0b11, 0b11), 0b101);
+        assert_eq!(clmul_soft(u64::MAX, 2), (u64::MAX as u128) << 1);
+        let mut x = 0x0123_4567_89ab_cdefu64;
+        for _ in 0..100 {
+            let y = x.rotate_left(17) ^ 0xdead_beef_cafe_f00d;
+            assert_eq!(clmul(x, y), clmul_soft(x, y));
+            x = x.wrapping_mul(0x9e37_79b9_7f4a_7c15).wrapping_add(1);
+        }
+    }
+
+    #[test]
+    fn mul_works() {
+        assert_eq!(GF2_128(2) * GF2_128(1 << 127), GF2_128(0x87));
+        assert_eq!(GF2_128(1 << 64) * GF2_128(1 << 64), GF2_128(0x87));
+        assert_eq!(
+            GF2_128(1 << 127) * GF2_128(1 << 127),
+            GF2_128(0xc000_0000_0000_0000_0000_0000_0000_1067)
+        );
+        assert_eq!(GF2_128(0x1234) * GF2_128(1), GF2_128(0x1234));
+        assert_eq!(GF2_128(0x1234) * GF2_128(0), GF2_128(0));
+
+        let (a, b, c) = (
+            GF2_128(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+            GF2_128(0xfedc_ba98_7654_3210_0f1e_2d3c_4b5a_6978),
+            GF2_128(0xdead_beef_cafe_f00d_1357_9bdf_0246_8ace),
+        );
+        assert_eq!(a * b, GF2_128(0x4282_ec19_4383_ed12_b1a3_fef4_2375_6c28));
+        assert_eq!(a * b, b * a);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * (b + c), a * b + a * c);
+    }
+
+    #[test]
+    fn div_works() {
+        for i in (0..128).map(|k| 0x0123_4567_89ab_cdef_0123_4567_89ab_cdefu128.rotate_left(k) >> k)
+        {
+            assert_eq!(GF2_128(i) / GF2_128(i), GF2_128(1));
+            assert_eq!(GF2_128(0x1234) * GF2_128(i) / GF2_128(i), GF2_128(0x1234));
+        }
+        assert_eq!(GF2_128(0) / GF2_128(7), GF2_128(0));
+    }
+
+    #[test]
+    fn bytes_roundtrip_works() {
+        let mut bytes = [0u8; 16];
+        GF2_128(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10).to_bytes(&mut bytes);
+        assert_eq!(
+            bytes,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+        assert_eq!(
+            GF2_128::from_bytes(&bytes),
+            Some(GF2_128(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10))
+        );
+        assert_eq!(GF2_128::from_index(0), None);
+    }
+}
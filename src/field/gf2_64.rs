@@ -0,0 +1,173 @@
+// Basic operations overrided for the Galois Field 2**64
+// Uses the x**64 + x**4 + x**3 + x + 1 irreducible polynomial
+// Operations run in constant time, without branches depending on the elements values
+
+use core::iter::{Product, Sum};
+use core::ops::{Add, Div, Mul, Sub};
+
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+
+use super::Field;
+
+// Low terms of the field polynomial, the x**64 one being implicit
+const POLYNOMIAL: u64 = 0x1b;
+
+/// Element of the Galois Field 2**64, each one holding eight bytes of the secret.
+/// Large secrets are split with eight times fewer field operations than with `GF256`,
+/// each of them being more expensive though, and practically any number of shares can be generated.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct GF2_64(pub u64);
+
+impl Field for GF2_64 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const BYTES: usize = 8;
+
+    fn from_index(n: usize) -> Option<Self> {
+        match n {
+            0 => None,
+            _ => Some(Self(n as u64)),
+        }
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(Uniform::new_inclusive(1, u64::MAX).sample(rng))
+    }
+
+    // Computed as `self ** (2**64 - 2)`
+    fn inverse(self) -> Self {
+        let mut result = Self(1);
+        let mut base = self;
+        let mut exp = u64::MAX - 1;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut be = [0u8; 8];
+        be.copy_from_slice(bytes);
+        Some(Self(u64::from_be_bytes(be)))
+    }
+}
+
+impl zeroize::Zeroize for GF2_64 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF2_64 {
+    type Output = GF2_64;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for GF2_64 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for GF2_64 {
+    type Output = Self;
+
+    // Carry-less multiplication interleaved with the reduction modulo the field polynomial,
+    // all the conditional steps being computed through masks
+    fn mul(self, other: Self) -> Self::Output {
+        let mut a = self.0;
+        let mut b = other.0;
+        let mut result = 0u64;
+
+        for _ in 0..64 {
+            result ^= a & (b & 1).wrapping_neg();
+            a = (a << 1) ^ (POLYNOMIAL & (a >> 63).wrapping_neg());
+            b >>= 1;
+        }
+
+        Self(result)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Div for GF2_64 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl Sum for GF2_64 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |acc, x| acc + x)
+    }
+}
+
+impl Product for GF2_64 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, GF2_64};
+
+    #[test]
+    fn mul_works() {
+        assert_eq!(GF2_64(2) * GF2_64(1 << 63), GF2_64(0x1b));
+        assert_eq!(GF2_64(1 << 32) * GF2_64(1 << 32), GF2_64(0x1b));
+        assert_eq!(GF2_64(0x1234) * GF2_64(1), GF2_64(0x1234));
+        assert_eq!(GF2_64(0x1234) * GF2_64(0), GF2_64(0));
+
+        let (a, b, c) = (
+            GF2_64(0x0123_4567_89ab_cdef),
+            GF2_64(0xfedc_ba98_7654_3210),
+            GF2_64(0xdead_beef_cafe_f00d),
+        );
+        assert_eq!(a * b, b * a);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * (b + c), a * b + a * c);
+    }
+
+    #[test]
+    fn div_works() {
+        for i in (1..u64::MAX).step_by(0x0123_4567_89ab_cdef) {
+            assert_eq!(GF2_64(i) / GF2_64(i), GF2_64(1));
+            assert_eq!(GF2_64(0x1234) * GF2_64(i) / GF2_64(i), GF2_64(0x1234));
+        }
+        assert_eq!(GF2_64(0) / GF2_64(7), GF2_64(0));
+    }
+
+    #[test]
+    fn bytes_roundtrip_works() {
+        let mut bytes = [0u8; 8];
+        GF2_64(0x0102_0304_0506_0708).to_bytes(&mut bytes);
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            GF2_64::from_bytes(&bytes),
+            Some(GF2_64(0x0102_0304_0506_0708))
+        );
+        assert_eq!(GF2_64::from_index(0), None);
+    }
+}
@@ -0,0 +1,186 @@
+// Basic operations overrided for the Galois Field 65536 (2**16)
+// Uses the 0x1100b primitive polynomial (x**16 + x**12 + x**3 + x + 1)
+// Operations run in constant time, without branches depending on the elements values
+
+use core::iter::{Product, Sum};
+use core::ops::{Add, Div, Mul, Sub};
+
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+
+use super::Field;
+
+const POLYNOMIAL: u32 = 0x1100b;
+
+/// Element of the Galois Field 65536 (2**16), each one holding two bytes of the secret.
+/// Allows to generate up to 65535 shares.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct GF65536(pub u16);
+
+impl Field for GF65536 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const BYTES: usize = 2;
+
+    fn from_index(n: usize) -> Option<Self> {
+        match n {
+            1..=0xffff => Some(Self(n as u16)),
+            _ => None,
+        }
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(Uniform::new_inclusive(1, 0xffff).sample(rng))
+    }
+
+    // Computed as `self ** (2**16 - 2)`
+    fn inverse(self) -> Self {
+        let mut result = Self(1);
+        let mut base = self;
+        let mut exp = 0xfffeu16;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(Self(u16::from_be_bytes([bytes[0], bytes[1]])))
+    }
+}
+
+impl zeroize::Zeroize for GF65536 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF65536 {
+    type Output = GF65536;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for GF65536 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for GF65536 {
+    type Output = Self;
+
+    // Carry-less multiplication followed by the reduction modulo the field polynomial,
+    // all the conditional steps being computed through masks
+    fn mul(self, other: Self) -> Self::Output {
+        let mut a = self.0 as u32;
+        let mut b = other.0 as u32;
+        let mut result = 0u32;
+
+        for _ in 0..16 {
+            result ^= a & (b & 1).wrapping_neg();
+            a <<= 1;
+            a ^= POLYNOMIAL & (a >> 16).wrapping_neg();
+            b >>= 1;
+        }
+
+        Self(result as u16)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Div for GF65536 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl Sum for GF65536 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |acc, x| acc + x)
+    }
+}
+
+impl Product for GF65536 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, GF65536};
+
+    #[test]
+    fn add_works() {
+        assert_eq!(GF65536(0x1234) + GF65536(0xff00), GF65536(0xed34));
+        assert_eq!(GF65536(0x1234) - GF65536(0xff00), GF65536(0xed34));
+    }
+
+    #[test]
+    fn mul_works() {
+        assert_eq!(GF65536(2) * GF65536(0x8000), GF65536(0x100b));
+        assert_eq!(GF65536(0x1234) * GF65536(1), GF65536(0x1234));
+        assert_eq!(GF65536(0x1234) * GF65536(0), GF65536(0));
+        assert_eq!(
+            GF65536(0x1234) * GF65536(0xabcd),
+            GF65536(0xabcd) * GF65536(0x1234)
+        );
+    }
+
+    #[test]
+    fn generator_order_works() {
+        let mut x = GF65536(2);
+        let mut order = 1;
+        while x != GF65536(1) {
+            x = x * GF65536(2);
+            order += 1;
+        }
+        assert_eq!(order, 0xffff);
+    }
+
+    #[test]
+    fn div_works() {
+        for i in (1..=0xffffu16).step_by(97) {
+            assert_eq!(GF65536(i) / GF65536(i), GF65536(1));
+            assert_eq!(GF65536(0x1234) * GF65536(i) / GF65536(i), GF65536(0x1234));
+        }
+        assert_eq!(GF65536(0) / GF65536(7), GF65536(0));
+    }
+
+    #[test]
+    fn sum_product_works() {
+        let values = vec![GF65536(0x5300), GF65536(0x00ca), GF65536(0)];
+        assert_eq!(values.into_iter().sum::<GF65536>(), GF65536(0x53ca));
+        let values = vec![GF65536(1), GF65536(2), GF65536(0x8000)];
+        assert_eq!(values.into_iter().product::<GF65536>(), GF65536(0x100b));
+    }
+
+    #[test]
+    fn bytes_roundtrip_works() {
+        let mut bytes = [0u8; 2];
+        GF65536(0x1234).to_bytes(&mut bytes);
+        assert_eq!(bytes, [0x12, 0x34]);
+        assert_eq!(GF65536::from_bytes(&bytes), Some(GF65536(0x1234)));
+        assert_eq!(GF65536::from_index(0x10000), None);
+    }
+}
@@ -0,0 +1,171 @@
+// Finite fields over which secrets are shared, all of them implementing the `Field` trait
+
+#[cfg(feature = "bitslice")]
+mod bitslice;
+mod gf256;
+mod gf256poly;
+mod gf2_128;
+mod gf2_64;
+mod gf65536;
+#[cfg(feature = "curve25519")]
+mod scalar;
+mod simd;
+
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::iter::{Product, Sum};
+use core::ops::{Add, Mul, Sub};
+
+use rand::Rng;
+use zeroize::Zeroize;
+
+pub use gf256::GF256;
+pub use gf256poly::GF256Poly;
+pub use gf2_128::GF2_128;
+pub use gf2_64::GF2_64;
+pub use gf65536::GF65536;
+#[cfg(feature = "curve25519")]
+pub use scalar::ScalarCommitments;
+
+/// A finite field over which secrets can be shared, implemented by `GF256`, `GF256Poly`, `GF65536`, `GF2_64` and `GF2_128`,
+/// as well as the Ed25519 scalar field with the `curve25519` feature.
+/// Implement it for your own type to split secrets over any other field,
+/// the dealing and interpolation logic of `Sharks` being generic over it.
+///
+/// Each element holds `SECRET_BYTES` bytes of the secret, and the number of non-zero elements
+/// determines the maximum number of shares that can be generated.
+/// Elements must implement `Zeroize`, so that shares can be wiped from memory,
+/// as well as `Send` and `Sync` to be processed across threads with the `rayon` feature.
+///
+/// Example, a prime field modulo the Mersenne prime `2**31 - 1`:
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use std::iter::{Product, Sum};
+/// use std::ops::{Add, Mul, Sub};
+///
+/// use rand::Rng;
+/// use sharks::zeroize::Zeroize;
+/// use sharks::{Field, FieldShare, Sharks};
+///
+/// const P: u64 = (1 << 31) - 1;
+///
+/// #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+/// struct Fp(u64);
+///
+/// impl Add for Fp {
+///     type Output = Self;
+///     fn add(self, other: Self) -> Self { Fp((self.0 + other.0) % P) }
+/// }
+///
+/// impl Sub for Fp {
+///     type Output = Self;
+///     fn sub(self, other: Self) -> Self { Fp((self.0 + P - other.0) % P) }
+/// }
+///
+/// impl Mul for Fp {
+///     type Output = Self;
+///     fn mul(self, other: Self) -> Self { Fp(self.0 * other.0 % P) }
+/// }
+///
+/// impl Sum for Fp {
+///     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Fp(0), |a, b| a + b) }
+/// }
+///
+/// impl Product for Fp {
+///     fn product<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Fp(1), |a, b| a * b) }
+/// }
+///
+/// impl Zeroize for Fp {
+///     fn zeroize(&mut self) { self.0.zeroize() }
+/// }
+///
+/// impl Field for Fp {
+///     const ZERO: Self = Fp(0);
+///     const ONE: Self = Fp(1);
+///     const BYTES: usize = 4;
+///     // Any 3 bytes value is lower than `P`
+///     const SECRET_BYTES: usize = 3;
+///
+///     fn from_index(n: usize) -> Option<Self> {
+///         if n > 0 && (n as u64) < P { Some(Fp(n as u64)) } else { None }
+///     }
+///
+///     fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+///         Fp(rng.gen_range(1, P))
+///     }
+///
+///     // Fermat's little theorem: `a**(p - 2) = a**-1 mod p`
+///     fn inverse(self) -> Self {
+///         (0..31).fold((Fp(1), self), |(acc, base), i| {
+///             (if (P - 2) >> i & 1 == 1 { acc * base } else { acc }, base * base)
+///         }).0
+///     }
+///
+///     fn to_bytes(&self, bytes: &mut [u8]) {
+///         bytes.copy_from_slice(&(self.0 as u32).to_be_bytes());
+///     }
+///
+///     fn from_bytes(bytes: &[u8]) -> Option<Self> {
+///         let n = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+///         if n < P { Some(Fp(n)) } else { None }
+///     }
+/// }
+///
+/// let sharks = Sharks(3);
+/// let shares: Vec<FieldShare<Fp>> = sharks.dealer_in(&[1, 2, 3, 4]).take(3).collect();
+/// assert_eq!(sharks.recover_in(&shares).unwrap(), vec![1, 2, 3, 4]);
+/// ```
+pub trait Field:
+    Copy
+    + Debug
+    + Eq
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Sum
+    + Product
+    + Zeroize
+    + Send
+    + Sync
+{
+    /// Additive identity of the field.
+    const ZERO: Self;
+    /// Multiplicative identity of the field.
+    const ONE: Self;
+    /// Length in bytes of the big endian representation of an element.
+    const BYTES: usize;
+    /// Number of secret bytes held by each element, at most `BYTES`.
+    /// Must be low enough for any `SECRET_BYTES` long big endian value to be an element of the field.
+    const SECRET_BYTES: usize = Self::BYTES;
+
+    /// Returns the `n`th non-zero element of the field, used as the `x` coordinate of the `n`th share.
+    /// `None` is returned if `n` is zero or greater than the number of non-zero elements.
+    fn from_index(n: usize) -> Option<Self>;
+
+    /// Returns a random non-zero element, used as polynomial coefficient.
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Returns the multiplicative inverse of a non-zero element.
+    fn inverse(self) -> Self;
+
+    /// Writes the big endian representation of the element into `bytes`, which is `BYTES` long.
+    fn to_bytes(&self, bytes: &mut [u8]);
+
+    /// Reads an element from its big endian representation, `bytes` being `BYTES` long.
+    /// `None` is returned if the bytes do not represent an element of the field.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// Computes `acc[i] = acc[i] * c + b[i]` for every element of `acc`, `b` being as long.
+    /// Used to evaluate the polynomials of all the secret chunks at once, fields may override it
+    /// with an implementation processing several elements at a time.
+    fn scale_add_slice(acc: &mut [Self], c: Self, b: &[Self]) {
+        acc.iter_mut().zip(b).for_each(|(a, b)| *a = *a * c + *b);
+    }
+
+    /// Computes `acc[i] = acc[i] + c * b[i]` for every element of `acc`, `b` being as long.
+    /// Used to interpolate all the secret chunks at once, fields may override it as `scale_add_slice`.
+    fn add_scaled_slice(acc: &mut [Self], c: Self, b: &[Self]) {
+        acc.iter_mut().zip(b).for_each(|(a, b)| *a = *a + c * *b);
+    }
+}
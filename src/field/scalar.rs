@@ -0,0 +1,388 @@
+// Prime field of the Ed25519 / Ristretto255 group order, which allows to share private key scalars
+// directly in order to later combine partial results homomorphically, like in threshold signing.
+// Scalar shares can be dealt along with Feldman commitments to the polynomial coefficients, points of the
+// Ristretto255 group, from which each participant derives the public key and verification key of any share.
+
+use std::convert::TryFrom;
+
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use rand::Rng;
+use sha2::{Digest, Sha512};
+
+use super::Field;
+use crate::{math, FieldShare, Sharks, SharksError};
+
+/// Feldman commitments to the polynomial a secret scalar was split with, `a_i * G` for each coefficient `a_i`
+/// and the Ristretto255 base point `G`, lower degrees first. Published by the dealer of `Sharks::dealer_scalar_feldman`,
+/// they let participants verify their shares and derive the public keys of threshold signing schemes.
+/// Can be serialized to and from a byte array of compressed points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScalarCommitments {
+    pub(crate) points: Vec<RistrettoPoint>,
+}
+
+impl ScalarCommitments {
+    /// Returns the minimum threshold of shares the commitments were generated for.
+    pub fn threshold(&self) -> u8 {
+        self.points.len() as u8
+    }
+
+    /// Returns the commitments to the coefficients, lower degrees first.
+    pub fn points(&self) -> &[RistrettoPoint] {
+        &self.points
+    }
+
+    /// Returns the commitment to the secret, `secret * G`, being the group public key if the secret is a private key.
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.points[0]
+    }
+
+    /// Returns the commitment to the share at `x`, `y * G`, being the verification key of its holder.
+    pub fn verification_key(&self, x: Scalar) -> RistrettoPoint {
+        self.points
+            .iter()
+            .rev()
+            .fold(RistrettoPoint::default(), |acc, c| acc * x + c)
+    }
+}
+
+/// Obtains a byte vector from a `ScalarCommitments` instance, the compressed points following each other.
+impl From<&ScalarCommitments> for Vec<u8> {
+    fn from(c: &ScalarCommitments) -> Vec<u8> {
+        c.points
+            .iter()
+            .flat_map(|p| p.compress().to_bytes())
+            .collect()
+    }
+}
+
+/// Obtains a `ScalarCommitments` instance from a byte slice, validating its contents.
+/// An `Err` is returned if there are no points or more than 255, or they are not canonical group elements.
+impl TryFrom<&[u8]> for ScalarCommitments {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<ScalarCommitments, Self::Error> {
+        if s.is_empty() || !s.len().is_multiple_of(32) || s.len() > 255 * 32 {
+            return Err(SharksError::InvalidLength);
+        }
+        let points = s
+            .chunks(32)
+            .map(|p| CompressedRistretto::from_slice(p).ok()?.decompress())
+            .collect::<Option<Vec<RistrettoPoint>>>()
+            .ok_or(SharksError::InvalidElement)?;
+        Ok(ScalarCommitments { points })
+    }
+}
+
+impl FieldShare<Scalar> {
+    /// Checks the share, generated by `dealer_scalar_feldman`, lies on the polynomial committed to by `commitments`,
+    /// that is, `y * G` is the verification key at its `x` coordinate.
+    pub fn verify_feldman(&self, commitments: &ScalarCommitments) -> bool {
+        match self.y.as_slice() {
+            [y] => RistrettoPoint::mul_base(y) == commitments.verification_key(self.x),
+            _ => false,
+        }
+    }
+}
+
+impl Field for Scalar {
+    const ZERO: Self = Scalar::ZERO;
+    const ONE: Self = Scalar::ONE;
+    const BYTES: usize = 32;
+    // The group order is greater than 2**252, so any 31 bytes value is a valid scalar
+    const SECRET_BYTES: usize = 31;
+
+    fn from_index(n: usize) -> Option<Self> {
+        match n {
+            0 => None,
+            _ => Some(Scalar::from(n as u64)),
+        }
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 64];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            let scalar = Scalar::from_bytes_mod_order_wide(&bytes);
+            if scalar != Scalar::ZERO {
+                return scalar;
+            }
+        }
+    }
+
+    fn inverse(self) -> Self {
+        self.invert()
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(self.as_bytes());
+        bytes.reverse();
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut le = [0u8; 32];
+        le.copy_from_slice(bytes);
+        le.reverse();
+        Scalar::from_canonical_bytes(le).into()
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` scalar, such as an Ed25519 private key, returns an `Iterator` along
+    /// new shares holding a single scalar each. Shares can be combined homomorphically,
+    /// or the secret recovered with `recover_scalar`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, FieldShare };
+    /// use sharks::curve25519_dalek::scalar::Scalar;
+    ///
+    /// let sharks = Sharks(3);
+    /// let secret = Scalar::from(42u64);
+    /// let shares: Vec<FieldShare<Scalar>> = sharks.dealer_scalar(secret).take(3).collect();
+    /// assert_eq!(sharks.recover_scalar(&shares).unwrap(), secret);
+    pub fn dealer_scalar(&self, secret: Scalar) -> impl Iterator<Item = FieldShare<Scalar>> {
+        self.deal(vec![secret])
+    }
+
+    /// Given an Ed25519 private key `seed`, returns an `Iterator` along shares of its secret scalar, the clamped
+    /// first half of the SHA-512 digest of the seed ([RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.5)),
+    /// shared in the scalar field so that threshold signing schemes can use the shares directly, weighting them
+    /// with `lagrange_coefficients`. The second half of the digest, used for deterministic nonces, is not shared,
+    /// as threshold signatures require jointly generated nonces anyway. The public key stays the same.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, FieldShare };
+    /// use sharks::curve25519_dalek::{edwards::EdwardsPoint, scalar::Scalar};
+    ///
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<FieldShare<Scalar>> = sharks.dealer_ed25519(&[7; 32]).take(3).collect();
+    /// // Each participant computes its partial public key, which are combined with the Lagrange coefficients
+    /// let xs = [shares[0].x, shares[2].x];
+    /// let coefficients = sharks.lagrange_coefficients(&xs).unwrap();
+    /// let public_key: EdwardsPoint = [&shares[0], &shares[2]]
+    ///     .iter()
+    ///     .zip(coefficients.iter())
+    ///     .map(|(s, c)| EdwardsPoint::mul_base(&(s.y[0] * c)))
+    ///     .sum();
+    /// let secret = sharks.recover_scalar(&shares[..2]).unwrap();
+    /// assert_eq!(public_key, EdwardsPoint::mul_base(&secret));
+    /// ```
+    pub fn dealer_ed25519(&self, seed: &[u8; 32]) -> impl Iterator<Item = FieldShare<Scalar>> {
+        let mut digest = Sha512::digest(seed);
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&digest[..32]);
+        let secret = Scalar::from_bytes_mod_order(clamp_integer(bytes));
+
+        zeroize::Zeroize::zeroize(&mut bytes);
+        zeroize::Zeroize::zeroize(digest.as_mut_slice());
+        self.dealer_scalar(secret)
+    }
+
+    /// Given an X25519 private key, returns an `Iterator` along shares of the clamped scalar it represents, see
+    /// `dealer_ed25519`. The scalar being reduced modulo the group order, combining partial Diffie-Hellman results
+    /// gives the shared secret of the key for points of the prime order subgroup, like the ones of honest peers.
+    pub fn dealer_x25519(&self, secret_key: &[u8; 32]) -> impl Iterator<Item = FieldShare<Scalar>> {
+        self.dealer_scalar(Scalar::from_bytes_mod_order(clamp_integer(*secret_key)))
+    }
+
+    /// Same as `dealer_scalar`, also returning Feldman commitments to the polynomial, with which each participant
+    /// checks its share through `FieldShare::verify_feldman`. Commitments reveal `secret * G`, the public key of
+    /// the secret, but nothing else about it. The maximum number of shares is 255, to match the threshold range.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, FieldShare };
+    /// use sharks::curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+    ///
+    /// let sharks = Sharks(2);
+    /// let secret = Scalar::from(42u64);
+    /// let (commitments, dealer) = sharks.dealer_scalar_feldman(secret);
+    /// let shares: Vec<FieldShare<Scalar>> = dealer.take(3).collect();
+    /// assert!(shares.iter().all(|s| s.verify_feldman(&commitments)));
+    /// assert_eq!(commitments.public_key(), RistrettoPoint::mul_base(&secret));
+    /// assert_eq!(sharks.recover_scalar(&shares[1..]).unwrap(), secret);
+    /// ```
+    pub fn dealer_scalar_feldman(
+        &self,
+        secret: Scalar,
+    ) -> (ScalarCommitments, impl Iterator<Item = FieldShare<Scalar>>) {
+        let coeffs = math::random_coefficients(vec![secret], self.0, &mut rand::thread_rng());
+        let points = coeffs
+            .iter()
+            .rev()
+            .map(|c| RistrettoPoint::mul_base(&c[0]))
+            .collect();
+
+        let threshold = self.0;
+        let shares = math::coefficient_evaluator(coeffs)
+            .take(255)
+            .map(move |mut s| {
+                s.threshold = Some(threshold);
+                s
+            });
+        (ScalarCommitments { points }, shares)
+    }
+
+    /// Given an iterable collection of shares generated by `dealer_scalar`, recovers the secret scalar.
+    /// If the number of distinct shares is less than the minimum threshold, or the shares do not
+    /// hold a single scalar, an `Err` is returned.
+    pub fn recover_scalar<'a, T>(&self, shares: T) -> Result<Scalar, SharksError>
+    where
+        T: IntoIterator<Item = &'a FieldShare<Scalar>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<Scalar>>,
+    {
+        match self.interpolate(shares)?.as_slice() {
+            [secret] => Ok(*secret),
+            _ => Err(SharksError::InvalidLength),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, FieldShare, Scalar, ScalarCommitments, Sharks};
+    use crate::SharksError;
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::montgomery::MontgomeryPoint;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn bytes_roundtrip_works() {
+        let mut bytes = [0u8; 32];
+        Field::to_bytes(&Scalar::from(0x0102u64), &mut bytes);
+        assert_eq!(bytes[30..], [1, 2]);
+        assert_eq!(
+            <Scalar as Field>::from_bytes(&bytes),
+            Some(Scalar::from(0x0102u64))
+        );
+        assert_eq!(<Scalar as Field>::from_bytes(&[0xff; 32]), None);
+    }
+
+    #[test]
+    fn inverse_works() {
+        let x = Scalar::from(1234u64);
+        assert_eq!(x * Field::inverse(x), Scalar::ONE);
+    }
+
+    #[test]
+    fn scalar_integration_works() {
+        let sharks = Sharks(5);
+        let secret = Field::random(&mut rand::thread_rng());
+        let shares: Vec<FieldShare<Scalar>> = sharks.dealer_scalar(secret).take(5).collect();
+        assert_eq!(sharks.recover_scalar(&shares).unwrap(), secret);
+        assert!(sharks.recover_scalar(&shares[1..]).is_err());
+
+        // Shares are homomorphic, summing shares of two secrets gives shares of their sum
+        let other = Scalar::from(7u64);
+        let sum: Vec<FieldShare<Scalar>> = shares
+            .iter()
+            .zip(sharks.dealer_scalar(other))
+            .map(|(a, b)| {
+                let mut s = a.clone();
+                s.y[0] += b.y[0];
+                s
+            })
+            .collect();
+        assert_eq!(sharks.recover_scalar(&sum).unwrap(), secret + other);
+    }
+
+    #[test]
+    fn bytes_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..100).collect();
+        let shares: Vec<FieldShare<Scalar>> = sharks.dealer_in(&secret).take(3).collect();
+        assert_eq!(sharks.recover_in(&shares).unwrap(), secret);
+        assert!(sharks.recover_scalar(&shares).is_err());
+
+        let bytes = Vec::from(&shares[0]);
+        assert_eq!(bytes.len(), 4 * 32 + 32);
+        assert!(FieldShare::<Scalar>::try_from(bytes.as_slice()).is_ok());
+    }
+
+    #[test]
+    fn feldman_integration_works() {
+        let sharks = Sharks(3);
+        let secret = Field::random(&mut rand::thread_rng());
+        let (commitments, dealer) = sharks.dealer_scalar_feldman(secret);
+        let shares: Vec<FieldShare<Scalar>> = dealer.collect();
+        assert_eq!(shares.len(), 255);
+        assert_eq!(commitments.threshold(), 3);
+        assert_eq!(commitments.public_key(), RistrettoPoint::mul_base(&secret));
+        assert!(shares.iter().all(|s| s.verify_feldman(&commitments)));
+        assert_eq!(
+            commitments.verification_key(shares[7].x),
+            RistrettoPoint::mul_base(&shares[7].y[0])
+        );
+        assert_eq!(sharks.recover_scalar(&shares[100..103]).unwrap(), secret);
+
+        let bytes = Vec::from(&commitments);
+        assert_eq!(bytes.len(), 3 * 32);
+        let commitments = ScalarCommitments::try_from(bytes.as_slice()).unwrap();
+        let mut forged = shares[0].clone();
+        forged.y[0] += Scalar::ONE;
+        assert!(!forged.verify_feldman(&commitments));
+        let (other, _) = sharks.dealer_scalar_feldman(secret);
+        assert!(!shares[0].verify_feldman(&other));
+    }
+
+    #[test]
+    fn feldman_commitments_err() {
+        let cases: [(&[u8], SharksError); 4] = [
+            (&[], SharksError::InvalidLength),
+            (&[0; 33], SharksError::InvalidLength),
+            (&[0; 256 * 32], SharksError::InvalidLength),
+            (&[0xff; 32], SharksError::InvalidElement),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ScalarCommitments::try_from(*bytes).err(), Some(*err));
+        }
+    }
+
+    #[test]
+    fn ed25519_integration_works() {
+        // First test vector of RFC 8032
+        let seed = <[u8; 32]>::try_from(
+            &hex(b"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")[..],
+        )
+        .unwrap();
+        let public_key = hex(b"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+
+        let sharks = Sharks(3);
+        let shares: Vec<FieldShare<Scalar>> = sharks.dealer_ed25519(&seed).take(5).collect();
+        let chosen = [&shares[4], &shares[1], &shares[2]];
+        let xs: Vec<Scalar> = chosen.iter().map(|s| s.x).collect();
+        let coefficients = sharks.lagrange_coefficients(&xs).unwrap();
+        let combined: EdwardsPoint = chosen
+            .iter()
+            .zip(coefficients.iter())
+            .map(|(s, c)| EdwardsPoint::mul_base(&s.y[0]) * c)
+            .sum();
+        assert_eq!(combined.compress().as_bytes()[..], public_key[..]);
+        assert_eq!(
+            sharks.lagrange_coefficients(&xs[..2]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+
+        let secret_key = [9u8; 32];
+        let shares: Vec<FieldShare<Scalar>> = sharks.dealer_x25519(&secret_key).take(3).collect();
+        let secret = sharks.recover_scalar(&shares).unwrap();
+        assert_eq!(
+            MontgomeryPoint::mul_base(&secret),
+            MontgomeryPoint::mul_base_clamped(secret_key)
+        );
+    }
+
+    fn hex(s: &[u8]) -> Vec<u8> {
+        s.chunks(2)
+            .map(|c| u8::from_str_radix(std::str::from_utf8(c).unwrap(), 16).unwrap())
+            .collect()
+    }
+}
@@ -0,0 +1,228 @@
+// Bulk GF256 arithmetic, multiplying whole byte slices by a constant at once.
+// A product `c * b` is split over the nibbles of `b` as `c * (b & 0x0f) ^ c * (b & 0xf0)`, so that both halves
+// are looked up in 16 entries tables, which fit a single vector register and are indexed with byte shuffles.
+// AVX2 and SSSE3 are detected at runtime on x86_64 when `std` is available, NEON being always present on aarch64.
+// Every other target, as well as the slices tails, use the scalar fallback.
+// The `bitslice` feature replaces all of them by the table free bit-sliced implementation.
+// Shuffles run in constant time, while the fallback does not index the tables but uses the constant time `GF256` product.
+
+use super::GF256;
+
+// Products of the constant with every low and high nibble.
+struct Tables {
+    low: [u8; 16],
+    high: [u8; 16],
+}
+
+impl Tables {
+    fn new(c: u8) -> Self {
+        let mut tables = Tables {
+            low: [0; 16],
+            high: [0; 16],
+        };
+        for n in 0..16u8 {
+            tables.low[n as usize] = (GF256(c) * GF256(n)).0;
+            tables.high[n as usize] = (GF256(c) * GF256(n << 4)).0;
+        }
+        tables
+    }
+}
+
+// Computes `acc[i] = acc[i] * c + b[i]`.
+pub fn scale_add(acc: &mut [u8], c: u8, b: &[u8]) {
+    assert_eq!(acc.len(), b.len());
+    let dst = acc.as_mut_ptr();
+    // SAFETY: both slices are `acc.len()` long, and each position of `acc` is read before being written.
+    unsafe { mul_xor(dst, dst, b.as_ptr(), acc.len(), &Tables::new(c)) }
+}
+
+// Computes `acc[i] = acc[i] + c * b[i]`.
+pub fn add_scaled(acc: &mut [u8], c: u8, b: &[u8]) {
+    assert_eq!(acc.len(), b.len());
+    let dst = acc.as_mut_ptr();
+    // SAFETY: see `scale_add`.
+    unsafe { mul_xor(dst, b.as_ptr(), dst, acc.len(), &Tables::new(c)) }
+}
+
+// Computes `dst[i] = c * a[i] ^ b[i]` over `len` bytes with the fastest available implementation.
+// `dst` may alias `a` or `b`, every position being read before being written.
+#[allow(unreachable_code)]
+unsafe fn mul_xor(dst: *mut u8, a: *const u8, b: *const u8, len: usize, tables: &Tables) {
+    #[cfg(feature = "bitslice")]
+    {
+        return super::bitslice::mul_xor(dst, a, b, len, tables.low[1]);
+    }
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return x86::mul_xor_avx2(dst, a, b, len, tables);
+        }
+        if is_x86_feature_detected!("ssse3") {
+            return x86::mul_xor_ssse3(dst, a, b, len, tables);
+        }
+    }
+    #[cfg(all(target_arch = "x86_64", not(feature = "std"), target_feature = "ssse3"))]
+    {
+        return x86::mul_xor_ssse3(dst, a, b, len, tables);
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        return neon::mul_xor(dst, a, b, len, tables);
+    }
+    mul_xor_scalar(dst, a, b, len, tables)
+}
+
+unsafe fn mul_xor_scalar(dst: *mut u8, a: *const u8, b: *const u8, len: usize, tables: &Tables) {
+    let c = GF256(tables.low[1]);
+    for i in 0..len {
+        *dst.add(i) = (c * GF256(*a.add(i))).0 ^ *b.add(i);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", any(feature = "std", target_feature = "ssse3")))]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    use super::{mul_xor_scalar, Tables};
+
+    #[cfg(feature = "std")]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mul_xor_avx2(
+        dst: *mut u8,
+        a: *const u8,
+        b: *const u8,
+        len: usize,
+        tables: &Tables,
+    ) {
+        // Shuffles only index within 128 bits lanes, so both lanes get a copy of the tables
+        let low = _mm256_broadcastsi128_si256(_mm_loadu_si128(tables.low.as_ptr() as *const _));
+        let high = _mm256_broadcastsi128_si256(_mm_loadu_si128(tables.high.as_ptr() as *const _));
+        let mask = _mm256_set1_epi8(0x0f);
+
+        let mut i = 0;
+        while i + 32 <= len {
+            let v = _mm256_loadu_si256(a.add(i) as *const _);
+            let l = _mm256_shuffle_epi8(low, _mm256_and_si256(v, mask));
+            let h = _mm256_shuffle_epi8(high, _mm256_and_si256(_mm256_srli_epi64(v, 4), mask));
+            let w = _mm256_loadu_si256(b.add(i) as *const _);
+            _mm256_storeu_si256(
+                dst.add(i) as *mut _,
+                _mm256_xor_si256(_mm256_xor_si256(l, h), w),
+            );
+            i += 32;
+        }
+        mul_xor_ssse3(dst.add(i), a.add(i), b.add(i), len - i, tables)
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn mul_xor_ssse3(
+        dst: *mut u8,
+        a: *const u8,
+        b: *const u8,
+        len: usize,
+        tables: &Tables,
+    ) {
+        let low = _mm_loadu_si128(tables.low.as_ptr() as *const _);
+        let high = _mm_loadu_si128(tables.high.as_ptr() as *const _);
+        let mask = _mm_set1_epi8(0x0f);
+
+        let mut i = 0;
+        while i + 16 <= len {
+            let v = _mm_loadu_si128(a.add(i) as *const _);
+            let l = _mm_shuffle_epi8(low, _mm_and_si128(v, mask));
+            let h = _mm_shuffle_epi8(high, _mm_and_si128(_mm_srli_epi64(v, 4), mask));
+            let w = _mm_loadu_si128(b.add(i) as *const _);
+            _mm_storeu_si128(dst.add(i) as *mut _, _mm_xor_si128(_mm_xor_si128(l, h), w));
+            i += 16;
+        }
+        mul_xor_scalar(dst.add(i), a.add(i), b.add(i), len - i, tables)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use core::arch::aarch64::*;
+
+    use super::{mul_xor_scalar, Tables};
+
+    pub unsafe fn mul_xor(dst: *mut u8, a: *const u8, b: *const u8, len: usize, tables: &Tables) {
+        #[cfg(feature = "bitslice")]
+        {
+            return super::bitslice::mul_xor(dst, a, b, len, tables.low[1]);
+        }
+        let low = vld1q_u8(tables.low.as_ptr());
+        let high = vld1q_u8(tables.high.as_ptr());
+        let mask = vdupq_n_u8(0x0f);
+
+        let mut i = 0;
+        while i + 16 <= len {
+            let v = vld1q_u8(a.add(i));
+            let l = vqtbl1q_u8(low, vandq_u8(v, mask));
+            let h = vqtbl1q_u8(high, vshrq_n_u8(v, 4));
+            let w = vld1q_u8(b.add(i));
+            vst1q_u8(dst.add(i), veorq_u8(veorq_u8(l, h), w));
+            i += 16;
+        }
+        mul_xor_scalar(dst.add(i), a.add(i), b.add(i), len - i, tables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_scaled, scale_add, Tables};
+    use crate::field::GF256;
+
+    // Lengths covering the vector bodies as well as the scalar tails
+    const LENGTHS: [usize; 6] = [0, 1, 15, 16, 33, 100];
+
+    fn bytes(len: usize, seed: u8) -> Vec<u8> {
+        (0..len)
+            .map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+            .collect()
+    }
+
+    #[test]
+    fn tables_work() {
+        for c in 0..=255 {
+            let tables = Tables::new(c);
+            for n in 0..16 {
+                assert_eq!(tables.low[n as usize], (GF256(c) * GF256(n)).0);
+                assert_eq!(tables.high[n as usize], (GF256(c) * GF256(n << 4)).0);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_add_works() {
+        for len in LENGTHS.iter().copied() {
+            for c in [0, 1, 2, 143, 255].iter().copied() {
+                let mut acc = bytes(len, 7);
+                let b = bytes(len, 200);
+                let expected: Vec<u8> = acc
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| (GF256(*a) * GF256(c) + GF256(*b)).0)
+                    .collect();
+                scale_add(&mut acc, c, &b);
+                assert_eq!(acc, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn add_scaled_works() {
+        for len in LENGTHS.iter().copied() {
+            for c in [0, 1, 2, 143, 255].iter().copied() {
+                let mut acc = bytes(len, 7);
+                let b = bytes(len, 200);
+                let expected: Vec<u8> = acc
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| (GF256(*a) + GF256(c) * GF256(*b)).0)
+                    .collect();
+                add_scaled(&mut acc, c, &b);
+                assert_eq!(acc, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,400 @@
+// Splitting of files into share files and their recovery, the files being memory mapped and processed in chunks.
+// Share files start with a header, `version || threshold || x || length`, the length of the secret being
+// 8 bytes big endian, followed by the `y` values. Files are created without overwriting existing ones
+// and synced to disk, along with their directory, before returning.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::stream::{cancelled, Progress};
+use super::Sharks;
+
+/// Version byte at the start of the share files written by `Sharks::split_file`.
+pub const FILE_VERSION: u8 = 1;
+
+const HEADER_BYTES: usize = 11;
+const CHUNK_SIZE: usize = 1 << 16;
+
+fn invalid_data(error: SharksError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+// Maps the file in memory, empty files being returned as `None` since they cannot be mapped on every platform
+fn map(path: &Path) -> io::Result<Option<Mmap>> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    // SAFETY: the mapped files must not be modified by other processes while being processed,
+    // which is documented by the public functions
+    unsafe { Mmap::map(&file) }.map(Some)
+}
+
+// Creates the file, failing if it already exists
+fn create(path: &Path) -> io::Result<BufWriter<File>> {
+    let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+// Flushes the file and syncs it to disk, along with the directory entry created for it
+fn sync(writer: BufWriter<File>, path: &Path) -> io::Result<()> {
+    let file = writer.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        let parent = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        File::open(parent)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+// Runs `f` on the files created at `paths`, removing the ones it created if it fails
+fn create_all<P, T>(
+    paths: &[P],
+    f: impl FnOnce(&mut [BufWriter<File>]) -> io::Result<T>,
+) -> io::Result<T>
+where
+    P: AsRef<Path>,
+{
+    let mut writers = Vec::with_capacity(paths.len());
+    let mut created = 0;
+    let result = paths
+        .iter()
+        .try_for_each(|p| {
+            writers.push(create(p.as_ref())?);
+            created += 1;
+            Ok(())
+        })
+        .and_then(|_| f(&mut writers))
+        .and_then(|result| {
+            writers
+                .drain(..)
+                .zip(paths.iter())
+                .try_for_each(|(w, p)| sync(w, p.as_ref()))
+                .map(|_| result)
+        });
+    if result.is_err() {
+        drop(writers);
+        for p in paths[..created].iter() {
+            let _ = fs::remove_file(p.as_ref());
+        }
+    }
+    result
+}
+
+impl Sharks {
+    /// Splits the file at `secret` into one share file at each of the `shares` paths, which must not exist.
+    /// The secret is memory mapped and processed in chunks, so it can be larger than the available memory.
+    /// The share files are synced to disk before returning, and removed if an error occurs.
+    /// An `Err` is returned if there are fewer share paths than the threshold or more than 255,
+    /// a share file already exists, or reading or writing fails.
+    /// The secret file must not be modified while it is being split.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use sharks::Sharks;
+    /// let sharks = Sharks(2);
+    /// sharks.split_file("wallet.dat", &["share1", "share2", "share3"]).unwrap();
+    /// sharks.recover_file(&["share3", "share1"], "recovered.dat").unwrap();
+    /// ```
+    pub fn split_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        secret: P,
+        shares: &[Q],
+    ) -> io::Result<()> {
+        self.split_file_with_progress(secret, shares, |_| true)
+    }
+
+    /// Same as `split_file`, calling `progress` after each chunk written to the share files, with the number of
+    /// secret bytes split so far and the secret size. The operation is cancelled if the callback returns `false`,
+    /// in which case the share files are removed and an `Err` holding `SharksError::Cancelled` is returned.
+    pub fn split_file_with_progress<P, Q, F>(
+        &self,
+        secret: P,
+        shares: &[Q],
+        mut progress: F,
+    ) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(Progress) -> bool,
+    {
+        if shares.len() < self.0 as usize || shares.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                SharksError::NotEnoughShares {
+                    needed: (self.0 as usize).max(1),
+                    got: shares.len(),
+                    provided: shares.len(),
+                },
+            ));
+        } else if shares.len() > 255 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                SharksError::TooManyShares { max: 255 },
+            ));
+        }
+
+        let mapped = map(secret.as_ref())?;
+        let secret: &[u8] = mapped.as_deref().unwrap_or(&[]);
+        create_all(shares, |writers| {
+            for (x, writer) in (1..=255u8).zip(writers.iter_mut()) {
+                writer.write_all(&[FILE_VERSION, self.0, x])?;
+                writer.write_all(&(secret.len() as u64).to_be_bytes())?;
+            }
+            let mut processed = 0;
+            for chunk in secret.chunks(CHUNK_SIZE) {
+                for (share, writer) in self.dealer(chunk).zip(writers.iter_mut()) {
+                    writer.write_all(GF256::as_bytes(&share.y))?;
+                }
+                processed += chunk.len() as u64;
+                if !progress(Progress {
+                    processed,
+                    total: Some(secret.len() as u64),
+                }) {
+                    return Err(cancelled());
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Recovers the secret from the share files written by `split_file`, writing it to a new file at `secret`.
+    /// The shares are memory mapped and processed in chunks, so the secret can be larger than the available memory.
+    /// The secret file is synced to disk before returning, and removed if an error occurs.
+    /// An `Err` is returned if the share files are invalid, inconsistent or fewer than the threshold,
+    /// the secret file already exists, or reading or writing fails.
+    /// The share files must not be modified while the secret is being recovered.
+    pub fn recover_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        shares: &[P],
+        secret: Q,
+    ) -> io::Result<()> {
+        self.recover_file_with_progress(shares, secret, |_| true)
+    }
+
+    /// Same as `recover_file`, calling `progress` after each chunk written to the secret file, with the number of
+    /// secret bytes recovered so far and the secret size. The operation is cancelled if the callback returns `false`,
+    /// in which case the secret file is removed and an `Err` holding `SharksError::Cancelled` is returned.
+    pub fn recover_file_with_progress<P, Q, F>(
+        &self,
+        shares: &[P],
+        secret: Q,
+        mut progress: F,
+    ) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(Progress) -> bool,
+    {
+        let mapped = shares
+            .iter()
+            .map(|p| map(p.as_ref()))
+            .collect::<io::Result<Vec<Option<Mmap>>>>()?;
+        let files: Vec<&[u8]> = mapped.iter().map(|m| m.as_deref().unwrap_or(&[])).collect();
+
+        let mut xs: Vec<GF256> = Vec::with_capacity(files.len());
+        let mut length = None;
+        for file in files.iter() {
+            if file.len() < HEADER_BYTES {
+                return Err(invalid_data(SharksError::InvalidLength));
+            } else if file[0] != FILE_VERSION {
+                return Err(invalid_data(SharksError::UnknownVersion(file[0])));
+            } else if file[1] != self.0 {
+                return Err(invalid_data(SharksError::ThresholdMismatch {
+                    expected: self.0,
+                    found: file[1],
+                }));
+            } else if file[2] == 0 {
+                return Err(invalid_data(SharksError::ZeroShareX));
+            } else if xs.contains(&GF256(file[2])) {
+                return Err(invalid_data(SharksError::DuplicateShareX));
+            }
+            let mut len = [0u8; 8];
+            len.copy_from_slice(&file[3..HEADER_BYTES]);
+            let len = u64::from_be_bytes(len);
+            if len != (file.len() - HEADER_BYTES) as u64 {
+                return Err(invalid_data(SharksError::InvalidLength));
+            } else if *length.get_or_insert(len) != len {
+                return Err(invalid_data(SharksError::InconsistentLengths));
+            }
+            xs.push(GF256(file[2]));
+        }
+        if xs.len() < self.0 as usize || xs.is_empty() {
+            return Err(invalid_data(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: xs.len(),
+                provided: xs.len(),
+            }));
+        }
+
+        let len = length.unwrap_or(0) as usize;
+        create_all(&[secret], |writer| {
+            for start in (0..len).step_by(CHUNK_SIZE) {
+                let end = (start + CHUNK_SIZE).min(len);
+                let ys: Vec<&[GF256]> = files
+                    .iter()
+                    .map(|f| GF256::as_elements(&f[HEADER_BYTES + start..HEADER_BYTES + end]))
+                    .collect();
+                let mut chunk = math::interpolate_points(&xs, &ys, GF256::ZERO);
+                let written = writer[0].write_all(GF256::as_bytes(&chunk));
+                chunk.zeroize();
+                written?;
+                if !progress(Progress {
+                    processed: end as u64,
+                    total: Some(len as u64),
+                }) {
+                    return Err(cancelled());
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHUNK_SIZE, FILE_VERSION};
+    use crate::{Progress, Sharks, SharksError};
+    use std::fs;
+    use std::io::{self, ErrorKind};
+    use std::path::PathBuf;
+
+    fn reason(error: io::Error) -> SharksError {
+        *error
+            .into_inner()
+            .unwrap()
+            .downcast::<SharksError>()
+            .unwrap()
+    }
+
+    #[test]
+    fn files_integration_works() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| -> PathBuf { dir.path().join(name) };
+        let secret: Vec<u8> = (0..2 * CHUNK_SIZE + 10).map(|i| (i * 7) as u8).collect();
+        fs::write(path("secret"), &secret).unwrap();
+
+        let sharks = Sharks(3);
+        let shares: Vec<PathBuf> = (1..=5).map(|i| path(&format!("share{}", i))).collect();
+        sharks.split_file(path("secret"), &shares).unwrap();
+        let share = fs::read(&shares[1]).unwrap();
+        assert_eq!(share.len(), 11 + secret.len());
+        assert_eq!(share[..3], [FILE_VERSION, 3, 2]);
+
+        sharks
+            .recover_file(&shares[2..], path("recovered"))
+            .unwrap();
+        assert_eq!(fs::read(path("recovered")).unwrap(), secret);
+
+        // Empty secrets are supported as well
+        fs::write(path("empty"), []).unwrap();
+        let empty: Vec<PathBuf> = shares[..3].iter().map(|p| p.with_extension("e")).collect();
+        sharks.split_file(path("empty"), &empty).unwrap();
+        sharks.recover_file(&empty, path("empty.out")).unwrap();
+        assert!(fs::read(path("empty.out")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn files_err() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| -> PathBuf { dir.path().join(name) };
+        fs::write(path("secret"), [1, 2, 3]).unwrap();
+        let sharks = Sharks(2);
+        let shares = [path("a"), path("b"), path("c")];
+
+        let error = sharks.split_file(path("secret"), &shares[..1]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        // Existing files are not overwritten, and the shares written until then are removed
+        fs::write(&shares[2], []).unwrap();
+        let error = sharks.split_file(path("secret"), &shares).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert!(!shares[0].exists());
+        fs::remove_file(&shares[2]).unwrap();
+
+        sharks.split_file(path("secret"), &shares).unwrap();
+        let error = sharks.recover_file(&shares[..1], path("out")).unwrap_err();
+        assert_eq!(
+            reason(error),
+            SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            }
+        );
+        let error = Sharks(3).recover_file(&shares, path("out")).unwrap_err();
+        assert_eq!(
+            reason(error),
+            SharksError::ThresholdMismatch {
+                expected: 3,
+                found: 2
+            }
+        );
+
+        let mut truncated = fs::read(&shares[1]).unwrap();
+        truncated.pop();
+        fs::write(path("truncated"), truncated).unwrap();
+        let error = sharks
+            .recover_file(&[&shares[0], &path("truncated")], path("out"))
+            .unwrap_err();
+        assert_eq!(reason(error), SharksError::InvalidLength);
+        assert!(!path("out").exists());
+    }
+
+    #[test]
+    fn files_progress_works() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| -> PathBuf { dir.path().join(name) };
+        let secret = vec![9u8; CHUNK_SIZE + 1];
+        fs::write(path("secret"), &secret).unwrap();
+        let sharks = Sharks(2);
+        let shares = [path("a"), path("b")];
+
+        let mut reports = Vec::new();
+        sharks
+            .split_file_with_progress(path("secret"), &shares, |p| {
+                reports.push(p);
+                true
+            })
+            .unwrap();
+        assert_eq!(
+            reports,
+            vec![
+                Progress {
+                    processed: CHUNK_SIZE as u64,
+                    total: Some(secret.len() as u64)
+                },
+                Progress {
+                    processed: secret.len() as u64,
+                    total: Some(secret.len() as u64)
+                }
+            ]
+        );
+
+        // Cancelled recoveries remove the partially written secret
+        let error = sharks
+            .recover_file_with_progress(&shares, path("out"), |p| p.fraction() < Some(0.5))
+            .unwrap_err();
+        assert_eq!(reason(error), SharksError::Cancelled);
+        assert!(!path("out").exists());
+        let error = sharks
+            .split_file_with_progress(path("secret"), &[path("c"), path("d")], |_| false)
+            .unwrap_err();
+        assert_eq!(reason(error), SharksError::Cancelled);
+        assert!(!path("c").exists());
+    }
+}
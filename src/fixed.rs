@@ -0,0 +1,231 @@
+// Threshold known at compile time, so that recovering a secret takes exactly that many shares,
+// their Lagrange weights being computed into fixed size arrays without any runtime threshold check.
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::array::{ArrayDealer, ArrayShare};
+use super::error::SharksError;
+use super::field::{Field, GF256};
+#[cfg(feature = "std")]
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+/// Variant of `Sharks` whose threshold `K` is a const generic, for the common case of a threshold known at build time.
+/// Recovering takes an array of exactly `K` shares, so that there are neither threshold checks
+/// nor allocations for the Lagrange weights. Thresholds out of the `1..=255` range are rejected at compile time.
+///
+/// Example:
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// # use sharks::{ ConstSharks, Share };
+/// let sharks = ConstSharks::<3>;
+/// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+/// assert_eq!(sharks.recover(&[&shares[4], &shares[0], &shares[2]]).unwrap(), vec![1, 2, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use sharks::{ ConstSharks, Share };
+/// let shares: Vec<Share> = ConstSharks::<256>.dealer(&[1, 2, 3]).take(5).collect();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ConstSharks<const K: usize>;
+
+impl<const K: usize> ConstSharks<K> {
+    /// The minimum shares threshold.
+    pub const THRESHOLD: u8 = {
+        assert!(
+            K >= 1 && K <= 255,
+            "the threshold must be between 1 and 255"
+        );
+        K as u8
+    };
+
+    /// The equivalent `Sharks` instance, with a runtime threshold.
+    pub const fn sharks(&self) -> Sharks {
+        Sharks(Self::THRESHOLD)
+    }
+
+    /// Given a `secret` byte array and a random number generator, returns an `Iterator` along new
+    /// fixed size shares, without any allocation. See `Sharks::dealer_array`.
+    pub fn dealer_array<R: CryptoRng + RngCore, const N: usize>(
+        &self,
+        rng: &mut R,
+        secret: &[u8; N],
+    ) -> ArrayDealer<N> {
+        self.sharks().dealer_array(rng, secret)
+    }
+
+    /// Given exactly `K` fixed size shares, recovers the original secret without any allocation.
+    /// If some of them are repeated or their `x` is zero, an `Err` is returned.
+    ///
+    /// Example:
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// # use sharks::{ ConstSharks, ArrayShare };
+    /// let sharks = ConstSharks::<2>;
+    /// let mut dealer = sharks.dealer_array(&mut rand::rngs::OsRng, &[1, 2, 3, 4]);
+    /// let shares: [ArrayShare<4>; 2] = [dealer.next().unwrap(), dealer.next().unwrap()];
+    /// assert_eq!(sharks.recover_array(&shares).unwrap(), [1, 2, 3, 4]);
+    pub fn recover_array<const N: usize>(
+        &self,
+        shares: &[ArrayShare<N>; K],
+    ) -> Result<[u8; N], SharksError> {
+        let weights = weights::<K>(&shares.each_ref().map(|s| s.x))?;
+
+        let mut secret = [GF256::ZERO; N];
+        for (w, s_i) in weights.iter().zip(shares.iter()) {
+            for (s, y) in secret.iter_mut().zip(s_i.y.iter()) {
+                *s = *s + *w * *y;
+            }
+        }
+
+        let mut bytes = [0; N];
+        for (b, s) in bytes.iter_mut().zip(secret.iter_mut()) {
+            *b = s.0;
+            s.zeroize();
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const K: usize> ConstSharks<K> {
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares. See `Sharks::dealer`.
+    pub fn dealer(&self, secret: &[u8]) -> impl Iterator<Item = Share> {
+        self.sharks().dealer(secret)
+    }
+
+    /// Given exactly `K` shares, recovers the original secret. Shares of different lengths,
+    /// repeated ones or ones embedding another threshold return an `Err`. See `Sharks::recover`.
+    pub fn recover<S: AsShareRef>(&self, shares: &[&S; K]) -> Result<Vec<u8>, SharksError> {
+        let shares = shares.map(|s| s.as_share_ref());
+        if let Some(found) = shares
+            .iter()
+            .filter_map(|s| s.threshold())
+            .find(|t| *t != Self::THRESHOLD)
+        {
+            return Err(SharksError::ThresholdMismatch {
+                expected: Self::THRESHOLD,
+                found,
+            });
+        } else if shares.iter().any(|s| s.y.len() != shares[0].y.len()) {
+            return Err(SharksError::InconsistentLengths);
+        }
+        let weights = weights(&shares.map(|s| s.x))?;
+
+        let mut secret = vec![0; shares[0].y.len()];
+        for (w, s_i) in weights.iter().zip(shares.iter()) {
+            GF256::add_scaled_slice(
+                GF256::as_elements_mut(&mut secret),
+                *w,
+                GF256::as_elements(s_i.y),
+            );
+        }
+        Ok(secret)
+    }
+}
+
+impl<const K: usize> From<ConstSharks<K>> for Sharks {
+    fn from(s: ConstSharks<K>) -> Sharks {
+        s.sharks()
+    }
+}
+
+// Lagrange weights of the shares at zero, failing if an `x` is zero or repeated
+fn weights<const K: usize>(xs: &[GF256; K]) -> Result<[GF256; K], SharksError> {
+    // Rejects invalid thresholds at compile time
+    let _ = ConstSharks::<K>::THRESHOLD;
+    for (i, x_i) in xs.iter().enumerate() {
+        if *x_i == GF256::ZERO {
+            return Err(SharksError::ZeroShareX);
+        } else if xs[..i].contains(x_i) {
+            return Err(SharksError::DuplicateShareX);
+        }
+    }
+
+    Ok(xs.map(|x_i| {
+        let (num, den) = xs
+            .iter()
+            .filter(|x_j| **x_j != x_i)
+            .fold((GF256::ONE, GF256::ONE), |(num, den), x_j| {
+                (num * *x_j, den * (*x_j - x_i))
+            });
+        num * den.inverse()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstSharks;
+    #[cfg(feature = "std")]
+    use crate::Share;
+    use crate::{ArrayShare, Sharks, SharksError, GF256};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    #[cfg(feature = "std")]
+    use std::convert::TryFrom;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn const_integration_works() {
+        let sharks = ConstSharks::<3>;
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        assert_eq!(
+            sharks.recover(&[&shares[1], &shares[3], &shares[4]]),
+            Ok(vec![1, 2, 3, 4])
+        );
+        assert_eq!(Sharks(3).recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn const_array_integration_works() {
+        let sharks = ConstSharks::<3>;
+        assert_eq!(Sharks::from(sharks).0, 3);
+        let arrays: Vec<ArrayShare<4>> = sharks
+            .dealer_array(&mut ChaCha8Rng::from_seed([1; 32]), &[1, 2, 3, 4])
+            .take(4)
+            .collect();
+        let shares = [arrays[3].clone(), arrays[0].clone(), arrays[1].clone()];
+        assert_eq!(sharks.recover_array(&shares), Ok([1, 2, 3, 4]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn const_recover_err() {
+        let sharks = ConstSharks::<2>;
+        let shares: Vec<Share> = Sharks(3).dealer(&[1, 2]).take(3).collect();
+        assert_eq!(
+            sharks.recover(&[&shares[0], &shares[1]]),
+            Err(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let a = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let b = Share::try_from(&[2, 3][..]).unwrap();
+        let c = Share::try_from(&[1, 4, 5][..]).unwrap();
+        assert_eq!(
+            sharks.recover(&[&a, &b]),
+            Err(SharksError::InconsistentLengths)
+        );
+        assert_eq!(sharks.recover(&[&a, &c]), Err(SharksError::DuplicateShareX));
+    }
+
+    #[test]
+    fn const_recover_array_err() {
+        let sharks = ConstSharks::<2>;
+        let shares = [
+            ArrayShare {
+                x: GF256(0),
+                y: [GF256(1)],
+            },
+            ArrayShare {
+                x: GF256(1),
+                y: [GF256(1)],
+            },
+        ];
+        assert_eq!(sharks.recover_array(&shares), Err(SharksError::ZeroShareX));
+    }
+}
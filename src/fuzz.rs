@@ -0,0 +1,207 @@
+// `Arbitrary` implementations for structure-aware fuzzing and property tests.
+// Generated values are structurally valid, so that they serialize and parse back, but not necessarily consistent
+// with each other: shares have a non zero `x` coordinate and at least one `y` value, thresholds are non zero,
+// and share sets hold shares of the same length with distinct coordinates. Their values are otherwise adversarial,
+// shares of a set usually not lying on a polynomial of its threshold degree.
+// Builders are generated with any parameters, valid or not, to exercise `SharksBuilder::build`.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::builder::{Scheme, ShareFormat, SharksBuilder};
+use super::envelope::ShareEnvelope;
+use super::field::GF256;
+use super::limits::Limits;
+use super::set::ShareSet;
+use super::share::Share;
+use super::Sharks;
+
+// A non zero threshold, or none
+fn threshold(u: &mut Unstructured) -> Result<Option<u8>> {
+    Ok(match u.arbitrary()? {
+        true => Some(u.int_in_range(1..=255)?),
+        false => None,
+    })
+}
+
+// A share at a non zero `x` coordinate with `len` `y` values
+fn share(u: &mut Unstructured, len: usize, threshold: Option<u8>) -> Result<Share> {
+    Ok(Share {
+        x: GF256(u.int_in_range(1..=255)?),
+        y: (0..len)
+            .map(|_| u.arbitrary().map(GF256))
+            .collect::<Result<Vec<GF256>>>()?,
+        threshold,
+        group: None,
+    })
+}
+
+impl<'a> Arbitrary<'a> for GF256 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary().map(GF256)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u8::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Sharks {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary().map(Sharks)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u8::size_hint(depth)
+    }
+}
+
+/// Shares with a non zero `x` coordinate, at least one `y` value and a non zero threshold, if any.
+impl<'a> Arbitrary<'a> for Share {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let threshold = threshold(u)?;
+        let len = u.arbitrary_len::<u8>()?.max(1);
+        share(u, len, threshold)
+    }
+}
+
+/// Envelopes of arbitrary shares, whose threshold field is the embedded one.
+impl<'a> Arbitrary<'a> for ShareEnvelope {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut envelope = ShareEnvelope::new(u.arbitrary()?);
+        envelope.label = u.arbitrary()?;
+        envelope.participant = u.arbitrary()?;
+        envelope.timestamp = u.arbitrary()?;
+        envelope.count = u.arbitrary()?;
+        Ok(envelope)
+    }
+}
+
+/// Sets of shares with the same length, threshold, label and count, at distinct `x` coordinates.
+impl<'a> Arbitrary<'a> for ShareSet {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let threshold = threshold(u)?;
+        let label: Option<String> = u.arbitrary()?;
+        let count: Option<u8> = u.arbitrary()?;
+        let len = u.int_in_range(1..=64)?;
+        let mut set = ShareSet::new();
+        for _ in 0..u.int_in_range(0..=255)? {
+            let mut envelope = ShareEnvelope::new(share(u, len, threshold)?);
+            envelope.label = label.clone();
+            envelope.participant = u.arbitrary()?;
+            envelope.timestamp = u.arbitrary()?;
+            envelope.count = count;
+            // Shares at an `x` coordinate already in the set are skipped
+            let _ = set.insert(envelope);
+        }
+        Ok(set)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ShareFormat {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[
+            ShareFormat::Bytes,
+            ShareFormat::Versioned,
+            ShareFormat::Checked,
+            ShareFormat::Wide,
+        ])
+        .copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Scheme {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[Scheme::Shamir, Scheme::Xor, Scheme::Blakley])
+            .copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Limits {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Limits {
+            max_share_bytes: u.arbitrary()?,
+            max_shares: u.arbitrary()?,
+        })
+    }
+}
+
+/// Builders with the thread local random number generator and any parameters, which `build` may reject.
+impl<'a> Arbitrary<'a> for SharksBuilder {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut builder = SharksBuilder::new()
+            .format(u.arbitrary()?)
+            .scheme(u.arbitrary()?);
+        if let Some(threshold) = u.arbitrary()? {
+            builder = builder.threshold(threshold);
+        }
+        if let Some(total_shares) = u.arbitrary::<Option<u16>>()? {
+            builder = builder.total_shares(total_shares as usize);
+        }
+        if let Some(block) = u.arbitrary::<Option<u8>>()? {
+            builder = builder.padding(block as usize);
+        }
+        if let Some(limits) = u.arbitrary()? {
+            builder = builder.limits(limits);
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, ShareEnvelope, ShareSet, SharksBuilder, SharksError};
+    use arbitrary::{Arbitrary, Unstructured};
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn arbitrary_integration_works() {
+        let mut rng = ChaCha8Rng::from_seed([4; 32]);
+        for _ in 0..200 {
+            let mut data = vec![0; 4096];
+            rng.fill_bytes(&mut data);
+            let mut u = Unstructured::new(&data);
+
+            let share = Share::arbitrary(&mut u).unwrap();
+            let parsed = Share::try_from(Vec::from(&share).as_slice()).unwrap();
+            assert!(parsed.x == share.x && parsed.y == share.y);
+            if let Ok(bytes) = share.to_versioned_bytes() {
+                let parsed = Share::from_versioned_bytes(&bytes).unwrap();
+                assert_eq!(parsed.threshold(), share.threshold());
+            }
+
+            let envelope = ShareEnvelope::arbitrary(&mut u).unwrap();
+            let parsed = ShareEnvelope::try_from(Vec::from(&envelope).as_slice()).unwrap();
+            assert_eq!(parsed.label, envelope.label);
+            assert_eq!(parsed.threshold, envelope.share.threshold());
+
+            let set = ShareSet::arbitrary(&mut u).unwrap();
+            let parsed = ShareSet::try_from(Vec::from(&set).as_slice()).unwrap();
+            assert_eq!(parsed.len(), set.len());
+
+            let builder = SharksBuilder::arbitrary(&mut u).unwrap();
+            if let Ok(mut engine) = builder.build() {
+                let shares = engine.split(&[1, 2, 3]);
+                match engine.recover(&shares) {
+                    Ok(secret) => assert_eq!(secret, vec![1, 2, 3]),
+                    Err(e) => assert!(matches!(
+                        e,
+                        SharksError::ShareTooLong { .. } | SharksError::TooManyShares { .. }
+                    )),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arbitrary_err() {
+        // Exhausted data still yields valid values
+        let mut u = Unstructured::new(&[]);
+        let share = Share::arbitrary(&mut u).unwrap();
+        assert_eq!(share.x.0, 1);
+        assert_eq!(share.y.len(), 1);
+        assert!(ShareSet::arbitrary(&mut u).unwrap().is_empty());
+        assert!(SharksBuilder::arbitrary(&mut u).unwrap().build().is_err());
+    }
+}
@@ -0,0 +1,220 @@
+// Hierarchical threshold secret sharing, following [Tassa's scheme](https://doi.org/10.1007/s00145-006-0334-8).
+// Participants are split in levels, each with a cumulative threshold: recovery requires, for every level,
+// at least its threshold of shares from that level or more privileged ones. Shares of the first level are points
+// of the dealer polynomial, while shares of the following levels are points of its derivative of order the threshold
+// of the previous level, so that they carry no information on the lowest coefficients, the secret among them.
+// Secrets are recovered by Birkhoff interpolation. In binary fields like `GF256` most derivatives vanish,
+// so shares are computed over the prime field of Ed25519 scalars, with `x` coordinates increasing along levels,
+// which for any practical number of participants guarantees that every authorized set recovers the secret.
+
+use std::convert::TryFrom;
+
+use curve25519_dalek::scalar::Scalar;
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::Field;
+use super::math;
+use super::share::FieldShare;
+use super::Sharks;
+
+/// A share of a hierarchical threshold scheme, see `Sharks::dealer_hierarchical`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct HierarchicalShare {
+    /// Level of the share, the first one being the most privileged.
+    pub level: u8,
+    /// Order of the dealer polynomial derivative held by the share, the threshold of the previous level.
+    pub order: u8,
+    /// Points of the dealer polynomials derivatives.
+    pub share: FieldShare<Scalar>,
+}
+
+/// Obtains a byte vector from a `HierarchicalShare` instance: `level || order || share`.
+impl From<&HierarchicalShare> for Vec<u8> {
+    fn from(s: &HierarchicalShare) -> Vec<u8> {
+        let mut bytes = vec![s.level, s.order];
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `HierarchicalShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for HierarchicalShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<HierarchicalShare, Self::Error> {
+        if s.len() < 2 {
+            return Err(SharksError::InvalidLength);
+        }
+        Ok(HierarchicalShare {
+            level: s[0],
+            order: s[1],
+            share: FieldShare::try_from(&s[2..])?,
+        })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice and the `levels` of a hierarchy, each as a pair of its cumulative threshold
+    /// and number of participants, returns the shares of every participant, level after level.
+    /// Recovering the secret requires, for each level, at least its threshold of shares from that level or previous ones.
+    /// Thresholds must be increasing and the last one must be the `Sharks` threshold, otherwise an `Err` is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, HierarchicalShare };
+    /// // Any 3 shares including at least one executive one
+    /// let sharks = Sharks(3);
+    /// let shares = sharks.dealer_hierarchical(&[1, 2, 3], &[(1, 2), (3, 5)]).unwrap();
+    /// let (executives, employees) = shares.split_at(2);
+    ///
+    /// let quorum = vec![&executives[1], &employees[0], &employees[4]];
+    /// assert_eq!(sharks.recover_hierarchical(quorum).unwrap(), vec![1, 2, 3]);
+    /// assert!(sharks.recover_hierarchical(&employees[..3]).is_err());
+    /// ```
+    pub fn dealer_hierarchical(
+        &self,
+        secret: &[u8],
+        levels: &[(u8, usize)],
+    ) -> Result<Vec<HierarchicalShare>, SharksError> {
+        let mut previous = 0;
+        for (threshold, _) in levels {
+            if *threshold <= previous {
+                return Err(SharksError::InvalidThreshold);
+            }
+            previous = *threshold;
+        }
+        if previous != self.0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        // Coefficients of the polynomials, lower degrees first, the constant terms being the secret chunks
+        let chunks: Vec<Scalar> = math::encode_secret(secret);
+        let mut rng = rand::thread_rng();
+        let mut coeffs: Vec<Vec<Scalar>> = (1..self.0)
+            .map(|_| chunks.iter().map(|_| Scalar::random(&mut rng)).collect())
+            .collect();
+        coeffs.insert(0, chunks);
+
+        let mut shares = Vec::new();
+        let mut order = 0;
+        let mut index = 1;
+        for (level, (threshold, count)) in levels.iter().enumerate() {
+            for _ in 0..*count {
+                let x = Scalar::from_index(index).expect("scalars are never exhausted");
+                shares.push(HierarchicalShare {
+                    level: level as u8,
+                    order,
+                    share: FieldShare {
+                        x,
+                        y: math::evaluate_derivative(&coeffs, x, order as usize),
+                        threshold: Some(self.0),
+                        group: None,
+                    },
+                });
+                index += 1;
+            }
+            order = *threshold;
+        }
+
+        coeffs.zeroize();
+        Ok(shares)
+    }
+
+    /// Given an iterable collection of shares generated by `dealer_hierarchical`, recovers the original secret.
+    /// An `Err` is returned if the shares do not satisfy the access structure, or would fail to `recover`.
+    pub fn recover_hierarchical<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a HierarchicalShare>,
+        T::IntoIter: Iterator<Item = &'a HierarchicalShare>,
+    {
+        let shares: Vec<&HierarchicalShare> = shares.into_iter().collect();
+        let points = self.validated(shares.iter().map(|s| &s.share), self.0 as usize)?;
+        let orders: Vec<usize> = points
+            .iter()
+            .filter_map(|p| shares.iter().find(|s| s.share.x == p.x))
+            .map(|s| s.order as usize)
+            .collect();
+
+        let chunks = math::interpolate_birkhoff(&points, &orders, self.0 as usize)
+            .ok_or(SharksError::UnauthorizedShares)?;
+        math::decode_secret(&chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HierarchicalShare;
+    use crate::{Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn hierarchical_integration_works() {
+        let sharks = Sharks(4);
+        let secret: Vec<u8> = (0..70).collect();
+        // At least 1 share of the first level, 2 of the first two, and 4 overall
+        let shares = sharks
+            .dealer_hierarchical(&secret, &[(1, 2), (2, 3), (4, 4)])
+            .unwrap();
+        assert_eq!(shares.len(), 9);
+        assert_eq!(shares[4].level, 1);
+        assert_eq!(shares[5].order, 2);
+
+        let quorum = |indexes: &[usize]| {
+            let quorum: Vec<&HierarchicalShare> = indexes.iter().map(|i| &shares[*i]).collect();
+            sharks.recover_hierarchical(quorum)
+        };
+        assert_eq!(quorum(&[0, 1, 2, 3]).unwrap(), secret);
+        assert_eq!(quorum(&[0, 2, 5, 8]).unwrap(), secret);
+        assert_eq!(quorum(&[1, 4, 6, 7]).unwrap(), secret);
+        assert_eq!(quorum(&[0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap(), secret);
+
+        // Missing the first level, or the first two levels threshold
+        assert_eq!(
+            quorum(&[2, 3, 4, 5]).err(),
+            Some(SharksError::UnauthorizedShares)
+        );
+        assert_eq!(
+            quorum(&[0, 5, 6, 7]).err(),
+            Some(SharksError::UnauthorizedShares)
+        );
+        assert_eq!(
+            quorum(&[0, 1, 2]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 4,
+                got: 3,
+                provided: 3
+            })
+        );
+
+        let shares: Vec<HierarchicalShare> = shares
+            .iter()
+            .map(|s| HierarchicalShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_hierarchical(&shares[1..5]).unwrap(), secret);
+    }
+
+    #[test]
+    fn hierarchical_levels_err() {
+        let sharks = Sharks(3);
+        for levels in [
+            vec![],
+            vec![(1, 2), (2, 2)],
+            vec![(2, 1), (2, 3)],
+            vec![(0, 1), (3, 1)],
+        ]
+        .iter()
+        {
+            assert_eq!(
+                sharks.dealer_hierarchical(&[1], levels).err(),
+                Some(SharksError::InvalidThreshold)
+            );
+        }
+        assert_eq!(
+            HierarchicalShare::try_from(&[0][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// JSON documents of share envelopes, to be kept in JSON based secret stores and inspected by eye.
+// A document holds the envelope metadata, the `x` coordinate of the share, its group identifier if any
+// as 16 lowercase hexadecimal digits, its bytes `x || y` as padded Base64 and their CRC-32 checksum
+// as 8 lowercase hexadecimal digits. A bundle is an array of documents.
+// Documents are strictly validated on import: unknown or repeated fields, non canonical encodings,
+// a checksum mismatch or an `x` coordinate differing from the payload one are rejected.
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+
+use super::crc::crc32;
+use super::encoding::{from_base64, from_hex, to_base64, to_hex};
+use super::envelope::ShareEnvelope;
+use super::error::SharksError;
+use super::share::Share;
+
+/// Version of the JSON documents written by `ShareEnvelope::to_json`.
+pub const JSON_VERSION: u8 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Document {
+    version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    participant: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    x: u8,
+    payload: String,
+    checksum: String,
+}
+
+impl From<&ShareEnvelope> for Document {
+    fn from(e: &ShareEnvelope) -> Document {
+        let bytes = Vec::from(&e.share);
+        Document {
+            version: JSON_VERSION,
+            label: e.label.clone(),
+            participant: e.participant.clone(),
+            timestamp: e.timestamp,
+            threshold: e.threshold,
+            count: e.count,
+            group: e.share.group().map(|g| to_hex(&g.to_be_bytes())),
+            x: e.share.x.0,
+            payload: to_base64(&bytes),
+            checksum: to_hex(&crc32(&bytes).to_be_bytes()),
+        }
+    }
+}
+
+impl Document {
+    fn into_envelope(self) -> Result<ShareEnvelope, SharksError> {
+        if self.version != JSON_VERSION {
+            return Err(SharksError::UnknownVersion(self.version));
+        } else if self.threshold == Some(0) {
+            return Err(SharksError::InvalidThreshold);
+        }
+        let bytes = from_base64(&self.payload).ok_or(SharksError::InvalidEncoding)?;
+        let checksum = from_hex(&self.checksum)
+            .filter(|c| c.len() == 4 && to_hex(c) == self.checksum)
+            .ok_or(SharksError::InvalidEncoding)?;
+        if checksum != crc32(&bytes).to_be_bytes() {
+            return Err(SharksError::InvalidChecksum);
+        }
+        let group = match &self.group {
+            Some(group) => from_hex(group)
+                .filter(|g| to_hex(g) == *group)
+                .and_then(|g| <[u8; 8]>::try_from(g.as_slice()).ok())
+                .map(|g| Some(u64::from_be_bytes(g)))
+                .ok_or(SharksError::InvalidEncoding)?,
+            None => None,
+        };
+
+        let mut share = Share::try_from(bytes.as_slice())?;
+        if share.x.0 != self.x {
+            return Err(SharksError::InvalidEncoding);
+        }
+        share.threshold = self.threshold;
+        share.group = group;
+        Ok(ShareEnvelope {
+            label: self.label,
+            participant: self.participant,
+            timestamp: self.timestamp,
+            threshold: self.threshold,
+            count: self.count,
+            share,
+        })
+    }
+}
+
+impl ShareEnvelope {
+    /// Exports the envelope as a pretty printed JSON document, holding its metadata, the share `x` coordinate
+    /// and `group` identifier, the share bytes `x || y` as a Base64 `payload` and their CRC-32 `checksum`.
+    /// Absent metadata fields are omitted.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, ShareEnvelope };
+    /// let share = Sharks(2).dealer(&[1, 2, 3]).next().unwrap();
+    /// let mut envelope = ShareEnvelope::new(share);
+    /// envelope.label = Some("database master key".into());
+    /// let json = envelope.to_json();
+    /// assert!(json.contains("\"threshold\": 2"));
+    ///
+    /// let imported = ShareEnvelope::from_json(&json).unwrap();
+    /// assert_eq!(imported.label.as_deref(), Some("database master key"));
+    /// assert_eq!(imported.share.threshold(), Some(2));
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&Document::from(self)).expect("documents serialize to JSON")
+    }
+
+    /// Imports an envelope from a JSON document written by `to_json`, strictly validating it.
+    /// An `Err` is returned if the document is not valid JSON, has unknown or missing fields, an unknown version,
+    /// a non canonical payload or checksum, a checksum mismatch, an `x` coordinate differing from the payload one
+    /// or an invalid share.
+    pub fn from_json(s: &str) -> Result<ShareEnvelope, SharksError> {
+        let document: Document =
+            serde_json::from_str(s).map_err(|_| SharksError::InvalidEncoding)?;
+        document.into_envelope()
+    }
+
+    /// Exports a bundle of envelopes as a pretty printed JSON array of documents, see `to_json`.
+    pub fn bundle_to_json(envelopes: &[ShareEnvelope]) -> String {
+        let documents: Vec<Document> = envelopes.iter().map(Document::from).collect();
+        serde_json::to_string_pretty(&documents).expect("documents serialize to JSON")
+    }
+
+    /// Imports a bundle of envelopes from a JSON array written by `bundle_to_json`, validating each as `from_json`.
+    pub fn bundle_from_json(s: &str) -> Result<Vec<ShareEnvelope>, SharksError> {
+        let documents: Vec<Document> =
+            serde_json::from_str(s).map_err(|_| SharksError::InvalidEncoding)?;
+        documents.into_iter().map(Document::into_envelope).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, ShareEnvelope, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn json_integration_works() {
+        let sharks = Sharks(2);
+        let envelopes: Vec<ShareEnvelope> = sharks
+            .dealer(&[1, 2, 3])
+            .take(3)
+            .map(|share| {
+                let mut envelope = ShareEnvelope::new(share);
+                envelope.label = Some("vault".into());
+                envelope.count = Some(3);
+                envelope
+            })
+            .collect();
+
+        let json = ShareEnvelope::bundle_to_json(&envelopes[1..]);
+        let imported = ShareEnvelope::bundle_from_json(&json).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].count, Some(3));
+        assert_eq!(
+            sharks.recover(imported.iter().map(|e| &e.share)).unwrap(),
+            vec![1, 2, 3]
+        );
+
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let json = ShareEnvelope::new(share).to_json();
+        assert_eq!(
+            json,
+            "{\n  \"version\": 1,\n  \"x\": 1,\n  \"payload\": \"AQID\",\n  \"checksum\": \"55bc801d\"\n}"
+        );
+        let imported = ShareEnvelope::from_json(&json).unwrap();
+        assert_eq!(imported.threshold, None);
+        assert_eq!(Vec::from(&imported.share), vec![1, 2, 3]);
+
+        let mut share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        share.group = Some(0x0102);
+        let json = ShareEnvelope::new(share).to_json();
+        assert!(json.contains("\"group\": \"0000000000000102\""));
+        assert_eq!(
+            ShareEnvelope::from_json(&json).unwrap().share.group(),
+            Some(0x0102)
+        );
+        let a: Vec<ShareEnvelope> = sharks
+            .dealer_grouped(&[1, 2])
+            .take(2)
+            .map(ShareEnvelope::new)
+            .collect();
+        let b: Vec<ShareEnvelope> = sharks
+            .dealer_grouped(&[1, 2])
+            .take(2)
+            .map(ShareEnvelope::new)
+            .collect();
+        let json = ShareEnvelope::bundle_to_json(&[a[0].clone(), b[1].clone()]);
+        let imported = ShareEnvelope::bundle_from_json(&json).unwrap();
+        assert_eq!(
+            sharks.recover(imported.iter().map(|e| &e.share)).err(),
+            Some(SharksError::MixedGroups)
+        );
+    }
+
+    #[test]
+    fn json_strict_import_err() {
+        let document = |fields: &str| format!("{{\"version\": 1, {}}}", fields);
+        let valid = document("\"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\"");
+        assert!(ShareEnvelope::from_json(&valid).is_ok());
+
+        let cases = [
+            (
+                document("\"x\": 1, \"payload\": \"AQIE\", \"checksum\": \"55bc801d\""),
+                SharksError::InvalidChecksum,
+            ),
+            (
+                document("\"x\": 2, \"payload\": \"AQID\", \"checksum\": \"55bc801d\""),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document("\"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55BC801D\""),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document("\"x\": 1, \"payload\": \"AQI\", \"checksum\": \"55bc801d\""),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document(
+                    "\"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\", \"extra\": 0",
+                ),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document("\"x\": 1, \"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\""),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document("\"x\": 1, \"payload\": \"AQID\""),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                document(
+                    "\"threshold\": 0, \"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\"",
+                ),
+                SharksError::InvalidThreshold,
+            ),
+            (
+                document(
+                    "\"group\": \"0102\", \"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\"",
+                ),
+                SharksError::InvalidEncoding,
+            ),
+            (
+                "{\"version\": 2, \"x\": 1, \"payload\": \"AQID\", \"checksum\": \"55bc801d\"}"
+                    .into(),
+                SharksError::UnknownVersion(2),
+            ),
+        ];
+        for (json, error) in cases.iter() {
+            assert_eq!(ShareEnvelope::from_json(json).err(), Some(*error));
+        }
+        assert!(ShareEnvelope::bundle_from_json(&valid).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+// Splitting of symmetric keys, such as AES or LUKS keys, along with a key check value validated on recovery.
+// The key check value is the first bytes of `SHA-256(domain || key)`, appended to the key before splitting it,
+// so that recovering it from wrong shares or with a wrong threshold is detected before the key is used.
+// Keys must be 16, 24 or 32 bytes long, and are recovered as the `RecoveredKey` of the same length.
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::error::SharksError;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+/// Length of the key check value appended to the keys split by `Sharks::dealer_key`.
+pub const KEY_CHECK_BYTES: usize = 3;
+
+// Domain separation of the key check value
+const DOMAIN: &[u8] = b"sharks key check value";
+
+fn check_value(key: &[u8]) -> [u8; KEY_CHECK_BYTES] {
+    let hash = Sha256::new()
+        .chain_update(DOMAIN)
+        .chain_update(key)
+        .finalize();
+    let mut check = [0u8; KEY_CHECK_BYTES];
+    check.copy_from_slice(&hash[..KEY_CHECK_BYTES]);
+    check
+}
+
+/// Symmetric key recovered by `Sharks::recover_key`, its key check value having been validated.
+#[derive(Clone)]
+pub enum RecoveredKey {
+    /// 128 bits key.
+    Key128([u8; 16]),
+    /// 192 bits key.
+    Key192([u8; 24]),
+    /// 256 bits key.
+    Key256([u8; 32]),
+}
+
+impl RecoveredKey {
+    // Returns the key of the same length as `bytes`, which must be 16, 24 or 32 bytes long
+    fn from_slice(bytes: &[u8]) -> Result<Self, SharksError> {
+        match bytes.len() {
+            16 => Ok(RecoveredKey::Key128(copy(bytes))),
+            24 => Ok(RecoveredKey::Key192(copy(bytes))),
+            32 => Ok(RecoveredKey::Key256(copy(bytes))),
+            _ => Err(SharksError::InvalidLength),
+        }
+    }
+
+    /// Returns the key bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            RecoveredKey::Key128(key) => key,
+            RecoveredKey::Key192(key) => key,
+            RecoveredKey::Key256(key) => key,
+        }
+    }
+
+    /// Returns the length of the key in bits.
+    pub fn bits(&self) -> usize {
+        self.as_bytes().len() * 8
+    }
+
+    /// Returns the key check value of the key, which can be displayed to compare keys without revealing them.
+    pub fn check_value(&self) -> [u8; KEY_CHECK_BYTES] {
+        check_value(self.as_bytes())
+    }
+}
+
+fn copy<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut key = [0u8; N];
+    key.copy_from_slice(bytes);
+    key
+}
+
+impl AsRef<[u8]> for RecoveredKey {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ConstantTimeEq for RecoveredKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+/// Constant time, see the `ConstantTimeEq` implementation.
+impl PartialEq for RecoveredKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for RecoveredKey {}
+
+/// Prints the key length, the key bytes being redacted so that keys do not leak into logs.
+impl fmt::Debug for RecoveredKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RecoveredKey")
+            .field("key", &format_args!("<{} redacted>", self.as_bytes().len()))
+            .finish()
+    }
+}
+
+/// Wipes the key from memory.
+/// This is done automatically when the key is dropped.
+impl Zeroize for RecoveredKey {
+    fn zeroize(&mut self) {
+        match self {
+            RecoveredKey::Key128(key) => key.zeroize(),
+            RecoveredKey::Key192(key) => key.zeroize(),
+            RecoveredKey::Key256(key) => key.zeroize(),
+        }
+    }
+}
+
+impl Drop for RecoveredKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for RecoveredKey {}
+
+impl Sharks {
+    /// Given a 16, 24 or 32 bytes symmetric `key`, returns an `Iterator` along new shares of it followed
+    /// by its key check value, making each share 3 bytes longer. `recover_key` validates the key check value.
+    /// An `Err` is returned if the key has another length.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, RecoveredKey };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer_key(&[7; 32]).unwrap().take(3).collect();
+    /// let key = sharks.recover_key(&shares[1..]).unwrap();
+    /// assert_eq!(key, RecoveredKey::Key256([7; 32]));
+    /// assert_eq!(key.bits(), 256);
+    /// ```
+    pub fn dealer_key(&self, key: &[u8]) -> Result<impl Iterator<Item = Share>, SharksError> {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err(SharksError::InvalidLength);
+        }
+        let mut payload = Vec::with_capacity(key.len() + KEY_CHECK_BYTES);
+        payload.extend_from_slice(key);
+        payload.extend_from_slice(&check_value(key));
+        let dealer = self.dealer(&payload);
+        payload.zeroize();
+        Ok(dealer)
+    }
+
+    /// Given an iterable collection of shares dealt by `dealer_key`, recovers the key and validates
+    /// its key check value in constant time. An `Err` is returned in the same cases as `recover`,
+    /// if the recovered key has an invalid length, or if the key check value does not match,
+    /// in which case the shares or the threshold are wrong.
+    pub fn recover_key<'a, S, T>(&self, shares: T) -> Result<RecoveredKey, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut payload = self.recover(shares)?;
+        let result = match payload.len().checked_sub(KEY_CHECK_BYTES) {
+            Some(len) => {
+                let (key, expected) = payload.split_at(len);
+                RecoveredKey::from_slice(key).and_then(|key| {
+                    if bool::from(key.check_value().ct_eq(expected)) {
+                        Ok(key)
+                    } else {
+                        Err(SharksError::InvalidDigest)
+                    }
+                })
+            }
+            None => Err(SharksError::InvalidLength),
+        };
+        payload.zeroize();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecoveredKey;
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn key_integration_works() {
+        let sharks = Sharks(3);
+        for len in [16, 24, 32] {
+            let key: Vec<u8> = (0..len as u8).collect();
+            let shares: Vec<Share> = sharks.dealer_key(&key).unwrap().take(5).collect();
+            assert_eq!(shares[0].y.len(), len + 3);
+            let recovered = sharks.recover_key(&shares[2..]).unwrap();
+            assert_eq!(recovered.as_bytes(), key.as_slice());
+            assert_eq!(recovered.bits(), len * 8);
+        }
+
+        let shares: Vec<Share> = sharks.dealer_key(&[1; 16]).unwrap().take(3).collect();
+        let recovered = sharks.recover_key(&shares).unwrap();
+        assert_eq!(recovered, RecoveredKey::Key128([1; 16]));
+        assert_eq!(recovered.check_value(), super::check_value(&[1; 16]));
+        assert_eq!(
+            format!("{:?}", recovered),
+            "RecoveredKey { key: <16 redacted> }"
+        );
+        assert_ne!(recovered, RecoveredKey::Key128([2; 16]));
+        assert_ne!(recovered, RecoveredKey::Key256([1; 32]));
+    }
+
+    #[test]
+    fn key_err() {
+        let sharks = Sharks(3);
+        assert!(sharks.dealer_key(&[1; 20]).is_err());
+        assert!(sharks.dealer_key(&[]).is_err());
+
+        let mut shares: Vec<Share> = sharks
+            .dealer_key(&[1; 32])
+            .unwrap()
+            .take(3)
+            .map(|s| Share::try_from(Vec::from(&s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(
+            Sharks(2).recover_key(&shares[..2]),
+            Err(SharksError::InvalidDigest)
+        );
+        shares[1].y[4] = shares[1].y[4] + GF256(1);
+        assert_eq!(sharks.recover_key(&shares), Err(SharksError::InvalidDigest));
+
+        let plain: Vec<Share> = sharks.dealer(&[1; 10]).take(3).collect();
+        assert_eq!(sharks.recover_key(&plain), Err(SharksError::InvalidLength));
+        let short: Vec<Share> = sharks.dealer(&[1; 2]).take(3).collect();
+        assert_eq!(sharks.recover_key(&short), Err(SharksError::InvalidLength));
+    }
+}
@@ -0,0 +1,372 @@
+// Lazy dealing of large secrets, each share being computed byte by byte as it is read.
+// The dealer stores the random coefficients of the polynomials once, borrowing the secret instead of copying it,
+// so that issuing many shares only takes as much memory as the `threshold - 1` coefficient vectors,
+// instead of one full share per participant. Each lazy share has the same layout as the bytes of a `Share`: `x || y..`.
+// Recovery is lazy as well: the Lagrange weights of the shares are computed once, and each secret byte is
+// interpolated from the borrowed shares only when it is read.
+
+use std::io::{self, Read};
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::{AsShareRef, ShareRef};
+use super::Sharks;
+
+/// Dealer of lazily computed shares of a borrowed secret, see `Sharks::lazy_dealer`.
+/// The random coefficients are wiped from memory once dropped.
+pub struct LazyDealer<'a> {
+    secret: &'a [u8],
+    coeffs: Vec<Vec<GF256>>,
+    threshold: u8,
+}
+
+/// Share whose bytes `x || y..` are computed on demand, through its `Iterator` or `Read` implementations.
+/// The bytes are the same as those of the `Share` dealt with the same coefficients, and can be parsed as one.
+pub struct LazyShare<'d, 'a> {
+    dealer: &'d LazyDealer<'a>,
+    x: GF256,
+    position: usize,
+}
+
+/// Secret whose bytes are interpolated on demand from borrowed shares, through its `Iterator` or `Read`
+/// implementations, see `Sharks::recover_iter`.
+pub struct LazySecret<'a> {
+    weights: Vec<GF256>,
+    shares: Vec<ShareRef<'a>>,
+    position: usize,
+}
+
+impl Sharks {
+    /// Returns a dealer of shares of the `secret` computed lazily, without materializing them in memory.
+    /// Only the random coefficients are stored, taking `threshold - 1` times the secret length,
+    /// so huge secrets can be split into many shares. Shares are obtained with `LazyDealer::share`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # use std::convert::TryFrom;
+    /// use std::io::Read;
+    ///
+    /// let sharks = Sharks(2);
+    /// let dealer = sharks.lazy_dealer(&[1, 2, 3, 4]);
+    /// let shares: Vec<Share> = (1..=3)
+    ///     .map(|x| {
+    ///         let mut bytes = Vec::new();
+    ///         dealer.share(x).unwrap().read_to_end(&mut bytes).unwrap();
+    ///         Share::try_from(bytes.as_slice()).unwrap()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(sharks.recover(&shares[1..]).unwrap(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn lazy_dealer<'a>(&self, secret: &'a [u8]) -> LazyDealer<'a> {
+        self.lazy_dealer_with_rng(&mut rand::thread_rng(), secret)
+    }
+
+    /// Same as `lazy_dealer`, generating the coefficients with the `rng` random number generator
+    /// instead of the thread local one, in the same order as `dealer_with_rng`.
+    pub fn lazy_dealer_with_rng<'a, R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &'a [u8],
+    ) -> LazyDealer<'a> {
+        LazyDealer {
+            secret,
+            coeffs: math::random_terms(secret.len(), self.0, rng),
+            threshold: self.0,
+        }
+    }
+
+    /// Returns the secret recovered from the `shares` as bytes interpolated lazily when read,
+    /// so that it can be streamed into a decryptor or a file without ever being held in memory.
+    /// The shares are borrowed and validated upfront: an `Err` is returned in the same cases as `recover`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// use std::io::Read;
+    ///
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(2).collect();
+    /// let secret = sharks.recover_iter(&shares).unwrap();
+    /// assert_eq!(secret.len(), 4);
+    /// assert_eq!(secret.collect::<Vec<u8>>(), vec![1, 2, 3, 4]);
+    ///
+    /// let mut buf = [0; 3];
+    /// sharks.recover_iter(&shares).unwrap().read_exact(&mut buf).unwrap();
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// ```
+    pub fn recover_iter<'a, S, T>(&self, shares: T) -> Result<LazySecret<'a>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let shares = self.distinct(
+            shares.into_iter().map(AsShareRef::as_share_ref),
+            self.0 as usize,
+        )?;
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        Ok(LazySecret {
+            weights: math::lagrange_weights(&xs, GF256::ZERO),
+            shares,
+            position: 0,
+        })
+    }
+}
+
+impl<'a> LazyDealer<'a> {
+    /// Minimum number of shares needed to recover the secret.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Returns the lazy share at the `x` coordinate, none of its bytes being computed yet.
+    /// An `Err` is returned if `x` is zero, as that share would be the secret itself.
+    pub fn share(&self, x: u8) -> Result<LazyShare<'_, 'a>, SharksError> {
+        if x == 0 {
+            return Err(SharksError::ZeroShareX);
+        }
+        Ok(LazyShare {
+            dealer: self,
+            x: GF256(x),
+            position: 0,
+        })
+    }
+
+    /// Returns the lazy shares at every `x` coordinate, from 1 to 255.
+    pub fn shares(&self) -> impl Iterator<Item = LazyShare<'_, 'a>> {
+        (1..=255).map(move |x| LazyShare {
+            dealer: self,
+            x: GF256(x),
+            position: 0,
+        })
+    }
+}
+
+impl Drop for LazyDealer<'_> {
+    fn drop(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
+impl LazyShare<'_, '_> {
+    /// The `x` coordinate of the share.
+    pub fn x(&self) -> u8 {
+        self.x.0
+    }
+
+    /// Total length of the share bytes, `1 + secret length`.
+    pub fn byte_len(&self) -> usize {
+        1 + self.dealer.secret.len()
+    }
+
+    // Evaluates the polynomials of the secret bytes from `start` at `x` into `y`, in single linear passes
+    fn evaluate(&self, start: usize, y: &mut [GF256]) {
+        let end = start + y.len();
+        let secret = GF256::as_elements(&self.dealer.secret[start..end]);
+        match self.dealer.coeffs.split_first() {
+            Some((first, rest)) => {
+                y.copy_from_slice(&first[start..end]);
+                for c in rest {
+                    GF256::scale_add_slice(y, self.x, &c[start..end]);
+                }
+                GF256::scale_add_slice(y, self.x, secret);
+            }
+            None => y.copy_from_slice(secret),
+        }
+    }
+}
+
+impl Iterator for LazyShare<'_, '_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = match self.position {
+            0 => self.x.0,
+            p if p < self.byte_len() => {
+                let mut y = [GF256(0)];
+                self.evaluate(p - 1, &mut y);
+                y[0].0
+            }
+            _ => return None,
+        };
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.byte_len().saturating_sub(self.position);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for LazyShare<'_, '_> {}
+
+impl Read for LazyShare<'_, '_> {
+    fn read(&mut self, mut buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        if self.position == 0 && !buf.is_empty() {
+            buf[0] = self.x.0;
+            buf = &mut buf[1..];
+            self.position = 1;
+            read = 1;
+        }
+        let n = buf.len().min(self.byte_len() - self.position);
+        self.evaluate(self.position - 1, GF256::as_elements_mut(&mut buf[..n]));
+        self.position += n;
+        Ok(read + n)
+    }
+}
+
+impl LazySecret<'_> {
+    // Interpolates the secret bytes from the current position into `buf`, advancing it
+    fn interpolate(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.len());
+        let (start, end) = (self.position, self.position + n);
+        let y = GF256::as_elements_mut(&mut buf[..n]);
+        y.iter_mut().for_each(|e| *e = GF256::ZERO);
+        for (w, s) in self.weights.iter().zip(self.shares.iter()) {
+            GF256::add_scaled_slice(y, *w, GF256::as_elements(&s.y[start..end]));
+        }
+        self.position = end;
+        n
+    }
+}
+
+impl Iterator for LazySecret<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        match self.interpolate(&mut byte) {
+            0 => None,
+            _ => Some(byte[0]),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.shares[0].y.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for LazySecret<'_> {}
+
+impl Read for LazySecret<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.interpolate(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, Sharks, SharksError};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::convert::TryFrom;
+    use std::io::Read;
+
+    #[test]
+    fn lazy_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+        let dealer = sharks.lazy_dealer_with_rng(&mut ChaCha8Rng::from_seed([1; 32]), &secret);
+        let expected: Vec<Vec<u8>> = sharks
+            .dealer_with_rng(&mut ChaCha8Rng::from_seed([1; 32]), &secret)
+            .take(5)
+            .map(|s| Vec::from(&s))
+            .collect();
+
+        for (share, expected) in dealer.shares().zip(expected.iter()) {
+            assert_eq!(share.len(), 1001);
+            assert_eq!(share.collect::<Vec<u8>>(), *expected);
+        }
+
+        // Reads in uneven chunks
+        let mut share = dealer.share(4).unwrap();
+        let mut bytes = Vec::new();
+        let mut buf = [0u8; 77];
+        loop {
+            let n = share.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(bytes, expected[3]);
+
+        let shares: Vec<Share> = dealer
+            .shares()
+            .skip(2)
+            .take(3)
+            .map(|s| Share::try_from(s.collect::<Vec<u8>>().as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn lazy_edge_cases_work() {
+        let dealer = Sharks(1).lazy_dealer(&[1, 2, 3]);
+        assert_eq!(dealer.threshold(), 1);
+        assert_eq!(
+            dealer.share(9).unwrap().collect::<Vec<u8>>(),
+            vec![9, 1, 2, 3]
+        );
+        assert_eq!(dealer.share(0).err(), Some(SharksError::ZeroShareX));
+        assert_eq!(dealer.shares().count(), 255);
+
+        let dealer = Sharks(0).lazy_dealer(&[1, 2, 3]);
+        assert_eq!(
+            dealer.share(1).unwrap().collect::<Vec<u8>>(),
+            vec![1, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn recover_iter_works() {
+        let sharks = Sharks(4);
+        let secret: Vec<u8> = (0..5000).map(|i| (i * 13) as u8).collect();
+        let shares: Vec<Share> = sharks.dealer(&secret).take(6).collect();
+
+        let lazy = sharks.recover_iter(&shares[1..5]).unwrap();
+        assert_eq!(lazy.len(), 5000);
+        assert_eq!(lazy.collect::<Vec<u8>>(), secret);
+
+        // Reads in uneven chunks, after a few bytes were iterated
+        let mut lazy = sharks.recover_iter(shares.iter().rev()).unwrap();
+        let mut bytes: Vec<u8> = (0..10).filter_map(|_| lazy.next()).collect();
+        let mut buf = [0u8; 333];
+        loop {
+            let n = lazy.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(bytes, secret);
+        assert_eq!(lazy.next(), None);
+    }
+
+    #[test]
+    fn recover_iter_err() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+        assert_eq!(
+            sharks.recover_iter(&shares[1..]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        shares[2].y.pop();
+        assert_eq!(
+            sharks.recover_iter(&shares).err(),
+            Some(SharksError::InconsistentLengths)
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Fast, small and secure [Shamir's Secret Sharing](https://en.wikipedia.org/wiki/Shamir%27s_Secret_Sharing) library crate
 //!
 //! Usage example:
-//! ```
+#![cfg_attr(feature = "std", doc = "```")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
 //! use sharks::{ Sharks, Share };
 //!
 //! // Set a minimum threshold of 10 shares
@@ -14,21 +15,255 @@
 //! let secret = sharks.recover(shares.as_slice()).unwrap();
 //! assert_eq!(secret, vec![1, 2, 3, 4]);
 //! ```
+//!
+//! For the simple case of shares exchanged as bytes, `split` and `combine` do it all in one call:
+#![cfg_attr(feature = "std", doc = "```")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
+//! let shares = sharks::split(&[1, 2, 3, 4], 3, 5).unwrap();
+//! assert_eq!(sharks::combine(&shares[..3]).unwrap(), vec![1, 2, 3, 4]);
+//! ```
+//!
+//! Without the default `std` feature the crate is `no_std` and needs no allocator,
+//! only the fixed size `ArrayShare` API is available then.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "std")]
+mod accumulator;
+#[cfg(feature = "std")]
+mod additive;
+mod array;
+#[cfg(feature = "async")]
+mod async_io;
+#[cfg(feature = "std")]
+mod audit;
+#[cfg(feature = "std")]
+mod beaver;
+#[cfg(feature = "std")]
+mod blakley;
+#[cfg(feature = "std")]
+mod block;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod cbor;
+#[cfg(feature = "std")]
+mod changeable;
+#[cfg(feature = "commitments")]
+mod commitment;
+#[cfg(feature = "compact")]
+mod compact;
+#[cfg(feature = "compat")]
+mod compat;
+#[cfg(feature = "std")]
+mod crc;
+#[cfg(feature = "digest")]
+mod digest;
+#[cfg(feature = "curve25519")]
+mod dkg;
+#[cfg(any(feature = "serde", feature = "text"))]
+mod encoding;
+mod entropy;
+#[cfg(feature = "std")]
+mod envelope;
+mod error;
 mod field;
-mod math;
+#[cfg(feature = "files")]
+mod files;
+mod fixed;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "curve25519")]
+mod hierarchy;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "key")]
+mod key;
+#[cfg(feature = "std")]
+mod lazy;
+#[cfg(feature = "std")]
+mod limits;
+#[cfg(feature = "std")]
+pub mod math;
+#[cfg(feature = "mnemonic")]
+mod mnemonic;
+#[cfg(feature = "std")]
+mod multi;
+#[cfg(feature = "std")]
+mod nested;
+#[cfg(feature = "std")]
+mod padding;
+#[cfg(feature = "std")]
+mod partition;
+#[cfg(feature = "passphrase")]
+mod passphrase;
+#[cfg(feature = "std")]
+mod policy;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod ramp;
+#[cfg(feature = "std")]
+mod recoverer;
+#[cfg(feature = "std")]
+mod report;
+#[cfg(feature = "std")]
+mod reshare;
+#[cfg(feature = "robust")]
+mod robust;
+#[cfg(feature = "seal")]
+pub mod seal;
+#[cfg(feature = "secrecy")]
+mod secret;
+#[cfg(feature = "std")]
+mod set;
+#[cfg(feature = "std")]
 mod share;
+#[cfg(feature = "std")]
+mod sharing;
+#[cfg(feature = "std")]
+mod simple;
+#[cfg(feature = "slip39")]
+pub mod slip39;
+#[cfg(feature = "std")]
+mod state;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "json")]
+mod value;
+#[cfg(feature = "vectors")]
+mod vectors;
+#[cfg(feature = "vss")]
+pub mod vss;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "std")]
+mod weighted;
+#[cfg(feature = "std")]
+mod xor;
 
-use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::fmt;
 
-use field::GF256;
-pub use share::Share;
+#[cfg(feature = "std")]
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+#[cfg(feature = "std")]
+use rand_chacha::ChaCha20Rng;
+#[cfg(feature = "std")]
+use share::Point;
+#[cfg(feature = "std")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "std")]
+pub use accumulator::RecoveryAccumulator;
+#[cfg(feature = "std")]
+pub use additive::AdditiveShare;
+pub use array::{ArrayDealer, ArrayShare};
+#[cfg(feature = "std")]
+pub use audit::Observer;
+#[cfg(feature = "std")]
+pub use beaver::BeaverTriple;
+#[cfg(feature = "std")]
+pub use block::BlockShare;
+#[cfg(feature = "std")]
+pub use builder::{Scheme, ShareFormat, SharksBuilder, SharksEngine};
+#[cfg(feature = "std")]
+pub use cbor::CBOR_VERSION;
+#[cfg(feature = "std")]
+pub use changeable::{ActivationKey, ChangeableShare};
+#[cfg(feature = "commitments")]
+pub use commitment::{ShareCommitments, COMMITMENTS_VERSION};
+#[cfg(feature = "compact")]
+pub use compact::CompactShare;
+#[cfg(feature = "compat")]
+pub use compat::SsssShare;
+#[cfg(feature = "curve25519")]
+pub use dkg::DkgShare;
+#[cfg(feature = "getrandom")]
+pub use entropy::OsEntropy;
+pub use entropy::{EntropyRng, EntropySource};
+#[cfg(feature = "std")]
+pub use envelope::{ShareEnvelope, ENVELOPE_VERSION};
+pub use error::SharksError;
+#[cfg(feature = "curve25519")]
+pub use field::ScalarCommitments;
+pub use field::{Field, GF256Poly, GF256, GF2_128, GF2_64, GF65536};
+#[cfg(feature = "files")]
+pub use files::FILE_VERSION;
+pub use fixed::ConstSharks;
+#[cfg(feature = "curve25519")]
+pub use hierarchy::HierarchicalShare;
+#[cfg(feature = "json")]
+pub use json::JSON_VERSION;
+#[cfg(feature = "key")]
+pub use key::{RecoveredKey, KEY_CHECK_BYTES};
+#[cfg(feature = "std")]
+pub use lazy::{LazyDealer, LazySecret, LazyShare};
+#[cfg(feature = "std")]
+pub use limits::Limits;
+#[cfg(feature = "std")]
+pub use multi::MultiShare;
+#[cfg(feature = "std")]
+pub use nested::NestedShare;
+#[cfg(feature = "std")]
+pub use partition::{Partition, ShareGroup};
+#[cfg(feature = "passphrase")]
+pub use passphrase::PASSPHRASE_VERSION;
+#[cfg(feature = "std")]
+pub use policy::{Policy, PolicyShare};
+#[cfg(feature = "std")]
+pub use recoverer::Recoverer;
+#[cfg(feature = "std")]
+pub use report::{RecoveryReport, ShareFinding};
+#[cfg(feature = "std")]
+pub use reshare::SubShare;
+#[cfg(feature = "robust")]
+pub use robust::RobustShare;
+#[cfg(feature = "std")]
+pub use set::{ShareSet, SHARE_SET_VERSION};
+#[cfg(feature = "std")]
+pub use share::{AsShareRef, FieldShare, Share, ShareRef, FORMAT_VERSION, GROUPED_FORMAT_VERSION};
+#[cfg(feature = "std")]
+pub use sharing::{Blakley, Ramp, SecretSharing, Xor};
+#[cfg(feature = "std")]
+pub use simple::{combine, split};
+#[cfg(feature = "std")]
+pub use state::{DealerState, STATE_VERSION};
+#[cfg(feature = "std")]
+pub use stream::{Progress, SplitToWritersError};
+pub use subtle;
+#[cfg(feature = "vectors")]
+pub use vectors::{test_vectors, verify_vectors, TestVector};
+#[cfg(feature = "std")]
+pub use weighted::WeightedShare;
+pub use zeroize;
+
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+#[cfg(feature = "passphrase")]
+pub use argon2;
+#[cfg(feature = "curve25519")]
+pub use curve25519_dalek;
+#[cfg(feature = "protobuf")]
+pub use prost;
+#[cfg(feature = "qr")]
+pub use qrcode;
+#[cfg(feature = "secrecy")]
+pub use secrecy;
 
 /// Tuple struct which implements methods to generate shares and recover secrets over a 256 bits Galois Field.
 /// Its only parameter is the minimum shares threshold.
+/// The `_in` variants of the methods allow to work over other fields instead, like `GF65536`.
 ///
 /// Usage example:
-/// ```
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
 /// # use sharks::{ Sharks, Share };
 /// // Set a minimum threshold of 10 shares
 /// let sharks = Sharks(10);
@@ -40,11 +275,37 @@ pub use share::Share;
 /// let secret = sharks.recover(shares.as_slice()).unwrap();
 /// assert_eq!(secret, vec![1, 2, 3, 4]);
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sharks(pub u8);
 
+/// Error returned by `Sharks::recover_checked`, identifying the cheaters if possible.
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq)]
+pub enum CheckedRecoveryError<F: Field> {
+    /// The shares cannot be used to recover the secret, for the given reason.
+    Invalid(SharksError),
+    /// The shares with these `x` coordinates are inconsistent with the polynomial the others lie on.
+    Inconsistent(Vec<F>),
+}
+
+#[cfg(feature = "std")]
+impl<F: Field> fmt::Display for CheckedRecoveryError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckedRecoveryError::Invalid(reason) => write!(f, "{}", reason),
+            CheckedRecoveryError::Inconsistent(x) => write!(f, "Inconsistent shares at {:?}", x),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Field> std::error::Error for CheckedRecoveryError<F> {}
+
+#[cfg(feature = "std")]
 impl Sharks {
     /// Given a `secret` byte slice, returns an `Iterator` along new shares.
-    /// The maximum number of shares that can be generated is 256.
+    /// The maximum number of shares that can be generated is 255, the iterator silently ending afterwards,
+    /// see `try_dealer` to be told instead.
     ///
     /// Example:
     /// ```
@@ -55,18 +316,214 @@ impl Sharks {
     /// // Get 3 shares
     /// let shares: Vec<Share> = dealer.take(3).collect();
     pub fn dealer(&self, secret: &[u8]) -> impl Iterator<Item = Share> {
-        let mut polys = Vec::with_capacity(secret.len());
+        self.dealer_in::<GF256>(secret)
+    }
+
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares over the field `F`.
+    /// The maximum number of shares that can be generated is the number of non-zero elements of `F`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, FieldShare, GF65536 };
+    /// # let sharks = Sharks(3);
+    /// // Obtain an iterator over the shares for secret [1, 2]
+    /// let dealer = sharks.dealer_in::<GF65536>(&[1, 2]);
+    /// // Get 1000 shares
+    /// let shares: Vec<FieldShare<GF65536>> = dealer.take(1000).collect();
+    pub fn dealer_in<F: Field>(&self, secret: &[u8]) -> impl Iterator<Item = FieldShare<F>> {
+        self.deal(math::encode_secret(secret))
+    }
+
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares, generated with the `rng`
+    /// random number generator instead of the thread local one. Allows to use a hardware or otherwise
+    /// specific source of entropy. The maximum number of shares that can be generated is 255.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(3);
+    /// let mut rng = rand::rngs::OsRng;
+    /// let shares: Vec<Share> = sharks.dealer_with_rng(&mut rng, &[1, 2]).take(3).collect();
+    /// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2]);
+    pub fn dealer_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+    ) -> impl Iterator<Item = Share> {
+        self.deal_with_rng(math::encode_secret(secret), rng)
+    }
 
-        for chunk in secret {
-            polys.push(math::random_polynomial(GF256(*chunk), self.0))
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares deterministically derived
+    /// from `seed`, using the ChaCha20 stream cipher as random number generator. The same seed and
+    /// secret always result in the same shares, allowing to regenerate lost ones or to obtain test vectors.
+    /// The seed must be kept as secret as the secret itself and never reused for different secrets,
+    /// otherwise the shares reveal relations between them.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(3);
+    /// let seed = [7; 32];
+    /// let shares: Vec<Share> = sharks.dealer_from_seed(seed, &[1, 2]).take(3).collect();
+    /// let again: Vec<Share> = sharks.dealer_from_seed(seed, &[1, 2]).take(3).collect();
+    /// assert_eq!(shares[2].y, again[2].y);
+    pub fn dealer_from_seed(&self, seed: [u8; 32], secret: &[u8]) -> impl Iterator<Item = Share> {
+        self.dealer_with_rng(&mut ChaCha20Rng::from_seed(seed), secret)
+    }
+
+    /// Same as `dealer`, all the shares being stamped with the same random group identifier, see `Share::group`.
+    /// The identifier is kept by `Share::to_versioned_bytes`, and recovering refuses to combine shares stamped
+    /// with different identifiers, returning `SharksError::MixedGroups` instead of a wrong secret.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// # let sharks = Sharks(2);
+    /// let mut shares: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(2).collect();
+    /// assert_eq!(shares[0].group(), shares[1].group());
+    /// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2]);
+    ///
+    /// shares[1] = sharks.dealer_grouped(&[1, 2]).nth(1).unwrap();
+    /// assert_eq!(sharks.recover(&shares).err(), Some(SharksError::MixedGroups));
+    pub fn dealer_grouped(&self, secret: &[u8]) -> impl Iterator<Item = Share> {
+        self.dealer_grouped_with_rng(&mut rand::thread_rng(), secret)
+    }
+
+    /// Same as `dealer_grouped`, the coefficients and group identifier being drawn from the `rng`.
+    pub fn dealer_grouped_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+    ) -> impl Iterator<Item = Share> {
+        let group = rng.next_u64();
+        self.dealer_with_rng(rng, secret).map(move |mut s| {
+            s.group = Some(group);
+            s
+        })
+    }
+
+    /// Given a `secret` byte slice, returns one share at each of the `ids` `x` coordinates, in the same order.
+    /// Allows participants to keep the same `x` coordinate, such as a stable identifier, across several splits.
+    /// An `Err` is returned if any of the ids is zero or appears more than once.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, GF256 };
+    /// # let sharks = Sharks(3);
+    /// let shares = sharks.dealer_for_ids(&[1, 2, 3], &[10, 42, 7, 99]).unwrap();
+    /// assert_eq!(shares[1].x, GF256(42));
+    /// assert_eq!(sharks.recover(&shares[1..]).unwrap(), vec![1, 2, 3]);
+    pub fn dealer_for_ids(&self, secret: &[u8], ids: &[u8]) -> Result<Vec<Share>, SharksError> {
+        if ids.contains(&0) {
+            return Err(SharksError::ZeroShareX);
+        }
+        let mut keys = HashSet::new();
+        if !ids.iter().all(|id| keys.insert(id)) {
+            return Err(SharksError::DuplicateShareX);
         }
 
-        math::get_evaluator(polys)
+        let mut rng = rand::thread_rng();
+        let mut coeffs = math::random_coefficients(math::encode_secret(secret), self.0, &mut rng);
+
+        let shares = ids
+            .iter()
+            .map(|id| Share {
+                x: GF256(*id),
+                y: math::evaluate_coefficients(&coeffs, GF256(*id)),
+                threshold: Some(self.0),
+                group: None,
+            })
+            .collect();
+
+        coeffs.zeroize();
+        Ok(shares)
+    }
+
+    /// Given a `secret` byte slice, returns exactly `n` new shares, at the `x` coordinates `1..=n`.
+    /// Unlike taking them from `dealer`, asking for more shares than the field allows cannot go unnoticed:
+    /// an `Err` is returned if `n` exceeds 255, or if it is less than the threshold so that the secret could not be recovered.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, SharksError };
+    /// let sharks = Sharks(3);
+    /// let shares = sharks.dealer_n(&[1, 2, 3], 5).unwrap();
+    /// assert_eq!(shares.len(), 5);
+    /// assert_eq!(sharks.dealer_n(&[1, 2, 3], 256).err(), Some(SharksError::TooManyShares { max: 255 }));
+    pub fn dealer_n(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        if n > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        } else if n < self.0 as usize {
+            return Err(SharksError::TooFewShares {
+                min: self.0 as usize,
+            });
+        }
+        Ok(self.dealer(secret).take(n).collect())
+    }
+
+    /// Same as `dealer`, the shares being wrapped in `Ok`, and followed by a final `Err` once the `x` coordinates
+    /// of the field are exhausted, so that taking more shares than 255 is noticed even when `n` is not known upfront.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// let sharks = Sharks(3);
+    /// let shares: Result<Vec<Share>, SharksError> = sharks.try_dealer(&[1, 2]).take(5).collect();
+    /// assert_eq!(shares.unwrap().len(), 5);
+    ///
+    /// let shares: Result<Vec<Share>, SharksError> = sharks.try_dealer(&[1, 2]).take(300).collect();
+    /// assert_eq!(shares.err(), Some(SharksError::TooManyShares { max: 255 }));
+    /// ```
+    pub fn try_dealer(&self, secret: &[u8]) -> impl Iterator<Item = Result<Share, SharksError>> {
+        self.try_dealer_in::<GF256>(secret)
+    }
+
+    /// Same as `try_dealer`, over the field `F`. The final `Err` holds the number of shares that were dealt.
+    pub fn try_dealer_in<F: Field>(
+        &self,
+        secret: &[u8],
+    ) -> impl Iterator<Item = Result<FieldShare<F>, SharksError>> {
+        let mut shares = Some(self.dealer_in::<F>(secret));
+        let mut dealt = 0;
+        std::iter::from_fn(move || match shares.as_mut()?.next() {
+            Some(share) => {
+                dealt += 1;
+                Some(Ok(share))
+            }
+            None => {
+                shares = None;
+                Some(Err(SharksError::TooManyShares { max: dealt }))
+            }
+        })
+    }
+
+    // Returns an iterator over the shares of the secret `chunks`, stamped with the threshold
+    fn deal<F: Field>(&self, chunks: Vec<F>) -> impl Iterator<Item = FieldShare<F>> {
+        self.deal_with_rng(chunks, &mut rand::thread_rng())
+    }
+
+    fn deal_with_rng<F: Field, R: Rng + ?Sized>(
+        &self,
+        chunks: Vec<F>,
+        rng: &mut R,
+    ) -> impl Iterator<Item = FieldShare<F>> {
+        let coeffs = math::random_coefficients(chunks, self.0, rng);
+
+        let threshold = self.0;
+        math::coefficient_evaluator(coeffs).map(move |mut s| {
+            s.threshold = Some(threshold);
+            s
+        })
     }
 
     /// Given an iterable collection of shares, recovers the original secret.
     /// If the number of distinct shares is less than the minimum threshold an `Err` is returned,
-    /// otherwise an `Ok` containing the secret.
+    /// otherwise an `Ok` containing the secret. Identical copies of a share are ignored, but shares
+    /// with the same `x` coordinate and different `y` values, or of different lengths, return an `Err`.
+    /// Shares carrying an embedded threshold (see `Share::to_versioned_bytes`) must all agree on it
+    /// and it must match the `Sharks` threshold, otherwise an `Err` is returned as well.
+    /// Unless the `tables` feature is enabled, the recovery runs in constant time with respect to the shares `y` values
+    /// and therefore to the secret, its duration only depending on the number of shares, their `x` coordinates and the secret length.
     ///
     /// Example:
     /// ```
@@ -82,35 +539,628 @@ impl Sharks {
     /// secret = sharks.recover(&shares);
     /// // Not enough shares to recover secret
     /// assert!(secret.is_err());
-    pub fn recover<'a, T>(&self, shares: T) -> Result<Vec<u8>, &str>
+    /// ```
+    ///
+    /// Shares can also be borrowed from their bytes as `ShareRef`s, avoiding to copy them.
+    pub fn recover<'a, S, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
     where
-        T: IntoIterator<Item = &'a Share>,
-        T::IntoIter: Iterator<Item = &'a Share>,
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
     {
-        let (keys, shares) = shares
+        let shares = self.distinct(
+            shares.into_iter().map(AsShareRef::as_share_ref),
+            self.0 as usize,
+        )?;
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        let ys: Vec<&[GF256]> = shares.iter().map(|s| s.y()).collect();
+        math::decode_secret(&math::interpolate_points(&xs, &ys, GF256::ZERO))
+    }
+
+    /// Same as `recover`, skipping all the validation for shares already checked upstream, such as batches of shares
+    /// stored together. Only the first `threshold` shares are interpolated, without checking that there are enough,
+    /// that their `x` coordinates are distinct and non zero, or that their lengths, embedded thresholds and group
+    /// identifiers agree. Invalid shares result in a wrong secret instead of an `Err`,
+    /// or in a panic if their lengths differ, so `recover` should be preferred for shares of untrusted origin.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2]).take(3).collect();
+    /// assert_eq!(sharks.recover_unchecked(&shares), vec![1, 2]);
+    /// // Duplicate shares are not detected
+    /// assert_ne!(sharks.recover_unchecked(vec![&shares[0], &shares[0]]), vec![1, 2]);
+    /// ```
+    pub fn recover_unchecked<'a, S, T>(&self, shares: T) -> Vec<u8>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+    {
+        let shares: Vec<ShareRef> = shares
             .into_iter()
-            .map(|s| {
-                (
-                    s.x.0,
-                    Share {
-                        x: s.x,
-                        y: s.y.clone(),
-                    },
-                )
+            .take(self.0 as usize)
+            .map(AsShareRef::as_share_ref)
+            .collect();
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        let ys: Vec<&[GF256]> = shares.iter().map(|s| s.y()).collect();
+        // Elements of `GF256` always decode to bytes
+        math::decode_secret(&math::interpolate_points(&xs, &ys, GF256::ZERO)).unwrap()
+    }
+
+    /// Same as `recover`, the secret being checked to be UTF-8 text and returned as a `String`.
+    /// An `Err` is returned if it is not valid UTF-8, in which case the recovered bytes are wiped
+    /// from memory.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer("correct horse".as_bytes()).take(2).collect();
+    /// assert_eq!(sharks.recover_string(&shares).unwrap(), "correct horse");
+    /// ```
+    pub fn recover_string<'a, S, T>(&self, shares: T) -> Result<String, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut secret = self.recover(shares)?;
+        if core::str::from_utf8(&secret).is_err() {
+            secret.zeroize();
+            return Err(SharksError::InvalidUtf8);
+        }
+        Ok(String::from_utf8(secret).expect("secret was checked to be UTF-8"))
+    }
+
+    /// Same as `recover`, the secret being checked to be exactly `N` bytes long and returned as an array,
+    /// such as a key of a known size. An `Err` is returned if the length differs, in which case the recovered bytes
+    /// are wiped from memory.
+    /// Unlike `recover_array`, which recovers fixed size `ArrayShare`s, the shares are regular ones.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[7; 32]).take(2).collect();
+    /// let key: [u8; 32] = sharks.recover_to_array(&shares).unwrap();
+    /// assert_eq!(key, [7; 32]);
+    /// assert_eq!(
+    ///     sharks.recover_to_array::<16, _, _>(&shares).err(),
+    ///     Some(SharksError::UnexpectedLength { expected: 16, found: 32 })
+    /// );
+    /// ```
+    pub fn recover_to_array<'a, const N: usize, S, T>(
+        &self,
+        shares: T,
+    ) -> Result<[u8; N], SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut secret = self.recover(shares)?;
+        let result =
+            <[u8; N]>::try_from(secret.as_slice()).map_err(|_| SharksError::UnexpectedLength {
+                expected: N,
+                found: secret.len(),
+            });
+        secret.zeroize();
+        result
+    }
+
+    /// Given an iterable collection of shares, recovers the original secret like `recover`, using the extra
+    /// distinct shares beyond the threshold to check its integrity: the secret is interpolated from the first
+    /// threshold shares, and all the others must lie on the same polynomials. If they do not, an `Err` is returned
+    /// instead of a secret that may be wrong. See `recover_checked` to identify the corrupted shares.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError, GF256 };
+    /// let sharks = Sharks(2);
+    /// let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+    /// assert_eq!(sharks.recover_consistent(&shares).unwrap(), vec![1, 2, 3]);
+    /// shares[2].y[1] = shares[2].y[1] + GF256(1);
+    /// assert_eq!(sharks.recover_consistent(&shares), Err(SharksError::InconsistentShares));
+    /// ```
+    pub fn recover_consistent<'a, S, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let shares = self.distinct(
+            shares.into_iter().map(AsShareRef::as_share_ref),
+            self.0 as usize,
+        )?;
+        let (used, extra) = shares.split_at((self.0 as usize).max(1));
+        let xs: Vec<GF256> = used.iter().map(|s| s.x).collect();
+        let ys: Vec<&[GF256]> = used.iter().map(|s| s.y()).collect();
+        if extra
+            .iter()
+            .any(|s| math::interpolate_points(&xs, &ys, s.x) != s.y())
+        {
+            return Err(SharksError::InconsistentShares);
+        }
+        math::decode_secret(&math::interpolate_points(&xs, &ys, GF256::ZERO))
+    }
+
+    /// Given an iterable collection of fallibly obtained shares, for instance parsed from files or received
+    /// over the network, recovers the original secret. Stops at the first `Err` and returns it,
+    /// otherwise works the same way as `recover`, its `SharksError`s being converted into `E`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # use std::convert::TryFrom;
+    /// # use std::error::Error;
+    /// # let sharks = Sharks(2);
+    /// let bytes: Vec<Vec<u8>> = sharks.dealer(&[1, 2, 3]).take(2).map(|s| Vec::from(&s)).collect();
+    /// let shares = bytes.iter().map(|b| Share::try_from(b.as_slice()).map_err(Box::<dyn Error>::from));
+    /// assert_eq!(sharks.recover_from_iter(shares).unwrap(), vec![1, 2, 3]);
+    ///
+    /// let shares: Vec<Result<Share, Box<dyn Error>>> = vec![Ok(Share::try_from(&[1, 2][..])?), Err("connection lost".into())];
+    /// assert_eq!(sharks.recover_from_iter(shares).unwrap_err().to_string(), "connection lost");
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn recover_from_iter<E, T>(&self, shares: T) -> Result<Vec<u8>, E>
+    where
+        E: From<SharksError>,
+        T: IntoIterator<Item = Result<Share, E>>,
+    {
+        let shares = shares.into_iter().collect::<Result<Vec<Share>, E>>()?;
+        Ok(self.recover(&shares)?)
+    }
+
+    /// Given an iterable collection of shares, recovers the original secret into `out`, returning its length.
+    /// Works the same way as `recover`, but the secret is never written to memory allocated by the crate,
+    /// allowing to recover it into a locked memory region or a static buffer.
+    /// If `out` is shorter than the secret an `Err` is returned, its contents being left untouched.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(3);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+    /// let mut buffer = [0u8; 32];
+    /// let len = sharks.recover_into(&shares, &mut buffer).unwrap();
+    /// assert_eq!(&buffer[..len], &[1, 2, 3]);
+    /// assert!(sharks.recover_into(&shares, &mut buffer[..2]).is_err());
+    pub fn recover_into<'a, S, T>(&self, shares: T, out: &mut [u8]) -> Result<usize, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let shares = self.distinct(
+            shares.into_iter().map(AsShareRef::as_share_ref),
+            self.0 as usize,
+        )?;
+        let len = shares[0].y.len();
+        if out.len() < len {
+            return Err(SharksError::InvalidLength);
+        }
+
+        // Each byte is accumulated in place, so that no intermediate copy of the secret is made
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        let weights = math::lagrange_weights(&xs, GF256::ZERO);
+        for (c, byte) in out[..len].iter_mut().enumerate() {
+            *byte = 0;
+            for (w, s) in weights.iter().zip(shares.iter()) {
+                *byte ^= (*w * GF256(s.y[c])).0;
+            }
+        }
+        Ok(len)
+    }
+
+    /// Given an iterable collection of shares, recovers only the `len` bytes of the secret starting at `offset`.
+    /// Each byte being shared independently, the other ones are not interpolated at all, so that a header
+    /// or a single record can be cheaply extracted from a large secret. Works the same way as `recover`,
+    /// an `Err` being returned as well if the range exceeds the secret length.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(3);
+    /// let secret: Vec<u8> = (0..200).collect();
+    /// let shares: Vec<Share> = sharks.dealer(&secret).take(3).collect();
+    /// assert_eq!(sharks.recover_range(&shares, 100, 4).unwrap(), vec![100, 101, 102, 103]);
+    /// assert!(sharks.recover_range(&shares, 199, 2).is_err());
+    pub fn recover_range<'a, S, T>(
+        &self,
+        shares: T,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let shares = self.distinct(
+            shares.into_iter().map(AsShareRef::as_share_ref),
+            self.0 as usize,
+        )?;
+        let range = offset
+            .checked_add(len)
+            .filter(|end| *end <= shares[0].y.len())
+            .map(|end| offset..end)
+            .ok_or(SharksError::InvalidIndex)?;
+
+        let xs: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        let ys: Vec<&[GF256]> = shares.iter().map(|s| &s.y()[range.clone()]).collect();
+        math::decode_secret(&math::interpolate_points(&xs, &ys, GF256::ZERO))
+    }
+
+    /// Given an iterable collection of shares over the field `F`, recovers the original secret.
+    /// Works the same way as `recover`, which is restricted to `GF256` shares.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, FieldShare, GF65536 };
+    /// # let sharks = Sharks(3);
+    /// let shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).skip(1000).take(3).collect();
+    /// let secret = sharks.recover_in(&shares).unwrap();
+    /// assert_eq!(secret, vec![1, 2, 3]);
+    pub fn recover_in<'a, F, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        math::decode_secret(&self.interpolate(shares)?)
+    }
+
+    /// Given the `x` coordinates of a set of at least threshold participants, returns the Lagrange coefficient
+    /// of each of them, in the same order: the secret is the sum of their shares `y` values multiplied by them.
+    /// Threshold schemes combine partial results the same way, each participant applying its coefficient
+    /// to its share, for instance to sign with a shared private key without ever recovering it.
+    /// An `Err` is returned if there are fewer coordinates than the threshold, or some of them are zero or repeated.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, GF256 };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<Share> = sharks.dealer(&[42]).take(3).collect();
+    /// let coefficients = sharks.lagrange_coefficients(&[shares[0].x, shares[2].x]).unwrap();
+    /// let secret = coefficients[0] * shares[0].y[0] + coefficients[1] * shares[2].y[0];
+    /// assert_eq!(secret, GF256(42));
+    pub fn lagrange_coefficients<F: Field>(&self, xs: &[F]) -> Result<Vec<F>, SharksError> {
+        if xs.len() < (self.0 as usize).max(1) {
+            return Err(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: xs.len(),
+                provided: xs.len(),
+            });
+        }
+        for (i, x) in xs.iter().enumerate() {
+            if *x == F::ZERO {
+                return Err(SharksError::ZeroShareX);
+            } else if xs[..i].contains(x) {
+                return Err(SharksError::DuplicateShareX);
+            }
+        }
+        Ok(math::lagrange_weights(xs, F::ZERO))
+    }
+
+    /// Given an iterable collection of shares over the field `F`, recovers the original secret
+    /// even if some of them are corrupted, using Reed-Solomon decoding (Berlekamp-Welch).
+    /// Out of `n` distinct shares, up to `(n - threshold) / 2` corrupted ones are tolerated.
+    /// If there are more, or not enough shares to begin with, an `Err` is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, GF256 };
+    /// # let sharks = Sharks(3);
+    /// let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+    /// // Corrupt one of the shares
+    /// shares[2].y[0] = shares[2].y[0] + GF256(42);
+    /// let secret = sharks.recover_with_correction(&shares).unwrap();
+    /// assert_eq!(secret, vec![1, 2, 3]);
+    pub fn recover_with_correction<'a, F, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        let shares = self.validated(shares, self.0 as usize)?;
+        let chunks = (0..shares[0].y.len())
+            .map(|c| {
+                let points: Vec<(F, F)> = shares.iter().map(|s| (s.x, s.y[c])).collect();
+                math::berlekamp_welch(&points, self.0 as usize)
+                    .and_then(|poly| poly.last().copied())
+                    .ok_or(SharksError::TooManyErrors)
+            })
+            .collect::<Result<Vec<F>, SharksError>>()?;
+        math::decode_secret(&chunks)
+    }
+
+    /// Given an iterable collection of shares over the field `F`, recovers the original secret
+    /// after checking all of them lie on the same polynomials. Otherwise the `x` coordinates
+    /// of the inconsistent shares are returned, which is possible if they are at most
+    /// `(n - threshold) / 2` out of `n` distinct shares. Note `threshold` shares are always consistent.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, GF256, CheckedRecoveryError };
+    /// # let sharks = Sharks(3);
+    /// let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+    /// assert_eq!(sharks.recover_checked(&shares).unwrap(), vec![1, 2, 3]);
+    /// // Tamper with the third share
+    /// shares[2].y[0] = shares[2].y[0] + GF256(42);
+    /// let error = sharks.recover_checked(&shares).unwrap_err();
+    /// assert_eq!(error, CheckedRecoveryError::Inconsistent(vec![shares[2].x]));
+    pub fn recover_checked<'a, F, T>(&self, shares: T) -> Result<Vec<u8>, CheckedRecoveryError<F>>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        let shares = self
+            .validated(shares, self.0 as usize)
+            .map_err(CheckedRecoveryError::Invalid)?;
+        let polys = (0..shares[0].y.len())
+            .map(|c| {
+                let points: Vec<(F, F)> = shares.iter().map(|s| (s.x, s.y[c])).collect();
+                math::berlekamp_welch(&points, self.0 as usize)
+                    .ok_or(CheckedRecoveryError::Invalid(SharksError::TooManyErrors))
             })
-            .unzip::<u8, Share, HashSet<u8>, Vec<Share>>();
+            .collect::<Result<Vec<Vec<F>>, CheckedRecoveryError<F>>>()?;
+
+        let inconsistent: Vec<F> = shares
+            .iter()
+            .filter(|s| s.y != math::evaluate(&polys, s.x))
+            .map(|s| s.x)
+            .collect();
+        if !inconsistent.is_empty() {
+            return Err(CheckedRecoveryError::Inconsistent(inconsistent));
+        }
 
-        if keys.len() < self.0 as usize {
-            Err("Not enough shares to recover original secret")
+        let chunks: Vec<F> = polys.iter().filter_map(|p| p.last().copied()).collect();
+        math::decode_secret(&chunks).map_err(CheckedRecoveryError::Invalid)
+    }
+
+    /// Given the shares held by every participant, returns new shares of the same secret at the same `x`
+    /// coordinates, obtained adding to them the points of random polynomials whose constant term is zero.
+    /// Any threshold of new shares recovers the secret, but old and new shares cannot be combined,
+    /// so shares which leaked before the refresh become useless once the old ones are destroyed.
+    /// An `Err` is returned if the shares are inconsistent.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(3);
+    /// let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+    /// let refreshed = sharks.refresh(&shares).unwrap();
+    /// assert_eq!(sharks.recover(&refreshed[2..]).unwrap(), vec![1, 2, 3]);
+    pub fn refresh<'a, F, T>(&self, shares: T) -> Result<Vec<FieldShare<F>>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        let mut shares = self.validated(shares, 1)?;
+        let mut coeffs = math::random_coefficients(
+            vec![F::ZERO; shares[0].y.len()],
+            self.0,
+            &mut rand::thread_rng(),
+        );
+
+        for share in shares.iter_mut() {
+            let delta = math::evaluate_coefficients(&coeffs, share.x);
+            share.y.iter_mut().zip(delta).for_each(|(y, d)| *y = *y + d);
+            share.threshold = Some(self.0);
+        }
+
+        coeffs.zeroize();
+        Ok(shares)
+    }
+
+    /// Given an iterable collection of at least threshold shares, returns a new share of the same secret
+    /// at the `x` coordinate, which must not be held by any other participant. The secret is never recovered,
+    /// the new share is interpolated directly, so it can be done by the participants of a quorum without a dealer.
+    /// An `Err` is returned if `x` is zero, there are not enough shares, or they are inconsistent.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, GF256 };
+    /// # let sharks = Sharks(3);
+    /// let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+    /// // Onboard a new participant at x = 200
+    /// let share = sharks.mint_share(&shares, GF256(200)).unwrap();
+    /// shares[0] = share;
+    /// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+    pub fn mint_share<'a, F, T>(&self, shares: T, x: F) -> Result<FieldShare<F>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        if x == F::ZERO {
+            return Err(SharksError::ZeroShareX);
+        }
+        let shares = self.validated(shares, self.0 as usize)?;
+        Ok(FieldShare {
+            x,
+            y: math::interpolate_at(&shares, x),
+            threshold: Some(self.0),
+            group: None,
+        })
+    }
+
+    // Validates the shares and recovers the secret chunks as field elements
+    fn interpolate<'a, F, T>(&self, shares: T) -> Result<Vec<F>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+    {
+        Ok(math::interpolate(&self.validated(shares, self.0 as usize)?))
+    }
+
+    // Checks the shares are consistent and at least `min` of them are distinct, returning the distinct ones.
+    // Identical copies of a share are ignored, but shares with the same `x` and different `y` values are rejected.
+    fn validated<'a, F, T>(&self, shares: T, min: usize) -> Result<Vec<FieldShare<F>>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+    {
+        Ok(self.distinct(shares, min)?.into_iter().cloned().collect())
+    }
+
+    // Same as `validated`, returning the distinct shares without copying them.
+    fn distinct<F, P, T>(&self, shares: T, min: usize) -> Result<Vec<P>, SharksError>
+    where
+        F: Field,
+        P: Point<F>,
+        T: IntoIterator<Item = P>,
+    {
+        let mut keys = HashMap::new();
+        let mut thresholds = HashSet::new();
+        let mut group = None;
+        let mut distinct: Vec<P> = Vec::new();
+        let mut provided = 0;
+        for s in shares {
+            provided += 1;
+            thresholds.insert(s.threshold());
+            // Checked first, as shares of different splits usually conflict at the same `x` coordinates
+            if let Some(g) = s.group() {
+                if *group.get_or_insert(g) != g {
+                    return Err(SharksError::MixedGroups);
+                }
+            }
+            match keys.get(&s.x()) {
+                // Identical copies are ignored, while conflicting ones would corrupt the secret
+                Some(i) => {
+                    let d: &P = &distinct[*i];
+                    if d.y().len() != s.y().len() {
+                        return Err(SharksError::InconsistentLengths);
+                    }
+                    let conflict = d
+                        .y()
+                        .iter()
+                        .zip(s.y().iter())
+                        .fold(false, |acc, (a, b)| acc | (a != b));
+                    if conflict {
+                        return Err(SharksError::DuplicateShareX);
+                    }
+                }
+                None => {
+                    keys.insert(s.x(), distinct.len());
+                    distinct.push(s);
+                }
+            }
+        }
+        let shares = distinct;
+
+        if thresholds.len() > 1 {
+            Err(SharksError::InconsistentThresholds)
+        } else if let Some(found) = thresholds.into_iter().flatten().find(|t| *t != self.0) {
+            Err(SharksError::ThresholdMismatch {
+                expected: self.0,
+                found,
+            })
+        } else if shares.len() < min.max(1) {
+            Err(SharksError::NotEnoughShares {
+                needed: min.max(1),
+                got: shares.len(),
+                provided,
+            })
+        } else if shares.iter().any(|s| s.y().len() != shares[0].y().len()) {
+            Err(SharksError::InconsistentLengths)
         } else {
-            Ok(math::interpolate(shares.as_slice()))
+            Ok(shares)
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use super::{Share, Sharks};
+    use super::{
+        CheckedRecoveryError, FieldShare, Share, ShareRef, Sharks, SharksError, GF256, GF65536,
+    };
+    use std::convert::TryFrom;
+
+    #[test]
+    fn dealer_for_ids_works() {
+        let sharks = Sharks(3);
+        let shares = sharks.dealer_for_ids(&[1, 2, 3], &[200, 3, 77]).unwrap();
+        let x: Vec<GF256> = shares.iter().map(|s| s.x).collect();
+        assert_eq!(x, vec![GF256(200), GF256(3), GF256(77)]);
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+
+        assert_eq!(
+            sharks.dealer_for_ids(&[1], &[1, 0, 2]).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert_eq!(
+            sharks.dealer_for_ids(&[1], &[1, 2, 1]).err(),
+            Some(SharksError::DuplicateShareX)
+        );
+    }
+
+    #[test]
+    fn dealer_n_works() {
+        let sharks = Sharks(3);
+        let shares = sharks.dealer_n(&[1, 2], 255).unwrap();
+        assert_eq!(shares.len(), 255);
+        assert_eq!(shares[254].x, GF256(255));
+        assert_eq!(sharks.recover(&shares[100..103]).unwrap(), vec![1, 2]);
+
+        assert_eq!(
+            sharks.dealer_n(&[1], 256).err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+        assert_eq!(
+            sharks.dealer_n(&[1], 2).err(),
+            Some(SharksError::TooFewShares { min: 3 })
+        );
+    }
+
+    #[test]
+    fn recover_typed_works() {
+        let sharks = Sharks(2);
+        let shares: Vec<Share> = sharks.dealer("sécret".as_bytes()).take(3).collect();
+        assert_eq!(sharks.recover_string(&shares[1..]).unwrap(), "sécret");
+        assert_eq!(
+            sharks.recover_to_array::<7, _, _>(&shares).unwrap(),
+            *"sécret".as_bytes()
+        );
+        assert_eq!(
+            sharks.recover_to_array::<6, _, _>(&shares).err(),
+            Some(SharksError::UnexpectedLength {
+                expected: 6,
+                found: 7
+            })
+        );
+
+        let shares: Vec<Share> = sharks.dealer(&[0xff, 0xfe]).take(2).collect();
+        assert_eq!(
+            sharks.recover_string(&shares).err(),
+            Some(SharksError::InvalidUtf8)
+        );
+        assert_eq!(
+            sharks.recover_string(&shares[..1]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+    }
+
+    #[test]
+    fn try_dealer_works() {
+        let sharks = Sharks(3);
+        let mut dealer = sharks.try_dealer(&[1, 2]);
+        let shares: Vec<Share> = dealer.by_ref().take(255).map(Result::unwrap).collect();
+        assert_eq!(shares[254].x, GF256(255));
+        assert_eq!(sharks.recover(&shares[10..13]).unwrap(), vec![1, 2]);
+        assert_eq!(
+            dealer.next(),
+            Some(Err(SharksError::TooManyShares { max: 255 }))
+        );
+        assert!(dealer.next().is_none());
+    }
 
     #[test]
     fn test_insufficient_shares_err() {
@@ -118,7 +1168,57 @@ mod tests {
         let dealer = sharks.dealer(&[1]);
         let shares: Vec<Share> = dealer.take(254).collect();
         let secret = sharks.recover(&shares);
-        assert!(secret.is_err());
+        assert_eq!(
+            secret,
+            Err(SharksError::NotEnoughShares {
+                needed: 255,
+                got: 254,
+                provided: 254
+            })
+        );
+    }
+
+    #[test]
+    fn insufficient_copies_err() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1]).take(2).collect();
+        let copies = [&shares[0], &shares[1], &shares[0], &shares[1]];
+        let err = sharks.recover(copies).unwrap_err();
+        assert_eq!(
+            err,
+            SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 4
+            }
+        );
+        assert_eq!(err.missing_shares(), Some(1));
+        assert_eq!(
+            err.to_string(),
+            "Not enough shares to recover original secret, 3 needed but 2 distinct of 4 given"
+        );
+    }
+
+    #[test]
+    fn recover_consistent_works() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+        assert_eq!(sharks.recover_consistent(&shares[..3]), Ok(vec![1, 2, 3]));
+        assert_eq!(sharks.recover_consistent(&shares), Ok(vec![1, 2, 3]));
+
+        // Exactly threshold shares cannot be checked, more can whichever of them is corrupted
+        shares[0].y[2] = shares[0].y[2] + GF256(1);
+        assert_ne!(sharks.recover_consistent(&shares[..3]), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            sharks.recover_consistent(&shares),
+            Err(SharksError::InconsistentShares)
+        );
+        assert_eq!(sharks.recover_consistent(&shares[1..]), Ok(vec![1, 2, 3]));
+        let other: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+        assert_eq!(
+            sharks.recover_consistent([&shares[1], &shares[2], &shares[3], &other[4]]),
+            Err(SharksError::InconsistentShares)
+        );
     }
 
     #[test]
@@ -126,14 +1226,33 @@ mod tests {
         let sharks = Sharks(255);
         let dealer = sharks.dealer(&[1]);
         let mut shares: Vec<Share> = dealer.take(255).collect();
-        shares[1] = Share {
-            x: shares[0].x,
-            y: shares[0].y.clone(),
-        };
+        shares[1] = shares[0].clone();
         let secret = sharks.recover(&shares);
         assert!(secret.is_err());
     }
 
+    #[test]
+    fn test_conflicting_shares_err() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(4).collect();
+        shares.push(shares[1].clone());
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+
+        shares[4].y[2] = shares[4].y[2] + GF256(1);
+        assert_eq!(sharks.recover(&shares), Err(SharksError::DuplicateShareX));
+        shares[4].y.pop();
+        assert_eq!(
+            sharks.recover(&shares),
+            Err(SharksError::InconsistentLengths)
+        );
+        shares.pop();
+        shares[3].y.push(GF256(1));
+        assert_eq!(
+            sharks.recover(&shares),
+            Err(SharksError::InconsistentLengths)
+        );
+    }
+
     #[test]
     fn test_integration_works() {
         let sharks = Sharks(255);
@@ -142,4 +1261,361 @@ mod tests {
         let secret = sharks.recover(&shares).unwrap();
         assert_eq!(secret, vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_zero_threshold_integration_works() {
+        // A threshold of 0 behaves as a threshold of 1
+        let sharks = Sharks(0);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(2).collect();
+        assert!(shares.iter().all(|s| s.y.len() == 4));
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(sharks.recover(&shares[1..]).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_recover_from_iter_works() {
+        let sharks = Sharks(3);
+        let bytes: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3, 4])
+            .take(3)
+            .map(|s| Vec::from(&s))
+            .collect();
+        let shares = bytes.iter().map(|b| Share::try_from(b.as_slice()));
+        assert_eq!(sharks.recover_from_iter(shares).unwrap(), vec![1, 2, 3, 4]);
+
+        let shares = bytes[..2].iter().map(|b| Share::try_from(b.as_slice()));
+        assert_eq!(
+            sharks.recover_from_iter(shares),
+            Err(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+
+        // The first error is returned, without consuming the remaining shares
+        let mut consumed = 0;
+        let parts: [&[u8]; 4] = [&bytes[0], &[], &[0, 1], &bytes[1]];
+        let shares = parts.iter().map(|b| {
+            consumed += 1;
+            Share::try_from(*b)
+        });
+        assert_eq!(
+            sharks.recover_from_iter(shares),
+            Err(SharksError::EmptyShare)
+        );
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn test_recover_into_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let mut buffer = [0xffu8; 6];
+        assert_eq!(sharks.recover_into(&shares[1..4], &mut buffer), Ok(4));
+        assert_eq!(buffer, [1, 2, 3, 4, 0xff, 0xff]);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(sharks.recover_into(&shares, &mut buffer), Ok(4));
+        assert_eq!(buffer, [1, 2, 3, 4]);
+        assert_eq!(
+            sharks.recover_into(&shares, &mut buffer[..3]),
+            Err(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            sharks.recover_into(&shares[..2], &mut buffer),
+            Err(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_recover_range_works() {
+        let sharks = Sharks(4);
+        let secret: Vec<u8> = (0..=255).collect();
+        let shares: Vec<Share> = sharks.dealer(&secret).take(5).collect();
+        assert_eq!(sharks.recover_range(&shares, 0, 256).unwrap(), secret);
+        assert_eq!(
+            sharks.recover_range(&shares[1..], 250, 6).unwrap(),
+            &secret[250..]
+        );
+        assert!(sharks.recover_range(&shares, 256, 0).unwrap().is_empty());
+        assert_eq!(
+            sharks.recover_range(&shares, 255, 2),
+            Err(SharksError::InvalidIndex)
+        );
+        assert_eq!(
+            sharks.recover_range(&shares, usize::MAX, 2),
+            Err(SharksError::InvalidIndex)
+        );
+        assert_eq!(
+            sharks.recover_range(&shares[2..], 0, 1),
+            Err(SharksError::NotEnoughShares {
+                needed: 4,
+                got: 3,
+                provided: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_versioned_integration_works() {
+        let sharks = Sharks(3);
+        let bytes: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3, 4])
+            .take(3)
+            .map(|s| s.to_versioned_bytes().unwrap())
+            .collect();
+        let shares: Vec<Share> = bytes
+            .iter()
+            .map(|b| Share::from_versioned_bytes(b).unwrap())
+            .collect();
+        let secret = sharks.recover(&shares).unwrap();
+        assert_eq!(secret, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_gf65536_integration_works() {
+        let sharks = Sharks(10);
+        let secret: Vec<u8> = (0..33).collect();
+        let shares: Vec<FieldShare<GF65536>> =
+            sharks.dealer_in(&secret).skip(60000).take(10).collect();
+        assert_eq!(shares[9].x, GF65536(60010));
+        assert_eq!(sharks.recover_in(&shares).unwrap(), secret);
+        assert!(sharks.recover_in(&shares[1..]).is_err());
+    }
+
+    #[test]
+    fn test_threshold_mismatch_err() {
+        let shares: Vec<Share> = Sharks(3).dealer(&[1]).take(4).collect();
+        assert_eq!(
+            Sharks(2).recover(&shares),
+            Err(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_mixed_formats_err() {
+        let sharks = Sharks(2);
+        let mut shares: Vec<Share> = sharks.dealer(&[1]).take(2).collect();
+        shares[1] = Share::try_from(Vec::from(&shares[1]).as_slice()).unwrap();
+        assert_eq!(
+            sharks.recover(&shares),
+            Err(SharksError::InconsistentThresholds)
+        );
+    }
+
+    #[test]
+    fn test_recover_with_correction_works() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(7).collect();
+        shares[0].y[1] = shares[0].y[1] + GF256(1);
+        shares[5].y = vec![GF256(0); 4];
+        assert_ne!(sharks.recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            sharks.recover_with_correction(&shares).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            sharks.recover_with_correction(&shares[..6]),
+            Err(SharksError::TooManyErrors)
+        );
+        assert!(sharks.recover_with_correction(&shares[..2]).is_err());
+
+        let mut shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).take(5).collect();
+        shares[4].y[0] = GF65536(0);
+        assert_eq!(
+            sharks.recover_with_correction(&shares).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_recover_checked_works() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(7).collect();
+        assert_eq!(sharks.recover_checked(&shares).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            sharks.recover_checked(&shares[..3]).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+
+        shares[1].y[3] = shares[1].y[3] + GF256(1);
+        shares[6].y[0] = shares[6].y[0] + GF256(1);
+        assert_eq!(
+            sharks.recover_checked(&shares),
+            Err(CheckedRecoveryError::Inconsistent(vec![GF256(2), GF256(7)]))
+        );
+        // Corrupted chunks are decoded independently
+        assert_eq!(
+            sharks.recover_checked(&shares[1..]),
+            Err(CheckedRecoveryError::Inconsistent(vec![GF256(2), GF256(7)]))
+        );
+        shares[3].y[0] = shares[3].y[0] + GF256(1);
+        assert!(matches!(
+            sharks.recover_checked(&shares[1..]),
+            Err(CheckedRecoveryError::Invalid(_))
+        ));
+        assert!(matches!(
+            sharks.recover_checked(&shares[..2]),
+            Err(CheckedRecoveryError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_refresh_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let refreshed = sharks.refresh(&shares).unwrap();
+        assert_eq!(refreshed.len(), 5);
+        assert!(refreshed
+            .iter()
+            .zip(&shares)
+            .all(|(r, s)| r.x == s.x && r.y != s.y));
+        assert_eq!(sharks.recover(&refreshed[2..]).unwrap(), vec![1, 2, 3, 4]);
+
+        let mixed = vec![
+            shares[0].clone(),
+            refreshed[1].clone(),
+            refreshed[2].clone(),
+        ];
+        assert_ne!(sharks.recover(&mixed).unwrap(), vec![1, 2, 3, 4]);
+
+        let shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).take(3).collect();
+        let refreshed = sharks.refresh(&shares).unwrap();
+        assert_eq!(sharks.recover_in(&refreshed).unwrap(), vec![1, 2, 3]);
+        assert!(sharks.refresh(&shares[..0]).is_err());
+    }
+
+    #[test]
+    fn test_mint_share_works() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(4).collect();
+        let minted = sharks.mint_share(&shares[1..], GF256(1)).unwrap();
+        assert_eq!(Vec::from(&minted), Vec::from(&shares[0]));
+
+        shares[1] = sharks.mint_share(&shares, GF256(255)).unwrap();
+        assert_eq!(shares[1].threshold(), Some(3));
+        assert_eq!(sharks.recover(&shares[..3]).unwrap(), vec![1, 2, 3, 4]);
+
+        assert_eq!(
+            sharks.mint_share(&shares, GF256(0)).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert!(sharks.mint_share(&shares[..2], GF256(100)).is_err());
+    }
+
+    #[test]
+    fn test_dealer_with_rng_works() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks
+            .dealer_with_rng(&mut StdRng::seed_from_u64(42), &[1, 2, 3, 4])
+            .take(5)
+            .collect();
+        let again: Vec<Share> = sharks
+            .dealer_with_rng(&mut StdRng::seed_from_u64(42), &[1, 2, 3, 4])
+            .take(5)
+            .collect();
+        assert!(shares.iter().zip(&again).all(|(a, b)| a.y == b.y));
+        assert_eq!(shares[4].threshold(), Some(3));
+        assert_eq!(sharks.recover(&shares[2..]).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dealer_from_seed_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks
+            .dealer_from_seed([0; 32], &[1, 2, 3])
+            .take(3)
+            .collect();
+        let bytes: Vec<Vec<u8>> = shares.iter().map(Vec::from).collect();
+        assert_eq!(
+            bytes,
+            vec![
+                vec![1, 63, 206, 160],
+                vec![2, 190, 227, 239],
+                vec![3, 128, 47, 76]
+            ]
+        );
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+
+        let other: Vec<Share> = sharks
+            .dealer_from_seed([1; 32], &[1, 2, 3])
+            .take(3)
+            .collect();
+        assert_ne!(Vec::from(&other[0]), bytes[0]);
+    }
+
+    #[test]
+    fn test_recover_unchecked_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        assert_eq!(sharks.recover_unchecked(&shares), vec![1, 2, 3, 4]);
+        assert_eq!(sharks.recover_unchecked(&shares[2..]), vec![1, 2, 3, 4]);
+        let bytes: Vec<Vec<u8>> = shares.iter().map(Vec::from).collect();
+        let refs: Vec<ShareRef> = bytes
+            .iter()
+            .map(|b| ShareRef::try_from(b.as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_unchecked(&refs), vec![1, 2, 3, 4]);
+
+        // Only the first `threshold` shares are used
+        let mut corrupted = shares.clone();
+        corrupted[4].y[0] = corrupted[4].y[0] + GF256(1);
+        assert_eq!(sharks.recover_unchecked(&corrupted), vec![1, 2, 3, 4]);
+        assert_eq!(Sharks(0).recover_unchecked(&shares), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_recover_unchecked_err() {
+        // Shares which `recover` rejects give a wrong secret instead
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(3).collect();
+        assert!(sharks.recover(&shares[..2]).is_err());
+        assert_ne!(sharks.recover_unchecked(&shares[..2]), vec![1, 2, 3, 4]);
+        let duplicates = [&shares[0], &shares[1], &shares[1]];
+        assert!(sharks.recover(duplicates.iter().copied()).is_err());
+        assert_ne!(
+            sharks.recover_unchecked(duplicates.iter().copied()),
+            vec![1, 2, 3, 4]
+        );
+        assert!(Sharks(2).recover(&shares).is_err());
+        assert_ne!(Sharks(2).recover_unchecked(&shares), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dealer_grouped_works() {
+        let sharks = Sharks(2);
+        let a: Vec<Share> = sharks.dealer_grouped(&[1, 2, 3]).take(3).collect();
+        let b: Vec<Share> = sharks.dealer_grouped(&[4, 5, 6]).take(3).collect();
+        assert!(a[0].group().is_some() && a.iter().all(|s| s.group() == a[0].group()));
+        assert_ne!(a[0].group(), b[0].group());
+
+        let parsed: Vec<Share> = a
+            .iter()
+            .map(|s| Share::from_versioned_bytes(&s.to_versioned_bytes().unwrap()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover(&parsed[1..]).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            sharks.recover(vec![&a[0], &b[1]]).err(),
+            Some(SharksError::MixedGroups)
+        );
+        // Checked before conflicting coordinates
+        assert_eq!(
+            sharks.recover(vec![&a[0], &a[1], &b[1]]).err(),
+            Some(SharksError::MixedGroups)
+        );
+        // Shares without identifier are not rejected
+        let mut plain = a[2].clone();
+        plain.group = None;
+        assert_eq!(sharks.recover(vec![&a[0], &plain]).unwrap(), vec![1, 2, 3]);
+    }
 }
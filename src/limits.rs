@@ -0,0 +1,218 @@
+// Limits on untrusted shares, checked before they are parsed and recovered, so that services accepting shares
+// from the network bound the memory and time spent on each request. The parsers themselves never allocate more
+// than their input, but a request would otherwise be free to hold a huge share or a huge number of them.
+// Shares are counted while being read from their iterator, which is not consumed beyond the first share past the limit.
+
+use super::error::SharksError;
+use super::field::Field;
+use super::share::FieldShare;
+use super::Sharks;
+
+/// Maximum length and number of untrusted shares, see `Limits::parse_all` and `Sharks::recover_limited`.
+/// The default limits of 1 MiB per share and 255 shares suit most services, which should lower them
+/// to the largest secrets and quorums they expect.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, Share, Limits, SharksError };
+/// let sharks = Sharks(2);
+/// let shares: Vec<Vec<u8>> = sharks.dealer(&[1, 2, 3]).take(3).map(|s| s.to_checked_bytes()).collect();
+///
+/// let limits = Limits { max_share_bytes: 64, max_shares: 3 };
+/// let parsed: Vec<Share> = limits.parse_all(&shares, Share::from_checked_bytes).unwrap();
+/// assert_eq!(sharks.recover_limited(&parsed, &limits).unwrap(), vec![1, 2, 3]);
+///
+/// let huge = vec![1; 65];
+/// assert_eq!(limits.parse(&huge, Share::from_checked_bytes).err(), Some(SharksError::ShareTooLong { max: 64 }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Maximum length in bytes of a serialized share, or of the `x || y` bytes of a parsed one.
+    pub max_share_bytes: usize,
+    /// Maximum number of shares, identical copies included.
+    pub max_shares: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_share_bytes: 1 << 20,
+            max_shares: 255,
+        }
+    }
+}
+
+impl Limits {
+    /// Checks the length of the share bytes `s` against the limit.
+    pub fn check_share(&self, s: &[u8]) -> Result<(), SharksError> {
+        if s.len() > self.max_share_bytes {
+            return Err(SharksError::ShareTooLong {
+                max: self.max_share_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Parses the share bytes `s` with `parse`, such as `Share::from_checked_bytes`, after checking their length.
+    /// An `Err` is returned if the bytes are too long, or cannot be parsed.
+    pub fn parse<'a, T, P>(&self, s: &'a [u8], parse: P) -> Result<T, SharksError>
+    where
+        P: FnOnce(&'a [u8]) -> Result<T, SharksError>,
+    {
+        self.check_share(s)?;
+        parse(s)
+    }
+
+    /// Parses each of the `shares` with `parse`, after checking their number and length.
+    /// An `Err` is returned if there are too many shares, the iterator being left at the first one past the limit,
+    /// or any of them is too long or cannot be parsed.
+    pub fn parse_all<'a, B, T, I, P>(&self, shares: I, mut parse: P) -> Result<Vec<T>, SharksError>
+    where
+        B: AsRef<[u8]> + ?Sized + 'a,
+        I: IntoIterator<Item = &'a B>,
+        P: FnMut(&'a [u8]) -> Result<T, SharksError>,
+    {
+        let mut parsed = Vec::new();
+        for (i, s) in shares.into_iter().enumerate() {
+            if i >= self.max_shares {
+                return Err(SharksError::TooManyShares {
+                    max: self.max_shares,
+                });
+            }
+            parsed.push(self.parse(s.as_ref(), &mut parse)?);
+        }
+        Ok(parsed)
+    }
+
+    /// Parses a bundle of shares `s` with `parse`, such as `Share::bundle_from_cbor`, after checking its length
+    /// is at most the one of the maximum number of shares, then checks the number of parsed shares.
+    pub fn parse_bundle<'a, T, P>(&self, s: &'a [u8], parse: P) -> Result<Vec<T>, SharksError>
+    where
+        P: FnOnce(&'a [u8]) -> Result<Vec<T>, SharksError>,
+    {
+        let max = self.max_share_bytes.saturating_mul(self.max_shares);
+        if s.len() > max {
+            return Err(SharksError::ShareTooLong { max });
+        }
+        let shares = parse(s)?;
+        if shares.len() > self.max_shares {
+            return Err(SharksError::TooManyShares {
+                max: self.max_shares,
+            });
+        }
+        Ok(shares)
+    }
+}
+
+impl Sharks {
+    /// Same as `recover_in`, the shares being checked against the `limits` first: an `Err` is returned
+    /// if there are more shares than allowed, the iterator being left at the first one past the limit,
+    /// or any of them is longer.
+    pub fn recover_limited<'a, F, T>(
+        &self,
+        shares: T,
+        limits: &Limits,
+    ) -> Result<Vec<u8>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+        T::IntoIter: Iterator<Item = &'a FieldShare<F>>,
+    {
+        let mut checked = Vec::new();
+        for (i, s) in shares.into_iter().enumerate() {
+            if i >= limits.max_shares {
+                return Err(SharksError::TooManyShares {
+                    max: limits.max_shares,
+                });
+            } else if (s.y.len() + 1).saturating_mul(F::BYTES) > limits.max_share_bytes {
+                return Err(SharksError::ShareTooLong {
+                    max: limits.max_share_bytes,
+                });
+            }
+            checked.push(s);
+        }
+        self.recover_in(checked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Limits;
+    use crate::{FieldShare, Share, Sharks, SharksError, GF65536};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn limits_integration_works() {
+        let sharks = Sharks(2);
+        let limits = Limits {
+            max_share_bytes: 8,
+            max_shares: 3,
+        };
+        let shares: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).take(4).collect();
+        assert_eq!(
+            sharks.recover_limited(&shares[..3], &limits).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            sharks.recover_limited(&shares, &limits).err(),
+            Some(SharksError::TooManyShares { max: 3 })
+        );
+        let long: Vec<FieldShare<GF65536>> =
+            sharks.dealer_in(&[1, 2, 3, 4, 5, 6]).take(2).collect();
+        assert_eq!(
+            sharks.recover_limited(&long, &limits).err(),
+            Some(SharksError::ShareTooLong { max: 8 })
+        );
+
+        // Reading stops at the first share past the limit
+        let bytes: Vec<Vec<u8>> = shares.iter().map(Vec::from).collect();
+        let mut read = 0;
+        let parsed = limits.parse_all(
+            bytes.iter().inspect(|_| read += 1),
+            FieldShare::<GF65536>::try_from,
+        );
+        assert_eq!(parsed.err(), Some(SharksError::TooManyShares { max: 3 }));
+        assert_eq!(read, 4);
+        let parsed: Vec<FieldShare<GF65536>> =
+            limits.parse_all(&bytes[1..], FieldShare::try_from).unwrap();
+        assert_eq!(sharks.recover_in(&parsed).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn limits_bundle_works() {
+        let shares: Vec<Share> = Sharks(2).dealer(&[1, 2, 3]).take(3).collect();
+        let bundle = Share::bundle_to_cbor(&shares);
+        let limits = Limits {
+            max_share_bytes: 16,
+            max_shares: 3,
+        };
+        assert_eq!(
+            limits
+                .parse_bundle(&bundle, Share::bundle_from_cbor)
+                .unwrap()
+                .len(),
+            3
+        );
+        let limits = Limits {
+            max_share_bytes: 1 << 10,
+            max_shares: 2,
+        };
+        assert_eq!(
+            limits.parse_bundle(&bundle, Share::bundle_from_cbor).err(),
+            Some(SharksError::TooManyShares { max: 2 })
+        );
+        assert_eq!(
+            limits
+                .parse_bundle(&vec![0; 2049], Share::bundle_from_cbor)
+                .err(),
+            Some(SharksError::ShareTooLong { max: 2048 })
+        );
+        assert_eq!(
+            limits
+                .parse(&Vec::from(&shares[0]), Share::try_from)
+                .unwrap()
+                .x,
+            shares[0].x
+        );
+    }
+}
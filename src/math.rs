@@ -1,70 +1,490 @@
-// A module which contains necessary algorithms to compute Shamir's shares and recover secrets
+//! Low level polynomial arithmetic over any `Field`, on which the dealing and recovery of shares are built,
+//! for downstream crates implementing their own schemes on top of the same primitives.
+//!
+//! Sets of polynomials are evaluated and interpolated all at once, one per secret chunk, each point being a share
+//! whose `y` values hold the evaluations of every polynomial at its `x` coordinate.
+//! Field arithmetic itself is provided by the `Field` trait and its implementations, such as `GF256`.
+//!
+//! Example:
+//! ```
+//! # use sharks::GF256;
+//! use sharks::math;
+//!
+//! // Polynomials `3x + 1` and `5x + 2`, coefficient degrees going from higher to lower
+//! let polys = vec![vec![GF256(3), GF256(1)], vec![GF256(5), GF256(2)]];
+//! let xs = [GF256(1), GF256(2)];
+//! let ys: Vec<Vec<GF256>> = xs.iter().map(|x| math::evaluate(&polys, *x)).collect();
+//! let ys: Vec<&[GF256]> = ys.iter().map(|y| y.as_slice()).collect();
+//!
+//! assert_eq!(math::interpolate_points(&xs, &ys, GF256(0)), vec![GF256(1), GF256(2)]);
+//! assert_eq!(math::interpolate_points(&xs, &ys, GF256(7)), math::evaluate(&polys, GF256(7)));
+//!
+//! let weights = math::lagrange_weights(&xs, GF256(0));
+//! assert_eq!(weights[0] * ys[0][0] + weights[1] * ys[1][0], GF256(1));
+//! ```
 
-use rand::distributions::{Distribution, Uniform};
+use zeroize::Zeroize;
 
-use super::field::GF256;
-use super::share::Share;
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-// Finds the [root of the Lagrange polynomial](https://en.wikipedia.org/wiki/Shamir%27s_Secret_Sharing#Computationally_efficient_approach).
-// The expected `shares` argument format is the same as the output by the `get_evaluator´ function.
-// Where each (key, value) pair corresponds to one share, where the key is the `x` and the value is a vector of `y`,
-// where each element corresponds to one of the secret's byte chunks.
-pub fn interpolate(shares: &[Share]) -> Vec<u8> {
-    (0..shares[0].y.len())
-        .map(|s| {
-            shares
+use super::error::SharksError;
+use super::field::Field;
+use super::share::FieldShare;
+
+/// Evaluates the Lagrange polynomials going through the `shares` at zero, finding their
+/// [constant terms](https://en.wikipedia.org/wiki/Shamir%27s_Secret_Sharing#Computationally_efficient_approach),
+/// one per `y` value. The shares `x` coordinates must be distinct, and their `y` values equally long.
+pub fn interpolate<F: Field>(shares: &[FieldShare<F>]) -> Vec<F> {
+    interpolate_at(shares, F::ZERO)
+}
+
+/// Evaluates the Lagrange polynomials going through the `shares` at `x`, see `interpolate`.
+/// The Lagrange basis weights only depend on the `x` coordinates, so they are computed once for all the `y` values.
+pub fn interpolate_at<F: Field>(shares: &[FieldShare<F>], x: F) -> Vec<F> {
+    let xs: Vec<F> = shares.iter().map(|s_i| s_i.x).collect();
+    let ys: Vec<&[F]> = shares.iter().map(|s_i| s_i.y.as_slice()).collect();
+    interpolate_points(&xs, &ys, x)
+}
+
+/// Same as `interpolate_at`, the shares being given as their `x` coordinates and borrowed `y` values.
+pub fn interpolate_points<F: Field>(xs: &[F], ys: &[&[F]], x: F) -> Vec<F> {
+    let terms: Vec<(F, &[F])> = lagrange_weights(xs, x)
+        .into_iter()
+        .zip(ys.iter().copied())
+        .collect();
+    let mut result = vec![F::ZERO; ys.first().map_or(0, |y| y.len())];
+    combine(&mut result, &terms, F::add_scaled_slice);
+    result
+}
+
+/// Weights of each share `y` values in the evaluation of the Lagrange polynomials at `x`, in the `xs` order,
+/// so that the evaluation is the sum of the `y` values multiplied by them. The `xs` must be distinct.
+pub fn lagrange_weights<F: Field>(xs: &[F], x: F) -> Vec<F> {
+    xs.iter()
+        .map(|x_i| {
+            let (num, den) = xs
                 .iter()
-                .map(|s_i| {
-                    shares
-                        .iter()
-                        .filter(|s_j| s_j.x != s_i.x)
-                        .map(|s_j| s_j.x / (s_j.x - s_i.x))
-                        .product::<GF256>()
-                        * s_i.y[s]
-                })
-                .sum::<GF256>()
-                .0
+                .filter(|x_j| *x_j != x_i)
+                .fold((F::ONE, F::ONE), |(num, den), x_j| {
+                    (num * (*x_j - x), den * (*x_j - *x_i))
+                });
+            num * den.inverse()
         })
         .collect()
 }
 
-// Generates `k` polynomial coefficients, being the last one `s` and the others randomly generated between `[1, 255]`.
+/// Finds the `count` lowest degree coefficients of the Lagrange polynomials going through the `shares`,
+/// lower degrees first. Each coefficient is returned as a vector with one element per `y` value,
+/// like the `interpolate` result, which is the first one.
+pub fn interpolate_coefficients<F: Field>(shares: &[FieldShare<F>], count: usize) -> Vec<Vec<F>> {
+    // Coefficients of each Lagrange basis polynomial, degrees going from lower to higher
+    let bases: Vec<Vec<F>> = shares
+        .iter()
+        .map(|s_i| {
+            let mut basis = vec![F::ONE];
+            let mut den = F::ONE;
+            for s_j in shares.iter().filter(|s_j| s_j.x != s_i.x) {
+                basis.push(F::ZERO);
+                for d in (1..basis.len()).rev() {
+                    basis[d] = basis[d - 1] - s_j.x * basis[d];
+                }
+                basis[0] = F::ZERO - s_j.x * basis[0];
+                den = den * (s_i.x - s_j.x);
+            }
+            let inv = den.inverse();
+            basis.into_iter().map(|c| c * inv).collect()
+        })
+        .collect();
+
+    (0..count)
+        .map(|d| {
+            let terms: Vec<(F, &[F])> = bases
+                .iter()
+                .zip(shares.iter())
+                .map(|(b, s_i)| (b.get(d).copied().unwrap_or(F::ZERO), s_i.y.as_slice()))
+                .collect();
+            let mut coeff = vec![F::ZERO; shares.first().map_or(0, |s| s.y.len())];
+            combine(&mut coeff, &terms, F::add_scaled_slice);
+            coeff
+        })
+        .collect()
+}
+
+// Coefficients of the `order`th derivative of a polynomial of degree lower than `k` evaluated at `x`, lower degrees first,
+// so that the derivative is the sum of the polynomial coefficients multiplied by them.
+// Derivatives are only meaningful in fields of large characteristic, like prime fields, as `n! / (n - order)!` vanishes otherwise.
+#[cfg(feature = "curve25519")]
+pub(crate) fn derivative_row<F: Field>(x: F, order: usize, k: usize) -> Vec<F> {
+    let mut row = vec![F::ZERO; k];
+    let mut power = F::ONE;
+    for (n, r) in row.iter_mut().enumerate().skip(order) {
+        let factor =
+            (n - order + 1..=n).fold(F::ONE, |acc, m| acc * (0..m).map(|_| F::ONE).sum::<F>());
+        *r = factor * power;
+        power = power * x;
+    }
+    row
+}
+
+// Finds the constant term of the polynomial of degree lower than `k` whose derivatives of the given `orders` at each
+// of the `shares` `x` coordinates are their `y` values, following [Birkhoff interpolation](https://en.wikipedia.org/wiki/Birkhoff_interpolation).
+// Weights `w` such that `a_0 = sum(w_i * y_i)` solve the transposed system `M^T w = e_0`, which is consistent as long as
+// the constant term is determined by the shares, `None` being returned otherwise.
+#[cfg(feature = "curve25519")]
+pub(crate) fn interpolate_birkhoff<F: Field>(
+    shares: &[FieldShare<F>],
+    orders: &[usize],
+    k: usize,
+) -> Option<Vec<F>> {
+    let matrix: Vec<Vec<F>> = shares
+        .iter()
+        .zip(orders.iter())
+        .map(|(s, order)| derivative_row(s.x, *order, k))
+        .collect();
+    let rows = (0..k)
+        .map(|n| {
+            let mut row: Vec<F> = matrix.iter().map(|m| m[n]).collect();
+            row.push(if n == 0 { F::ONE } else { F::ZERO });
+            row
+        })
+        .collect();
+    let weights = solve(rows)?;
+
+    let terms: Vec<(F, &[F])> = weights
+        .into_iter()
+        .zip(shares.iter().map(|s| s.y.as_slice()))
+        .collect();
+    let mut result = vec![F::ZERO; shares[0].y.len()];
+    combine(&mut result, &terms, F::add_scaled_slice);
+    Some(result)
+}
+
+// Evaluates the `order`th derivative of a set of polynomials at `x`, each coefficient being given as a vector
+// with one element per polynomial, lower degrees first.
+#[cfg(feature = "curve25519")]
+pub(crate) fn evaluate_derivative<F: Field>(coeffs: &[Vec<F>], x: F, order: usize) -> Vec<F> {
+    let row = derivative_row(x, order, coeffs.len());
+    let terms: Vec<(F, &[F])> = row
+        .into_iter()
+        .zip(coeffs.iter().map(|c| c.as_slice()))
+        .collect();
+    let mut result = vec![F::ZERO; coeffs.first().map_or(0, |c| c.len())];
+    combine(&mut result, &terms, F::add_scaled_slice);
+    result
+}
+
+// Number of secret chunks processed by each task with the `rayon` feature.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNKS: usize = 1 << 16;
+
+// Successively applies `op` to `acc` with each of the `terms` constants and slices, as long as `acc`.
+// With the `rayon` feature `acc` is split in `PARALLEL_CHUNKS` long parts which are processed across cores.
+pub(crate) fn combine<F: Field>(acc: &mut [F], terms: &[(F, &[F])], op: fn(&mut [F], F, &[F])) {
+    #[cfg(feature = "rayon")]
+    acc.par_chunks_mut(PARALLEL_CHUNKS)
+        .enumerate()
+        .for_each(|(i, acc)| {
+            let start = i * PARALLEL_CHUNKS;
+            for (c, b) in terms {
+                op(acc, *c, &b[start..start + acc.len()]);
+            }
+        });
+
+    #[cfg(not(feature = "rayon"))]
+    for (c, b) in terms {
+        op(acc, *c, b);
+    }
+}
+
+// Finds the polynomial of degree lower than `k` going through all but at most `(n - k) / 2` of the `n` points,
+// following the [Berlekamp-Welch algorithm](https://en.wikipedia.org/wiki/Berlekamp%E2%80%93Welch_algorithm).
+// It solves `Q(x_i) = y_i * E(x_i)` for a monic error locator `E` of degree `e = (n - k) / 2`, then divides `Q` by `E`.
+// Coefficient degrees go from higher to lower in the returned vector order, `None` is returned if there are too many errors.
+pub(crate) fn berlekamp_welch<F: Field>(points: &[(F, F)], k: usize) -> Option<Vec<F>> {
+    let e = (points.len() - k) / 2;
+    let rows = points
+        .iter()
+        .map(|(x, y)| {
+            let powers: Vec<F> = (0..=k + e)
+                .scan(F::ONE, |p, _| {
+                    let power = *p;
+                    *p = power * *x;
+                    Some(power)
+                })
+                .collect();
+            let mut row = powers[..k + e].to_vec();
+            row.extend(powers[..e].iter().map(|p| F::ZERO - *y * *p));
+            row.push(*y * powers[e]);
+            row
+        })
+        .collect();
+
+    let solution = solve(rows)?;
+    let mut locator = solution[k + e..].to_vec();
+    locator.push(F::ONE);
+    let mut poly = divide(&solution[..k + e], &locator)?;
+    poly.reverse();
+
+    let errors = points
+        .iter()
+        .filter(|(x, y)| evaluate(std::slice::from_ref(&poly), *x)[0] != *y)
+        .count();
+    if errors <= e {
+        Some(poly)
+    } else {
+        None
+    }
+}
+
+// Solves the linear system given as augmented matrix rows by Gauss-Jordan elimination.
+// Free variables are set to zero, `None` is returned if the system is inconsistent.
+pub(crate) fn solve<F: Field>(mut rows: Vec<Vec<F>>) -> Option<Vec<F>> {
+    let cols = rows[0].len() - 1;
+    let mut pivots = Vec::with_capacity(cols);
+
+    for c in 0..cols {
+        let r = pivots.len();
+        if let Some(p) = (r..rows.len()).find(|i| rows[*i][c] != F::ZERO) {
+            rows.swap(r, p);
+            let inv = rows[r][c].inverse();
+            rows[r].iter_mut().for_each(|v| *v = *v * inv);
+            let pivot = rows[r].clone();
+            for (i, row) in rows.iter_mut().enumerate() {
+                let factor = row[c];
+                if i != r && factor != F::ZERO {
+                    row.iter_mut()
+                        .zip(pivot.iter())
+                        .for_each(|(v, p)| *v = *v - factor * *p);
+                }
+            }
+            pivots.push(c);
+        }
+    }
+
+    if rows[pivots.len()..].iter().any(|row| row[cols] != F::ZERO) {
+        return None;
+    }
+    let mut solution = vec![F::ZERO; cols];
+    for (row, c) in rows.iter().zip(pivots) {
+        solution[c] = row[cols];
+    }
+    Some(solution)
+}
+
+// Divides the polynomial `num` by the monic polynomial `den`, coefficient degrees going from lower to higher.
+// Returns `None` if the division leaves a remainder.
+fn divide<F: Field>(num: &[F], den: &[F]) -> Option<Vec<F>> {
+    let mut rem = num.to_vec();
+    let mut quotient = vec![F::ZERO; num.len() + 1 - den.len()];
+    for i in (0..quotient.len()).rev() {
+        let c = rem[i + den.len() - 1];
+        quotient[i] = c;
+        rem[i..]
+            .iter_mut()
+            .zip(den.iter())
+            .for_each(|(r, d)| *r = *r - c * *d);
+    }
+
+    if rem.iter().all(|r| *r == F::ZERO) {
+        Some(quotient)
+    } else {
+        None
+    }
+}
+
+// Generates `k` polynomial coefficients, being the last one `s` and the others randomly generated non-zero elements.
 // Coefficient degrees go from higher to lower in the returned vector order.
-pub fn random_polynomial(s: GF256, k: u8) -> Vec<GF256> {
+#[cfg(test)]
+pub(crate) fn random_polynomial<F: Field, R: Rng + ?Sized>(s: F, k: u8, rng: &mut R) -> Vec<F> {
     let k = k as usize;
     let mut poly = Vec::with_capacity(k);
-    let between = Uniform::new_inclusive(1, 255);
-    let mut rng = rand::thread_rng();
 
     for _ in 1..k {
-        poly.push(GF256(between.sample(&mut rng)));
+        poly.push(F::random(rng));
     }
     poly.push(s);
 
     poly
 }
 
+// Generates the coefficients of one polynomial of `k` coefficients per secret chunk, structure-of-arrays:
+// one vector per degree, with one element per polynomial, degrees going from higher to lower and the last one
+// being the `chunks`. Random coefficients are drawn in the same order as by `random_polynomial`, chunk after chunk,
+// but the polynomials are never stored one by one, so that large secrets stream through the cache linearly.
+pub(crate) fn random_coefficients<F: Field, R: Rng + ?Sized>(
+    chunks: Vec<F>,
+    k: u8,
+    rng: &mut R,
+) -> Vec<Vec<F>> {
+    let mut coeffs = random_terms(chunks.len(), k, rng);
+    coeffs.push(chunks);
+    coeffs
+}
+
+// Generates the `k - 1` random coefficients of `len` polynomials, structure-of-arrays as `random_coefficients`
+// but without the secret chunks.
+pub(crate) fn random_terms<F: Field, R: Rng + ?Sized>(
+    len: usize,
+    k: u8,
+    rng: &mut R,
+) -> Vec<Vec<F>> {
+    let mut coeffs: Vec<Vec<F>> = (1..k as usize).map(|_| Vec::with_capacity(len)).collect();
+    for _ in 0..len {
+        coeffs.iter_mut().for_each(|c| c.push(F::random(rng)));
+    }
+    coeffs
+}
+
+// Returns a uniformly random element, zero included unlike `Field::random`, so that masks and pads leak nothing
+pub(crate) fn random_element<F: Field, R: Rng + ?Sized>(rng: &mut R) -> F {
+    let mut bytes = vec![0u8; F::BYTES];
+    loop {
+        rng.fill_bytes(&mut bytes);
+        if let Some(e) = F::from_bytes(&bytes) {
+            bytes.zeroize();
+            return e;
+        }
+    }
+}
+
+// Iterator over the points of a set of polynomials, see `get_evaluator`.
+// Coefficients are stored by degree, so that each Horner step works over all the polynomials at once.
+// The polynomials are wiped from memory once dropped.
+pub(crate) struct Evaluator<F: Field> {
+    coeffs: Vec<Vec<F>>,
+    index: usize,
+}
+
+/// Evaluates each of the `polys` polynomials at `x` with Horner's method, coefficient degrees going from higher to lower.
+pub fn evaluate<F: Field>(polys: &[Vec<F>], x: F) -> Vec<F> {
+    polys
+        .iter()
+        .map(|p| p.iter().fold(F::ZERO, |acc, c| acc * x + *c))
+        .collect()
+}
+
+/// Same as `evaluate`, the polynomials being given by their coefficients by degree, structure-of-arrays:
+/// `coeffs[d][i]` is the coefficient of the polynomial `i` of degree `coeffs.len() - 1 - d`.
+/// Each Horner step scales the partial results and adds the next degree coefficients, in single linear passes.
+pub fn evaluate_coefficients<F: Field>(coeffs: &[Vec<F>], x: F) -> Vec<F> {
+    let mut y = coeffs.first().cloned().unwrap_or_default();
+    let terms: Vec<(F, &[F])> = coeffs.iter().skip(1).map(|c| (x, c.as_slice())).collect();
+    combine(&mut y, &terms, F::scale_add_slice);
+    y
+}
+
+impl<F: Field> Iterator for Evaluator<F> {
+    type Item = FieldShare<F>;
+
+    fn next(&mut self) -> Option<FieldShare<F>> {
+        let x = F::from_index(self.index)?;
+        self.index += 1;
+
+        Some(FieldShare {
+            x,
+            y: evaluate_coefficients(&self.coeffs, x),
+            threshold: None,
+            group: None,
+        })
+    }
+}
+
+impl<F: Field> Drop for Evaluator<F> {
+    fn drop(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
 // Returns an iterator over the points of the `polys` polynomials passed as argument.
 // Each item of the iterator is a tuple `(x, [f_1(x), f_2(x)..])` where eaxh `f_i` is the result for the ith polynomial.
-// Each polynomial corresponds to one byte chunk of the original secret.
-// The iterator will start at `x = 1` and end at `x = 255`.
-pub fn get_evaluator(polys: Vec<Vec<GF256>>) -> impl Iterator<Item = Share> {
-    (1..=u8::max_value()).map(GF256).map(move |x| Share {
-        x,
-        y: polys
-            .iter()
-            .map(|p| p.iter().fold(GF256(0), |acc, c| acc * x + *c))
-            .collect(),
-    })
+// Each polynomial corresponds to one chunk of the original secret.
+// The iterator will start at the first non-zero element of the field and end at the last one.
+pub(crate) fn get_evaluator<F: Field>(mut polys: Vec<Vec<F>>) -> Evaluator<F> {
+    let degrees = polys.first().map_or(0, |p| p.len());
+    let coeffs = (0..degrees)
+        .map(|d| polys.iter().map(|p| p[d]).collect())
+        .collect();
+
+    polys.zeroize();
+
+    coefficient_evaluator(coeffs)
+}
+
+// Same as `get_evaluator`, the polynomials being given by their `coeffs` as returned by `random_coefficients`.
+pub(crate) fn coefficient_evaluator<F: Field>(coeffs: Vec<Vec<F>>) -> Evaluator<F> {
+    Evaluator { coeffs, index: 1 }
+}
+
+// Splits the secret in field element chunks of `F::SECRET_BYTES` bytes.
+// Fields holding more than one byte per element require the secret to be padded up to a multiple
+// of the chunk length, which is done appending a `0x80` byte followed by zeroes.
+pub(crate) fn encode_secret<F: Field>(secret: &[u8]) -> Vec<F> {
+    let mut padded = secret.to_vec();
+    if F::SECRET_BYTES > 1 {
+        padded.push(0x80);
+        padded.resize(padded.len().div_ceil(F::SECRET_BYTES) * F::SECRET_BYTES, 0);
+    }
+
+    let mut bytes = vec![0u8; F::BYTES];
+    let chunks = padded
+        .chunks(F::SECRET_BYTES)
+        .map(|c| {
+            bytes[F::BYTES - F::SECRET_BYTES..].copy_from_slice(c);
+            F::from_bytes(&bytes).expect("secret chunks must be valid field elements")
+        })
+        .collect();
+
+    {
+        padded.zeroize();
+        bytes.zeroize();
+    }
+    chunks
+}
+
+// Inverse of `encode_secret`, fails if the recovered elements are not correctly padded.
+pub(crate) fn decode_secret<F: Field>(chunks: &[F]) -> Result<Vec<u8>, SharksError> {
+    let mut secret = Vec::with_capacity(chunks.len() * F::SECRET_BYTES);
+    let mut bytes = vec![0u8; F::BYTES];
+    for c in chunks {
+        c.to_bytes(&mut bytes);
+        let (high, low) = bytes.split_at(F::BYTES - F::SECRET_BYTES);
+        if high.iter().any(|b| *b != 0) {
+            return Err(SharksError::InvalidPadding);
+        }
+        secret.extend_from_slice(low);
+    }
+
+    bytes.zeroize();
+
+    if F::SECRET_BYTES > 1 {
+        while let Some(0) = secret.last() {
+            secret.pop();
+        }
+        if secret.pop() != Some(0x80) {
+            return Err(SharksError::InvalidPadding);
+        }
+    }
+
+    Ok(secret)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{get_evaluator, interpolate, random_polynomial, Share, GF256};
+    use super::{
+        berlekamp_welch, coefficient_evaluator, decode_secret, encode_secret, evaluate,
+        get_evaluator, interpolate, interpolate_at, interpolate_coefficients, interpolate_points,
+        lagrange_weights, random_coefficients, random_polynomial,
+    };
+    use crate::field::{GF256, GF65536};
+    use crate::share::{FieldShare, Share};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
 
     #[test]
     fn random_polynomial_works() {
-        let poly = random_polynomial(GF256(1), 3);
+        let poly = random_polynomial(GF256(1), 3, &mut rand::thread_rng());
         assert_eq!(poly.len(), 3);
         assert_eq!(poly[2], GF256(1));
     }
@@ -72,19 +492,160 @@ mod tests {
     #[test]
     fn evaluator_works() {
         let iter = get_evaluator(vec![vec![GF256(3), GF256(2), GF256(5)]]);
-        let values: Vec<_> = iter.take(2).map(|s| (s.x, s.y)).collect();
+        let values: Vec<_> = iter.take(2).map(|s| (s.x, s.y.clone())).collect();
         assert_eq!(
             values,
             vec![(GF256(1), vec![GF256(4)]), (GF256(2), vec![GF256(13)])]
         );
     }
 
+    #[test]
+    fn random_coefficients_works() {
+        let chunks = vec![GF256(1), GF256(2), GF256(3)];
+        let coeffs = random_coefficients(chunks.clone(), 4, &mut ChaCha20Rng::from_seed([7; 32]));
+        assert_eq!(coeffs.len(), 4);
+        assert_eq!(coeffs[3], chunks);
+
+        // Same polynomials and points as drawn one by one
+        let mut rng = ChaCha20Rng::from_seed([7; 32]);
+        let polys: Vec<Vec<GF256>> = chunks
+            .iter()
+            .map(|c| random_polynomial(*c, 4, &mut rng))
+            .collect();
+        let points: Vec<_> = coefficient_evaluator(coeffs)
+            .take(5)
+            .map(|s| s.y.clone())
+            .collect();
+        let expected: Vec<_> = get_evaluator(polys).take(5).map(|s| s.y.clone()).collect();
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn evaluator_bounds_works() {
+        assert_eq!(get_evaluator::<GF256>(vec![]).count(), 255);
+        assert_eq!(get_evaluator::<GF65536>(vec![]).count(), 65535);
+    }
+
     #[test]
     fn interpolate_works() {
-        let poly = random_polynomial(GF256(185), 10);
+        let poly = random_polynomial(GF256(185), 10, &mut rand::thread_rng());
         let iter = get_evaluator(vec![poly]);
         let shares: Vec<Share> = iter.take(10).collect();
         let root = interpolate(&shares);
-        assert_eq!(root, vec![185]);
+        assert_eq!(root, vec![GF256(185)]);
+    }
+
+    #[test]
+    fn interpolate_gf65536_works() {
+        let poly = random_polynomial(GF65536(48879), 10, &mut rand::thread_rng());
+        let iter = get_evaluator(vec![poly]);
+        let shares: Vec<FieldShare<GF65536>> = iter.skip(1000).take(10).collect();
+        let root = interpolate(&shares);
+        assert_eq!(root, vec![GF65536(48879)]);
+    }
+
+    #[test]
+    fn interpolate_many_chunks_works() {
+        // Long enough to be split in several `rayon` tasks
+        let secret: Vec<GF256> = (0..150_000).map(|i| GF256(i as u8)).collect();
+        let polys = secret
+            .iter()
+            .map(|s| random_polynomial(*s, 3, &mut rand::thread_rng()))
+            .collect();
+        let shares: Vec<Share> = get_evaluator(polys).take(3).collect();
+        assert_eq!(interpolate(&shares), secret);
+    }
+
+    #[test]
+    fn interpolate_at_works() {
+        let poly = random_polynomial(GF256(185), 4, &mut rand::thread_rng());
+        let shares: Vec<Share> = get_evaluator(vec![poly]).take(10).collect();
+        assert_eq!(interpolate_at(&shares[..4], shares[9].x), shares[9].y);
+        assert_eq!(interpolate_at(&shares[..4], shares[2].x), shares[2].y);
+    }
+
+    #[test]
+    fn interpolate_coefficients_works() {
+        let poly = random_polynomial(GF256(185), 4, &mut rand::thread_rng());
+        let shares: Vec<Share> = get_evaluator(vec![poly.clone()]).take(6).collect();
+        let coeffs: Vec<GF256> = interpolate_coefficients(&shares, 5)
+            .into_iter()
+            .map(|c| c[0])
+            .collect();
+        let mut expected = poly;
+        expected.reverse();
+        expected.push(GF256(0));
+        assert_eq!(coeffs, expected);
+    }
+
+    #[test]
+    fn berlekamp_welch_works() {
+        let poly = random_polynomial(GF256(185), 3, &mut rand::thread_rng());
+        let mut points: Vec<(GF256, GF256)> = get_evaluator(vec![poly.clone()])
+            .take(7)
+            .map(|s| (s.x, s.y[0]))
+            .collect();
+        assert_eq!(berlekamp_welch(&points, 3), Some(poly.clone()));
+
+        // Up to (7 - 3) / 2 = 2 errors can be corrected
+        points[1].1 = points[1].1 + GF256(1);
+        points[4].1 = points[4].1 + GF256(3);
+        assert_eq!(berlekamp_welch(&points, 3), Some(poly.clone()));
+        assert_eq!(berlekamp_welch(&points[..6], 3), None);
+        points[5].1 = points[5].1 + GF256(7);
+        assert_ne!(berlekamp_welch(&points, 3), Some(poly));
+    }
+
+    #[test]
+    fn berlekamp_welch_gf65536_works() {
+        let poly = random_polynomial(GF65536(48879), 4, &mut rand::thread_rng());
+        let mut points: Vec<(GF65536, GF65536)> = get_evaluator(vec![poly.clone()])
+            .skip(1000)
+            .take(9)
+            .map(|s| (s.x, s.y[0]))
+            .collect();
+        points[0].1 = GF65536(1);
+        points[8].1 = GF65536(2);
+        assert_eq!(berlekamp_welch(&points, 4), Some(poly));
+    }
+
+    #[test]
+    fn secret_encoding_works() {
+        assert_eq!(
+            encode_secret::<GF256>(&[1, 2, 3]),
+            vec![GF256(1), GF256(2), GF256(3)]
+        );
+        assert_eq!(
+            encode_secret::<GF65536>(&[1, 2, 3]),
+            vec![GF65536(0x0102), GF65536(0x0380)]
+        );
+        assert_eq!(
+            encode_secret::<GF65536>(&[1, 2]),
+            vec![GF65536(0x0102), GF65536(0x8000)]
+        );
+
+        for len in 0..5 {
+            let secret: Vec<u8> = (0..len).collect();
+            let chunks = encode_secret::<GF65536>(&secret);
+            assert_eq!(decode_secret(&chunks).unwrap(), secret);
+        }
+        assert!(decode_secret(&[GF65536(0)]).is_err());
+    }
+
+    #[test]
+    fn interpolate_points_works() {
+        let xs = [GF256(1), GF256(2), GF256(3)];
+        let polys = vec![vec![GF256(7), GF256(3), GF256(1)]];
+        let ys: Vec<Vec<GF256>> = xs.iter().map(|x| evaluate(&polys, *x)).collect();
+        let ys: Vec<&[GF256]> = ys.iter().map(|y| y.as_slice()).collect();
+        for x in [0, 4, 200].iter() {
+            assert_eq!(
+                interpolate_points(&xs, &ys, GF256(*x)),
+                evaluate(&polys, GF256(*x))
+            );
+        }
+        let weights = lagrange_weights(&xs, GF256(2));
+        assert_eq!(weights, vec![GF256(0), GF256(1), GF256(0)]);
+        assert_eq!(interpolate_points::<GF256>(&[], &[], GF256(0)), vec![]);
     }
 }
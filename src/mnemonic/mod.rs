@@ -0,0 +1,180 @@
+// Encoding of shares as sentences of words from the BIP-39 English wordlist, for paper backups.
+//
+// Each word holds 11 bits of the share bytes, zero padded at the end. The last word holds a flag
+// telling whether the padding spans a whole byte, followed by the first 10 bits of the SHA-256
+// digest of the share bytes, so transcription mistakes are detected when parsing the mnemonic.
+
+use std::convert::TryFrom;
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use crate::error::SharksError;
+use crate::field::Field;
+use crate::share::FieldShare;
+
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+const WORD_BITS: usize = 11;
+const CHECKSUM_BITS: usize = 10;
+
+fn checksum(bytes: &[u8]) -> u16 {
+    let digest = Sha256::digest(bytes);
+    u16::from_be_bytes([digest[0], digest[1]]) >> (16 - CHECKSUM_BITS)
+}
+
+impl<F: Field> FieldShare<F> {
+    /// Encodes the share bytes as a sentence of words from the BIP-39 English wordlist,
+    /// ending with a checksum word. Far less error prone than raw bytes when written down on paper.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(3);
+    /// let shares: Vec<String> = sharks.dealer(&[1, 2, 3]).take(3).map(|s| s.to_mnemonic()).collect();
+    /// // Write the words down and recover the shares later on
+    /// let shares: Vec<Share> = shares.iter().map(|m| Share::from_mnemonic(m).unwrap()).collect();
+    /// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+    pub fn to_mnemonic(&self) -> String {
+        let mut bytes = Vec::from(self);
+        let wordlist: Vec<&str> = WORDLIST.lines().collect();
+
+        let data_words = (bytes.len() * 8).div_ceil(WORD_BITS);
+        let padding = data_words * WORD_BITS - bytes.len() * 8;
+        let mut words = Vec::with_capacity(data_words + 1);
+        let (mut acc, mut bits) = (0u32, 0);
+        for byte in bytes.iter() {
+            acc = acc << 8 | u32::from(*byte);
+            bits += 8;
+            if bits >= WORD_BITS {
+                bits -= WORD_BITS;
+                words.push(wordlist[(acc >> bits) as usize]);
+                acc &= (1 << bits) - 1;
+            }
+        }
+        if bits > 0 {
+            words.push(wordlist[(acc << (WORD_BITS - bits)) as usize]);
+        }
+
+        let last = usize::from(padding >= 8) << CHECKSUM_BITS | checksum(&bytes) as usize;
+        words.push(wordlist[last]);
+
+        bytes.zeroize();
+        words.join(" ")
+    }
+
+    /// Parses a share from a mnemonic obtained with `to_mnemonic`.
+    /// An `Err` is returned if a word is not in the wordlist, the checksum does not match
+    /// or the decoded bytes are not a valid share.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<FieldShare<F>, SharksError> {
+        let wordlist: Vec<&str> = WORDLIST.lines().collect();
+        let words = mnemonic
+            .split_whitespace()
+            .map(|w| {
+                wordlist
+                    .binary_search(&w.to_lowercase().as_str())
+                    .map(|i| i as u32)
+                    .map_err(|_| SharksError::InvalidWord)
+            })
+            .collect::<Result<Vec<u32>, SharksError>>()?;
+
+        let (last, data) = words.split_last().ok_or(SharksError::EmptyShare)?;
+        let length = ((data.len() * WORD_BITS) / 8)
+            .checked_sub((*last >> CHECKSUM_BITS) as usize)
+            .filter(|l| *l > 0 && (l * 8).div_ceil(WORD_BITS) == data.len())
+            .ok_or(SharksError::InvalidLength)?;
+
+        let mut bytes = Vec::with_capacity(length + 1);
+        let (mut acc, mut bits) = (0u32, 0);
+        for word in data {
+            acc = acc << WORD_BITS | word;
+            bits += WORD_BITS;
+            while bits >= 8 {
+                bits -= 8;
+                bytes.push((acc >> bits) as u8);
+                acc &= (1 << bits) - 1;
+            }
+        }
+        let valid_padding = acc == 0 && bytes[length..].iter().all(|b| *b == 0);
+        bytes.truncate(length);
+
+        let share = if !valid_padding {
+            Err(SharksError::InvalidPadding)
+        } else if checksum(&bytes) != (*last & ((1 << CHECKSUM_BITS) - 1)) as u16 {
+            Err(SharksError::InvalidChecksum)
+        } else {
+            FieldShare::try_from(bytes.as_slice())
+        };
+
+        bytes.zeroize();
+        share
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldShare, Share, Sharks, SharksError, GF256, GF65536};
+
+    #[test]
+    fn mnemonic_roundtrip_works() {
+        // Cover every padding length, including those spanning a whole byte
+        for len in 1..=12 {
+            let secret: Vec<u8> = (0..len).collect();
+            for share in Sharks(2).dealer(&secret).take(3) {
+                let mnemonic = share.to_mnemonic();
+                let parsed = Share::from_mnemonic(&mnemonic).unwrap();
+                assert_eq!(Vec::from(&parsed), Vec::from(&share));
+            }
+        }
+
+        let share: FieldShare<GF65536> = Sharks(2).dealer_in(&[1, 2, 3]).nth(1000).unwrap();
+        let parsed = FieldShare::<GF65536>::from_mnemonic(&share.to_mnemonic()).unwrap();
+        assert_eq!(parsed.x, share.x);
+        assert_eq!(parsed.y, share.y);
+    }
+
+    #[test]
+    fn mnemonic_format_works() {
+        let share = Share {
+            x: GF256(1),
+            y: vec![GF256(2), GF256(3)],
+            threshold: None,
+            group: None,
+        };
+        // 0x010203 followed by 9 zero padding bits, then the checksum word
+        let mnemonic = share.to_mnemonic();
+        let words: Vec<&str> = mnemonic.split(' ').collect();
+        assert_eq!(words[..3], ["absurd", "avoid", "scale"][..]);
+        assert_eq!(words.len(), 4);
+        assert_eq!(
+            Share::from_mnemonic(&mnemonic.to_uppercase()).unwrap().y,
+            share.y
+        );
+    }
+
+    #[test]
+    fn invalid_mnemonic_err() {
+        let mnemonic = Sharks(2)
+            .dealer(&[1, 2, 3, 4])
+            .next()
+            .unwrap()
+            .to_mnemonic();
+        let mut words: Vec<&str> = mnemonic.split(' ').collect();
+
+        assert_eq!(
+            Share::from_mnemonic("").err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            Share::from_mnemonic("sharks").err(),
+            Some(SharksError::InvalidWord)
+        );
+        assert!(Share::from_mnemonic(&words[1..].join(" ")).is_err());
+
+        words[0] = if words[0] == "zoo" { "abandon" } else { "zoo" };
+        assert_eq!(
+            Share::from_mnemonic(&words.join(" ")).err(),
+            Some(SharksError::InvalidChecksum)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+// Sharing of several independent secrets at once, each participant holding a single combined share.
+// The secrets are shared side by side over the same `x` coordinates, and the combined share records
+// the length of each of them, so that any single secret can be recovered without the others.
+
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+use zeroize::Zeroize;
+
+/// A combined share of several secrets, see `Sharks::dealer_multi`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct MultiShare {
+    /// Length of each of the secrets, in bytes.
+    pub lengths: Vec<u32>,
+    /// Share of all the secrets, one after another.
+    pub share: Share,
+}
+
+impl MultiShare {
+    // Range of the `y` values belonging to the secret at `index`
+    fn range(&self, index: usize) -> Option<Range<usize>> {
+        let start: usize = self.lengths.get(..index)?.iter().map(|l| *l as usize).sum();
+        Some(start..start + *self.lengths.get(index)? as usize)
+    }
+}
+
+/// Obtains a byte vector from a `MultiShare` instance: `count || lengths || share`,
+/// being the count and each of the lengths 4 bytes big endian integers.
+impl From<&MultiShare> for Vec<u8> {
+    fn from(s: &MultiShare) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 * s.lengths.len() + s.share.y.len() + 5);
+        bytes.extend_from_slice(&(s.lengths.len() as u32).to_be_bytes());
+        s.lengths
+            .iter()
+            .for_each(|l| bytes.extend_from_slice(&l.to_be_bytes()));
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `MultiShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for MultiShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<MultiShare, Self::Error> {
+        if s.len() < 4 {
+            return Err(SharksError::InvalidLength);
+        }
+        let count = u32::from_be_bytes([s[0], s[1], s[2], s[3]]) as usize;
+        let header = count
+            .checked_mul(4)
+            .and_then(|l| l.checked_add(4))
+            .filter(|l| *l <= s.len())
+            .ok_or(SharksError::InvalidLength)?;
+
+        let lengths: Vec<u32> = s[4..header]
+            .chunks(4)
+            .map(|l| u32::from_be_bytes([l[0], l[1], l[2], l[3]]))
+            .collect();
+        let share = Share::try_from(&s[header..])?;
+        if lengths.iter().map(|l| *l as usize).sum::<usize>() != share.y.len() {
+            return Err(SharksError::InvalidLength);
+        }
+        Ok(MultiShare { lengths, share })
+    }
+}
+
+impl Sharks {
+    /// Given several `secrets` byte slices, returns an `Iterator` along new combined shares of all of them.
+    /// Each participant holds a single share, from which any or all of the secrets can be recovered
+    /// with `recover_multi` and `recover_multi_at`. Shares are as large as all the secrets together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` secrets, or any of them is longer than `u32::MAX` bytes.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, MultiShare };
+    /// # let sharks = Sharks(3);
+    /// let shares: Vec<MultiShare> = sharks.dealer_multi(&[&[1, 2], &[3, 4, 5]]).take(5).collect();
+    /// assert_eq!(sharks.recover_multi(&shares).unwrap(), vec![vec![1, 2], vec![3, 4, 5]]);
+    /// assert_eq!(sharks.recover_multi_at(&shares[2..], 1).unwrap(), vec![3, 4, 5]);
+    /// ```
+    pub fn dealer_multi(&self, secrets: &[&[u8]]) -> impl Iterator<Item = MultiShare> {
+        u32::try_from(secrets.len()).expect("Too many secrets");
+        let lengths: Vec<u32> = secrets
+            .iter()
+            .map(|s| u32::try_from(s.len()).expect("Secret too long"))
+            .collect();
+        let mut secret = secrets.concat();
+        let shares = self.dealer(&secret);
+
+        secret.zeroize();
+
+        shares.map(move |share| MultiShare {
+            lengths: lengths.clone(),
+            share,
+        })
+    }
+
+    /// Given many `secrets` byte slices, returns an `Iterator` along the bundles of each participant,
+    /// holding a regular share of each of the secrets, in the same order. The secrets are split in a single pass
+    /// as with `dealer_multi`, amortizing the polynomials generation and evaluation over all of them,
+    /// and each of them can then be recovered on its own with `recover`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(2);
+    /// let bundles: Vec<Vec<Share>> = sharks.dealer_batch(&[&[1, 2], &[3, 4, 5]]).take(3).collect();
+    /// let shares: Vec<&Share> = bundles.iter().map(|b| &b[1]).collect();
+    /// assert_eq!(sharks.recover(shares).unwrap(), vec![3, 4, 5]);
+    /// ```
+    pub fn dealer_batch(&self, secrets: &[&[u8]]) -> impl Iterator<Item = Vec<Share>> {
+        let lengths: Vec<usize> = secrets.iter().map(|s| s.len()).collect();
+        let mut secret = secrets.concat();
+        let shares = self.dealer(&secret);
+
+        secret.zeroize();
+
+        shares.map(move |share| {
+            let mut start = 0;
+            lengths
+                .iter()
+                .map(|l| {
+                    start += l;
+                    Share {
+                        x: share.x,
+                        y: share.y[start - l..start].to_vec(),
+                        threshold: share.threshold,
+                        group: share.group,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Given an iterable collection of combined shares, recovers all the original secrets in order.
+    /// An `Err` is returned if the shares hold different secret lengths, or would fail to `recover`.
+    pub fn recover_multi<'a, T>(&self, shares: T) -> Result<Vec<Vec<u8>>, SharksError>
+    where
+        T: IntoIterator<Item = &'a MultiShare>,
+        T::IntoIter: Iterator<Item = &'a MultiShare>,
+    {
+        let shares = self.validate_multi(shares)?;
+        let secret = self.recover(shares.iter().map(|s| &s.share))?;
+        Ok((0..shares[0].lengths.len())
+            .filter_map(|i| shares[0].range(i))
+            .map(|r| secret[r].to_vec())
+            .collect())
+    }
+
+    /// Given an iterable collection of combined shares, recovers only the secret at `index`.
+    /// The other secrets are not recovered, their shares being left aside.
+    /// An `Err` is returned if the index is out of range, or `recover_multi` would fail.
+    pub fn recover_multi_at<'a, T>(&self, shares: T, index: usize) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a MultiShare>,
+        T::IntoIter: Iterator<Item = &'a MultiShare>,
+    {
+        let shares = self.validate_multi(shares)?;
+        let range = shares[0].range(index).ok_or(SharksError::InvalidIndex)?;
+        let parts: Vec<Share> = shares
+            .iter()
+            .map(|s| Share {
+                x: s.share.x,
+                y: s.share.y[range.clone()].to_vec(),
+                threshold: s.share.threshold,
+                group: s.share.group,
+            })
+            .collect();
+        self.recover(&parts)
+    }
+
+    // Checks there is at least one share and all of them hold the same secret lengths
+    fn validate_multi<'a, T>(&self, shares: T) -> Result<Vec<&'a MultiShare>, SharksError>
+    where
+        T: IntoIterator<Item = &'a MultiShare>,
+    {
+        let shares: Vec<&MultiShare> = shares.into_iter().collect();
+        let first = shares.first().ok_or(SharksError::NotEnoughShares {
+            needed: self.0 as usize,
+            got: 0,
+            provided: 0,
+        })?;
+        if shares.iter().any(|s| s.lengths != first.lengths) {
+            return Err(SharksError::MixedShares);
+        }
+        Ok(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiShare;
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn multi_integration_works() {
+        let sharks = Sharks(3);
+        let secrets: Vec<Vec<u8>> = vec![(0..10).collect(), vec![], (100..150).collect()];
+        let refs: Vec<&[u8]> = secrets.iter().map(|s| s.as_slice()).collect();
+        let shares: Vec<MultiShare> = sharks.dealer_multi(&refs).take(5).collect();
+        assert_eq!(shares[0].share.y.len(), 60);
+
+        assert_eq!(sharks.recover_multi(&shares[1..4]).unwrap(), secrets);
+        for (i, secret) in secrets.iter().enumerate() {
+            assert_eq!(&sharks.recover_multi_at(&shares[2..], i).unwrap(), secret);
+        }
+
+        let shares: Vec<MultiShare> = shares
+            .iter()
+            .map(|s| MultiShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_multi(&shares).unwrap(), secrets);
+    }
+
+    #[test]
+    fn batch_integration_works() {
+        let sharks = Sharks(3);
+        let secrets: Vec<Vec<u8>> = (0..100).map(|i| vec![i; i as usize % 7 + 1]).collect();
+        let refs: Vec<&[u8]> = secrets.iter().map(|s| s.as_slice()).collect();
+        let bundles: Vec<Vec<Share>> = sharks.dealer_batch(&refs).take(5).collect();
+        assert_eq!(bundles[4].len(), 100);
+        assert_eq!(bundles[4][10].x, GF256(5));
+        assert_eq!(bundles[4][10].threshold(), Some(3));
+
+        for (i, secret) in secrets.iter().enumerate() {
+            let shares: Vec<&Share> = bundles[1..4].iter().map(|b| &b[i]).collect();
+            assert_eq!(&sharks.recover(shares).unwrap(), secret);
+        }
+        assert_eq!(sharks.dealer_batch(&[]).next().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn multi_errors_work() {
+        let sharks = Sharks(2);
+        let mut shares: Vec<MultiShare> = sharks.dealer_multi(&[&[1], &[2, 3]]).take(3).collect();
+        assert_eq!(
+            sharks.recover_multi_at(&shares, 2).err(),
+            Some(SharksError::InvalidIndex)
+        );
+        assert_eq!(
+            sharks.recover_multi(&shares[..1]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+        shares[0].lengths = vec![2, 1];
+        assert_eq!(
+            sharks.recover_multi(&shares).err(),
+            Some(SharksError::MixedShares)
+        );
+    }
+
+    #[test]
+    fn multi_share_bytes_err() {
+        assert_eq!(
+            MultiShare::try_from(&[0, 0, 0][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            MultiShare::try_from(&[0, 0, 0, 2, 0, 0, 0, 1][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            MultiShare::try_from(&[0, 0, 0, 1, 0, 0, 0, 2, 1, 2][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert!(MultiShare::try_from(&[0, 0, 0, 1, 0, 0, 0, 2, 1, 2, 3][..]).is_ok());
+    }
+}
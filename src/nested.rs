@@ -0,0 +1,271 @@
+// Nested sharing, shares being split again into sub-shares, such as a share held by an officer split among deputies.
+// Each nested share records the thresholds and `x` coordinates of the shares it descends from, so that recovery
+// rebuilds the tree bottom up: sub-shares of a same share recover it, until the top level shares recover the secret.
+
+use std::convert::TryFrom;
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::GF256;
+use super::share::Share;
+use super::Sharks;
+
+// Threshold and `x` coordinate of a share others were split from
+type Level = (u8, GF256);
+
+/// A share which may have been split from another share, see `Sharks::split_nested`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestedShare {
+    /// Thresholds and `x` coordinates of the shares this one descends from, the top level one first.
+    /// Top level shares have an empty path.
+    pub path: Vec<(u8, GF256)>,
+    /// The share itself, its threshold being the one it was split with.
+    pub share: Share,
+}
+
+impl NestedShare {
+    /// Returns the nesting depth of the share, 0 for top level shares.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+}
+
+/// Wraps a top level share.
+impl From<Share> for NestedShare {
+    fn from(share: Share) -> NestedShare {
+        NestedShare {
+            path: Vec::new(),
+            share,
+        }
+    }
+}
+
+/// Obtains a byte vector from a `NestedShare` instance: `depth || (threshold || x)... || threshold || x || y`,
+/// the path being serialized top level first and a missing share threshold as 0.
+impl From<&NestedShare> for Vec<u8> {
+    fn from(s: &NestedShare) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + 2 * s.path.len() + 1 + s.share.y.len());
+        bytes.push(s.path.len() as u8);
+        for (threshold, x) in s.path.iter() {
+            bytes.extend_from_slice(&[*threshold, x.0]);
+        }
+        bytes.push(s.share.threshold.unwrap_or(0));
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `NestedShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for NestedShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<NestedShare, Self::Error> {
+        let (depth, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        let depth = *depth as usize;
+        if s.len() < 2 * depth + 1 {
+            return Err(SharksError::InvalidLength);
+        }
+        let (path, s) = s.split_at(2 * depth);
+        let path = path
+            .chunks(2)
+            .map(|level| match level {
+                [0, _] => Err(SharksError::InvalidThreshold),
+                [_, 0] => Err(SharksError::ZeroShareX),
+                [threshold, x] => Ok((*threshold, GF256(*x))),
+                _ => unreachable!(),
+            })
+            .collect::<Result<Vec<Level>, SharksError>>()?;
+        let mut share = Share::try_from(&s[1..])?;
+        share.threshold = Some(s[0]).filter(|t| *t > 0);
+        Ok(NestedShare { path, share })
+    }
+}
+
+impl Sharks {
+    /// Splits a nested `share` into sub-shares, `Sharks` being the threshold of the sub-shares,
+    /// returning an `Iterator` along them. Top level shares are converted with `NestedShare::from`.
+    /// An `Err` is returned if the share does not hold its threshold, or it is nested 255 levels deep already.
+    ///
+    /// Example, the share of an officer being split 2-of-3 among its deputies:
+    /// ```
+    /// # use sharks::{ Sharks, NestedShare };
+    /// let sharks = Sharks(2);
+    /// let shares: Vec<NestedShare> = sharks.dealer(&[1, 2, 3]).take(3).map(NestedShare::from).collect();
+    /// let deputies: Vec<NestedShare> = Sharks(2).split_nested(&shares[0]).unwrap().take(3).collect();
+    /// // Two deputies stand in for the officer
+    /// let collected = [deputies[0].clone(), deputies[2].clone(), shares[1].clone()];
+    /// assert_eq!(sharks.recover_nested(&collected).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn split_nested(
+        &self,
+        share: &NestedShare,
+    ) -> Result<impl Iterator<Item = NestedShare>, SharksError> {
+        let threshold = share.share.threshold.ok_or(SharksError::InvalidThreshold)?;
+        if share.path.len() >= 255 {
+            return Err(SharksError::InvalidLength);
+        }
+        let mut path = share.path.clone();
+        path.push((threshold, share.share.x));
+        Ok(self
+            .dealer(GF256::as_bytes(&share.share.y))
+            .map(move |share| NestedShare {
+                path: path.clone(),
+                share,
+            }))
+    }
+
+    /// Given an iterable collection of nested shares, recovers the original secret. The sub-shares of a same share
+    /// recover it with the threshold they were split with, from the deepest level up to the top level shares,
+    /// which recover the secret with the `Sharks` threshold. Shares that could not be recovered from too few
+    /// sub-shares are skipped, an `Err` being returned only if the secret cannot be recovered in the end,
+    /// or if sub-shares are inconsistent.
+    pub fn recover_nested<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a NestedShare>,
+        T::IntoIter: Iterator<Item = &'a NestedShare>,
+    {
+        let mut shares: Vec<NestedShare> = shares.into_iter().cloned().collect();
+        while let Some(depth) = shares.iter().map(|s| s.depth()).max().filter(|d| *d > 0) {
+            let (deepest, mut upper): (Vec<NestedShare>, Vec<NestedShare>) =
+                shares.into_iter().partition(|s| s.depth() == depth);
+
+            // Sub-shares of a same share have the same path
+            let mut groups: Vec<(Vec<Level>, Vec<Share>)> = Vec::new();
+            for s in deepest {
+                match groups.iter_mut().find(|(path, _)| *path == s.path) {
+                    Some((_, group)) => group.push(s.share),
+                    None => groups.push((s.path, vec![s.share])),
+                }
+            }
+
+            for (mut path, group) in groups {
+                let Some((threshold, x)) = path.pop() else {
+                    continue;
+                };
+                if upper.iter().any(|s| s.path == path && s.share.x == x) {
+                    continue;
+                }
+                let sharks = Sharks(group[0].threshold.ok_or(SharksError::InvalidThreshold)?);
+                let mut y = match sharks.recover(&group) {
+                    Ok(y) => y,
+                    Err(SharksError::NotEnoughShares { .. }) => continue,
+                    Err(e) => return Err(e),
+                };
+                upper.push(NestedShare {
+                    path,
+                    share: Share {
+                        x,
+                        y: GF256::as_elements(&y).to_vec(),
+                        threshold: Some(threshold),
+                        group: None,
+                    },
+                });
+                y.zeroize();
+            }
+            shares = upper;
+        }
+        self.recover(shares.iter().map(|s| &s.share))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NestedShare;
+    use crate::{Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn nested_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..20).collect();
+        let shares: Vec<NestedShare> = sharks
+            .dealer(&secret)
+            .take(4)
+            .map(NestedShare::from)
+            .collect();
+        // The first share is split 2-of-3, and one of these sub-shares 3-of-4 again
+        let subshares: Vec<NestedShare> = Sharks(2)
+            .split_nested(&shares[0])
+            .unwrap()
+            .take(3)
+            .collect();
+        let deeper: Vec<NestedShare> = Sharks(3)
+            .split_nested(&subshares[1])
+            .unwrap()
+            .take(4)
+            .collect();
+        assert_eq!(deeper[0].depth(), 2);
+        assert_eq!(
+            deeper[0].path,
+            vec![(3, shares[0].share.x), (2, subshares[1].share.x)]
+        );
+
+        let collected: Vec<NestedShare> = vec![
+            deeper[0].clone(),
+            deeper[2].clone(),
+            deeper[3].clone(),
+            subshares[2].clone(),
+            shares[2].clone(),
+            shares[3].clone(),
+        ];
+        assert_eq!(sharks.recover_nested(&collected).unwrap(), secret);
+
+        // Sub-shares of a share already collected, or too few to recover their share, are skipped
+        let collected = vec![
+            shares[0].clone(),
+            subshares[0].clone(),
+            deeper[1].clone(),
+            shares[1].clone(),
+            shares[3].clone(),
+        ];
+        assert_eq!(sharks.recover_nested(&collected).unwrap(), secret);
+
+        // Serialization records the nesting
+        let parsed: Vec<NestedShare> = collected
+            .iter()
+            .map(|s| NestedShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(parsed, collected);
+        assert_eq!(parsed[1].share.threshold(), Some(2));
+        assert_eq!(sharks.recover_nested(&parsed).unwrap(), secret);
+    }
+
+    #[test]
+    fn nested_err() {
+        let sharks = Sharks(2);
+        let shares: Vec<NestedShare> = sharks
+            .dealer(&[1, 2, 3])
+            .take(2)
+            .map(NestedShare::from)
+            .collect();
+        let subshares: Vec<NestedShare> = Sharks(2)
+            .split_nested(&shares[0])
+            .unwrap()
+            .take(2)
+            .collect();
+        assert_eq!(
+            sharks.recover_nested(&[subshares[0].clone(), shares[1].clone()]),
+            Err(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+
+        let untracked =
+            NestedShare::from(Share::try_from(Vec::from(&shares[0].share).as_slice()).unwrap());
+        assert!(sharks.split_nested(&untracked).is_err());
+
+        assert_eq!(NestedShare::try_from(&[][..]), Err(SharksError::EmptyShare));
+        assert_eq!(
+            NestedShare::try_from(&[2, 2, 1, 2][..]),
+            Err(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            NestedShare::try_from(&[1, 2, 0, 2, 1, 5][..]),
+            Err(SharksError::ZeroShareX)
+        );
+    }
+}
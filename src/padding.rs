@@ -0,0 +1,107 @@
+// Length hiding padding, so that shares reveal the length of the secret only up to a block size.
+// The secret is followed by a 0x80 byte and as many zeros as needed to reach a multiple of the block size
+// (ISO/IEC 7816-4 padding), which is unambiguously removed after recovery whatever the secret bytes.
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+// Pads the secret to the next multiple of `block` bytes, at least one byte being always added
+pub(crate) fn pad(secret: &[u8], block: usize) -> Result<Vec<u8>, SharksError> {
+    if block == 0 {
+        return Err(SharksError::InvalidLength);
+    }
+    let mut padded = Vec::with_capacity((secret.len() / block + 1) * block);
+    padded.extend_from_slice(secret);
+    padded.push(0x80);
+    padded.resize(padded.len().div_ceil(block) * block, 0);
+    Ok(padded)
+}
+
+// Inverse of `pad`, fails if the padding is missing
+pub(crate) fn unpad(mut padded: Vec<u8>) -> Result<Vec<u8>, SharksError> {
+    while let Some(0) = padded.last() {
+        padded.pop();
+    }
+    if padded.pop() != Some(0x80) {
+        padded.zeroize();
+        return Err(SharksError::InvalidPadding);
+    }
+    Ok(padded)
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares of it padded to a multiple of `block` bytes,
+    /// so that the shares length only reveals the secret length rounded up to the block size.
+    /// Secrets which are already a multiple of the block size are padded with a whole block.
+    /// The secret is recovered without its padding with `recover_padded`.
+    /// An `Err` is returned if `block` is zero.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(2);
+    /// let short: Vec<Share> = sharks.dealer_padded(b"hunter2", 32).unwrap().take(2).collect();
+    /// let long: Vec<Share> = sharks.dealer_padded(b"correct horse battery", 32).unwrap().take(2).collect();
+    /// assert_eq!(short[0].y.len(), long[0].y.len());
+    /// assert_eq!(sharks.recover_padded(&short).unwrap(), b"hunter2");
+    /// ```
+    pub fn dealer_padded(
+        &self,
+        secret: &[u8],
+        block: usize,
+    ) -> Result<impl Iterator<Item = Share>, SharksError> {
+        let mut padded = pad(secret, block)?;
+        let dealer = self.dealer(&padded);
+        padded.zeroize();
+        Ok(dealer)
+    }
+
+    /// Given an iterable collection of shares dealt by `dealer_padded`, recovers the original secret without its padding.
+    /// An `Err` is returned in the same cases as `recover`, or if the recovered secret is not correctly padded.
+    pub fn recover_padded<'a, S, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        unpad(self.recover(shares)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pad, unpad};
+    use crate::{Share, Sharks, SharksError};
+
+    #[test]
+    fn pad_works() {
+        assert_eq!(pad(&[1, 2, 3], 4), Ok(vec![1, 2, 3, 0x80]));
+        assert_eq!(pad(&[1, 2, 3, 4], 4), Ok(vec![1, 2, 3, 4, 0x80, 0, 0, 0]));
+        assert_eq!(pad(&[], 2), Ok(vec![0x80, 0]));
+        assert_eq!(pad(&[0x80, 0], 1), Ok(vec![0x80, 0, 0x80]));
+        assert_eq!(unpad(vec![0x80, 0, 0x80]), Ok(vec![0x80, 0]));
+        assert_eq!(unpad(vec![1, 0, 0]), Err(SharksError::InvalidPadding));
+        assert_eq!(unpad(vec![]), Err(SharksError::InvalidPadding));
+        assert_eq!(pad(&[1], 0), Err(SharksError::InvalidLength));
+    }
+
+    #[test]
+    fn padded_integration_works() {
+        let sharks = Sharks(3);
+        for len in 0..40 {
+            let secret = vec![0u8; len];
+            let shares: Vec<Share> = sharks.dealer_padded(&secret, 16).unwrap().take(3).collect();
+            assert_eq!(shares[0].y.len(), (len / 16 + 1) * 16);
+            assert_eq!(sharks.recover_padded(&shares), Ok(secret));
+        }
+
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+        assert_eq!(
+            sharks.recover_padded(&shares),
+            Err(SharksError::InvalidPadding)
+        );
+    }
+}
@@ -0,0 +1,299 @@
+// Partitioning of a pool of shares mixing several splits into groups of shares of the same secret.
+// Shares are first bucketed by group label, threshold and length, which shares of a split always agree on.
+// Labelled buckets are groups as such. Unlabelled ones are split by consistency probing: shares of the same secret
+// lie on the same polynomials, so a share joins the group of `threshold` seed shares if it lies on the polynomials
+// they interpolate. As any `threshold` shares are consistent, unlabelled groups are only formed when at least one
+// more share confirms them, the others being reported as orphans.
+// Seeds are searched among all the combinations of shares of a bucket, so probing is meant for small pools.
+
+use std::fmt;
+
+use super::envelope::ShareEnvelope;
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::Share;
+use super::Sharks;
+
+/// Shares of a single secret found by `Sharks::partition`, and the secret recovered from them.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShareGroup {
+    /// Label of the envelopes of the group, if they were labelled.
+    pub label: Option<String>,
+    /// Threshold the secret was split with.
+    pub threshold: u8,
+    /// Indices of the shares of the group in the pool, identical copies included.
+    pub members: Vec<usize>,
+    /// Secret recovered from the shares, or the reason it could not be.
+    pub secret: Result<Vec<u8>, SharksError>,
+}
+
+/// Prints the group members, the recovered secret being redacted.
+impl fmt::Debug for ShareGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShareGroup")
+            .field("label", &self.label)
+            .field("threshold", &self.threshold)
+            .field("members", &self.members)
+            .field(
+                "secret",
+                &self
+                    .secret
+                    .as_ref()
+                    .map(|s| format!("<{} redacted>", s.len())),
+            )
+            .finish()
+    }
+}
+
+/// Groups of shares of the same secret found in a pool by `Sharks::partition`, and the shares left out of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    /// Groups found, ordered by their first member.
+    pub groups: Vec<ShareGroup>,
+    /// Indices of the shares which could not be assigned to a group, in the order of the pool.
+    pub orphans: Vec<usize>,
+}
+
+// Whether the share lies on the polynomials interpolated by the `seed` shares, at the `xs` coordinates
+fn lies_on(seed: &[&Share], xs: &[GF256], share: &Share) -> bool {
+    let weights = math::lagrange_weights(xs, share.x);
+    (0..share.y.len()).all(|j| {
+        let predicted = weights
+            .iter()
+            .zip(seed.iter())
+            .fold(GF256::ZERO, |acc, (w, s)| acc + *w * s.y[j]);
+        predicted == share.y[j]
+    })
+}
+
+// Advances `c` to the next combination of as many indices among `n`, in lexicographic order
+fn next_combination(c: &mut [usize], n: usize) -> bool {
+    let k = c.len();
+    match (0..k).rev().find(|i| c[*i] < n - k + i) {
+        Some(i) => {
+            c[i] += 1;
+            for j in i + 1..k {
+                c[j] = c[j - 1] + 1;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+// Finds a group of the first share of `bucket` with `threshold - 1` others confirmed by at least one more,
+// returning the positions of its members in the bucket
+fn probe(pool: &[&Share], bucket: &[usize], threshold: usize) -> Option<Vec<usize>> {
+    let first = pool[bucket[0]];
+    let others: Vec<usize> = (1..bucket.len())
+        .filter(|i| pool[bucket[*i]].x != first.x)
+        .collect();
+    if others.len() < threshold {
+        return None;
+    }
+    let mut c: Vec<usize> = (0..threshold - 1).collect();
+    loop {
+        let mut seed = vec![first];
+        seed.extend(c.iter().map(|i| pool[bucket[others[*i]]]));
+        let xs: Vec<GF256> = seed.iter().map(|s| s.x).collect();
+        let distinct = (1..xs.len()).all(|i| !xs[..i].contains(&xs[i]));
+        if distinct {
+            let members: Vec<usize> = (0..bucket.len())
+                .filter(|i| lies_on(&seed, &xs, pool[bucket[*i]]))
+                .collect();
+            let mut confirmed: Vec<GF256> = members.iter().map(|i| pool[bucket[*i]].x).collect();
+            confirmed.sort_unstable_by_key(|x| x.0);
+            confirmed.dedup();
+            if confirmed.len() > threshold {
+                return Some(members);
+            }
+        }
+        if !next_combination(&mut c, others.len()) {
+            return None;
+        }
+    }
+}
+
+impl Sharks {
+    /// Clusters a pool of envelopes mixing shares of several secrets into groups of shares of the same secret,
+    /// and recovers the secret of each group. Envelopes are grouped by label when they have one. Unlabelled ones
+    /// are grouped by consistency probing, which needs at least `threshold + 1` distinct shares of a secret,
+    /// as any `threshold` shares are consistent. Shares embedding no threshold are assumed to be split with this one.
+    /// Shares which cannot be grouped are reported as orphans.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, ShareEnvelope };
+    /// let sharks = Sharks(2);
+    /// let mut pool: Vec<ShareEnvelope> = sharks.dealer(&[1, 2]).take(3).map(ShareEnvelope::new).collect();
+    /// pool.extend(sharks.dealer(&[3, 4]).take(4).map(ShareEnvelope::new));
+    /// pool.swap(1, 5);
+    /// pool.extend(sharks.dealer(&[5, 6]).take(1).map(ShareEnvelope::new));
+    ///
+    /// let partition = sharks.partition(&pool);
+    /// assert_eq!(partition.groups.len(), 2);
+    /// assert_eq!(partition.groups[0].members, vec![0, 2, 5]);
+    /// assert_eq!(partition.groups[0].secret, Ok(vec![1, 2]));
+    /// assert_eq!(partition.groups[1].secret, Ok(vec![3, 4]));
+    /// assert_eq!(partition.orphans, vec![7]);
+    /// ```
+    pub fn partition(&self, pool: &[ShareEnvelope]) -> Partition {
+        let shares: Vec<&Share> = pool.iter().map(|e| &e.share).collect();
+        let labels: Vec<Option<&str>> = pool.iter().map(|e| e.label.as_deref()).collect();
+        let thresholds: Vec<Option<u8>> = pool
+            .iter()
+            .map(|e| e.threshold.or(e.share.threshold()))
+            .collect();
+        self.partition_in(&shares, &labels, &thresholds)
+    }
+
+    /// Same as `partition`, for shares without envelopes, which are all grouped by consistency probing.
+    pub fn partition_shares(&self, pool: &[Share]) -> Partition {
+        let shares: Vec<&Share> = pool.iter().collect();
+        let thresholds: Vec<Option<u8>> = pool.iter().map(|s| s.threshold()).collect();
+        self.partition_in(&shares, &vec![None; pool.len()], &thresholds)
+    }
+
+    fn partition_in(
+        &self,
+        pool: &[&Share],
+        labels: &[Option<&str>],
+        thresholds: &[Option<u8>],
+    ) -> Partition {
+        // Buckets of shares with the same label, threshold and length, in the order of their first share
+        let mut buckets: Vec<(Option<&str>, u8, usize, Vec<usize>)> = Vec::new();
+        for (i, s) in pool.iter().enumerate() {
+            let threshold = thresholds[i].unwrap_or(self.0);
+            match buckets
+                .iter_mut()
+                .find(|(l, t, len, _)| *l == labels[i] && *t == threshold && *len == s.y.len())
+            {
+                Some((_, _, _, members)) => members.push(i),
+                None => buckets.push((labels[i], threshold, s.y.len(), vec![i])),
+            }
+        }
+
+        let mut groups = Vec::new();
+        let mut orphans = Vec::new();
+        for (label, threshold, _, mut bucket) in buckets {
+            if label.is_some() {
+                groups.push((label, threshold, bucket));
+                continue;
+            }
+            while !bucket.is_empty() {
+                match probe(pool, &bucket, (threshold as usize).max(1)) {
+                    Some(members) => {
+                        let members: Vec<usize> = members.iter().map(|i| bucket[*i]).collect();
+                        bucket.retain(|i| !members.contains(i));
+                        groups.push((label, threshold, members));
+                    }
+                    None => orphans.push(bucket.remove(0)),
+                }
+            }
+        }
+
+        groups.sort_by_key(|(_, _, members)| members[0]);
+        orphans.sort_unstable();
+        Partition {
+            groups: groups
+                .into_iter()
+                .map(|(label, threshold, members)| ShareGroup {
+                    label: label.map(str::to_string),
+                    threshold,
+                    secret: Sharks(threshold).recover(members.iter().map(|i| pool[*i])),
+                    members,
+                })
+                .collect(),
+            orphans,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, ShareEnvelope, Sharks, SharksError};
+
+    #[test]
+    fn partition_integration_works() {
+        let sharks = Sharks(3);
+        let a: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(5).collect();
+        let b: Vec<Share> = sharks.dealer(&[4, 5, 6]).take(4).collect();
+        let c: Vec<Share> = Sharks(2).dealer(&[7, 8, 9]).take(3).collect();
+        let pool = vec![
+            b[0].clone(),
+            a[4].clone(),
+            c[0].clone(),
+            a[0].clone(),
+            b[3].clone(),
+            a[2].clone(),
+            c[1].clone(),
+            a[0].clone(),
+            b[1].clone(),
+            c[2].clone(),
+            a[3].clone(),
+            b[2].clone(),
+        ];
+
+        let partition = sharks.partition_shares(&pool);
+        assert!(partition.orphans.is_empty());
+        let groups: Vec<(Vec<usize>, Vec<u8>)> = partition
+            .groups
+            .iter()
+            .map(|g| (g.members.clone(), g.secret.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (vec![0, 4, 8, 11], vec![4, 5, 6]),
+                (vec![1, 3, 5, 7, 10], vec![1, 2, 3]),
+                (vec![2, 6, 9], vec![7, 8, 9]),
+            ]
+        );
+        assert_eq!(partition.groups[2].threshold, 2);
+
+        // Labels group shares which probing alone cannot
+        let mut pool: Vec<ShareEnvelope> = a[..3].iter().cloned().map(ShareEnvelope::new).collect();
+        pool.extend(b[..3].iter().cloned().map(|s| {
+            let mut e = ShareEnvelope::new(s);
+            e.label = Some("b".into());
+            e
+        }));
+        let partition = sharks.partition(&pool);
+        assert_eq!(partition.orphans, vec![0, 1, 2]);
+        assert_eq!(partition.groups.len(), 1);
+        assert_eq!(partition.groups[0].label.as_deref(), Some("b"));
+        assert_eq!(partition.groups[0].secret, Ok(vec![4, 5, 6]));
+        assert!(!format!("{:?}", partition).contains("4, 5, 6"));
+    }
+
+    #[test]
+    fn partition_err() {
+        let sharks = Sharks(2);
+        let partition = sharks.partition_shares(&[]);
+        assert!(partition.groups.is_empty() && partition.orphans.is_empty());
+
+        // Corrupted and unrelated shares are orphans
+        let mut shares: Vec<Share> = sharks.dealer(&[1, 2]).take(4).collect();
+        shares[1].y[1] = shares[1].y[1] + crate::GF256(1);
+        shares.push(sharks.dealer(&[1, 2, 3]).next().unwrap());
+        let partition = sharks.partition_shares(&shares);
+        assert_eq!(partition.groups.len(), 1);
+        assert_eq!(partition.groups[0].members, vec![0, 2, 3]);
+        assert_eq!(partition.orphans, vec![1, 4]);
+
+        // Labelled groups are recovered as such
+        let mut pool: Vec<ShareEnvelope> = shares.into_iter().map(ShareEnvelope::new).collect();
+        pool[0].label = Some("a".into());
+        let partition = sharks.partition(&pool);
+        assert_eq!(partition.groups[0].members, vec![0]);
+        assert_eq!(
+            partition.groups[0].secret,
+            Err(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Shares sealed with a passphrase, so that stored shares need an extra knowledge factor to be used.
+// The encryption key is derived from the passphrase with Argon2id, a memory hard function slowing down
+// brute force attacks, and the share is encrypted with ChaCha20-Poly1305. Containers are self describing:
+// `version || m_cost || t_cost || p_cost || salt || ciphertext`, costs being 4 bytes big endian integers
+// and the salt 16 random bytes, all of it authenticated along with the share.
+
+use std::convert::TryFrom;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::Share;
+
+/// Version byte prefixed to shares sealed with `Share::seal_with_passphrase`.
+pub const PASSPHRASE_VERSION: u8 = 1;
+
+const SALT_BYTES: usize = 16;
+const HEADER_BYTES: usize = 1 + 3 * 4 + SALT_BYTES;
+
+// Derives the encryption key from the passphrase, an `Err` being returned if the parameters are out of range
+fn cipher(
+    passphrase: &[u8],
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<ChaCha20Poly1305, SharksError> {
+    let params =
+        Params::new(m_cost, t_cost, p_cost, Some(32)).map_err(|_| SharksError::InvalidEncoding)?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|_| SharksError::InvalidEncoding)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    key.zeroize();
+    Ok(cipher)
+}
+
+impl Share {
+    /// Seals the share with a `passphrase`, using the default Argon2id parameters: 19 MiB of memory,
+    /// 2 iterations and a single lane. See `Share::seal_with_passphrase_params`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # let sharks = Sharks(1);
+    /// let share = sharks.dealer(&[1, 2, 3]).next().unwrap();
+    /// let sealed = share.seal_with_passphrase(b"correct horse", &mut rand::thread_rng());
+    /// let share = Share::open_with_passphrase(&sealed, b"correct horse").unwrap();
+    /// assert_eq!(sharks.recover(&[share]).unwrap(), vec![1, 2, 3]);
+    /// assert!(Share::open_with_passphrase(&sealed, b"battery staple").is_err());
+    pub fn seal_with_passphrase<R: CryptoRng + RngCore>(
+        &self,
+        passphrase: &[u8],
+        rng: &mut R,
+    ) -> Vec<u8> {
+        self.seal_with_passphrase_params(passphrase, &Params::default(), rng)
+    }
+
+    /// Seals the share with a `passphrase` in a self describing container, deriving the encryption key
+    /// with the given Argon2id parameters, which are stored along with the share. The threshold embedded
+    /// in the share, if known, is kept.
+    pub fn seal_with_passphrase_params<R: CryptoRng + RngCore>(
+        &self,
+        passphrase: &[u8],
+        params: &Params,
+        rng: &mut R,
+    ) -> Vec<u8> {
+        let mut sealed = vec![PASSPHRASE_VERSION];
+        for cost in [params.m_cost(), params.t_cost(), params.p_cost()].iter() {
+            sealed.extend_from_slice(&cost.to_be_bytes());
+        }
+        let mut salt = [0u8; SALT_BYTES];
+        rng.fill_bytes(&mut salt);
+        sealed.extend_from_slice(&salt);
+
+        let cipher = cipher(
+            passphrase,
+            &salt,
+            params.m_cost(),
+            params.t_cost(),
+            params.p_cost(),
+        )
+        .expect("parameters were validated by argon2");
+        let mut plaintext = self.to_sealed_bytes();
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: &plaintext,
+                    aad: &sealed,
+                },
+            )
+            .expect("shares are shorter than the ChaCha20-Poly1305 limit");
+        sealed.extend(ciphertext);
+
+        plaintext.zeroize();
+        sealed
+    }
+
+    /// Opens a share sealed with `Share::seal_with_passphrase` using the same `passphrase`.
+    /// An `Err` is returned if the version is unknown, the Argon2id parameters are out of range,
+    /// or if the passphrase is wrong or the container was tampered with.
+    /// The Argon2id parameters are read from the container, so only open containers from a trusted source
+    /// or sealed with parameters you expect, as opening takes as much memory and time as they require.
+    pub fn open_with_passphrase(sealed: &[u8], passphrase: &[u8]) -> Result<Share, SharksError> {
+        match sealed.first() {
+            None => return Err(SharksError::EmptyShare),
+            Some(version) if *version != PASSPHRASE_VERSION => {
+                return Err(SharksError::UnknownVersion(*version))
+            }
+            _ if sealed.len() < HEADER_BYTES => return Err(SharksError::InvalidLength),
+            _ => (),
+        }
+
+        let (header, ciphertext) = sealed.split_at(HEADER_BYTES);
+        let cost = |i: usize| {
+            u32::from_be_bytes(<[u8; 4]>::try_from(&header[1 + 4 * i..5 + 4 * i]).unwrap())
+        };
+        let cipher = cipher(
+            passphrase,
+            &header[HEADER_BYTES - SALT_BYTES..],
+            cost(0),
+            cost(1),
+            cost(2),
+        )?;
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| SharksError::InvalidCiphertext)?;
+
+        let share = Share::from_sealed_bytes(&plaintext);
+        plaintext.zeroize();
+        share
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PASSPHRASE_VERSION;
+    use crate::{Share, Sharks, SharksError};
+    use argon2::Params;
+
+    // Cheap parameters keeping the tests fast
+    fn params() -> Params {
+        Params::new(64, 1, 1, None).unwrap()
+    }
+
+    #[test]
+    fn passphrase_integration_works() {
+        let sharks = Sharks(3);
+        let sealed: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3, 4])
+            .take(3)
+            .map(|s| {
+                s.seal_with_passphrase_params(b"passphrase", &params(), &mut rand::thread_rng())
+            })
+            .collect();
+        assert_eq!(sealed[0].len(), 29 + 1 + 5 + 16);
+        assert_eq!(&sealed[0][..13], &[1, 0, 0, 0, 64, 0, 0, 0, 1, 0, 0, 0, 1]);
+
+        let shares: Vec<Share> = sealed
+            .iter()
+            .map(|s| Share::open_with_passphrase(s, b"passphrase").unwrap())
+            .collect();
+        assert_eq!(shares[2].x.0, 3);
+        assert_eq!(shares[2].threshold(), Some(3));
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn passphrase_err() {
+        let share = Sharks(2).dealer(&[1, 2]).next().unwrap();
+        let sealed =
+            share.seal_with_passphrase_params(b"passphrase", &params(), &mut rand::thread_rng());
+        assert_eq!(
+            Share::open_with_passphrase(&sealed, b"Passphrase").err(),
+            Some(SharksError::InvalidCiphertext)
+        );
+        for i in 13..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(
+                Share::open_with_passphrase(&tampered, b"passphrase").err(),
+                Some(SharksError::InvalidCiphertext)
+            );
+        }
+
+        let mut invalid = sealed.clone();
+        invalid[12] = 0;
+        let cases: [(&[u8], SharksError); 4] = [
+            (&[], SharksError::EmptyShare),
+            (&[PASSPHRASE_VERSION + 1], SharksError::UnknownVersion(2)),
+            (&sealed[..28], SharksError::InvalidLength),
+            (&invalid, SharksError::InvalidEncoding),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(
+                Share::open_with_passphrase(bytes, b"passphrase").err(),
+                Some(*err)
+            );
+        }
+    }
+}
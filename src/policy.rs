@@ -0,0 +1,291 @@
+// General monotone access structures, described as trees of threshold gates over participants.
+// The secret is split at the root gate, and the bytes of each of the resulting shares are split again
+// by the child gates, down to the participants, who hold the shares of their parent gate.
+// Recovery follows the tree bottom-up, recovering each gate secret once enough of its children are.
+
+use std::convert::TryFrom;
+use std::slice;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// An access structure, describing which sets of participants can recover the secret.
+///
+/// Example:
+/// ```
+/// # use sharks::Policy;
+/// // (0 and 1) or (2 and 2-of-{3, 4, 5})
+/// let policy = Policy::Or(vec![
+///     Policy::And(vec![Policy::Participant(0), Policy::Participant(1)]),
+///     Policy::And(vec![
+///         Policy::Participant(2),
+///         Policy::Threshold(2, (3..6).map(Policy::Participant).collect()),
+///     ]),
+/// ]);
+/// assert!(policy.is_satisfied(&[2, 5, 3]));
+/// assert!(!policy.is_satisfied(&[0, 2, 4]));
+///
+/// let shares = policy.dealer(&[1, 2, 3]).unwrap();
+/// let quorum: Vec<_> = shares.iter().filter(|s| [2, 5, 3].contains(&s.participant)).collect();
+/// assert_eq!(policy.recover(quorum).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// A single participant, identified by a number of your choice.
+    Participant(u32),
+    /// Satisfied if all of the policies are, a threshold gate of as many shares as policies.
+    And(Vec<Policy>),
+    /// Satisfied if any of the policies is, a threshold gate of one share.
+    Or(Vec<Policy>),
+    /// Satisfied if at least the threshold of the policies are.
+    Threshold(u8, Vec<Policy>),
+}
+
+impl Policy {
+    // Threshold and children of the gate, a single participant being a gate of itself
+    fn gate(&self) -> (usize, &[Policy]) {
+        match self {
+            Policy::Participant(_) => (1, slice::from_ref(self)),
+            Policy::And(children) => (children.len(), children),
+            Policy::Or(children) => (1, children),
+            Policy::Threshold(k, children) => (*k as usize, children),
+        }
+    }
+
+    // Checks every gate has between one and 255 children and a threshold not higher than their number
+    fn validate(&self) -> Result<(), SharksError> {
+        let (k, children) = self.gate();
+        if k == 0 || k > children.len() || children.len() > 255 {
+            return Err(SharksError::InvalidThreshold);
+        }
+        children
+            .iter()
+            .filter(|c| !matches!(c, Policy::Participant(_)))
+            .try_for_each(|c| c.validate())
+    }
+
+    /// Returns whether the set of `participants` satisfies the policy.
+    pub fn is_satisfied(&self, participants: &[u32]) -> bool {
+        match self {
+            Policy::Participant(id) => participants.contains(id),
+            _ => {
+                let (k, children) = self.gate();
+                children
+                    .iter()
+                    .filter(|c| c.is_satisfied(participants))
+                    .count()
+                    >= k
+            }
+        }
+    }
+
+    /// Given a `secret` byte slice, returns the shares of every participant of the policy,
+    /// one for each place the participant appears at. Any set of participants satisfying the policy
+    /// can recover the secret with `recover`, while the others learn nothing about it.
+    /// An `Err` is returned if a gate has no policies, more than 255, or a threshold out of range.
+    pub fn dealer(&self, secret: &[u8]) -> Result<Vec<PolicyShare>, SharksError> {
+        self.validate()?;
+        let mut shares = Vec::new();
+        self.deal(secret, &mut Vec::new(), &mut shares);
+        Ok(shares)
+    }
+
+    fn deal(&self, secret: &[u8], path: &mut Vec<u8>, shares: &mut Vec<PolicyShare>) {
+        let (k, children) = self.gate();
+        let dealer = Sharks(k as u8).dealer(secret);
+        for (i, (child, share)) in children.iter().zip(dealer).enumerate() {
+            path.push(i as u8);
+            match child {
+                Policy::Participant(participant) => shares.push(PolicyShare {
+                    participant: *participant,
+                    path: path.clone(),
+                    share,
+                }),
+                _ => child.deal(&Vec::from(&share), path, shares),
+            }
+            path.pop();
+        }
+    }
+
+    /// Given an iterable collection of shares generated by `dealer`, recovers the original secret.
+    /// Shares are matched to their place in the policy, so the same policy must be given.
+    /// An `Err` is returned if the shares do not satisfy the policy.
+    pub fn recover<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a PolicyShare>,
+        T::IntoIter: Iterator<Item = &'a PolicyShare>,
+    {
+        self.validate()?;
+        let shares: Vec<&PolicyShare> = shares.into_iter().collect();
+        self.recover_gate(&shares, &mut Vec::new())
+            .map_err(|_| SharksError::UnauthorizedShares)
+    }
+
+    fn recover_gate(
+        &self,
+        shares: &[&PolicyShare],
+        path: &mut Vec<u8>,
+    ) -> Result<Vec<u8>, SharksError> {
+        let (k, children) = self.gate();
+        let mut recovered = Vec::new();
+        for (i, child) in children.iter().enumerate() {
+            path.push(i as u8);
+            match child {
+                Policy::Participant(participant) => recovered.extend(
+                    shares
+                        .iter()
+                        .filter(|s| s.participant == *participant && s.path == *path)
+                        .map(|s| s.share.clone()),
+                ),
+                // Gates which cannot be recovered are simply missing, the others stamped like dealt shares
+                _ => recovered.extend(
+                    child
+                        .recover_gate(shares, path)
+                        .ok()
+                        .and_then(|bytes| Share::try_from(bytes.as_slice()).ok())
+                        .map(|mut share| {
+                            share.threshold = Some(k as u8);
+                            share
+                        }),
+                ),
+            }
+            path.pop();
+        }
+        Sharks(k as u8).recover(&recovered)
+    }
+}
+
+/// A share of a participant of a `Policy`, at one of the places it appears at.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct PolicyShare {
+    /// Identifier of the participant.
+    pub participant: u32,
+    /// Indexes of the policies leading from the root of the policy to the participant.
+    pub path: Vec<u8>,
+    /// Share of the secret of the gate the participant belongs to.
+    pub share: Share,
+}
+
+/// Obtains a byte vector from a `PolicyShare` instance: `participant || path length || path || share`,
+/// being the participant a 4 bytes big endian integer.
+impl From<&PolicyShare> for Vec<u8> {
+    fn from(s: &PolicyShare) -> Vec<u8> {
+        let mut bytes = s.participant.to_be_bytes().to_vec();
+        bytes.push(s.path.len() as u8);
+        bytes.extend_from_slice(&s.path);
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `PolicyShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for PolicyShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<PolicyShare, Self::Error> {
+        if s.len() < 5 || s.len() < 5 + s[4] as usize {
+            return Err(SharksError::InvalidLength);
+        }
+        let (path, share) = s[5..].split_at(s[4] as usize);
+        Ok(PolicyShare {
+            participant: u32::from_be_bytes([s[0], s[1], s[2], s[3]]),
+            path: path.to_vec(),
+            share: Share::try_from(share)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Policy, PolicyShare};
+    use crate::SharksError;
+    use std::convert::TryFrom;
+
+    fn policy() -> Policy {
+        // (A and B) or (C and 2-of-{D, E, F}), with A also being one of the later
+        Policy::Or(vec![
+            Policy::And(vec![Policy::Participant(0), Policy::Participant(1)]),
+            Policy::And(vec![
+                Policy::Participant(2),
+                Policy::Threshold(
+                    2,
+                    vec![
+                        Policy::Participant(3),
+                        Policy::Participant(0),
+                        Policy::Participant(5),
+                    ],
+                ),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn policy_integration_works() {
+        let policy = policy();
+        let secret: Vec<u8> = (0..50).collect();
+        let shares = policy.dealer(&secret).unwrap();
+        assert_eq!(shares.len(), 6);
+        assert_eq!(shares.iter().filter(|s| s.participant == 0).count(), 2);
+
+        let sets: [&[u32]; 7] = [
+            &[0, 1],
+            &[2, 3, 0],
+            &[2, 5, 3],
+            &[0],
+            &[2, 3],
+            &[1, 3, 5],
+            &[],
+        ];
+        for participants in sets.iter() {
+            let quorum: Vec<&PolicyShare> = shares
+                .iter()
+                .filter(|s| participants.contains(&s.participant))
+                .collect();
+            if policy.is_satisfied(participants) {
+                assert_eq!(policy.recover(quorum).unwrap(), secret);
+            } else {
+                assert_eq!(
+                    policy.recover(quorum).err(),
+                    Some(SharksError::UnauthorizedShares)
+                );
+            }
+        }
+        assert!(policy.is_satisfied(&[0, 1]));
+        assert!(!policy.is_satisfied(&[1, 3, 5]));
+
+        let shares: Vec<PolicyShare> = shares
+            .iter()
+            .map(|s| PolicyShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(policy.recover(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn single_participant_works() {
+        let policy = Policy::Participant(7);
+        let shares = policy.dealer(&[1, 2]).unwrap();
+        assert_eq!(policy.recover(&shares).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn policy_errors_work() {
+        for policy in [
+            Policy::And(vec![]),
+            Policy::Threshold(3, vec![Policy::Participant(0), Policy::Participant(1)]),
+            Policy::Or(vec![Policy::Threshold(0, vec![Policy::Participant(0)])]),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                policy.dealer(&[1]).err(),
+                Some(SharksError::InvalidThreshold)
+            );
+        }
+        assert_eq!(
+            PolicyShare::try_from(&[0, 0, 0, 0, 2, 1][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+    }
+}
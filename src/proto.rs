@@ -0,0 +1,216 @@
+//! Protocol Buffers messages of the shares, generated with `prost` from the `proto/sharks.proto` schema,
+//! so that services exchanging shares over gRPC share one message format.
+//!
+//! Shares are converted to messages with `From` and back with `TryFrom`, which validates them.
+//! Messages record the size of the field elements, so that shares of another field are rejected.
+//!
+//! Example:
+//! ```
+//! # use sharks::{ Sharks, Share };
+//! # use std::convert::TryFrom;
+//! use prost::Message;
+//! use sharks::proto;
+//!
+//! let sharks = Sharks(2);
+//! let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+//! let encoded = proto::ShareBundle::from(&shares[1..]).encode_to_vec();
+//!
+//! let bundle = proto::ShareBundle::decode(encoded.as_slice()).unwrap();
+//! let shares: Vec<Share> = Vec::try_from(&bundle).unwrap();
+//! assert_eq!(shares[0].threshold(), Some(2));
+//! assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+//! ```
+
+use std::convert::TryFrom;
+
+use zeroize::Zeroize;
+
+use crate::{Field, FieldShare, SharksError};
+
+/// Protocol Buffers schema of the messages, `package sharks.v1`.
+pub const SCHEMA: &str = include_str!("../proto/sharks.proto");
+
+/// A share of a secret, see the `sharks.v1.Share` message.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Share {
+    /// Size in bytes of the field elements.
+    #[prost(uint32, tag = "1")]
+    pub element_bytes: u32,
+    /// Minimum number of shares needed to recover the secret, if known.
+    #[prost(uint32, optional, tag = "2")]
+    pub threshold: Option<u32>,
+    /// The `x` coordinate.
+    #[prost(bytes = "vec", tag = "3")]
+    pub x: Vec<u8>,
+    /// The `y` values.
+    #[prost(bytes = "vec", tag = "4")]
+    pub y: Vec<u8>,
+    /// Identifier of the split the share was dealt by, if known.
+    #[prost(fixed64, optional, tag = "5")]
+    pub group: Option<u64>,
+}
+
+/// Shares of a same secret, see the `sharks.v1.ShareBundle` message.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ShareBundle {
+    #[prost(message, repeated, tag = "1")]
+    pub shares: Vec<Share>,
+}
+
+/// Wipes the `x` and `y` values from memory.
+impl Zeroize for Share {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.zeroize();
+    }
+}
+
+/// Obtains a message from a share, its elements being serialized big endian.
+impl<F: Field> From<&FieldShare<F>> for Share {
+    fn from(s: &FieldShare<F>) -> Share {
+        let mut bytes = Vec::from(s);
+        let share = Share {
+            element_bytes: F::BYTES as u32,
+            threshold: s.threshold().map(u32::from),
+            x: bytes[..F::BYTES].to_vec(),
+            y: bytes[F::BYTES..].to_vec(),
+            group: s.group(),
+        };
+        bytes.zeroize();
+        share
+    }
+}
+
+/// Obtains a share from a message, validating its contents.
+/// An `Err` is returned if the elements are of another field, the threshold is zero or out of range,
+/// or the `x` and `y` values are not a valid share.
+impl<F: Field> TryFrom<&Share> for FieldShare<F> {
+    type Error = SharksError;
+
+    fn try_from(s: &Share) -> Result<FieldShare<F>, Self::Error> {
+        if s.element_bytes as usize != F::BYTES {
+            return Err(SharksError::InvalidElement);
+        } else if s.x.len() != F::BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+        let threshold = match s.threshold {
+            Some(t) if t == 0 || t > u8::MAX as u32 => return Err(SharksError::InvalidThreshold),
+            t => t.map(|t| t as u8),
+        };
+
+        let mut bytes = s.x.clone();
+        bytes.extend_from_slice(&s.y);
+        let share = FieldShare::try_from(bytes.as_slice());
+        bytes.zeroize();
+        let mut share = share?;
+        share.threshold = threshold;
+        share.group = s.group;
+        Ok(share)
+    }
+}
+
+/// Obtains a bundle message from shares.
+impl<F: Field> From<&[FieldShare<F>]> for ShareBundle {
+    fn from(shares: &[FieldShare<F>]) -> ShareBundle {
+        ShareBundle {
+            shares: shares.iter().map(Share::from).collect(),
+        }
+    }
+}
+
+/// Obtains the shares of a bundle message, validating each of them.
+impl<F: Field> TryFrom<&ShareBundle> for Vec<FieldShare<F>> {
+    type Error = SharksError;
+
+    fn try_from(bundle: &ShareBundle) -> Result<Vec<FieldShare<F>>, Self::Error> {
+        bundle.shares.iter().map(FieldShare::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Share as Message, ShareBundle};
+    use crate::{FieldShare, Share, Sharks, SharksError, GF65536};
+    use prost::Message as _;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn proto_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let message = Message::from(&shares[1]);
+        assert_eq!(message.element_bytes, 1);
+        assert_eq!(message.threshold, Some(3));
+        assert_eq!(message.x, vec![2]);
+        let decoded = Message::decode(message.encode_to_vec().as_slice()).unwrap();
+        let share = Share::try_from(&decoded).unwrap();
+        assert!(share == shares[1]);
+        assert_eq!(share.threshold(), Some(3));
+
+        let bundle = ShareBundle::from(&shares[2..]);
+        let decoded = ShareBundle::decode(bundle.encode_to_vec().as_slice()).unwrap();
+        let decoded: Vec<Share> = Vec::try_from(&decoded).unwrap();
+        assert_eq!(sharks.recover(&decoded).unwrap(), vec![1, 2, 3, 4]);
+
+        let wide: Vec<FieldShare<GF65536>> = sharks.dealer_in(&[1, 2, 3]).take(3).collect();
+        let bundle = ShareBundle::from(wide.as_slice());
+        assert_eq!(bundle.shares[0].element_bytes, 2);
+        let decoded: Vec<FieldShare<GF65536>> = Vec::try_from(&bundle).unwrap();
+        assert_eq!(sharks.recover_in(&decoded).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            Vec::<Share>::try_from(&bundle).err(),
+            Some(SharksError::InvalidElement)
+        );
+
+        // Group identifiers are kept, so that shares of different splits are not mixed
+        let a: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(3).collect();
+        let b: Vec<Share> = sharks.dealer_grouped(&[1, 2]).take(3).collect();
+        let mixed = vec![a[0].clone(), a[1].clone(), b[2].clone()];
+        let bundle = ShareBundle::from(mixed.as_slice());
+        assert_eq!(bundle.shares[0].group, a[0].group());
+        let decoded = ShareBundle::decode(bundle.encode_to_vec().as_slice()).unwrap();
+        let decoded: Vec<Share> = Vec::try_from(&decoded).unwrap();
+        assert_eq!(decoded[2].group(), b[2].group());
+        assert_eq!(
+            sharks.recover(&decoded).err(),
+            Some(SharksError::MixedGroups)
+        );
+    }
+
+    #[test]
+    fn proto_err() {
+        let valid = Message {
+            element_bytes: 1,
+            threshold: None,
+            x: vec![1],
+            y: vec![2, 3],
+            group: None,
+        };
+        assert_eq!(Share::try_from(&valid).unwrap().threshold(), None);
+
+        let mut message = valid.clone();
+        message.threshold = Some(256);
+        assert_eq!(
+            Share::try_from(&message).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        let mut message = valid.clone();
+        message.x = vec![0];
+        assert_eq!(
+            Share::try_from(&message).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        let mut message = valid.clone();
+        message.x = vec![];
+        assert_eq!(
+            Share::try_from(&message).err(),
+            Some(SharksError::InvalidLength)
+        );
+        let mut message = valid;
+        message.y = vec![];
+        assert_eq!(
+            Share::try_from(&message).err(),
+            Some(SharksError::EmptyShare)
+        );
+    }
+}
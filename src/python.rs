@@ -0,0 +1,159 @@
+// Python bindings through PyO3, exposing the `sharks` extension module with the `Sharks` and `Share` classes
+// and the `split` and `recover` functions, exchanging secrets and shares as `bytes`.
+// The module is built as a shared library, for instance with `maturin` or `cargo rustc --crate-type cdylib`,
+// enabling the `pyo3/extension-module` feature as well.
+
+use std::convert::TryFrom;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+impl From<SharksError> for PyErr {
+    fn from(e: SharksError) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// A share used to reconstruct the secret, parsed and validated from its bytes.
+#[pyclass(name = "Share", module = "sharks", from_py_object)]
+#[derive(Clone)]
+pub struct PyShare(Share);
+
+#[pymethods]
+impl PyShare {
+    /// Parses a share from its bytes, raising `ValueError` if they are not valid.
+    #[new]
+    fn new(bytes: &[u8]) -> PyResult<PyShare> {
+        Ok(PyShare(Share::try_from(bytes)?))
+    }
+
+    /// The `x` coordinate of the share.
+    #[getter]
+    fn x(&self) -> u8 {
+        self.0.x.0
+    }
+
+    /// The `y` values of the share, one per secret byte.
+    #[getter]
+    fn y<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let mut y: Vec<u8> = self.0.y.iter().map(|y| y.0).collect();
+        let bytes = PyBytes::new(py, &y);
+        y.zeroize();
+        bytes
+    }
+
+    /// The threshold the share was generated with, if known.
+    #[getter]
+    fn threshold(&self) -> Option<u8> {
+        self.0.threshold()
+    }
+
+    /// Returns the bytes of the share, as accepted by the constructor.
+    fn __bytes__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let mut share = Vec::from(&self.0);
+        let bytes = PyBytes::new(py, &share);
+        share.zeroize();
+        bytes
+    }
+}
+
+/// Generates shares and recovers secrets with a minimum threshold of shares.
+#[pyclass(name = "Sharks", module = "sharks")]
+pub struct PySharks(Sharks);
+
+#[pymethods]
+impl PySharks {
+    #[new]
+    fn new(threshold: u8) -> PySharks {
+        PySharks(Sharks(threshold))
+    }
+
+    /// The minimum number of shares needed to recover the secret.
+    #[getter]
+    fn threshold(&self) -> u8 {
+        self.0 .0
+    }
+
+    /// Splits `secret` into `count` shares, raising `ValueError` if `count` is lower than the threshold.
+    fn dealer(&self, secret: &[u8], count: u8) -> PyResult<Vec<PyShare>> {
+        if count < self.0 .0 {
+            return Err(SharksError::TooFewShares {
+                min: self.0 .0 as usize,
+            }
+            .into());
+        }
+        Ok(self
+            .0
+            .dealer(secret)
+            .take(count as usize)
+            .map(PyShare)
+            .collect())
+    }
+
+    /// Recovers the secret from a list of shares, raising `ValueError` if they are invalid or not enough.
+    fn recover<'py>(&self, py: Python<'py>, shares: Vec<PyShare>) -> PyResult<Bound<'py, PyBytes>> {
+        let mut secret = self.0.recover(shares.iter().map(|s| &s.0))?;
+        let bytes = PyBytes::new(py, &secret);
+        secret.zeroize();
+        Ok(bytes)
+    }
+}
+
+/// Splits `secret` into `count` shares, any `threshold` of them recovering it.
+#[pyfunction]
+fn split(secret: &[u8], threshold: u8, count: u8) -> PyResult<Vec<PyShare>> {
+    PySharks(Sharks(threshold)).dealer(secret, count)
+}
+
+/// Recovers the secret from a list of shares obtained from `split`.
+#[pyfunction]
+fn recover<'py>(
+    py: Python<'py>,
+    shares: Vec<PyShare>,
+    threshold: u8,
+) -> PyResult<Bound<'py, PyBytes>> {
+    PySharks(Sharks(threshold)).recover(py, shares)
+}
+
+/// Shamir's Secret Sharing over the same field and share format as the `sharks` Rust crate.
+#[pymodule]
+fn sharks(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySharks>()?;
+    m.add_class::<PyShare>()?;
+    m.add_function(wrap_pyfunction!(split, m)?)?;
+    m.add_function(wrap_pyfunction!(recover, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PyShare, PySharks};
+    use pyo3::prelude::*;
+
+    #[test]
+    fn python_integration_works() {
+        Python::initialize();
+        Python::attach(|py| {
+            let sharks = PySharks::new(3);
+            let shares = sharks.dealer(&[1, 2, 3], 5).unwrap();
+            assert_eq!(shares[0].threshold(), Some(3));
+            let secret = sharks.recover(py, shares[2..].to_vec()).unwrap();
+            assert_eq!(secret.as_bytes(), [1, 2, 3]);
+
+            let bytes = shares[0].__bytes__(py);
+            let share = PyShare::new(bytes.as_bytes()).unwrap();
+            assert_eq!(share.x(), shares[0].x());
+            assert_eq!(share.threshold(), None);
+
+            assert!(sharks.recover(py, shares[3..].to_vec()).is_err());
+            assert!(sharks.dealer(&[1], 2).is_err());
+            assert!(PyShare::new(&[]).is_err());
+        });
+    }
+}
@@ -0,0 +1,170 @@
+// Ramp secret sharing, packing several secret bytes in the coefficients of each polynomial.
+// With a threshold `k` and `l` packed bytes, the `l` lowest degree coefficients hold the secret and the other `k - l`
+// are random, so that `k` shares recover the secret, but only up to `k - l` of them reveal nothing about it.
+// In exchange, shares are `l` times smaller than the secret instead of as large as it.
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::Share;
+use super::Sharks;
+
+use zeroize::Zeroize;
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns an `Iterator` along new shares of a ramp scheme packing `pack` secret bytes
+    /// in each polynomial, so that shares are about `pack` times smaller than the secret.
+    /// The secret is recovered from threshold shares with `recover_ramp`, but only up to `threshold - pack` shares
+    /// reveal nothing about it: intermediate numbers of shares leak part of the information.
+    /// A `pack` of one is regular Shamir secret sharing, while a `pack` equal to the threshold offers no secrecy at all.
+    /// An `Err` is returned if `pack` is zero or greater than the threshold.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(5);
+    /// let secret: Vec<u8> = (0..30).collect();
+    /// // Any 3 shares reveal nothing, while 5 recover the secret
+    /// let shares: Vec<Share> = sharks.dealer_ramp(&secret, 2).unwrap().take(5).collect();
+    /// assert_eq!(shares[0].y.len(), 16);
+    /// assert_eq!(sharks.recover_ramp(&shares, 2).unwrap(), secret);
+    /// ```
+    pub fn dealer_ramp(
+        &self,
+        secret: &[u8],
+        pack: u8,
+    ) -> Result<impl Iterator<Item = Share>, SharksError> {
+        self.check_packing(pack)?;
+        let pack = pack as usize;
+
+        // Padded with a `0x80` byte followed by zeroes, like multiple byte field elements
+        let mut padded = secret.to_vec();
+        if pack > 1 {
+            padded.push(0x80);
+            padded.resize(padded.len().div_ceil(pack) * pack, 0);
+        }
+
+        let mut rng = rand::thread_rng();
+        let polys: Vec<Vec<GF256>> = padded
+            .chunks(pack)
+            .map(|group| {
+                let mut poly: Vec<GF256> = (pack..self.0 as usize)
+                    .map(|_| GF256::random(&mut rng))
+                    .collect();
+                poly.extend(group.iter().rev().map(|b| GF256(*b)));
+                poly
+            })
+            .collect();
+
+        padded.zeroize();
+
+        let threshold = self.0;
+        Ok(math::get_evaluator(polys).map(move |mut s| {
+            s.threshold = Some(threshold);
+            s
+        }))
+    }
+
+    /// Given an iterable collection of shares generated by `dealer_ramp` with the same `pack`,
+    /// recovers the original secret. Works the same way as `recover`, requiring threshold distinct shares.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(4);
+    /// let shares: Vec<Share> = sharks.dealer_ramp(&[1, 2, 3], 3).unwrap().take(6).collect();
+    /// assert_eq!(sharks.recover_ramp(&shares[2..], 3).unwrap(), vec![1, 2, 3]);
+    /// assert!(sharks.recover_ramp(&shares[3..], 3).is_err());
+    /// ```
+    pub fn recover_ramp<'a, T>(&self, shares: T, pack: u8) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        self.check_packing(pack)?;
+        let shares = self.validated(shares, self.0 as usize)?;
+        let mut coeffs = math::interpolate_coefficients(&shares, pack as usize);
+
+        let mut secret: Vec<u8> = (0..shares[0].y.len())
+            .flat_map(|c| coeffs.iter().map(move |coeff| coeff[c].0))
+            .collect();
+
+        coeffs.zeroize();
+
+        if pack > 1 {
+            while let Some(0) = secret.last() {
+                secret.pop();
+            }
+            if secret.pop() != Some(0x80) {
+                return Err(SharksError::InvalidPadding);
+            }
+        }
+        Ok(secret)
+    }
+
+    fn check_packing(&self, pack: u8) -> Result<(), SharksError> {
+        if pack == 0 || pack > self.0 {
+            Err(SharksError::InvalidPacking)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, Sharks, SharksError};
+
+    #[test]
+    fn ramp_integration_works() {
+        let sharks = Sharks(6);
+        for pack in 1..=6 {
+            for len in [0, 1, 5, 6, 100].iter().copied() {
+                let secret: Vec<u8> = (0..len).collect();
+                let shares: Vec<Share> =
+                    sharks.dealer_ramp(&secret, pack).unwrap().take(9).collect();
+                let padded = if pack > 1 {
+                    len as usize + 1
+                } else {
+                    len as usize
+                };
+                assert_eq!(shares[0].y.len(), padded.div_ceil(pack as usize));
+                assert_eq!(sharks.recover_ramp(&shares[3..], pack).unwrap(), secret);
+                assert_eq!(sharks.recover_ramp(&shares, pack).unwrap(), secret);
+            }
+        }
+    }
+
+    #[test]
+    fn ramp_single_pack_matches_recover() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer_ramp(&[1, 2, 3], 1).unwrap().take(3).collect();
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn ramp_errors_work() {
+        let sharks = Sharks(3);
+        assert_eq!(
+            sharks.dealer_ramp(&[1], 0).err(),
+            Some(SharksError::InvalidPacking)
+        );
+        assert_eq!(
+            sharks.dealer_ramp(&[1], 4).err(),
+            Some(SharksError::InvalidPacking)
+        );
+        let shares: Vec<Share> = sharks.dealer_ramp(&[1, 2, 3], 2).unwrap().take(3).collect();
+        assert_eq!(
+            sharks.recover_ramp(&shares, 4).err(),
+            Some(SharksError::InvalidPacking)
+        );
+        assert_eq!(
+            sharks.recover_ramp(&shares[1..], 2).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+    }
+}
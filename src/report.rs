@@ -0,0 +1,300 @@
+// Validation of collected shares ahead of recovery, reporting every problem found along with the share causing it,
+// instead of the single error `recover` stops at.
+// The checks are the same as those of recovery: the share lengths are compared with the most common one,
+// and the embedded thresholds with the `Sharks` one.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::error::SharksError;
+use super::field::Field;
+use super::share::{FieldShare, Share};
+use super::Sharks;
+
+/// Problem found with one of the shares by `Sharks::validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ShareFinding {
+    /// The `x` coordinate is zero.
+    ZeroX,
+    /// Same `x` coordinate as the share at index `first`, but different `y` values.
+    DuplicateX { first: usize },
+    /// Identical copy of the share at index `first`, ignored by recovery.
+    Copy { first: usize },
+    /// The share holds `found` `y` values while most others hold `expected`.
+    LengthMismatch { expected: usize, found: usize },
+    /// The embedded threshold is `found` while the `Sharks` one is `expected`.
+    ThresholdMismatch { expected: u8, found: u8 },
+    /// No threshold is embedded while other shares embed one.
+    MissingThreshold,
+    /// The serialized share checksum does not match its bytes.
+    InvalidChecksum,
+    /// The serialized share could not be parsed.
+    Unparsable(SharksError),
+}
+
+impl ShareFinding {
+    /// Returns whether the finding prevents recovering with the share, identical copies being merely ignored.
+    pub fn is_blocking(&self) -> bool {
+        !matches!(self, ShareFinding::Copy { .. })
+    }
+}
+
+impl fmt::Display for ShareFinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShareFinding::ZeroX => write!(f, "Share x coordinate is zero"),
+            ShareFinding::DuplicateX { first } => write!(
+                f,
+                "Share has the same x coordinate as share {} but different values",
+                first
+            ),
+            ShareFinding::Copy { first } => write!(f, "Share is a copy of share {}", first),
+            ShareFinding::LengthMismatch { expected, found } => write!(
+                f,
+                "Share has {} values but {} were expected",
+                found, expected
+            ),
+            ShareFinding::ThresholdMismatch { expected, found } => write!(
+                f,
+                "Share threshold is {} but {} was expected",
+                found, expected
+            ),
+            ShareFinding::MissingThreshold => write!(f, "Share has no embedded threshold"),
+            ShareFinding::InvalidChecksum => write!(f, "Share has an invalid checksum"),
+            ShareFinding::Unparsable(e) => write!(f, "Share could not be parsed: {}", e),
+        }
+    }
+}
+
+/// Findings of `Sharks::validate` over a set of shares, each one referring to a share by its index.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, Share, ShareFinding };
+/// let sharks = Sharks(2);
+/// let mut shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(3).collect();
+/// shares[1].y.pop();
+/// shares.push(shares[0].clone());
+///
+/// let report = sharks.validate(&shares);
+/// assert!(!report.is_recoverable());
+/// assert_eq!(report.findings_for(1).next(), Some(&ShareFinding::LengthMismatch { expected: 3, found: 2 }));
+/// assert_eq!(report.findings_for(3).next(), Some(&ShareFinding::Copy { first: 0 }));
+/// assert_eq!(report.usable, vec![0, 2]);
+/// assert_eq!(sharks.recover(report.usable.iter().map(|i| &shares[*i])).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Number of distinct valid shares needed to recover the secret.
+    pub needed: usize,
+    /// Number of shares validated.
+    pub provided: usize,
+    /// Findings along with the index of the share they were found in, in the order of the shares.
+    pub findings: Vec<(usize, ShareFinding)>,
+    /// Indices of the distinct shares without blocking findings, which can recover the secret together.
+    pub usable: Vec<usize>,
+}
+
+impl RecoveryReport {
+    /// Returns whether recovering with all the validated shares would succeed.
+    pub fn is_recoverable(&self) -> bool {
+        self.findings.iter().all(|(_, f)| !f.is_blocking()) && self.usable.len() >= self.needed
+    }
+
+    /// Returns the findings of the share at `index`.
+    pub fn findings_for(&self, index: usize) -> impl Iterator<Item = &ShareFinding> {
+        self.findings
+            .iter()
+            .filter(move |(i, _)| *i == index)
+            .map(|(_, f)| f)
+    }
+
+    /// Number of additional distinct valid shares needed to recover the secret.
+    pub fn missing_shares(&self) -> usize {
+        self.needed.saturating_sub(self.usable.len())
+    }
+}
+
+impl Sharks {
+    /// Checks the shares as a recovery would, without attempting it, reporting every problem found per share:
+    /// zero or duplicate `x` coordinates, identical copies, lengths differing from the most common one and
+    /// embedded thresholds disagreeing with this one. `RecoveryReport::usable` lists the shares that can
+    /// be used to recover the secret.
+    pub fn validate<F: Field>(&self, shares: &[FieldShare<F>]) -> RecoveryReport {
+        self.report(shares.iter().enumerate(), Vec::new(), shares.len())
+    }
+
+    /// Same as `validate`, the shares being serialized with `Share::to_checked_bytes`.
+    /// Shares whose checksum does not match or that can not be parsed are reported and left out of the other checks.
+    pub fn validate_checked_bytes<B: AsRef<[u8]>>(&self, shares: &[B]) -> RecoveryReport {
+        let mut findings = Vec::new();
+        let mut parsed = Vec::new();
+        for (i, bytes) in shares.iter().enumerate() {
+            match Share::from_checked_bytes(bytes.as_ref()) {
+                Ok(share) => parsed.push((i, share)),
+                Err(SharksError::InvalidChecksum) => {
+                    findings.push((i, ShareFinding::InvalidChecksum))
+                }
+                Err(e) => findings.push((i, ShareFinding::Unparsable(e))),
+            }
+        }
+        let mut report = self.report(parsed.iter().map(|(i, s)| (*i, s)), findings, shares.len());
+        report.findings.sort_by_key(|(i, _)| *i);
+        report
+    }
+
+    fn report<'a, F, T>(
+        &self,
+        shares: T,
+        mut findings: Vec<(usize, ShareFinding)>,
+        provided: usize,
+    ) -> RecoveryReport
+    where
+        F: Field + 'a,
+        T: Iterator<Item = (usize, &'a FieldShare<F>)> + Clone,
+    {
+        // Most common length, the first one seen winning ties
+        let mut lengths: Vec<(usize, usize)> = Vec::new();
+        for (_, s) in shares.clone() {
+            match lengths.iter_mut().find(|(l, _)| *l == s.y.len()) {
+                Some((_, count)) => *count += 1,
+                None => lengths.push((s.y.len(), 1)),
+            }
+        }
+        let expected = lengths
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map_or(0, |(l, _)| *l);
+        let embedded = shares.clone().any(|(_, s)| s.threshold.is_some());
+
+        let mut seen: HashMap<F, (usize, &FieldShare<F>)> = HashMap::new();
+        let mut usable = Vec::new();
+        for (i, s) in shares {
+            let count = findings.len();
+            if s.x == F::ZERO {
+                findings.push((i, ShareFinding::ZeroX));
+            }
+            if s.y.len() != expected {
+                findings.push((
+                    i,
+                    ShareFinding::LengthMismatch {
+                        expected,
+                        found: s.y.len(),
+                    },
+                ));
+            }
+            match s.threshold {
+                Some(found) if found != self.0 => findings.push((
+                    i,
+                    ShareFinding::ThresholdMismatch {
+                        expected: self.0,
+                        found,
+                    },
+                )),
+                None if embedded => findings.push((i, ShareFinding::MissingThreshold)),
+                _ => {}
+            }
+            match seen.get(&s.x) {
+                Some((first, d)) => {
+                    // Compared without short circuiting, as `recover` does
+                    let conflict = d.y.len() != s.y.len()
+                        || d.y
+                            .iter()
+                            .zip(s.y.iter())
+                            .fold(false, |acc, (a, b)| acc | (a != b));
+                    let finding = if conflict {
+                        ShareFinding::DuplicateX { first: *first }
+                    } else {
+                        ShareFinding::Copy { first: *first }
+                    };
+                    findings.push((i, finding));
+                }
+                None => {
+                    seen.insert(s.x, (i, s));
+                    if findings.len() == count {
+                        usable.push(i);
+                    }
+                }
+            }
+        }
+
+        RecoveryReport {
+            needed: (self.0 as usize).max(1),
+            provided,
+            findings,
+            usable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShareFinding;
+    use crate::{Share, Sharks, GF256};
+
+    #[test]
+    fn validate_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3]).take(4).collect();
+        let report = sharks.validate(&shares);
+        assert!(report.is_recoverable());
+        assert_eq!(report.findings, vec![]);
+        assert_eq!(report.usable, vec![0, 1, 2, 3]);
+
+        let mut shares: Vec<Share> = shares.into_iter().take(3).collect();
+        let mut conflicting = shares[0].clone();
+        conflicting.y[1] = conflicting.y[1] + GF256(1);
+        let mut other = Sharks(4).dealer(&[1, 2, 3]).nth(5).unwrap();
+        other.x = GF256(0);
+        let mut bare = shares[2].clone();
+        bare.x = GF256(9);
+        bare.threshold = None;
+        shares.extend(vec![conflicting, other, bare]);
+
+        let report = sharks.validate(&shares);
+        assert!(!report.is_recoverable());
+        assert_eq!(report.provided, 6);
+        assert_eq!(
+            report.findings,
+            vec![
+                (3, ShareFinding::DuplicateX { first: 0 }),
+                (4, ShareFinding::ZeroX),
+                (
+                    4,
+                    ShareFinding::ThresholdMismatch {
+                        expected: 3,
+                        found: 4
+                    }
+                ),
+                (5, ShareFinding::MissingThreshold),
+            ]
+        );
+        assert_eq!(report.usable, vec![0, 1, 2]);
+        assert_eq!(report.missing_shares(), 0);
+        assert_eq!(
+            report.findings[0].1.to_string(),
+            "Share has the same x coordinate as share 0 but different values"
+        );
+    }
+
+    #[test]
+    fn validate_checked_bytes_works() {
+        let sharks = Sharks(2);
+        let mut bytes: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3])
+            .take(3)
+            .map(|s| s.to_checked_bytes())
+            .collect();
+        bytes[0][1] ^= 1;
+        bytes[2].truncate(2);
+
+        let report = sharks.validate_checked_bytes(&bytes);
+        assert!(!report.is_recoverable());
+        assert_eq!(report.findings[0], (0, ShareFinding::InvalidChecksum));
+        assert_eq!(report.findings[1].0, 2);
+        assert_eq!(report.usable, vec![1]);
+        assert_eq!(report.missing_shares(), 1);
+    }
+}
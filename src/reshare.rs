@@ -0,0 +1,177 @@
+// Resharing of a secret to a new threshold and set of participants, without reconstructing it.
+// Each old participant of a quorum splits its own share with the new threshold and sends one
+// sub-share to each new participant, who combines them weighting each by its Lagrange coefficient.
+
+use std::collections::HashSet;
+
+use super::error::SharksError;
+use super::field::Field;
+use super::math;
+use super::share::FieldShare;
+use super::Sharks;
+
+/// A sub-share sent by an old participant to a new one while resharing, see `Sharks::reshare`.
+#[derive(Clone, Debug)]
+pub struct SubShare<F: Field> {
+    /// The `x` coordinate of the old share the sub-share was generated from.
+    pub from: F,
+    /// Share of the old share values, for the new participant at its `x` coordinate.
+    pub share: FieldShare<F>,
+}
+
+impl Sharks {
+    /// Run by each old participant of a quorum, given its `share` returns an `Iterator` along the
+    /// sub-shares to send to the new participants, being `Sharks` the new threshold.
+    /// The `n`th sub-share is meant for the `n`th new participant.
+    ///
+    /// Example, turning a 2-of-3 split into a 3-of-4 one:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SubShare };
+    /// let (old, new) = (Sharks(2), Sharks(3));
+    /// let shares: Vec<Share> = old.dealer(&[1, 2, 3]).take(3).collect();
+    /// // A quorum of old participants reshares, each sending a sub-share to every new participant
+    /// let subshares: Vec<Vec<SubShare<_>>> = shares[1..]
+    ///     .iter()
+    ///     .map(|s| new.reshare(s).take(4).collect())
+    ///     .collect();
+    /// // Each new participant combines the sub-shares it received
+    /// let new_shares: Vec<Share> = (0..4)
+    ///     .map(|i| new.combine_reshared(old.0, subshares.iter().map(|s| &s[i])).unwrap())
+    ///     .collect();
+    /// assert_eq!(new.recover(&new_shares[1..]).unwrap(), vec![1, 2, 3]);
+    pub fn reshare<F: Field>(&self, share: &FieldShare<F>) -> impl Iterator<Item = SubShare<F>> {
+        let from = share.x;
+        self.deal(share.y.clone())
+            .map(move |share| SubShare { from, share })
+    }
+
+    /// Run by each new participant, combines the sub-shares received from a quorum of at least
+    /// `old_threshold` old participants into its new share.
+    /// An `Err` is returned if there are not enough sub-shares, or they are inconsistent.
+    pub fn combine_reshared<'a, F, T>(
+        &self,
+        old_threshold: u8,
+        subshares: T,
+    ) -> Result<FieldShare<F>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a SubShare<F>>,
+        T::IntoIter: Iterator<Item = &'a SubShare<F>>,
+    {
+        let subshares: Vec<&SubShare<F>> = subshares.into_iter().collect();
+        let x: HashSet<F> = subshares.iter().map(|s| s.share.x).collect();
+        if x.len() > 1 {
+            return Err(SharksError::MixedShares);
+        }
+        match subshares
+            .iter()
+            .map(|s| s.share.threshold)
+            .find(|t| *t != Some(self.0))
+        {
+            Some(Some(found)) => {
+                return Err(SharksError::ThresholdMismatch {
+                    expected: self.0,
+                    found,
+                })
+            }
+            Some(None) => return Err(SharksError::InvalidThreshold),
+            None => {}
+        }
+
+        // The old share values are recovered at zero from the sub-shares, as if they were shares
+        let points: Vec<FieldShare<F>> = subshares
+            .iter()
+            .map(|s| FieldShare {
+                x: s.from,
+                y: s.share.y.clone(),
+                threshold: None,
+                group: None,
+            })
+            .collect();
+        let points = Sharks(old_threshold).validated(&points, old_threshold as usize)?;
+
+        Ok(FieldShare {
+            x: subshares[0].share.x,
+            y: math::interpolate(&points),
+            threshold: Some(self.0),
+            group: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubShare;
+    use crate::{FieldShare, Share, Sharks, SharksError, GF65536};
+
+    fn reshare<F: crate::Field>(
+        old: &Sharks,
+        new: &Sharks,
+        quorum: &[FieldShare<F>],
+        n: usize,
+    ) -> Vec<FieldShare<F>> {
+        let subshares: Vec<Vec<SubShare<F>>> = quorum
+            .iter()
+            .map(|s| new.reshare(s).take(n).collect())
+            .collect();
+        (0..n)
+            .map(|i| {
+                new.combine_reshared(old.0, subshares.iter().map(|s| &s[i]))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reshare_works() {
+        let (old, new) = (Sharks(3), Sharks(4));
+        let shares: Vec<Share> = old.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let new_shares = reshare(&old, &new, &shares[1..4], 7);
+        assert_eq!(new_shares[6].threshold(), Some(4));
+        assert_eq!(new.recover(&new_shares[3..]).unwrap(), vec![1, 2, 3, 4]);
+        assert!(new.recover(&new_shares[4..]).is_err());
+
+        let (old, new) = (Sharks(2), Sharks(2));
+        let shares: Vec<FieldShare<GF65536>> = old.dealer_in(&[1, 2, 3]).take(2).collect();
+        let new_shares = reshare(&old, &new, &shares, 2);
+        assert_eq!(new.recover_in(&new_shares).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn combine_reshared_err() {
+        let (old, new) = (Sharks(3), Sharks(2));
+        let shares: Vec<Share> = old.dealer(&[1, 2, 3, 4]).take(3).collect();
+        let subshares: Vec<Vec<SubShare<_>>> = shares
+            .iter()
+            .map(|s| new.reshare(s).take(2).collect())
+            .collect();
+
+        // Not enough old participants
+        assert_eq!(
+            new.combine_reshared(old.0, subshares[1..].iter().map(|s| &s[0]))
+                .err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        // Sub-shares for different new participants
+        assert!(new
+            .combine_reshared(
+                old.0,
+                vec![&subshares[0][0], &subshares[1][1], &subshares[2][0]]
+            )
+            .is_err());
+        // Sub-shares generated with a different threshold
+        assert_eq!(
+            Sharks(3)
+                .combine_reshared(old.0, subshares.iter().map(|s| &s[0]))
+                .err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// Robust secret sharing in the fashion of Rabin and Ben-Or, authenticating shares with HMAC-SHA256.
+// Every participant holds a key for each of the other shares, and each share carries the tags computed
+// over its bytes with the keys held by the others. On recovery a share is only used if at least
+// threshold participants, itself included, vouch for it: forging a share requires the keys of honest
+// participants, so as long as less than threshold shares are malicious, forged ones are rejected.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+// Length in bytes of the keys and tags
+const MAC_BYTES: usize = 32;
+
+fn mac(key: &[u8; MAC_BYTES], share: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(share);
+    mac
+}
+
+fn tag(key: &[u8; MAC_BYTES], share: &[u8]) -> [u8; MAC_BYTES] {
+    mac(key, share).finalize().into_bytes().into()
+}
+
+/// A share authenticated by the other participants, see `Sharks::dealer_robust`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone)]
+pub struct RobustShare {
+    /// The underlying share.
+    pub share: Share,
+    // Keys verifying the share at `x = i + 1`
+    keys: Vec<[u8; MAC_BYTES]>,
+    // Tags of the share computed with the key held by the participant at `x = i + 1`
+    tags: Vec<[u8; MAC_BYTES]>,
+}
+
+/// Prints the share, whose `y` values are redacted, and the number of keys and tags, which are redacted as well.
+impl fmt::Debug for RobustShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RobustShare")
+            .field("share", &self.share)
+            .field("keys", &format_args!("<{} redacted>", self.keys.len()))
+            .field("tags", &format_args!("<{} redacted>", self.tags.len()))
+            .finish()
+    }
+}
+
+impl RobustShare {
+    // Checks whether `self` vouches for `other`, using the key it holds for it
+    fn vouches(&self, other: &RobustShare, other_bytes: &[u8]) -> bool {
+        let (i, j) = (self.share.x.0 as usize - 1, other.share.x.0 as usize - 1);
+        match (self.keys.get(j), other.tags.get(i)) {
+            (Some(key), Some(tag)) => mac(key, other_bytes).verify_slice(tag).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Wipes the share and its keys from memory.
+/// This is done automatically when the share is dropped.
+impl Zeroize for RobustShare {
+    fn zeroize(&mut self) {
+        self.share.zeroize();
+        self.keys.zeroize();
+        self.tags.zeroize();
+    }
+}
+
+impl Drop for RobustShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for RobustShare {}
+
+/// Obtains a byte vector from a `RobustShare` instance: `n || threshold || keys || tags || share`,
+/// being `n` the number of participants, `threshold` the one of the share or zero if unknown,
+/// and each key and tag 32 bytes long.
+impl From<&RobustShare> for Vec<u8> {
+    fn from(s: &RobustShare) -> Vec<u8> {
+        let mut bytes = vec![s.keys.len() as u8, s.share.threshold().unwrap_or(0)];
+        s.keys
+            .iter()
+            .chain(s.tags.iter())
+            .for_each(|k| bytes.extend_from_slice(k));
+        bytes.extend(Vec::from(&s.share));
+        bytes
+    }
+}
+
+/// Obtains a `RobustShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for RobustShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<RobustShare, Self::Error> {
+        let (n, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        let (threshold, s) = s.split_first().ok_or(SharksError::InvalidLength)?;
+        let macs = 2 * *n as usize * MAC_BYTES;
+        if *n == 0 || s.len() <= macs {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let mut macs = s[..macs].chunks(MAC_BYTES).map(|c| {
+            let mut mac = [0u8; MAC_BYTES];
+            mac.copy_from_slice(c);
+            mac
+        });
+        let keys = macs.by_ref().take(*n as usize).collect();
+        let tags = macs.collect();
+        let mut share = Share::try_from(&s[2 * *n as usize * MAC_BYTES..])?;
+        if share.x.0 > *n {
+            return Err(SharksError::InvalidIndex);
+        }
+        share.threshold = Some(*threshold).filter(|t| *t != 0);
+        Ok(RobustShare { share, keys, tags })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns `n` robust shares, authenticated by each other.
+    /// Each share holds `2 * n` keys and tags of 32 bytes, so the number of shares is fixed upfront.
+    /// An `Err` is returned if `n` is lower than the threshold.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, RobustShare, GF256 };
+    /// let sharks = Sharks(3);
+    /// let mut shares = sharks.dealer_robust(&[1, 2, 3], 5).unwrap();
+    /// // A malicious participant tampers with its share
+    /// shares[0].share.y[0] = shares[0].share.y[0] + GF256(42);
+    /// assert_eq!(sharks.recover_robust(&shares).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn dealer_robust(&self, secret: &[u8], n: u8) -> Result<Vec<RobustShare>, SharksError> {
+        if n < self.0 {
+            return Err(SharksError::TooFewShares {
+                min: self.0 as usize,
+            });
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut shares: Vec<RobustShare> = self
+            .dealer(secret)
+            .take(n as usize)
+            .map(|share| {
+                let mut keys = vec![[0u8; MAC_BYTES]; n as usize];
+                keys.iter_mut().for_each(|k| rng.fill_bytes(k));
+                RobustShare {
+                    share,
+                    keys,
+                    tags: Vec::with_capacity(n as usize),
+                }
+            })
+            .collect();
+
+        for j in 0..shares.len() {
+            let mut bytes = Vec::from(&shares[j].share);
+            let tags: Vec<[u8; MAC_BYTES]> =
+                shares.iter().map(|s| tag(&s.keys[j], &bytes)).collect();
+            shares[j].tags = tags;
+            bytes.zeroize();
+        }
+        Ok(shares)
+    }
+
+    /// Given an iterable collection of robust shares, recovers the original secret using only the shares
+    /// vouched for by at least threshold participants. Forged or corrupted shares are rejected as long as
+    /// they are less than the threshold, since they cannot obtain the tags of the honest participants.
+    /// Identical copies of a share are ignored, so that each participant vouches once, while different shares
+    /// at the same `x` coordinate return an `Err`.
+    /// An `Err` is returned as well if a share `x` coordinate is zero, if less than threshold distinct shares
+    /// are given, or less than threshold of them are authentic.
+    pub fn recover_robust<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a RobustShare>,
+        T::IntoIter: Iterator<Item = &'a RobustShare>,
+    {
+        // Copies would otherwise vouch several times for the same forged share
+        let mut distinct: Vec<&RobustShare> = Vec::new();
+        let mut provided = 0;
+        for s in shares {
+            provided += 1;
+            if s.share.x.0 == 0 {
+                return Err(SharksError::ZeroShareX);
+            }
+            match distinct.iter().find(|d| d.share.x == s.share.x) {
+                Some(d) if d.share.y != s.share.y || d.keys != s.keys || d.tags != s.tags => {
+                    return Err(SharksError::DuplicateShareX);
+                }
+                Some(_) => {}
+                None => distinct.push(s),
+            }
+        }
+        let shares = distinct;
+        if shares.len() < self.0 as usize {
+            return Err(SharksError::NotEnoughShares {
+                needed: self.0 as usize,
+                got: shares.len(),
+                provided,
+            });
+        }
+
+        let authentic: Vec<&Share> = shares
+            .iter()
+            .filter(|s| {
+                let mut bytes = Vec::from(&s.share);
+                let vouches = shares.iter().filter(|v| v.vouches(s, &bytes)).count();
+                bytes.zeroize();
+                vouches >= self.0 as usize
+            })
+            .map(|s| &s.share)
+            .collect();
+
+        if authentic.len() < self.0 as usize {
+            return Err(SharksError::TooManyErrors);
+        }
+        self.recover(authentic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tag, RobustShare};
+    use crate::{Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn robust_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..40).collect();
+        let shares = sharks.dealer_robust(&secret, 5).unwrap();
+        assert_eq!(sharks.recover_robust(&shares).unwrap(), secret);
+        assert_eq!(sharks.recover_robust(&shares[2..]).unwrap(), secret);
+
+        let shares: Vec<RobustShare> = shares
+            .iter()
+            .map(|s| RobustShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_robust(&shares[1..4]).unwrap(), secret);
+        assert!(shares.iter().all(|s| s.share.threshold() == Some(3)));
+
+        // Parsed and in-memory shares can be mixed
+        let dealt = sharks.dealer_robust(&secret, 3).unwrap();
+        let parsed = RobustShare::try_from(Vec::from(&dealt[0]).as_slice()).unwrap();
+        let mixed = vec![&parsed, &dealt[1], &dealt[2]];
+        assert_eq!(sharks.recover_robust(mixed).unwrap(), secret);
+    }
+
+    #[test]
+    fn forged_shares_rejected_works() {
+        let sharks = Sharks(3);
+        let mut shares = sharks.dealer_robust(&[1, 2, 3], 6).unwrap();
+
+        // Two colluding participants tamper with their shares and vouch for each other
+        shares[0].share.y[1] = shares[0].share.y[1] + GF256(1);
+        shares[4].share.y[2] = shares[4].share.y[2] + GF256(9);
+        for (i, j) in [(0, 0), (0, 4), (4, 0), (4, 4)].iter().copied() {
+            let bytes = Vec::from(&shares[i].share);
+            shares[i].tags[j] = tag(&shares[j].keys[i], &bytes);
+        }
+        assert_ne!(
+            sharks.recover(shares.iter().map(|s| &s.share)).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(sharks.recover_robust(&shares).unwrap(), vec![1, 2, 3]);
+
+        // Without enough authentic shares left, the forgery is detected
+        let forged = vec![shares[0].clone(), shares[1].clone(), shares[4].clone()];
+        assert_eq!(
+            sharks.recover_robust(&forged).err(),
+            Some(SharksError::TooManyErrors)
+        );
+        assert_eq!(
+            sharks.recover_robust(&shares[..2]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+    }
+
+    #[test]
+    fn duplicated_forged_shares_rejected_works() {
+        let sharks = Sharks(3);
+        let shares = sharks.dealer_robust(&[1, 2, 3], 5).unwrap();
+
+        // A malicious participant forges its share, vouches for it and submits several copies
+        let mut forged = shares[0].clone();
+        forged.share.y[0] = forged.share.y[0] + GF256(124);
+        let bytes = Vec::from(&forged.share);
+        forged.tags[0] = tag(&forged.keys[0], &bytes);
+        let pool = vec![
+            &forged, &forged, &forged, &shares[1], &shares[2], &shares[3],
+        ];
+        assert_eq!(sharks.recover_robust(pool).unwrap(), vec![1, 2, 3]);
+
+        let pool = vec![&forged, &forged, &forged, &shares[1], &shares[2]];
+        assert_eq!(
+            sharks.recover_robust(pool).err(),
+            Some(SharksError::TooManyErrors)
+        );
+        let pool = vec![&forged, &forged, &forged];
+        assert_eq!(
+            sharks.recover_robust(pool).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 1,
+                provided: 3
+            })
+        );
+
+        // A forged share conflicting with the honest one at the same `x` is rejected
+        let pool = vec![&forged, &shares[0], &shares[1], &shares[2]];
+        assert_eq!(
+            sharks.recover_robust(pool).err(),
+            Some(SharksError::DuplicateShareX)
+        );
+
+        // The share `x` coordinate is public, a zero one is rejected instead of indexing the keys with it
+        let mut zero = shares[4].clone();
+        zero.share.x = GF256(0);
+        let pool = vec![&shares[0], &shares[1], &shares[2], &zero];
+        assert_eq!(
+            sharks.recover_robust(pool).err(),
+            Some(SharksError::ZeroShareX)
+        );
+    }
+
+    #[test]
+    fn robust_share_bytes_err() {
+        assert!(Sharks(3).dealer_robust(&[1], 2).is_err());
+        assert_eq!(
+            RobustShare::try_from(&[][..]).err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            RobustShare::try_from(&[1; 66][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+
+        let mut bytes = Vec::from(&Sharks(1).dealer_robust(&[1], 1).unwrap()[0]);
+        bytes[66] = 2;
+        assert_eq!(
+            RobustShare::try_from(bytes.as_slice()).err(),
+            Some(SharksError::InvalidIndex)
+        );
+    }
+}
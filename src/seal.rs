@@ -0,0 +1,250 @@
+//! Shares sealed to a recipient's X25519 public key, so that they can be distributed over untrusted channels.
+//!
+//! Each share is encrypted ECIES-style: an ephemeral X25519 key agreement with the recipient key gives,
+//! through HKDF-SHA256, a ChaCha20-Poly1305 key only used once. Sealed shares are serialized as
+//! `version || ephemeral public key || ciphertext`, the plaintext being `threshold || x || y`,
+//! with a zero threshold if unknown. Only the holder of the recipient secret key can open them,
+//! and tampered ones are rejected.
+//!
+//! Example:
+//! ```
+//! # use sharks::{ Sharks, Share, seal };
+//! # use rand::RngCore;
+//! let mut rng = rand::rngs::OsRng;
+//! let mut secret_keys = [[0u8; 32]; 3];
+//! secret_keys.iter_mut().for_each(|k| rng.fill_bytes(k));
+//! let public_keys: Vec<[u8; 32]> = secret_keys.iter().map(seal::public_key).collect();
+//!
+//! let sharks = Sharks(2);
+//! let sealed = sharks.dealer_sealed(&[1, 2, 3], &public_keys, &mut rng).unwrap();
+//! let shares: Vec<Share> = sealed[1..]
+//!     .iter()
+//!     .zip(secret_keys[1..].iter())
+//!     .map(|(s, k)| Share::open(s, k).unwrap())
+//!     .collect();
+//! assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+//! // The first share was not sealed to the second participant
+//! assert!(Share::open(&sealed[0], &secret_keys[1]).is_err());
+//! ```
+
+use std::convert::TryFrom;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use crate::{Share, Sharks, SharksError};
+
+/// Version byte prefixed to sealed shares.
+pub const SEALED_VERSION: u8 = 1;
+
+// Length in bytes of X25519 keys
+const KEY_BYTES: usize = 32;
+
+// Domain separation of the derived encryption keys
+const INFO: &[u8] = b"sharks sealed share";
+
+/// Returns the X25519 public key corresponding to the `secret_key` of a recipient,
+/// to which shares can then be sealed.
+pub fn public_key(secret_key: &[u8; KEY_BYTES]) -> [u8; KEY_BYTES] {
+    MontgomeryPoint::mul_base_clamped(*secret_key).to_bytes()
+}
+
+// Derives the encryption key of a share sealed with `ephemeral` to `recipient`, given their shared secret
+fn cipher(
+    shared: MontgomeryPoint,
+    ephemeral: &[u8; KEY_BYTES],
+    recipient: &[u8; KEY_BYTES],
+) -> Result<ChaCha20Poly1305, SharksError> {
+    let mut shared = shared.to_bytes();
+    // Low order points would give a shared secret known to anyone
+    if shared == [0; KEY_BYTES] {
+        return Err(SharksError::InvalidElement);
+    }
+
+    let mut info = INFO.to_vec();
+    info.extend_from_slice(ephemeral);
+    info.extend_from_slice(recipient);
+    let mut key = [0u8; KEY_BYTES];
+    Hkdf::<Sha256>::new(None, &shared)
+        .expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+
+    key.zeroize();
+    shared.zeroize();
+    Ok(cipher)
+}
+
+impl Share {
+    /// Seals the share to the `recipient` X25519 public key, see the `seal` module.
+    /// An `Err` is returned if the key is a low order point, which no honest recipient uses.
+    pub fn seal<R: CryptoRng + RngCore>(
+        &self,
+        recipient: &[u8; KEY_BYTES],
+        rng: &mut R,
+    ) -> Result<Vec<u8>, SharksError> {
+        let mut ephemeral_secret = [0u8; KEY_BYTES];
+        rng.fill_bytes(&mut ephemeral_secret);
+        let ephemeral = MontgomeryPoint::mul_base_clamped(ephemeral_secret).to_bytes();
+        let shared = MontgomeryPoint(*recipient).mul_clamped(ephemeral_secret);
+        ephemeral_secret.zeroize();
+        let cipher = cipher(shared, &ephemeral, recipient)?;
+
+        let mut plaintext = self.to_sealed_bytes();
+        let mut sealed = vec![SEALED_VERSION];
+        sealed.extend_from_slice(&ephemeral);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: &plaintext,
+                    aad: &sealed,
+                },
+            )
+            .expect("shares are shorter than the ChaCha20-Poly1305 limit");
+        sealed.extend(ciphertext);
+
+        plaintext.zeroize();
+        Ok(sealed)
+    }
+
+    /// Opens a share sealed with `Share::seal` using the recipient X25519 `secret_key`.
+    /// An `Err` is returned if the version is unknown, or if the share was sealed to another key or tampered with.
+    pub fn open(sealed: &[u8], secret_key: &[u8; KEY_BYTES]) -> Result<Share, SharksError> {
+        let (version, sealed) = sealed.split_first().ok_or(SharksError::EmptyShare)?;
+        if *version != SEALED_VERSION {
+            return Err(SharksError::UnknownVersion(*version));
+        } else if sealed.len() < KEY_BYTES {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let (ephemeral, ciphertext) = sealed.split_at(KEY_BYTES);
+        let ephemeral = <[u8; KEY_BYTES]>::try_from(ephemeral).expect("the length was checked");
+        let shared = MontgomeryPoint(ephemeral).mul_clamped(*secret_key);
+        let cipher = cipher(shared, &ephemeral, &public_key(secret_key))?;
+
+        let mut aad = vec![SEALED_VERSION];
+        aad.extend_from_slice(&ephemeral);
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| SharksError::InvalidCiphertext)?;
+
+        let share = Share::from_sealed_bytes(&plaintext);
+        plaintext.zeroize();
+        share
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice and the X25519 public keys of the participants, returns
+    /// a share of the secret sealed to each of them, in the same order. See the `seal` module.
+    /// If there are more than 255 recipients, or one of the keys is invalid, an `Err` is returned.
+    pub fn dealer_sealed<R: CryptoRng + RngCore>(
+        &self,
+        secret: &[u8],
+        recipients: &[[u8; KEY_BYTES]],
+        rng: &mut R,
+    ) -> Result<Vec<Vec<u8>>, SharksError> {
+        if recipients.len() > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        }
+        let shares: Vec<Share> = self
+            .dealer_with_rng(rng, secret)
+            .take(recipients.len())
+            .collect();
+        shares
+            .iter()
+            .zip(recipients.iter())
+            .map(|(share, recipient)| share.seal(recipient, rng))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{public_key, SEALED_VERSION};
+    use crate::{Share, Sharks, SharksError};
+    use rand::RngCore;
+    use std::convert::TryFrom;
+
+    fn key_pair() -> ([u8; 32], [u8; 32]) {
+        let mut secret_key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret_key);
+        (secret_key, public_key(&secret_key))
+    }
+
+    #[test]
+    fn seal_integration_works() {
+        let keys: Vec<([u8; 32], [u8; 32])> = (0..5).map(|_| key_pair()).collect();
+        let public_keys: Vec<[u8; 32]> = keys.iter().map(|k| k.1).collect();
+
+        let sharks = Sharks(3);
+        let sealed = sharks
+            .dealer_sealed(&[1, 2, 3, 4], &public_keys, &mut rand::thread_rng())
+            .unwrap();
+        assert_eq!(sealed.len(), 5);
+        assert_eq!(sealed[0].len(), 1 + 32 + 1 + 5 + 16);
+        let shares: Vec<Share> = sealed
+            .iter()
+            .zip(keys.iter())
+            .map(|(s, k)| Share::open(s, &k.0).unwrap())
+            .collect();
+        assert_eq!(shares[4].x.0, 5);
+        assert_eq!(shares[4].threshold(), Some(3));
+        assert_eq!(sharks.recover(&shares[2..]).unwrap(), vec![1, 2, 3, 4]);
+
+        // Shares without a known threshold
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let sealed = share.seal(&keys[0].1, &mut rand::thread_rng()).unwrap();
+        let share = Share::open(&sealed, &keys[0].0).unwrap();
+        assert_eq!(share.threshold(), None);
+        assert_eq!(Vec::from(&share), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn seal_err() {
+        let (secret_key, public) = key_pair();
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let sealed = share.seal(&public, &mut rand::thread_rng()).unwrap();
+
+        assert_eq!(
+            share.seal(&[0; 32], &mut rand::thread_rng()).err(),
+            Some(SharksError::InvalidElement)
+        );
+        assert_eq!(
+            Share::open(&sealed, &key_pair().0).err(),
+            Some(SharksError::InvalidCiphertext)
+        );
+        for i in 0..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert!(Share::open(&tampered, &secret_key).is_err());
+        }
+
+        let cases: [(&[u8], SharksError); 3] = [
+            (&[], SharksError::EmptyShare),
+            (&[SEALED_VERSION + 1], SharksError::UnknownVersion(2)),
+            (&[SEALED_VERSION, 0], SharksError::InvalidLength),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(Share::open(bytes, &secret_key).err(), Some(*err));
+        }
+        assert_eq!(
+            Sharks(2)
+                .dealer_sealed(&[1], &[public; 256], &mut rand::thread_rng())
+                .err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+    }
+}
@@ -0,0 +1,72 @@
+// Integration with the `secrecy` crate, so that secrets are not exposed as plaintext at the API boundary.
+// The secret to split is only exposed while dealing it, and the recovered one is moved into a `SecretSlice`,
+// which zeroes it on drop, without leaving copies of it behind.
+
+use secrecy::{ExposeSecret, SecretSlice};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+impl Sharks {
+    /// Same as `dealer`, the `secret` being wrapped by the `secrecy` crate, such as a `SecretSlice<u8>`.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// use sharks::secrecy::{ExposeSecret, SecretSlice};
+    ///
+    /// let sharks = Sharks(2);
+    /// let secret = SecretSlice::from(vec![1, 2, 3]);
+    /// let shares: Vec<Share> = sharks.dealer_secret(&secret).take(3).collect();
+    /// let recovered = sharks.recover_secret(&shares[1..]).unwrap();
+    /// assert_eq!(recovered.expose_secret(), &[1, 2, 3]);
+    /// ```
+    pub fn dealer_secret<S: ExposeSecret<[u8]>>(&self, secret: &S) -> impl Iterator<Item = Share> {
+        self.dealer(secret.expose_secret())
+    }
+
+    /// Same as `recover`, the recovered secret being wrapped in a `SecretSlice<u8>`.
+    pub fn recover_secret<'a, S, T>(&self, shares: T) -> Result<SecretSlice<u8>, SharksError>
+    where
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut secret = self.recover(shares)?;
+        // Copied to an exact size allocation, as shrinking the vector could leave a copy in the freed memory
+        let boxed: Box<[u8]> = secret.as_slice().into();
+        secret.zeroize();
+        Ok(SecretSlice::from(boxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Share, Sharks, SharksError};
+    use secrecy::{ExposeSecret, SecretSlice};
+
+    #[test]
+    fn secret_integration_works() {
+        let sharks = Sharks(3);
+        let secret = SecretSlice::from((0..40).collect::<Vec<u8>>());
+        let shares: Vec<Share> = sharks.dealer_secret(&secret).take(5).collect();
+        let recovered = sharks.recover_secret(&shares[2..]).unwrap();
+        assert_eq!(recovered.expose_secret(), secret.expose_secret());
+
+        // Shares are regular ones
+        assert_eq!(
+            sharks.recover(&shares[..3]).unwrap(),
+            secret.expose_secret()
+        );
+        assert_eq!(
+            sharks.recover_secret(&shares[..2]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+    }
+}
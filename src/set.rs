@@ -0,0 +1,355 @@
+// Collections of the shares of one secret, gathered from several sources before recovering it.
+// Shares are kept in envelopes, whose label identifies the secret they belong to, and every added one is checked
+// against the ones already in the set: identical copies are ignored, while shares at the same `x` coordinate with
+// different values, other lengths or conflicting metadata are rejected. Sets are serialized as a single bundle.
+
+use std::convert::TryFrom;
+
+use super::envelope::ShareEnvelope;
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// Version byte prefixed to serialized `ShareSet`s.
+pub const SHARE_SET_VERSION: u8 = 1;
+
+/// Consistent set of the shares of a secret, each of them at a distinct `x` coordinate,
+/// along with their metadata. Can be serialized to and from a byte array.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, ShareEnvelope, ShareSet };
+/// # use std::convert::TryFrom;
+/// let sharks = Sharks(3);
+/// let envelopes: Vec<ShareEnvelope> = sharks
+///     .dealer(&[1, 2, 3])
+///     .take(4)
+///     .map(|share| {
+///         let mut envelope = ShareEnvelope::new(share);
+///         envelope.label = Some("vault".into());
+///         envelope
+///     })
+///     .collect();
+///
+/// let mut set = ShareSet::new();
+/// assert_eq!(set.insert(envelopes[0].clone()), Ok(true));
+/// assert_eq!(set.insert(envelopes[0].clone()), Ok(false));
+/// assert_eq!(set.remaining(), Some(2));
+///
+/// let mut other = ShareSet::new();
+/// other.insert(envelopes[2].clone()).unwrap();
+/// other.insert(envelopes[3].clone()).unwrap();
+/// assert_eq!(set.merge(&other), Ok(2));
+/// assert_eq!(set.label(), Some("vault"));
+///
+/// let set = ShareSet::try_from(Vec::from(&set).as_slice()).unwrap();
+/// assert_eq!(set.len(), 3);
+/// assert_eq!(set.recover().unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ShareSet {
+    envelopes: Vec<ShareEnvelope>,
+}
+
+// Reads a 4 bytes big endian length from the start of `s`, advancing it
+fn length(s: &mut &[u8]) -> Result<usize, SharksError> {
+    if s.len() < 4 {
+        return Err(SharksError::InvalidLength);
+    }
+    let (len, rest) = s.split_at(4);
+    *s = rest;
+    Ok(u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize)
+}
+
+// Checks two optional metadata values agree when both are known
+fn agree<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+impl ShareSet {
+    /// Returns an empty set.
+    pub fn new() -> ShareSet {
+        ShareSet::default()
+    }
+
+    /// Adds the share of the `envelope`, returning `false` if an identical copy of it is already in the set,
+    /// in which case the set is left unchanged. An `Err` is returned, and the envelope discarded, if another share
+    /// is at its `x` coordinate, its length differs from the others, or its label, threshold, count or group
+    /// identifier differs from the ones known so far.
+    pub fn insert(&mut self, mut envelope: ShareEnvelope) -> Result<bool, SharksError> {
+        envelope.threshold = envelope.threshold.or(envelope.share.threshold());
+        envelope.share.threshold = envelope.threshold;
+
+        if !agree(&envelope.threshold, &self.threshold()) {
+            return Err(SharksError::InconsistentThresholds);
+        } else if !agree(&envelope.label.as_deref(), &self.label())
+            || !agree(&envelope.count, &self.count())
+        {
+            return Err(SharksError::MixedShares);
+        } else if !agree(&envelope.share.group(), &self.group()) {
+            return Err(SharksError::MixedGroups);
+        } else if self
+            .envelopes
+            .first()
+            .is_some_and(|e| e.share.y.len() != envelope.share.y.len())
+        {
+            return Err(SharksError::InconsistentLengths);
+        }
+        match self
+            .envelopes
+            .iter()
+            .find(|e| e.share.x == envelope.share.x)
+        {
+            Some(e) if e.share.y == envelope.share.y => Ok(false),
+            Some(_) => Err(SharksError::DuplicateShareX),
+            None => {
+                self.envelopes.push(envelope);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Same as `insert`, for a share without metadata.
+    pub fn insert_share(&mut self, share: Share) -> Result<bool, SharksError> {
+        self.insert(ShareEnvelope::new(share))
+    }
+
+    /// Adds the shares of `other`, returning the number of them not already in the set.
+    /// An `Err` is returned, and the set left unchanged, if any of them cannot be inserted.
+    pub fn merge(&mut self, other: &ShareSet) -> Result<usize, SharksError> {
+        let mut merged = self.clone();
+        let mut added = 0;
+        for e in other.envelopes.iter() {
+            added += merged.insert(e.clone())? as usize;
+        }
+        *self = merged;
+        Ok(added)
+    }
+
+    /// Returns the number of distinct shares in the set.
+    pub fn len(&self) -> usize {
+        self.envelopes.len()
+    }
+
+    /// Returns `true` if the set holds no shares.
+    pub fn is_empty(&self) -> bool {
+        self.envelopes.is_empty()
+    }
+
+    /// Returns an `Iterator` along the envelopes of the shares, in the order they were added.
+    pub fn envelopes(&self) -> impl Iterator<Item = &ShareEnvelope> {
+        self.envelopes.iter()
+    }
+
+    /// Returns an `Iterator` along the shares, in the order they were added.
+    pub fn shares(&self) -> impl Iterator<Item = &Share> {
+        self.envelopes.iter().map(|e| &e.share)
+    }
+
+    /// Returns the label of the secret the shares belong to, if any of them has one.
+    pub fn label(&self) -> Option<&str> {
+        self.envelopes.iter().find_map(|e| e.label.as_deref())
+    }
+
+    /// Returns the threshold of the shares, if any of them knows it.
+    pub fn threshold(&self) -> Option<u8> {
+        self.envelopes.iter().find_map(|e| e.threshold)
+    }
+
+    /// Returns the total number of shares of the secret, if any of them knows it.
+    pub fn count(&self) -> Option<u8> {
+        self.envelopes.iter().find_map(|e| e.count)
+    }
+
+    /// Returns the group identifier of the split the shares were dealt by, if known, see `Share::group`.
+    pub fn group(&self) -> Option<u64> {
+        self.envelopes.iter().find_map(|e| e.share.group())
+    }
+
+    /// Returns the number of shares still needed to recover the secret, if the threshold is known.
+    pub fn remaining(&self) -> Option<usize> {
+        self.threshold()
+            .map(|t| (t as usize).saturating_sub(self.len()))
+    }
+
+    /// Recovers the secret at the threshold of the shares. An `Err` is returned if it is unknown,
+    /// see `Sharks::recover` to recover at a given threshold, or if there are not enough shares.
+    pub fn recover(&self) -> Result<Vec<u8>, SharksError> {
+        let threshold = self.threshold().ok_or(SharksError::InvalidThreshold)?;
+        Sharks(threshold).recover(self.shares())
+    }
+}
+
+/// Obtains a byte vector from a `ShareSet` instance: `version || count || envelopes`,
+/// each envelope being serialized as `length || envelope`, see `Vec::from(&ShareEnvelope)`,
+/// the count and lengths being 4 bytes big endian integers.
+impl From<&ShareSet> for Vec<u8> {
+    fn from(set: &ShareSet) -> Vec<u8> {
+        let mut bytes = vec![SHARE_SET_VERSION];
+        bytes.extend_from_slice(&(set.len() as u32).to_be_bytes());
+        for e in set.envelopes.iter() {
+            let envelope = Vec::from(e);
+            bytes.extend_from_slice(&(envelope.len() as u32).to_be_bytes());
+            bytes.extend(envelope);
+        }
+        bytes
+    }
+}
+
+/// Obtains a `ShareSet` instance from a byte slice, validating its contents.
+/// An `Err` is returned if the version is unknown, the lengths are malformed, an envelope cannot be parsed,
+/// or the shares are not consistent, as in `ShareSet::insert`.
+impl TryFrom<&[u8]> for ShareSet {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<ShareSet, Self::Error> {
+        let (version, mut s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        if *version != SHARE_SET_VERSION {
+            return Err(SharksError::UnknownVersion(*version));
+        }
+
+        let count = length(&mut s)?;
+        let mut set = ShareSet::new();
+        for _ in 0..count {
+            let len = length(&mut s)?;
+            if s.len() < len {
+                return Err(SharksError::InvalidLength);
+            }
+            let (envelope, rest) = s.split_at(len);
+            set.insert(ShareEnvelope::try_from(envelope)?)?;
+            s = rest;
+        }
+        if !s.is_empty() {
+            return Err(SharksError::InvalidLength);
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShareSet, SHARE_SET_VERSION};
+    use crate::{Share, ShareEnvelope, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn set_integration_works() {
+        let sharks = Sharks(3);
+        let shares: Vec<Share> = sharks.dealer(&[1, 2, 3, 4]).take(5).collect();
+        let mut set = ShareSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.remaining(), None);
+
+        // Bare shares and envelopes are accepted together
+        assert_eq!(set.insert_share(shares[1].clone()), Ok(true));
+        let mut envelope = ShareEnvelope::new(shares[4].clone());
+        envelope.label = Some("db".into());
+        envelope.count = Some(5);
+        envelope.participant = Some("alice".into());
+        assert_eq!(set.insert(envelope), Ok(true));
+        assert_eq!(set.insert_share(shares[4].clone()), Ok(false));
+        assert_eq!(set.remaining(), Some(1));
+        assert_eq!(set.count(), Some(5));
+        assert!(set.recover().is_err());
+
+        let mut other = ShareSet::new();
+        other.insert_share(shares[1].clone()).unwrap();
+        other.insert_share(shares[0].clone()).unwrap();
+        assert_eq!(set.merge(&other), Ok(1));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.remaining(), Some(0));
+
+        let bytes = Vec::from(&set);
+        assert_eq!(bytes[0], SHARE_SET_VERSION);
+        let parsed = ShareSet::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed.label(), Some("db"));
+        assert_eq!(
+            parsed.envelopes().nth(1).unwrap().participant.as_deref(),
+            Some("alice")
+        );
+        assert_eq!(parsed.recover(), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(
+            ShareSet::try_from(Vec::from(&ShareSet::new()).as_slice())
+                .unwrap()
+                .len(),
+            0
+        );
+
+        // Group identifiers survive persistence
+        let mut grouped = ShareSet::new();
+        for share in sharks.dealer_grouped(&[5, 6]).take(3) {
+            grouped.insert_share(share).unwrap();
+        }
+        let parsed = ShareSet::try_from(Vec::from(&grouped).as_slice()).unwrap();
+        assert!(parsed.group().is_some());
+        assert_eq!(parsed.group(), grouped.group());
+        assert_eq!(parsed.recover(), Ok(vec![5, 6]));
+    }
+
+    #[test]
+    fn set_err() {
+        let shares: Vec<Share> = Sharks(2).dealer(&[1, 2, 3]).take(3).collect();
+        let mut set = ShareSet::new();
+        let mut envelope = ShareEnvelope::new(shares[0].clone());
+        envelope.label = Some("a".into());
+        set.insert(envelope).unwrap();
+
+        let mut other = ShareEnvelope::new(shares[1].clone());
+        other.label = Some("b".into());
+        assert_eq!(set.insert(other), Err(SharksError::MixedShares));
+        let third: Vec<Share> = Sharks(3).dealer(&[1, 2, 3]).take(3).collect();
+        assert_eq!(
+            set.insert_share(third[2].clone()),
+            Err(SharksError::InconsistentThresholds)
+        );
+        let short: Vec<Share> = Sharks(2).dealer(&[1, 2]).take(3).collect();
+        assert_eq!(
+            set.insert_share(short[2].clone()),
+            Err(SharksError::InconsistentLengths)
+        );
+        let mut conflicting = shares[0].clone();
+        conflicting.y[0].0 ^= 1;
+        assert_eq!(
+            set.insert_share(conflicting.clone()),
+            Err(SharksError::DuplicateShareX)
+        );
+
+        // Failed merges leave the set unchanged
+        let mut other = ShareSet::new();
+        other.insert_share(shares[2].clone()).unwrap();
+        other.insert_share(conflicting).unwrap();
+        assert_eq!(set.merge(&other), Err(SharksError::DuplicateShareX));
+        assert_eq!(set.len(), 1);
+
+        let mut grouped = ShareSet::new();
+        let a: Vec<Share> = Sharks(2).dealer_grouped(&[1, 2]).take(2).collect();
+        let b: Vec<Share> = Sharks(2).dealer_grouped(&[1, 2]).take(2).collect();
+        grouped.insert_share(a[0].clone()).unwrap();
+        assert_eq!(
+            grouped.insert_share(b[1].clone()),
+            Err(SharksError::MixedGroups)
+        );
+
+        let bytes = Vec::from(&set);
+        let cases: [(&[u8], SharksError); 4] = [
+            (&[], SharksError::EmptyShare),
+            (&[SHARE_SET_VERSION + 1], SharksError::UnknownVersion(2)),
+            (&bytes[..bytes.len() - 1], SharksError::InvalidLength),
+            (
+                &[SHARE_SET_VERSION, 0, 0, 0, 0, 0],
+                SharksError::InvalidLength,
+            ),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ShareSet::try_from(*bytes).err(), Some(*err));
+        }
+        assert_eq!(
+            ShareSet::new().recover(),
+            Err(SharksError::InvalidThreshold)
+        );
+    }
+}
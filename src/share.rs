@@ -1,9 +1,21 @@
-use super::field::GF256;
+use std::convert::TryFrom;
+use std::fmt;
+
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::crc::crc32;
+#[cfg(feature = "text")]
+use super::encoding;
+use super::error::SharksError;
+use super::field::{Field, GF256};
 
 /// A share used to reconstruct the secret. Can be serialized to and from a byte array.
 ///
 /// Usage example:
 /// ```
+/// use std::convert::TryFrom;
 /// use sharks::{Sharks, Share};
 /// # fn send_to_printer(_: Vec<u8>) {}
 /// # fn ask_shares() -> Vec<Vec<u8>> {vec![vec![1, 2], vec![2, 3], vec![3, 4]]}
@@ -19,52 +31,990 @@ use super::field::GF256;
 ///
 /// // Get share bytes from an external source and recover secret
 /// let shares_bytes: Vec<Vec<u8>> = ask_shares();
-/// let shares: Vec<Share> = shares_bytes.iter().map(|s| Share::from(s.as_slice())).collect();
+/// let shares: Vec<Share> = shares_bytes.iter().map(|s| Share::try_from(s.as_slice()).unwrap()).collect();
 /// let secret = sharks.recover(&shares).unwrap();
+/// ```
+///
+/// Shares obtained from a dealer know the threshold they were generated with,
+/// which can be embedded in their byte representation using the versioned format:
+/// ```
+/// use sharks::{Sharks, Share};
+///
+/// let sharks = Sharks(3);
+/// let bytes: Vec<Vec<u8>> = sharks
+///     .dealer(&[1, 2, 3])
+///     .take(3)
+///     .map(|s| s.to_versioned_bytes().unwrap())
+///     .collect();
+///
+/// let shares: Vec<Share> = bytes.iter().map(|b| Share::from_versioned_bytes(b).unwrap()).collect();
+/// assert_eq!(shares[0].threshold(), Some(3));
+/// let secret = sharks.recover(&shares).unwrap();
+/// ```
+///
+/// Shares stored on paper or unreliable media can also carry a CRC-32 checksum,
+/// so that corrupted bytes are rejected on parsing instead of recovering a wrong secret:
+/// ```
+/// use sharks::{Sharks, Share};
+///
+/// let sharks = Sharks(3);
+/// let share = sharks.dealer(&[1, 2, 3]).next().unwrap();
+/// let mut bytes = share.to_checked_bytes();
+/// assert!(Share::from_checked_bytes(&bytes).is_ok());
+/// bytes[2] ^= 1;
+/// assert!(Share::from_checked_bytes(&bytes).is_err());
+pub type Share = FieldShare<GF256>;
+
+/// A share over the field `F` the secret was split in, see `Share` for the `GF256` one.
+/// Each `x` and `y` element is serialized as `F::BYTES` big endian bytes.
 #[derive(Clone)]
-pub struct Share {
-    pub x: GF256,
-    pub y: Vec<GF256>,
+pub struct FieldShare<F: Field> {
+    pub x: F,
+    pub y: Vec<F>,
+    pub(crate) threshold: Option<u8>,
+    pub(crate) group: Option<u64>,
+}
+
+/// Wipes the share `x` and `y` values from memory.
+/// This is done automatically when the share is dropped.
+impl<F: Field> Zeroize for FieldShare<F> {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.zeroize();
+    }
+}
+
+impl<F: Field> Drop for FieldShare<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<F: Field> ZeroizeOnDrop for FieldShare<F> {}
+
+/// Compares the share `x` and `y` values in constant time, their length excepted, so that checking
+/// received shares against stored ones does not leak them through timing. The embedded thresholds are ignored.
+impl<F: Field> ConstantTimeEq for FieldShare<F> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let (mut a, mut b) = (Vec::from(self), Vec::from(other));
+        let eq = a.ct_eq(&b);
+        {
+            a.zeroize();
+            b.zeroize();
+        }
+        eq
+    }
+}
+
+/// Constant time, see the `ConstantTimeEq` implementation.
+impl<F: Field> PartialEq for FieldShare<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<F: Field> Eq for FieldShare<F> {}
+
+/// Prints the share `x` coordinate, threshold and length, the `y` values being redacted
+/// so that shares do not leak into logs. See `FieldShare::expose_bytes` to obtain them.
+impl<F: Field> fmt::Debug for FieldShare<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldShare")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", self.y.len()))
+            .field("threshold", &self.threshold)
+            .field("group", &self.group)
+            .finish()
+    }
+}
+
+/// Version byte prefixed to shares serialized with `Share::to_versioned_bytes`
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Version byte prefixed to shares serialized with `Share::to_versioned_bytes` which carry a group identifier
+pub const GROUPED_FORMAT_VERSION: u8 = 2;
+
+// Splits versioned share bytes into their threshold, group identifier and `x || y` bytes
+fn split_versioned(s: &[u8]) -> Result<(u8, Option<u64>, &[u8]), SharksError> {
+    match s {
+        [] => Err(SharksError::EmptyShare),
+        [version, ..] if *version != FORMAT_VERSION && *version != GROUPED_FORMAT_VERSION => {
+            Err(SharksError::UnknownVersion(*version))
+        }
+        [_] | [_, 0, ..] => Err(SharksError::InvalidThreshold),
+        [FORMAT_VERSION, threshold, share @ ..] => Ok((*threshold, None, share)),
+        [_, threshold, rest @ ..] if rest.len() >= 8 => {
+            let (group, share) = rest.split_at(8);
+            let mut id = [0; 8];
+            id.copy_from_slice(group);
+            Ok((*threshold, Some(u64::from_be_bytes(id)), share))
+        }
+        _ => Err(SharksError::InvalidLength),
+    }
+}
+
+impl<F: Field> FieldShare<F> {
+    /// Returns the share serialized as `x || y`, like `Vec::from`, explicitly exposing the
+    /// `y` values redacted from the `Debug` output.
+    pub fn expose_bytes(&self) -> Vec<u8> {
+        Vec::from(self)
+    }
+
+    /// Returns the minimum threshold of shares embedded in this one, if known.
+    /// Only shares obtained from a dealer or parsed from the versioned format carry it.
+    pub fn threshold(&self) -> Option<u8> {
+        self.threshold
+    }
+
+    /// Returns the identifier of the split this share was dealt by, if known.
+    /// Only shares obtained from `Sharks::dealer_grouped` or parsed from the versioned format carry it,
+    /// and `recover` refuses to combine shares with different identifiers.
+    pub fn group(&self) -> Option<u64> {
+        self.group
+    }
+
+    /// Serializes the share using the versioned format: `version || threshold || x || y`,
+    /// or `version || threshold || group || x || y` with the `GROUPED_FORMAT_VERSION` if the share carries
+    /// a group identifier, as 8 big endian bytes. An `Err` is returned if the share does not know its threshold.
+    pub fn to_versioned_bytes(&self) -> Result<Vec<u8>, SharksError> {
+        let threshold = self.threshold.ok_or(SharksError::InvalidThreshold)?;
+
+        let mut bytes = Vec::with_capacity((self.y.len() + 1) * F::BYTES + 10);
+        match self.group {
+            Some(group) => {
+                bytes.push(GROUPED_FORMAT_VERSION);
+                bytes.push(threshold);
+                bytes.extend_from_slice(&group.to_be_bytes());
+            }
+            None => {
+                bytes.push(FORMAT_VERSION);
+                bytes.push(threshold);
+            }
+        }
+        bytes.extend(Vec::from(self));
+        Ok(bytes)
+    }
+
+    /// Parses a share serialized with `Share::to_versioned_bytes`.
+    /// An `Err` is returned if the format version is unknown, the threshold is zero, the group identifier
+    /// is truncated or the remaining bytes are not a valid share.
+    pub fn from_versioned_bytes(s: &[u8]) -> Result<FieldShare<F>, SharksError> {
+        let (threshold, group, share) = split_versioned(s)?;
+        let mut share = FieldShare::try_from(share)?;
+        share.threshold = Some(threshold);
+        share.group = group;
+        Ok(share)
+    }
+
+    /// Serializes the share as its bytes followed by their CRC-32 checksum, 4 bytes big endian: `x || y || crc`.
+    pub fn to_checked_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(self);
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+
+    /// Parses a share serialized with `Share::to_checked_bytes`.
+    /// An `Err` is returned if the checksum does not match, or the remaining bytes are not a valid share.
+    pub fn from_checked_bytes(s: &[u8]) -> Result<FieldShare<F>, SharksError> {
+        if s.len() < 4 {
+            return Err(SharksError::InvalidLength);
+        }
+        let (share, crc) = s.split_at(s.len() - 4);
+        if crc32(share).to_be_bytes() != crc {
+            return Err(SharksError::InvalidChecksum);
+        }
+        FieldShare::try_from(share)
+    }
+}
+
+/// Text representations of the share bytes, strictly validated when parsed.
+///
+/// Example:
+/// ```
+/// # use sharks::Share;
+/// # use std::convert::TryFrom;
+/// let share = Share::try_from(&[1, 2, 255][..]).unwrap();
+/// assert_eq!(share.to_hex(), "0102ff");
+/// assert_eq!(share.to_base64(), "AQL/");
+/// assert_eq!(Vec::from(&Share::from_base64("AQL/").unwrap()), vec![1, 2, 255]);
+/// assert!(Share::from_hex("0102f").is_err());
+///
+/// // Dealt shares can be encoded for QR codes, embedding their threshold
+/// # use sharks::Sharks;
+/// let share = Sharks(2).dealer(&[1, 2, 3]).next().unwrap();
+/// let text = share.to_qr_text().unwrap();
+/// assert_eq!(Share::from_qr_text(&text).unwrap().threshold(), Some(2));
+/// ```
+#[cfg(feature = "text")]
+impl<F: Field> FieldShare<F> {
+    /// Encodes the share bytes as lowercase hexadecimal digits.
+    pub fn to_hex(&self) -> String {
+        let mut bytes = Vec::from(self);
+        let text = encoding::to_hex(&bytes);
+        bytes.zeroize();
+        text
+    }
+
+    /// Parses a share from hexadecimal digits, either lowercase or uppercase.
+    /// An `Err` is returned if the input contains any other character, including whitespace,
+    /// has an odd length or the decoded bytes are not a valid share.
+    pub fn from_hex(s: &str) -> Result<FieldShare<F>, SharksError> {
+        let mut bytes = encoding::from_hex(s).ok_or(SharksError::InvalidEncoding)?;
+        let share = FieldShare::try_from(bytes.as_slice());
+        bytes.zeroize();
+        share
+    }
+
+    /// Encodes the share bytes using the standard Base64 alphabet with padding, as defined in RFC 4648.
+    pub fn to_base64(&self) -> String {
+        let mut bytes = Vec::from(self);
+        let text = encoding::to_base64(&bytes);
+        bytes.zeroize();
+        text
+    }
+
+    /// Parses a share from padded standard Base64. An `Err` is returned if the input is not canonically encoded,
+    /// including missing padding, whitespace or non-zero trailing bits, or the decoded bytes are not a valid share.
+    pub fn from_base64(s: &str) -> Result<FieldShare<F>, SharksError> {
+        let mut bytes = encoding::from_base64(s).ok_or(SharksError::InvalidEncoding)?;
+        let share = FieldShare::try_from(bytes.as_slice());
+        bytes.zeroize();
+        share
+    }
+
+    /// Encodes the share bytes in Crockford Base32, to be dictated or written by hand. The characters are grouped
+    /// by four and separated by hyphens, each group followed by a check character, so that `from_crockford` finds
+    /// which group was mistyped.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Share, SharksError };
+    /// # use std::convert::TryFrom;
+    /// let share = Share::try_from(&[1, 2, 255][..]).unwrap();
+    /// assert_eq!(share.to_crockford(), "041F$-YZ");
+    /// assert_eq!(Vec::from(&Share::from_crockford("o41f$-yz").unwrap()), vec![1, 2, 255]);
+    /// assert_eq!(Share::from_crockford("041F$-YX").err(), Some(SharksError::InvalidGroup { index: 1 }));
+    /// ```
+    pub fn to_crockford(&self) -> String {
+        let mut bytes = Vec::from(self);
+        let text = encoding::to_crockford(&bytes);
+        bytes.zeroize();
+        text
+    }
+
+    /// Parses a share encoded with `Share::to_crockford`, case insensitive and reading `I` and `L` as `1`
+    /// and `O` as `0`. An `InvalidGroup` error is returned with the index of the first group holding an invalid
+    /// character or check character, and another `Err` if the groups are not correctly separated or the decoded
+    /// bytes are not a valid share.
+    pub fn from_crockford(s: &str) -> Result<FieldShare<F>, SharksError> {
+        let mut bytes = encoding::from_crockford(s)?;
+        let share = FieldShare::try_from(bytes.as_slice());
+        bytes.zeroize();
+        share
+    }
+
+    /// Encodes the share for paper backups copied by hand, on numbered lines of four Crockford Base32 groups
+    /// with their check character, see `to_crockford`, each line also ending with two line check characters.
+    /// The versioned format bytes are encoded, so that the threshold is embedded.
+    /// An `Err` is returned if the share does not know its threshold.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share, SharksError };
+    /// let share = Sharks(2).dealer(&[7; 16]).next().unwrap();
+    /// let text = share.to_paper().unwrap();
+    /// assert_eq!(text.lines().count(), 2);
+    /// assert_eq!(Share::from_paper(&text.to_lowercase()).unwrap().threshold(), Some(2));
+    ///
+    /// // A mistyped character is located
+    /// let mut typo = text.into_bytes();
+    /// typo[24] = if typo[24] == b'0' { b'1' } else { b'0' };
+    /// let typo = String::from_utf8(typo).unwrap();
+    /// assert_eq!(Share::from_paper(&typo).err(), Some(SharksError::InvalidLine { line: 0, group: Some(3) }));
+    /// assert_eq!(Share::paper_errors(&typo).len(), 1);
+    /// ```
+    pub fn to_paper(&self) -> Result<String, SharksError> {
+        let mut bytes = self.to_versioned_bytes()?;
+        let text = encoding::to_paper(&bytes);
+        bytes.zeroize();
+        Ok(text)
+    }
+
+    /// Parses a share encoded with `Share::to_paper`, case insensitive, reading `I` and `L` as `1` and `O` as `0`,
+    /// and ignoring blank lines and repeated spaces. An `InvalidLine` error is returned with the index of the first
+    /// line, and of its group if known, which is mistyped, missing or out of order, and another `Err`
+    /// if the decoded bytes are not a valid versioned share. See `paper_errors` to locate all the mistyped lines.
+    pub fn from_paper(s: &str) -> Result<FieldShare<F>, SharksError> {
+        let mut bytes = encoding::from_paper(s)?;
+        let share = FieldShare::from_versioned_bytes(&bytes);
+        bytes.zeroize();
+        share
+    }
+
+    /// Returns an `InvalidLine` error for each line of the text encoded with `Share::to_paper` which is mistyped,
+    /// so that all of them are checked again at once, or an empty vector if every line is valid.
+    pub fn paper_errors(s: &str) -> Vec<SharksError> {
+        encoding::paper_lines(s)
+            .into_iter()
+            .filter_map(Result::err)
+            .collect()
+    }
+
+    /// Encodes the share in Base45, only made of QR code alphanumeric characters, which are the densest
+    /// to store in QR codes. The versioned format bytes are encoded, followed by their CRC-32 checksum,
+    /// so the threshold is embedded and transcription errors detected.
+    /// An `Err` is returned if the share does not know its threshold.
+    pub fn to_qr_text(&self) -> Result<String, SharksError> {
+        let mut bytes = self.to_versioned_bytes()?;
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes());
+        let text = encoding::to_base45(&bytes);
+        bytes.zeroize();
+        Ok(text)
+    }
+
+    /// Parses a share encoded with `Share::to_qr_text`. An `Err` is returned if the input is not canonically
+    /// Base45 encoded, the checksum does not match, or the decoded bytes are not a valid versioned share.
+    pub fn from_qr_text(s: &str) -> Result<FieldShare<F>, SharksError> {
+        let mut bytes = encoding::from_base45(s).ok_or(SharksError::InvalidEncoding)?;
+        let share = match bytes.len().checked_sub(4).map(|l| bytes.split_at(l)) {
+            None => Err(SharksError::InvalidLength),
+            Some((share, crc)) if crc32(share).to_be_bytes() != crc => {
+                Err(SharksError::InvalidChecksum)
+            }
+            Some((share, _)) => FieldShare::from_versioned_bytes(share),
+        };
+        bytes.zeroize();
+        share
+    }
+
+    /// Renders the share as a QR code holding its `Share::to_qr_text` encoding, in alphanumeric mode.
+    /// An `Err` is returned if the share does not know its threshold, or is too long to fit in a QR code.
+    #[cfg(feature = "qr")]
+    pub fn to_qr_code(&self) -> Result<qrcode::QrCode, SharksError> {
+        let text = self.to_qr_text()?;
+        qrcode::QrCode::new(text.as_bytes()).map_err(|_| SharksError::InvalidLength)
+    }
+}
+
+#[cfg(any(feature = "seal", feature = "passphrase"))]
+impl Share {
+    // Serializes the share as encrypted in sealed containers: `threshold || x || y`, with a zero threshold if unknown
+    pub(crate) fn to_sealed_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.threshold.unwrap_or(0)];
+        bytes.extend(Vec::from(self));
+        bytes
+    }
+
+    // Inverse of `to_sealed_bytes`
+    pub(crate) fn from_sealed_bytes(s: &[u8]) -> Result<Share, SharksError> {
+        let (threshold, share) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        let mut share = Share::try_from(share)?;
+        share.threshold = Some(*threshold).filter(|t| *t != 0);
+        Ok(share)
+    }
 }
 
 /// Obtains a byte vector from a `Share` instance
-impl From<&Share> for Vec<u8> {
-    fn from(s: &Share) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(s.y.len() + 1);
-        bytes.push(s.x.0);
-        bytes.extend(s.y.iter().map(|p| p.0));
+impl<F: Field> From<&FieldShare<F>> for Vec<u8> {
+    fn from(s: &FieldShare<F>) -> Vec<u8> {
+        let mut bytes = vec![0u8; (s.y.len() + 1) * F::BYTES];
+        for (e, b) in Some(&s.x)
+            .into_iter()
+            .chain(s.y.iter())
+            .zip(bytes.chunks_mut(F::BYTES))
+        {
+            e.to_bytes(b);
+        }
         bytes
     }
 }
 
-/// Obtains a `Share` instance from a byte slice
-impl From<&[u8]> for Share {
-    fn from(s: &[u8]) -> Share {
-        let x = GF256(s[0]);
-        let y = s[1..].iter().map(|p| GF256(*p)).collect();
-        Share { x, y }
+/// Obtains a `Share` instance from a byte slice, validating its contents.
+/// An `Err` is returned if the slice is empty, contains no `y` values, `x` is zero
+/// or the slice is not made of valid field elements.
+impl<F: Field> TryFrom<&[u8]> for FieldShare<F> {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<FieldShare<F>, Self::Error> {
+        if s.is_empty() {
+            return Err(SharksError::EmptyShare);
+        } else if !s.len().is_multiple_of(F::BYTES) {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let mut elements = s.chunks(F::BYTES).map(F::from_bytes);
+        let x = match elements.next() {
+            Some(Some(x)) if x == F::ZERO => return Err(SharksError::ZeroShareX),
+            Some(Some(x)) => x,
+            _ => return Err(SharksError::InvalidElement),
+        };
+        let y = elements
+            .collect::<Option<Vec<F>>>()
+            .ok_or(SharksError::InvalidElement)?;
+
+        if y.is_empty() {
+            Err(SharksError::EmptyShare)
+        } else {
+            Ok(FieldShare {
+                x,
+                y,
+                threshold: None,
+                group: None,
+            })
+        }
+    }
+}
+
+/// A borrowed view of a share serialized as `x || y`, wrapping its bytes without copying them.
+/// Accepted by `Sharks::recover` like a `Share`, allowing to recover a secret from many large shares
+/// already in memory without doubling the memory used.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, Share, ShareRef };
+/// # use std::convert::TryFrom;
+/// let sharks = Sharks(3);
+/// let bytes: Vec<Vec<u8>> = sharks.dealer(&[1, 2, 3]).take(3).map(|s| Vec::from(&s)).collect();
+/// let shares: Vec<ShareRef> = bytes.iter().map(|b| ShareRef::try_from(b.as_slice()).unwrap()).collect();
+/// assert_eq!(shares[0].y, &bytes[0][1..]);
+/// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ShareRef<'a> {
+    pub x: GF256,
+    pub y: &'a [u8],
+    pub(crate) threshold: Option<u8>,
+    pub(crate) group: Option<u64>,
+}
+
+/// Compares the share `x` and `y` values in constant time, their length excepted. The embedded thresholds are ignored.
+impl ConstantTimeEq for ShareRef<'_> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.x.0.ct_eq(&other.x.0) & self.y.ct_eq(other.y)
+    }
+}
+
+/// Constant time, see the `ConstantTimeEq` implementation.
+impl PartialEq for ShareRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for ShareRef<'_> {}
+
+/// Prints the share `x` coordinate, threshold and length, the `y` values being redacted.
+impl fmt::Debug for ShareRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShareRef")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", self.y.len()))
+            .field("threshold", &self.threshold)
+            .field("group", &self.group)
+            .finish()
+    }
+}
+
+impl<'a> ShareRef<'a> {
+    /// Returns the share serialized as `x || y`, explicitly exposing the `y` values redacted from the `Debug` output.
+    pub fn expose_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.x.0];
+        bytes.extend_from_slice(self.y);
+        bytes
+    }
+
+    /// Returns the minimum threshold of shares embedded in this one, if known.
+    pub fn threshold(&self) -> Option<u8> {
+        self.threshold
+    }
+
+    /// Returns the identifier of the split this share was dealt by, if known, see `Share::group`.
+    pub fn group(&self) -> Option<u64> {
+        self.group
+    }
+
+    /// Wraps a share serialized with `Share::to_versioned_bytes`, see `Share::from_versioned_bytes`.
+    pub fn from_versioned_bytes(s: &'a [u8]) -> Result<ShareRef<'a>, SharksError> {
+        let (threshold, group, share) = split_versioned(s)?;
+        let mut share = ShareRef::try_from(share)?;
+        share.threshold = Some(threshold);
+        share.group = group;
+        Ok(share)
+    }
+}
+
+/// Wraps a byte slice as a `ShareRef`, validating it the same way as a `Share`.
+impl<'a> TryFrom<&'a [u8]> for ShareRef<'a> {
+    type Error = SharksError;
+
+    fn try_from(s: &'a [u8]) -> Result<ShareRef<'a>, Self::Error> {
+        match s {
+            [] => Err(SharksError::EmptyShare),
+            [0, ..] => Err(SharksError::ZeroShareX),
+            [_] => Err(SharksError::EmptyShare),
+            [x, y @ ..] => Ok(ShareRef {
+                x: GF256(*x),
+                y,
+                threshold: None,
+                group: None,
+            }),
+        }
+    }
+}
+
+/// Copies the share bytes into an owned `Share`.
+impl From<&ShareRef<'_>> for Share {
+    fn from(s: &ShareRef<'_>) -> Share {
+        Share {
+            x: s.x,
+            y: GF256::as_elements(s.y).to_vec(),
+            threshold: s.threshold,
+            group: s.group,
+        }
+    }
+}
+
+/// Shares which can be borrowed as a `ShareRef`, as accepted by `Sharks::recover`.
+pub trait AsShareRef {
+    /// Borrows the share as a `ShareRef`.
+    fn as_share_ref(&self) -> ShareRef<'_>;
+}
+
+impl AsShareRef for Share {
+    fn as_share_ref(&self) -> ShareRef<'_> {
+        ShareRef {
+            x: self.x,
+            y: GF256::as_bytes(&self.y),
+            threshold: self.threshold,
+            group: self.group,
+        }
+    }
+}
+
+impl AsShareRef for ShareRef<'_> {
+    fn as_share_ref(&self) -> ShareRef<'_> {
+        *self
+    }
+}
+
+impl<S: AsShareRef + ?Sized> AsShareRef for &S {
+    fn as_share_ref(&self) -> ShareRef<'_> {
+        (**self).as_share_ref()
+    }
+}
+
+// Coordinates of a share, owned or borrowed, as validated before recovering a secret.
+pub(crate) trait Point<F: Field>: Copy {
+    fn x(&self) -> F;
+    fn y(&self) -> &[F];
+    fn threshold(&self) -> Option<u8>;
+    fn group(&self) -> Option<u64>;
+}
+
+impl<F: Field> Point<F> for &FieldShare<F> {
+    fn x(&self) -> F {
+        self.x
+    }
+
+    fn y(&self) -> &[F] {
+        &self.y
+    }
+
+    fn threshold(&self) -> Option<u8> {
+        self.threshold
+    }
+
+    fn group(&self) -> Option<u64> {
+        self.group
+    }
+}
+
+impl Point<GF256> for ShareRef<'_> {
+    fn x(&self) -> GF256 {
+        self.x
+    }
+
+    fn y(&self) -> &[GF256] {
+        GF256::as_elements(self.y)
+    }
+
+    fn threshold(&self) -> Option<u8> {
+        self.threshold
+    }
+
+    fn group(&self) -> Option<u64> {
+        self.group
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, Deserializer, SeqAccess, Visitor};
+    use serde::ser::Serializer;
+    use serde::{Deserialize, Serialize};
+
+    use super::super::encoding;
+    use super::{Field, FieldShare};
+
+    /// Serializes the share as a Base64 string for human readable formats and as raw bytes otherwise
+    impl<F: Field> Serialize for FieldShare<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let bytes = Vec::from(self);
+            if serializer.is_human_readable() {
+                serializer.serialize_str(&encoding::to_base64(&bytes))
+            } else {
+                serializer.serialize_bytes(&bytes)
+            }
+        }
+    }
+
+    struct ShareVisitor<F>(PhantomData<F>);
+
+    impl<'de, F: Field> Visitor<'de> for ShareVisitor<F> {
+        type Value = FieldShare<F>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("share bytes or a Base64 encoded share")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<FieldShare<F>, E> {
+            let bytes = encoding::from_base64(v)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))?;
+            self.visit_bytes(&bytes)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<FieldShare<F>, E> {
+            FieldShare::try_from(v).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FieldShare<F>, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                bytes.push(b);
+            }
+            self.visit_bytes(&bytes)
+        }
+    }
+
+    impl<'de, F: Field> Deserialize<'de> for FieldShare<F> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<FieldShare<F>, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(ShareVisitor(PhantomData))
+            } else {
+                deserializer.deserialize_bytes(ShareVisitor(PhantomData))
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Share, GF256};
+    use super::{FieldShare, Share, ShareRef, SharksError, GF256};
+    use crate::field::GF65536;
+    use crate::Sharks;
+    use std::convert::TryFrom;
 
     #[test]
     fn vec_from_share_works() {
         let share = Share {
             x: GF256(1),
             y: vec![GF256(2), GF256(3)],
+            threshold: None,
+            group: None,
         };
         let bytes = Vec::from(&share);
         assert_eq!(bytes, vec![1, 2, 3]);
     }
 
     #[test]
-    fn share_from_u8_slice_works() {
+    fn share_try_from_u8_slice_works() {
         let bytes = [1, 2, 3];
-        let share = Share::from(&bytes[..]);
+        let share = Share::try_from(&bytes[..]).unwrap();
+        assert_eq!(share.x, GF256(1));
+        assert_eq!(share.y, vec![GF256(2), GF256(3)]);
+    }
+
+    #[test]
+    fn share_try_from_invalid_bytes_err() {
+        assert_eq!(
+            Share::try_from(&[][..]).err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            Share::try_from(&[1][..]).err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            Share::try_from(&[0, 1, 2][..]).err(),
+            Some(SharksError::ZeroShareX)
+        );
+    }
+
+    #[test]
+    fn share_ref_integration_works() {
+        let sharks = Sharks(3);
+        let bytes: Vec<Vec<u8>> = sharks
+            .dealer(&[1, 2, 3, 4])
+            .take(4)
+            .map(|s| s.to_versioned_bytes().unwrap())
+            .collect();
+        let shares: Vec<ShareRef> = bytes
+            .iter()
+            .map(|b| ShareRef::from_versioned_bytes(b).unwrap())
+            .collect();
+        assert_eq!(shares[1].x, GF256(2));
+        assert_eq!(shares[1].y, &bytes[1][3..]);
+        assert_eq!(shares[1].threshold(), Some(3));
+        assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            Sharks(2).recover(&shares).err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let share = Share::from(&shares[1]);
+        assert_eq!(share.threshold(), Some(3));
+        assert_eq!(share.to_versioned_bytes().unwrap(), bytes[1]);
+    }
+
+    #[test]
+    fn share_ref_err() {
+        let cases: [(&[u8], SharksError); 3] = [
+            (&[], SharksError::EmptyShare),
+            (&[1], SharksError::EmptyShare),
+            (&[0, 1, 2], SharksError::ZeroShareX),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(ShareRef::try_from(*bytes).err(), Some(*err));
+            assert_eq!(Share::try_from(*bytes).err(), Some(*err));
+        }
+        assert_eq!(
+            ShareRef::from_versioned_bytes(&[1, 0, 1, 2]).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+    }
+
+    #[test]
+    fn share_gf65536_bytes_works() {
+        let share = FieldShare::<GF65536>::try_from(&[0, 1, 2, 3][..]).unwrap();
+        assert_eq!(share.x, GF65536(1));
+        assert_eq!(share.y, vec![GF65536(0x0203)]);
+        assert_eq!(Vec::from(&share), vec![0, 1, 2, 3]);
+
+        assert_eq!(
+            FieldShare::<GF65536>::try_from(&[0, 1, 2][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert!(FieldShare::<GF65536>::try_from(&[0, 0, 2, 3][..]).is_err());
+        assert!(FieldShare::<GF65536>::try_from(&[0, 1][..]).is_err());
+    }
+
+    #[test]
+    fn share_versioned_bytes_works() {
+        let share = Share {
+            x: GF256(1),
+            y: vec![GF256(2), GF256(3)],
+            threshold: Some(2),
+            group: None,
+        };
+        let bytes = share.to_versioned_bytes().unwrap();
+        assert_eq!(bytes, vec![1, 2, 1, 2, 3]);
+        let share = Share::from_versioned_bytes(&bytes).unwrap();
+        assert_eq!(share.threshold(), Some(2));
         assert_eq!(share.x, GF256(1));
         assert_eq!(share.y, vec![GF256(2), GF256(3)]);
+
+        let mut grouped = share.clone();
+        grouped.group = Some(0x0102);
+        let bytes = grouped.to_versioned_bytes().unwrap();
+        assert_eq!(bytes, vec![2, 2, 0, 0, 0, 0, 0, 0, 1, 2, 1, 2, 3]);
+        let parsed = Share::from_versioned_bytes(&bytes).unwrap();
+        assert_eq!(parsed.group(), Some(0x0102));
+        assert_eq!(parsed.threshold(), Some(2));
+        assert_eq!(
+            ShareRef::from_versioned_bytes(&bytes).unwrap().group(),
+            Some(0x0102)
+        );
+    }
+
+    #[test]
+    fn share_versioned_bytes_invalid_err() {
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(
+            share.to_versioned_bytes().err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            Share::from_versioned_bytes(&[]).err(),
+            Some(SharksError::EmptyShare)
+        );
+        assert_eq!(
+            Share::from_versioned_bytes(&[3, 2, 1, 2]).err(),
+            Some(SharksError::UnknownVersion(3))
+        );
+        assert_eq!(
+            Share::from_versioned_bytes(&[2, 2, 0, 0, 1, 2]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert!(Share::from_versioned_bytes(&[1]).is_err());
+        assert!(Share::from_versioned_bytes(&[1, 0, 1, 2]).is_err());
+        assert!(Share::from_versioned_bytes(&[1, 2, 1]).is_err());
+    }
+
+    #[test]
+    fn share_checked_bytes_works() {
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let bytes = share.to_checked_bytes();
+        assert_eq!(bytes, vec![1, 2, 3, 0x55, 0xbc, 0x80, 0x1d]);
+        let share = Share::from_checked_bytes(&bytes).unwrap();
+        assert_eq!(Vec::from(&share), vec![1, 2, 3]);
+
+        for i in 0..bytes.len() {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 0x10;
+            assert_eq!(
+                Share::from_checked_bytes(&corrupted).err(),
+                Some(SharksError::InvalidChecksum)
+            );
+        }
+        assert_eq!(
+            Share::from_checked_bytes(&bytes[..3]).err(),
+            Some(SharksError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn share_debug_redacted_works() {
+        let bytes = [1u8, 0xab, 0xcd, 0xef];
+        let share = Share::try_from(&bytes[..]).unwrap();
+        assert_eq!(
+            format!("{:?}", share),
+            "FieldShare { x: GF256(1), y: <3 redacted>, threshold: None, group: None }"
+        );
+        let share_ref = ShareRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(
+            format!("{:?}", share_ref),
+            "ShareRef { x: GF256(1), y: <3 redacted>, threshold: None, group: None }"
+        );
+        assert_eq!(share.expose_bytes(), bytes);
+        assert_eq!(share_ref.expose_bytes(), bytes);
+    }
+
+    #[test]
+    fn share_constant_time_eq_works() {
+        use subtle::ConstantTimeEq;
+
+        let share = Share::try_from(&[1u8, 2, 3][..]).unwrap();
+        let mut other = share.clone();
+        other.threshold = Some(2);
+        assert!(bool::from(share.ct_eq(&other)));
+        assert_eq!(share, other);
+        assert_ne!(share, Share::try_from(&[1u8, 2, 4][..]).unwrap());
+        assert_ne!(share, Share::try_from(&[2u8, 2, 3][..]).unwrap());
+        assert_ne!(share, Share::try_from(&[1u8, 2][..]).unwrap());
+
+        let share_ref = ShareRef::try_from(&[1u8, 2, 3][..]).unwrap();
+        assert_eq!(share_ref, ShareRef::try_from(&[1u8, 2, 3][..]).unwrap());
+        assert_ne!(share_ref, ShareRef::try_from(&[1u8, 3, 3][..]).unwrap());
+    }
+
+    #[test]
+    fn share_zeroize_works() {
+        use zeroize::Zeroize;
+
+        let mut share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        share.zeroize();
+        assert_eq!(share.x, GF256(0));
+        assert!(share.y.is_empty());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn share_text_works() {
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(
+            Vec::from(&Share::from_hex(&share.to_hex()).unwrap()),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            Vec::from(&Share::from_base64(&share.to_base64()).unwrap()),
+            vec![1, 2, 3]
+        );
+
+        assert_eq!(
+            Share::from_hex("01 02").err(),
+            Some(SharksError::InvalidEncoding)
+        );
+        assert_eq!(Share::from_hex("01").err(), Some(SharksError::EmptyShare));
+        assert_eq!(
+            Share::from_base64("AQID\n").err(),
+            Some(SharksError::InvalidEncoding)
+        );
+        assert_eq!(Share::from_base64("").err(), Some(SharksError::EmptyShare));
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn share_qr_text_works() {
+        let share = Share::from_versioned_bytes(&[1, 3, 1, 2, 3]).unwrap();
+        let text = share.to_qr_text().unwrap();
+        assert!(text
+            .bytes()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || b" $%*+-./:".contains(&c)));
+        let parsed = Share::from_qr_text(&text).unwrap();
+        assert_eq!(parsed.threshold(), Some(3));
+        assert_eq!(Vec::from(&parsed), vec![1, 2, 3]);
+
+        let mut corrupted = text.into_bytes();
+        corrupted[1] = if corrupted[1] == b'0' { b'1' } else { b'0' };
+        assert_eq!(
+            Share::from_qr_text(std::str::from_utf8(&corrupted).unwrap()).err(),
+            Some(SharksError::InvalidChecksum)
+        );
+        assert_eq!(
+            Share::from_qr_text("a").err(),
+            Some(SharksError::InvalidEncoding)
+        );
+        assert_eq!(
+            Share::from_qr_text("BB8").err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert!(Share::try_from(&[1, 2][..]).unwrap().to_qr_text().is_err());
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn share_qr_code_works() {
+        let share = Share::from_versioned_bytes(&[1, 3, 1, 2, 3]).unwrap();
+        let code = share.to_qr_code().unwrap();
+        assert_eq!(code.version(), qrcode::Version::Normal(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn share_serde_human_readable_works() {
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let json = serde_json::to_string(&share).unwrap();
+        assert_eq!(json, "\"AQID\"");
+        let share: Share = serde_json::from_str(&json).unwrap();
+        assert_eq!(Vec::from(&share), vec![1, 2, 3]);
+        assert!(serde_json::from_str::<Share>("\"AAID\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn share_serde_binary_works() {
+        let share = Share::try_from(&[1, 2, 3][..]).unwrap();
+        let bytes = bincode::serialize(&share).unwrap();
+        let share: Share = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(Vec::from(&share), vec![1, 2, 3]);
     }
 }
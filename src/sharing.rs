@@ -0,0 +1,205 @@
+// Common interface of the threshold schemes, so that downstream code can be generic over the scheme.
+// Each scheme is a small value holding its parameters, like `Sharks` holds the threshold of Shamir's scheme,
+// and implements `SecretSharing` on top of its dealing and recovery methods. New schemes are added as new
+// implementations, without changing the trait.
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// A threshold secret sharing scheme, splitting secrets into shares and recovering them.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, SecretSharing, Blakley, Ramp, Xor };
+/// fn roundtrip<S: SecretSharing>(scheme: &S, n: usize) -> Vec<u8> {
+///     let shares = scheme.split(&[1, 2, 3], n).ok().unwrap();
+///     scheme.recover(&shares).ok().unwrap()
+/// }
+///
+/// assert_eq!(roundtrip(&Sharks(3), 5), vec![1, 2, 3]);
+/// assert_eq!(roundtrip(&Ramp { threshold: 3, pack: 2 }, 5), vec![1, 2, 3]);
+/// assert_eq!(roundtrip(&Xor(3), 3), vec![1, 2, 3]);
+/// assert_eq!(roundtrip(&Blakley(3), 5), vec![1, 2, 3]);
+/// ```
+pub trait SecretSharing {
+    /// Type of the shares dealt.
+    type Share;
+    /// Type of the errors returned when splitting or recovering.
+    type Error;
+
+    /// Returns the minimum number of shares needed to recover a secret.
+    fn threshold(&self) -> u8;
+
+    /// Given a `secret` byte slice, returns exactly `n` new shares, or an `Err` if the scheme cannot deal them.
+    fn split(&self, secret: &[u8], n: usize) -> Result<Vec<Self::Share>, Self::Error>;
+
+    /// Recovers the secret from the `shares`, or returns an `Err` if they are not enough or inconsistent.
+    fn recover(&self, shares: &[Self::Share]) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Ramp scheme packing `pack` secret bytes in each polynomial, see `Sharks::dealer_ramp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ramp {
+    /// Minimum number of shares needed to recover the secret.
+    pub threshold: u8,
+    /// Number of secret bytes packed in each polynomial.
+    pub pack: u8,
+}
+
+/// Trivial n-of-n scheme splitting secrets with XORs of random pads, see `Sharks::dealer_xor`.
+/// The number of shares must be the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xor(pub u8);
+
+/// Blakley's hyperplane based scheme, see `Sharks::dealer_blakley`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Blakley(pub u8);
+
+/// Shamir's scheme over `GF256`, see `Sharks::dealer_n` and `Sharks::recover`.
+impl SecretSharing for Sharks {
+    type Share = Share;
+    type Error = SharksError;
+
+    fn threshold(&self) -> u8 {
+        self.0
+    }
+
+    fn split(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        self.dealer_n(secret, n)
+    }
+
+    fn recover(&self, shares: &[Share]) -> Result<Vec<u8>, SharksError> {
+        Sharks::recover(self, shares)
+    }
+}
+
+impl SecretSharing for Ramp {
+    type Share = Share;
+    type Error = SharksError;
+
+    fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    fn split(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        if n > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        } else if n < self.threshold as usize {
+            return Err(SharksError::NotEnoughShares {
+                needed: self.threshold as usize,
+                got: n,
+                provided: n,
+            });
+        }
+        Ok(Sharks(self.threshold)
+            .dealer_ramp(secret, self.pack)?
+            .take(n)
+            .collect())
+    }
+
+    fn recover(&self, shares: &[Share]) -> Result<Vec<u8>, SharksError> {
+        Sharks(self.threshold).recover_ramp(shares, self.pack)
+    }
+}
+
+impl SecretSharing for Xor {
+    type Share = Share;
+    type Error = SharksError;
+
+    fn threshold(&self) -> u8 {
+        self.0
+    }
+
+    fn split(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        if n != self.0 as usize || n == 0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+        Ok(Sharks(self.0).dealer_xor(secret))
+    }
+
+    fn recover(&self, shares: &[Share]) -> Result<Vec<u8>, SharksError> {
+        Sharks(self.0).recover_xor(shares)
+    }
+}
+
+impl SecretSharing for Blakley {
+    type Share = Share;
+    type Error = SharksError;
+
+    fn threshold(&self) -> u8 {
+        self.0
+    }
+
+    fn split(&self, secret: &[u8], n: usize) -> Result<Vec<Share>, SharksError> {
+        Sharks(self.0).dealer_blakley(secret, n)
+    }
+
+    fn recover(&self, shares: &[Share]) -> Result<Vec<u8>, SharksError> {
+        Sharks(self.0).recover_blakley(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Blakley, Ramp, SecretSharing, Xor};
+    use crate::{Share, Sharks, SharksError};
+
+    // Splits and recovers with any scheme, checking fewer shares than the threshold do not recover the secret
+    fn check<S: SecretSharing<Share = Share, Error = SharksError>>(scheme: &S, n: usize) {
+        let secret: Vec<u8> = (0..30).collect();
+        let shares = scheme.split(&secret, n).unwrap();
+        assert_eq!(shares.len(), n);
+        let t = scheme.threshold() as usize;
+        assert_eq!(scheme.recover(&shares[n - t..]).unwrap(), secret);
+        assert!(scheme.recover(&shares[n - t + 1..]).is_err());
+    }
+
+    #[test]
+    fn schemes_work() {
+        check(&Sharks(3), 5);
+        check(
+            &Ramp {
+                threshold: 4,
+                pack: 2,
+            },
+            6,
+        );
+        check(&Xor(4), 4);
+        check(&Blakley(2), 7);
+    }
+
+    #[test]
+    fn schemes_err() {
+        assert_eq!(
+            Sharks(3).split(&[1], 2).err(),
+            Some(SharksError::TooFewShares { min: 3 })
+        );
+        assert_eq!(
+            Ramp {
+                threshold: 3,
+                pack: 2
+            }
+            .split(&[1], 256)
+            .err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+        assert_eq!(
+            Ramp {
+                threshold: 3,
+                pack: 4
+            }
+            .split(&[1], 3)
+            .err(),
+            Some(SharksError::InvalidPacking)
+        );
+        assert_eq!(
+            Xor(3).split(&[1], 4).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            Blakley(3).split(&[1], 254).err(),
+            Some(SharksError::TooManyShares { max: 253 })
+        );
+    }
+}
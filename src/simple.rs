@@ -0,0 +1,113 @@
+// One call splitting and recovering, for the common case of shares exchanged as bytes.
+// Shares are serialized in the versioned format embedding the threshold, so that recovering them
+// only takes the shares themselves, as with the SSS libraries of other languages.
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// Splits the `secret` into `count` shares, any `threshold` of them recovering it with `combine`.
+/// Each share is serialized as `version || threshold || x || y`, see `Share::to_versioned_bytes`.
+/// An `Err` is returned if the threshold is zero, or `count` is lower than it or higher than 255.
+///
+/// Example:
+/// ```
+/// let shares = sharks::split(b"my secret", 3, 5).unwrap();
+/// assert_eq!(shares.len(), 5);
+/// assert_eq!(sharks::combine(&shares[1..4]).unwrap(), b"my secret");
+/// assert!(sharks::combine(&shares[..2]).is_err());
+/// ```
+pub fn split(secret: &[u8], threshold: u8, count: usize) -> Result<Vec<Vec<u8>>, SharksError> {
+    if threshold == 0 {
+        return Err(SharksError::InvalidThreshold);
+    }
+    Ok(Sharks(threshold)
+        .dealer_n(secret, count)?
+        .iter()
+        // Dealt shares always know their threshold
+        .map(|s| s.to_versioned_bytes().unwrap())
+        .collect())
+}
+
+/// Recovers the secret from shares serialized by `split`, at the threshold they embed.
+/// An `Err` is returned if there are no shares, any of them cannot be parsed, they embed different thresholds,
+/// or in the same cases as `Sharks::recover`.
+pub fn combine<T: AsRef<[u8]>>(shares: &[T]) -> Result<Vec<u8>, SharksError> {
+    let shares = shares
+        .iter()
+        .map(|s| Share::from_versioned_bytes(s.as_ref()))
+        .collect::<Result<Vec<Share>, SharksError>>()?;
+    let threshold =
+        shares
+            .first()
+            .and_then(|s| s.threshold())
+            .ok_or(SharksError::NotEnoughShares {
+                needed: 1,
+                got: 0,
+                provided: 0,
+            })?;
+    Sharks(threshold).recover(&shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{combine, split};
+    use crate::{Sharks, SharksError};
+
+    #[test]
+    fn simple_integration_works() {
+        let shares = split(&[1, 2, 3, 4], 2, 4).unwrap();
+        assert_eq!(shares.len(), 4);
+        assert_eq!(shares[0].len(), 2 + 1 + 4);
+        assert_eq!(combine(&shares[2..]).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(combine(&shares).unwrap(), vec![1, 2, 3, 4]);
+
+        let refs: Vec<&[u8]> = shares.iter().map(|s| s.as_slice()).collect();
+        assert_eq!(combine(&refs[..2]).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(split(&[], 1, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn simple_err() {
+        assert_eq!(split(&[1], 0, 3).err(), Some(SharksError::InvalidThreshold));
+        assert_eq!(
+            split(&[1], 2, 256).err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+        assert_eq!(
+            split(&[1], 3, 2).err(),
+            Some(SharksError::TooFewShares { min: 3 })
+        );
+        let empty: [Vec<u8>; 0] = [];
+        assert_eq!(
+            combine(&empty).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 1,
+                got: 0,
+                provided: 0
+            })
+        );
+
+        let mut shares = split(&[1, 2], 3, 3).unwrap();
+        assert_eq!(
+            combine(&shares[1..]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        let other: Vec<u8> = Sharks(2)
+            .dealer(&[1, 2])
+            .next()
+            .unwrap()
+            .to_versioned_bytes()
+            .unwrap();
+        shares[0] = other;
+        assert_eq!(
+            combine(&shares).err(),
+            Some(SharksError::InconsistentThresholds)
+        );
+        assert!(combine(&[vec![1, 2, 3]]).is_err());
+    }
+}
@@ -0,0 +1,682 @@
+//! [SLIP-0039](https://github.com/satoshilabs/slips/blob/master/slip-0039.md) mnemonic shares,
+//! interoperable with Trezor wallets and other SLIP-39 tooling.
+//!
+//! SLIP-39 defines its own sharing scheme on top of Shamir's: the secret is first encrypted with a
+//! passphrase, then split over the Rijndael Galois Field along with a digest allowing to detect
+//! invalid recoveries. Each share is encoded as a mnemonic of at least 20 words including a checksum.
+//! Shares are generated as a single group, while recovery supports any group configuration.
+//!
+//! Usage example:
+//! ```
+//! use sharks::Sharks;
+//! use sharks::slip39::{self, Share};
+//!
+//! let sharks = Sharks(2);
+//! let secret = [7; 16];
+//! // Obtain 3 mnemonic shares, protected by the passphrase "TREZOR"
+//! let mnemonics: Vec<String> = sharks
+//!     .dealer_slip39(&secret, b"TREZOR")
+//!     .unwrap()
+//!     .take(3)
+//!     .map(|s| s.to_string())
+//!     .collect();
+//! // Parse any 2 of them back and recover the original secret!
+//! let shares: Vec<Share> = mnemonics[1..].iter().map(|m| m.parse().unwrap()).collect();
+//! assert_eq!(slip39::recover(&shares, b"TREZOR").unwrap(), secret);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use rand::Rng;
+use sha2::Sha256;
+
+use crate::{Sharks, SharksError};
+
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+// Each mnemonic word encodes 10 bits
+const RADIX_BITS: usize = 10;
+// Identifier and iteration exponent, group and member parameters, checksum
+const METADATA_WORDS: usize = 7;
+const CHECKSUM_WORDS: usize = 3;
+const MIN_SECRET_BYTES: usize = 16;
+const MAX_SHARES: u8 = 16;
+
+const DIGEST_BYTES: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+
+const BASE_ITERATIONS: u32 = 10000;
+const ROUNDS: u8 = 4;
+// Iteration exponent used for new shares, the PBKDF2 function runs 20000 iterations in total
+const ITERATION_EXPONENT: u8 = 1;
+
+const RS1024_GENERATOR: [u32; 10] = [
+    0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+    0x21b1f890, 0x3f3f120,
+];
+
+// Exponential and logarithm tables of the Rijndael field (polynomial 0x11b, generator 3)
+const fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0; 255];
+    let mut log = [0; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        poly ^= poly << 1;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const TABLES: ([u8; 255], [u8; 256]) = tables();
+const EXP_TABLE: [u8; 255] = TABLES.0;
+const LOG_TABLE: [u8; 256] = TABLES.1;
+
+/// A SLIP-39 share, which is displayed as and parsed from its mnemonic representation.
+#[derive(Clone, PartialEq)]
+pub struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+/// Prints the share metadata, the share value being redacted so that shares do not leak into logs.
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("extendable", &self.extendable)
+            .field("iteration_exponent", &self.iteration_exponent)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .field("value", &format_args!("<{} redacted>", self.value.len()))
+            .finish()
+    }
+}
+
+impl Share {
+    /// Returns the random identifier common to all the shares of a secret.
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    /// Returns the index of the group the share belongs to.
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    /// Returns the minimum number of groups required to recover the secret.
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    /// Returns the total number of groups.
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    /// Returns the index of the share within its group.
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    /// Returns the minimum number of shares of the group required to recover the group secret.
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    fn words(&self) -> Vec<u32> {
+        let header = u32::from(self.identifier) << 5
+            | u32::from(self.extendable) << 4
+            | u32::from(self.iteration_exponent);
+        let params = u32::from(self.group_index) << 16
+            | u32::from(self.group_threshold - 1) << 12
+            | u32::from(self.group_count - 1) << 8
+            | u32::from(self.member_index) << 4
+            | u32::from(self.member_threshold - 1);
+        let mut words = vec![header >> 10, header & 1023, params >> 10, params & 1023];
+
+        // The value is left padded with zero bits up to a multiple of the word size
+        let value_words = (self.value.len() * 8).div_ceil(RADIX_BITS);
+        let mut bits = value_words * RADIX_BITS - self.value.len() * 8;
+        let mut acc: u32 = 0;
+        for byte in &self.value {
+            acc = acc << 8 | u32::from(*byte);
+            bits += 8;
+            while bits >= RADIX_BITS {
+                bits -= RADIX_BITS;
+                words.push(acc >> bits & 1023);
+            }
+            acc &= (1 << bits) - 1;
+        }
+
+        let checksum = polymod(
+            customization(self.extendable),
+            words.iter().copied().chain(vec![0; 3]),
+        ) ^ 1;
+        words.extend(
+            (0..CHECKSUM_WORDS)
+                .rev()
+                .map(|i| checksum >> (RADIX_BITS * i) & 1023),
+        );
+        words
+    }
+}
+
+/// Displays the mnemonic representation of a `Share`.
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wordlist: Vec<&str> = WORDLIST.lines().collect();
+        let words: Vec<&str> = self
+            .words()
+            .into_iter()
+            .map(|w| wordlist[w as usize])
+            .collect();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// Parses a `Share` from its mnemonic representation, validating its checksum and contents.
+impl FromStr for Share {
+    type Err = SharksError;
+
+    fn from_str(s: &str) -> Result<Share, Self::Err> {
+        let wordlist: Vec<&str> = WORDLIST.lines().collect();
+        let words = s
+            .split_whitespace()
+            .map(|w| {
+                wordlist
+                    .binary_search(&w.to_lowercase().as_str())
+                    .map(|i| i as u32)
+                    .map_err(|_| SharksError::InvalidWord)
+            })
+            .collect::<Result<Vec<u32>, Self::Err>>()?;
+
+        let value_words = words.len().saturating_sub(METADATA_WORDS);
+        let padding = value_words * RADIX_BITS % 16;
+        if value_words * RADIX_BITS < MIN_SECRET_BYTES * 8 || padding > 8 {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let header = words[0] << 10 | words[1];
+        let extendable = header >> 4 & 1 == 1;
+        if polymod(customization(extendable), words.iter().copied()) != 1 {
+            return Err(SharksError::InvalidChecksum);
+        }
+
+        let params = words[2] << 10 | words[3];
+        let (group_threshold, group_count) =
+            ((params >> 12 & 15) as u8 + 1, (params >> 8 & 15) as u8 + 1);
+        if group_threshold > group_count {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let value_words = &words[4..words.len() - CHECKSUM_WORDS];
+        if value_words[0] >> (RADIX_BITS - padding) != 0 {
+            return Err(SharksError::InvalidPadding);
+        }
+        let mut value = Vec::with_capacity((value_words.len() * RADIX_BITS - padding) / 8);
+        let mut bits = RADIX_BITS - padding;
+        let mut acc = value_words[0];
+        for word in &value_words[1..] {
+            acc = acc << RADIX_BITS | word;
+            bits += RADIX_BITS;
+            while bits >= 8 {
+                bits -= 8;
+                value.push((acc >> bits) as u8);
+            }
+            acc &= (1 << bits) - 1;
+        }
+        if !value.len().is_multiple_of(2) {
+            return Err(SharksError::InvalidLength);
+        }
+
+        Ok(Share {
+            identifier: (header >> 5) as u16,
+            extendable,
+            iteration_exponent: (header & 15) as u8,
+            group_index: (params >> 16) as u8,
+            group_threshold,
+            group_count,
+            member_index: (params >> 4 & 15) as u8,
+            member_threshold: (params & 15) as u8 + 1,
+            value,
+        })
+    }
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+// Reed-Solomon code over GF(1024) used as checksum of the mnemonic words
+fn polymod(customization: &[u8], words: impl Iterator<Item = u32>) -> u32 {
+    customization
+        .iter()
+        .map(|c| u32::from(*c))
+        .chain(words)
+        .fold(1, |chk, v| {
+            let b = chk >> 20;
+            (0..10)
+                .filter(|i| b >> i & 1 == 1)
+                .fold((chk & 0xfffff) << 10 ^ v, |chk, i| {
+                    chk ^ RS1024_GENERATOR[i]
+                })
+        })
+}
+
+// Evaluates at `x` the polynomial going through `points`, which must have distinct `x` coordinates
+fn interpolate(points: &[(u8, Vec<u8>)], x: u8) -> Vec<u8> {
+    if let Some((_, y)) = points.iter().find(|(xi, _)| *xi == x) {
+        return y.clone();
+    }
+
+    let mut result = vec![0; points[0].1.len()];
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let log_basis = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (xj, _))| {
+                LOG_TABLE[(x ^ xj) as usize] as usize + 255 - LOG_TABLE[(xi ^ xj) as usize] as usize
+            })
+            .sum::<usize>();
+        for (r, y) in result.iter_mut().zip(yi) {
+            if *y != 0 {
+                *r ^= EXP_TABLE[(LOG_TABLE[*y as usize] as usize + log_basis) % 255];
+            }
+        }
+    }
+    result
+}
+
+fn digest(random: &[u8], secret: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("HMAC accepts keys of any length");
+    mac.update(secret);
+    mac.finalize().into_bytes()[..DIGEST_BYTES].to_vec()
+}
+
+// Returns the points defining the polynomial of degree `threshold - 1` which shares `secret`
+fn split_secret(threshold: u8, secret: Vec<u8>) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        return vec![(SECRET_INDEX, secret)];
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut random = |len| (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+    let mut points: Vec<(u8, Vec<u8>)> = (0..threshold - 2)
+        .map(|x| (x, random(secret.len())))
+        .collect();
+    let mut digest_share = random(secret.len() - DIGEST_BYTES);
+    digest_share.splice(0..0, digest(&digest_share, &secret));
+    points.push((DIGEST_INDEX, digest_share));
+    points.push((SECRET_INDEX, secret));
+    points
+}
+
+fn recover_secret(threshold: u8, points: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, SharksError> {
+    if threshold == 1 {
+        return Ok(points[0].1.clone());
+    }
+
+    let secret = interpolate(points, SECRET_INDEX);
+    let digest_share = interpolate(points, DIGEST_INDEX);
+    if digest(&digest_share[DIGEST_BYTES..], &secret) != digest_share[..DIGEST_BYTES] {
+        Err(SharksError::InvalidDigest)
+    } else {
+        Ok(secret)
+    }
+}
+
+// Four rounds Feistel network, whose round function is PBKDF2 keyed with the passphrase
+fn feistel(
+    secret: &[u8],
+    passphrase: &[u8],
+    share: &Share,
+    rounds: impl Iterator<Item = u8>,
+) -> Vec<u8> {
+    let half = secret.len() / 2;
+    let (mut l, mut r) = (secret[..half].to_vec(), secret[half..].to_vec());
+    let mut salt = Vec::new();
+    if !share.extendable {
+        salt.extend(customization(false));
+        salt.extend(&share.identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATIONS << share.iteration_exponent) / u32::from(ROUNDS);
+
+    for i in rounds {
+        let password: Vec<u8> = std::iter::once(i)
+            .chain(passphrase.iter().copied())
+            .collect();
+        let salt: Vec<u8> = salt.iter().chain(r.iter()).copied().collect();
+        let mut f = vec![0; half];
+        pbkdf2_hmac::<Sha256>(&password, &salt, iterations, &mut f);
+        l.iter_mut().zip(f).for_each(|(a, b)| *a ^= b);
+        std::mem::swap(&mut l, &mut r);
+    }
+
+    r.extend(l);
+    r
+}
+
+/// Given an iterable collection of SLIP-39 shares and the `passphrase` used to generate them,
+/// recovers the original secret. The group and member thresholds are read from the shares.
+/// If there are not enough shares in enough groups, or the shares are inconsistent, an `Err` is returned,
+/// `SharksError::NotEnoughShares` counting the groups whose secret could be recovered.
+/// Note a wrong passphrase cannot be detected and recovers a different secret.
+pub fn recover<'a, T>(shares: T, passphrase: &[u8]) -> Result<Vec<u8>, SharksError>
+where
+    T: IntoIterator<Item = &'a Share>,
+    T::IntoIter: Iterator<Item = &'a Share>,
+{
+    let shares: Vec<&Share> = shares.into_iter().collect();
+    let first = shares.first().ok_or(SharksError::NotEnoughShares {
+        needed: 1,
+        got: 0,
+        provided: 0,
+    })?;
+    if shares.iter().any(|s| {
+        (
+            s.identifier,
+            s.extendable,
+            s.iteration_exponent,
+            s.group_threshold,
+            s.group_count,
+            s.value.len(),
+        ) != (
+            first.identifier,
+            first.extendable,
+            first.iteration_exponent,
+            first.group_threshold,
+            first.group_count,
+            first.value.len(),
+        )
+    }) {
+        return Err(SharksError::MixedShares);
+    }
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in shares.iter() {
+        groups.entry(share.group_index).or_default().push(share);
+    }
+
+    let mut points = Vec::new();
+    for (index, members) in groups {
+        let threshold = members[0].member_threshold;
+        if members.iter().any(|s| s.member_threshold != threshold) {
+            return Err(SharksError::InconsistentThresholds);
+        }
+
+        let mut values: BTreeMap<u8, &Vec<u8>> = BTreeMap::new();
+        for share in members {
+            if *values.entry(share.member_index).or_insert(&share.value) != &share.value {
+                return Err(SharksError::DuplicateShareX);
+            }
+        }
+
+        if values.len() >= threshold as usize {
+            let members: Vec<(u8, Vec<u8>)> = values
+                .into_iter()
+                .take(threshold as usize)
+                .map(|(x, y)| (x, y.clone()))
+                .collect();
+            points.push((index, recover_secret(threshold, &members)?));
+        }
+    }
+
+    if points.len() < first.group_threshold as usize {
+        return Err(SharksError::NotEnoughShares {
+            needed: first.group_threshold as usize,
+            got: points.len(),
+            provided: points.len(),
+        });
+    }
+    points.truncate(first.group_threshold as usize);
+    let encrypted = recover_secret(first.group_threshold, &points)?;
+    Ok(feistel(&encrypted, passphrase, first, (0..ROUNDS).rev()))
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice and a `passphrase`, returns an `Iterator` along new SLIP-39 shares,
+    /// forming a single group. The maximum number of shares that can be generated is 16.
+    /// As in the specification, a single share is generated if the threshold is 1.
+    /// If the secret is not an even number of bytes, of at least 16, or the threshold is greater than 16,
+    /// an `Err` is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use sharks::slip39::Share;
+    /// # let sharks = Sharks(3);
+    /// // Obtain an iterator over the shares for a 128 bits secret
+    /// let dealer = sharks.dealer_slip39(&[1; 16], b"").unwrap();
+    /// // Get 5 shares
+    /// let shares: Vec<Share> = dealer.take(5).collect();
+    pub fn dealer_slip39(
+        &self,
+        secret: &[u8],
+        passphrase: &[u8],
+    ) -> Result<impl Iterator<Item = Share>, SharksError> {
+        if secret.len() < MIN_SECRET_BYTES || !secret.len().is_multiple_of(2) {
+            return Err(SharksError::InvalidLength);
+        } else if self.0 == 0 || self.0 > MAX_SHARES {
+            return Err(SharksError::InvalidThreshold);
+        }
+
+        let mut share = Share {
+            identifier: rand::thread_rng().gen::<u16>() >> 1,
+            extendable: true,
+            iteration_exponent: ITERATION_EXPONENT,
+            group_index: 0,
+            group_threshold: 1,
+            group_count: 1,
+            member_index: 0,
+            member_threshold: self.0,
+            value: Vec::new(),
+        };
+
+        // With a single group, the group secret is the encrypted secret itself
+        let points = split_secret(self.0, feistel(secret, passphrase, &share, 0..ROUNDS));
+        let count = if self.0 == 1 { 1 } else { MAX_SHARES };
+        Ok((0..count).map(move |x| {
+            share.member_index = x;
+            share.value = interpolate(&points, x);
+            share.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate, recover, Share};
+    use crate::{Sharks, SharksError};
+
+    fn parse(mnemonics: &[&str]) -> Vec<Share> {
+        mnemonics.iter().map(|m| m.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn interpolate_works() {
+        // Line going through (1, 1) and (2, 2) over the Rijndael field
+        let points = vec![(1, vec![1]), (2, vec![2])];
+        assert_eq!(interpolate(&points, 3), vec![3]);
+        assert_eq!(interpolate(&points, 2), vec![2]);
+    }
+
+    #[test]
+    fn mnemonic_roundtrip_works() {
+        let shares: Vec<Share> = Sharks(3)
+            .dealer_slip39(&[1; 32], b"")
+            .unwrap()
+            .take(16)
+            .collect();
+        assert_eq!(shares[15].member_index(), 15);
+        for share in shares {
+            let mnemonic = share.to_string();
+            assert_eq!(mnemonic.split(' ').count(), 33);
+            assert_eq!(mnemonic.parse::<Share>().unwrap(), share);
+        }
+    }
+
+    #[test]
+    fn integration_works() {
+        let secret: Vec<u8> = (0..16).collect();
+        let shares: Vec<Share> = Sharks(3)
+            .dealer_slip39(&secret, b"TREZOR")
+            .unwrap()
+            .take(5)
+            .collect();
+        assert_eq!(recover(&shares[2..], b"TREZOR").unwrap(), secret);
+        assert_ne!(recover(&shares[2..], b"").unwrap(), secret);
+        assert_eq!(
+            recover(&shares[3..], b"TREZOR"),
+            Err(SharksError::NotEnoughShares {
+                needed: 1,
+                got: 0,
+                provided: 0
+            })
+        );
+
+        let shares: Vec<Share> = Sharks(1)
+            .dealer_slip39(&secret, b"TREZOR")
+            .unwrap()
+            .collect();
+        assert_eq!(shares.len(), 1);
+        assert_eq!(recover(&shares, b"TREZOR").unwrap(), secret);
+    }
+
+    #[test]
+    fn dealer_params_err() {
+        let err = |sharks: Sharks, secret: &[u8]| sharks.dealer_slip39(secret, b"").err();
+        assert_eq!(err(Sharks(2), &[1; 15]), Some(SharksError::InvalidLength));
+        assert_eq!(err(Sharks(2), &[1; 17]), Some(SharksError::InvalidLength));
+        assert_eq!(
+            err(Sharks(17), &[1; 16]),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            err(Sharks(0), &[1; 16]),
+            Some(SharksError::InvalidThreshold)
+        );
+    }
+
+    // Test vectors from https://github.com/trezor/python-shamir-mnemonic
+    #[test]
+    fn no_sharing_vector_works() {
+        let shares = parse(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"]);
+        assert_eq!(
+            recover(&shares, b"TREZOR").unwrap(),
+            [
+                0xbb, 0x54, 0xaa, 0xc4, 0xb8, 0x9d, 0xc8, 0x68, 0xba, 0x37, 0xd9, 0xcc, 0x21, 0xb2,
+                0xce, 0xce
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_mnemonic_vectors_err() {
+        let err = |mnemonic: &str| mnemonic.parse::<Share>().err();
+        assert_eq!(err("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"), Some(SharksError::InvalidChecksum));
+        assert_eq!(err("duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"), Some(SharksError::InvalidPadding));
+        assert_eq!(err("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision sharks"), Some(SharksError::InvalidWord));
+    }
+
+    #[test]
+    fn basic_sharing_vector_works() {
+        let shares = parse(&[
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ]);
+        assert_eq!(
+            recover(&shares, b"TREZOR").unwrap(),
+            [
+                0xb4, 0x3c, 0xeb, 0x7e, 0x57, 0xa0, 0xea, 0x87, 0x66, 0x22, 0x16, 0x24, 0xd0, 0x1b,
+                0x08, 0x64
+            ]
+        );
+        assert!(recover(&shares[1..], b"TREZOR").is_err());
+    }
+
+    #[test]
+    fn invalid_sharing_vectors_err() {
+        // Duplicate member indices
+        let shares = parse(&[
+            "device stay academic always dive coal antenna adult black exceed stadium herald advance soldier busy dryer daughter evaluate minister laser",
+            "device stay academic always dwarf afraid robin gravity crunch adjust soul branch walnut coastal dream costume scholar mortgage mountain pumps",
+        ]);
+        assert_eq!(
+            recover(&shares, b"TREZOR"),
+            Err(SharksError::DuplicateShareX)
+        );
+
+        // Invalid digest
+        let shares = parse(&[
+            "guilt walnut academic acid deliver remove equip listen vampire tactics nylon rhythm failure husband fatigue alive blind enemy teaspoon rebound",
+            "guilt walnut academic agency brave hamster hobo declare herd taste alpha slim criminal mild arcade formal romp branch pink ambition",
+        ]);
+        assert_eq!(recover(&shares, b"TREZOR"), Err(SharksError::InvalidDigest));
+
+        // Insufficient number of members in one group
+        let shares = parse(&[
+            "eraser senior decision shadow artist work morning estate greatest pipeline plan ting petition forget hormone flexible general goat admit surface",
+            "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+        ]);
+        assert!(recover(&shares, b"TREZOR").is_err());
+    }
+
+    #[test]
+    fn group_sharing_vectors_work() {
+        let shares = parse(&[
+            "eraser senior decision roster beard treat identify grumpy salt index fake aviation theater cubic bike cause research dragon emphasis counter",
+            "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+            "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+            "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+            "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+        ]);
+        assert_eq!(shares[0].group_threshold(), 2);
+        assert_eq!(
+            recover(&shares, b"TREZOR").unwrap(),
+            [
+                0x7c, 0x33, 0x97, 0xa2, 0x92, 0xa5, 0x94, 0x16, 0x82, 0xd7, 0xa4, 0xae, 0x2d, 0x89,
+                0x8d, 0x11
+            ]
+        );
+
+        let shares = parse(&[
+            "wildlife deal decision scared acne fatal snake paces obtain election dryer dominant romp tactics railroad marvel trust helpful flip peanut theory theater photo luck install entrance taxi step oven network dictate intimate listen",
+            "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+            "wildlife deal decision smug ancestor genuine move huge cubic strategy smell game costume extend swimming false desire fake traffic vegan senior twice timber submit leader payroll fraction apart exact forward pulse tidy install",
+        ]);
+        assert_eq!(
+            recover(&shares, b"TREZOR").unwrap(),
+            [
+                0x53, 0x85, 0x57, 0x7c, 0x8c, 0xfc, 0x6c, 0x1a, 0x8a, 0xa0, 0xf7, 0xf1, 0x0e, 0xcd,
+                0xe0, 0xa3, 0x31, 0x84, 0x93, 0x26, 0x25, 0x91, 0xe7, 0x8b, 0x8c, 0x14, 0xc6, 0x68,
+                0x61, 0x67, 0x12, 0x3b
+            ]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+// Dealers whose polynomials can be exported and imported back, so that more shares can be issued later on
+// without splitting the secret again. The state is serialized as `version || threshold || next x || coefficients`,
+// coefficients being stored by degree, from higher to lower, with one byte per secret byte each. The last ones
+// are the secret itself, so the serialized state must be stored as securely as the secret.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::Share;
+use super::Sharks;
+
+/// Version byte prefixed to serialized `DealerState`s.
+pub const STATE_VERSION: u8 = 1;
+
+/// Resumable dealer over `GF256`, returned by `Sharks::dealer_state`. It is an `Iterator` along new shares
+/// like `Sharks::dealer`, which can be serialized to and from an opaque byte array holding its polynomials,
+/// to issue more shares in a later session. Shares are issued at increasing `x` coordinates, and the iteration
+/// resumes after the last issued one. The polynomials are wiped from memory once dropped.
+///
+/// Example:
+/// ```
+/// # use sharks::{ Sharks, Share, DealerState };
+/// # use std::convert::TryFrom;
+/// let sharks = Sharks(3);
+/// let mut dealer = sharks.dealer_state(&[1, 2, 3]);
+/// let mut shares: Vec<Share> = dealer.by_ref().take(2).collect();
+/// let exported = Vec::from(&dealer);
+/// drop(dealer);
+///
+/// // In a later session a third participant joins
+/// let mut dealer = DealerState::try_from(exported.as_slice()).unwrap();
+/// shares.push(dealer.next().unwrap());
+/// assert_eq!(shares[2].x.0, 3);
+/// assert_eq!(sharks.recover(&shares).unwrap(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone)]
+pub struct DealerState {
+    threshold: u8,
+    coeffs: Vec<Vec<GF256>>,
+    index: usize,
+}
+
+/// Prints the threshold, secret length and next `x` coordinate, the polynomials being redacted.
+impl fmt::Debug for DealerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DealerState")
+            .field("threshold", &self.threshold)
+            .field(
+                "coeffs",
+                &format_args!(
+                    "<{} redacted>",
+                    self.coeffs.iter().map(Vec::len).sum::<usize>()
+                ),
+            )
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl DealerState {
+    /// Returns the threshold of the shares issued by the dealer.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Returns the share at the `x` coordinate, whether it was already issued or not.
+    /// An `Err` is returned if `x` is zero, as the share would be the secret itself.
+    pub fn share(&self, x: u8) -> Result<Share, SharksError> {
+        if x == 0 {
+            return Err(SharksError::ZeroShareX);
+        }
+        Ok(Share {
+            x: GF256(x),
+            y: math::evaluate_coefficients(&self.coeffs, GF256(x)),
+            threshold: Some(self.threshold),
+            group: None,
+        })
+    }
+}
+
+impl Iterator for DealerState {
+    type Item = Share;
+
+    fn next(&mut self) -> Option<Share> {
+        let x = GF256::from_index(self.index)?;
+        self.index += 1;
+        self.share(x.0).ok()
+    }
+}
+
+/// Wipes the polynomials from memory.
+/// This is done automatically when the dealer is dropped.
+impl Zeroize for DealerState {
+    fn zeroize(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
+impl Drop for DealerState {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for DealerState {}
+
+/// Obtains a byte vector from a `DealerState` instance: `version || threshold || next x || coefficients`,
+/// the next `x` coordinate being zero once all the shares were issued.
+impl From<&DealerState> for Vec<u8> {
+    fn from(s: &DealerState) -> Vec<u8> {
+        let mut bytes = vec![STATE_VERSION, s.threshold, (s.index % 256) as u8];
+        for c in s.coeffs.iter() {
+            bytes.extend(c.iter().map(|c| c.0));
+        }
+        bytes
+    }
+}
+
+/// Obtains a `DealerState` instance from a byte slice, validating its contents.
+/// An `Err` is returned if the version is unknown, the threshold is zero
+/// or the coefficients cannot be split in threshold polynomials.
+impl TryFrom<&[u8]> for DealerState {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<DealerState, Self::Error> {
+        match s.first() {
+            None => return Err(SharksError::EmptyShare),
+            Some(version) if *version != STATE_VERSION => {
+                return Err(SharksError::UnknownVersion(*version))
+            }
+            _ if s.len() < 3 => return Err(SharksError::InvalidLength),
+            _ if s[1] == 0 => return Err(SharksError::InvalidThreshold),
+            _ => (),
+        }
+
+        let (threshold, bytes) = (s[1] as usize, &s[3..]);
+        if !bytes.len().is_multiple_of(threshold) {
+            return Err(SharksError::InvalidLength);
+        }
+        let len = bytes.len() / threshold;
+        let coeffs = (0..threshold)
+            .map(|d| {
+                bytes[d * len..(d + 1) * len]
+                    .iter()
+                    .map(|c| GF256(*c))
+                    .collect()
+            })
+            .collect();
+        Ok(DealerState {
+            threshold: s[1],
+            coeffs,
+            index: if s[2] == 0 { 256 } else { s[2] as usize },
+        })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns a `DealerState` issuing its shares, which can be exported
+    /// and imported back to issue more of them later on. The maximum number of shares is 255.
+    pub fn dealer_state(&self, secret: &[u8]) -> DealerState {
+        DealerState {
+            threshold: self.0,
+            coeffs: math::random_coefficients(
+                math::encode_secret(secret),
+                self.0,
+                &mut rand::thread_rng(),
+            ),
+            index: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DealerState, STATE_VERSION};
+    use crate::{Share, Sharks, SharksError, GF256};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn dealer_state_integration_works() {
+        let sharks = Sharks(4);
+        let mut dealer = sharks.dealer_state(&[1, 2, 3, 4, 5]);
+        assert_eq!(dealer.threshold(), 4);
+        let mut shares: Vec<Share> = dealer.by_ref().take(2).collect();
+
+        let bytes = Vec::from(&dealer);
+        assert_eq!(bytes.len(), 3 + 4 * 5);
+        assert_eq!(&bytes[..3], &[STATE_VERSION, 4, 3]);
+        assert_eq!(&bytes[18..], &[1, 2, 3, 4, 5]);
+
+        let mut dealer = DealerState::try_from(bytes.as_slice()).unwrap();
+        shares.extend(dealer.by_ref().take(2));
+        assert_eq!(shares[3].x, GF256(4));
+        assert_eq!(shares[3].threshold(), Some(4));
+        assert_eq!(sharks.recover(&shares), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(dealer.count(), 251);
+
+        let mut dealer = DealerState::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(dealer.share(1).unwrap().y, shares[0].y);
+        assert_eq!(dealer.by_ref().count(), 253);
+        let bytes = Vec::from(&dealer);
+        assert_eq!(bytes[2], 0);
+        assert!(DealerState::try_from(bytes.as_slice())
+            .unwrap()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn dealer_state_err() {
+        let dealer = Sharks(2).dealer_state(&[1, 2]);
+        assert_eq!(dealer.share(0).err(), Some(SharksError::ZeroShareX));
+
+        let cases: [(&[u8], SharksError); 5] = [
+            (&[], SharksError::EmptyShare),
+            (&[STATE_VERSION + 1], SharksError::UnknownVersion(2)),
+            (&[STATE_VERSION, 2], SharksError::InvalidLength),
+            (&[STATE_VERSION, 0, 1, 2], SharksError::InvalidThreshold),
+            (&[STATE_VERSION, 2, 1, 5, 6, 7], SharksError::InvalidLength),
+        ];
+        for (bytes, err) in cases.iter() {
+            assert_eq!(DealerState::try_from(*bytes).err(), Some(*err));
+        }
+    }
+}
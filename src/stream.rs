@@ -0,0 +1,493 @@
+// Streaming split and recovery of secrets, processing them in buffered chunks so they don't need to fit in memory.
+// Each stream written by the dealer has the same layout as the bytes of a `Share`: `x || y..`.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::GF256;
+use super::math;
+use super::share::Share;
+use super::Sharks;
+
+const BUFFER_SIZE: usize = 8192;
+
+/// Progress of a long running split or recovery, passed to the callbacks of `Sharks::split_stream_with_progress`
+/// and similar functions after each processed chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of secret bytes processed so far.
+    pub processed: u64,
+    /// Total number of secret bytes, if known upfront.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// Returns the processed fraction of the total, between 0 and 1, if the total is known.
+    /// An empty secret is reported as fully processed.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| match total {
+            0 => 1.0,
+            total => self.processed as f64 / total as f64,
+        })
+    }
+}
+
+/// Error returned by `Sharks::split_to_writers`.
+#[derive(Debug)]
+pub enum SplitToWritersError {
+    /// The shares cannot be dealt to the writers, for the given reason.
+    Invalid(SharksError),
+    /// Writing failed for the writers at these indices, with these errors. The other writers hold their share.
+    Writers(Vec<(usize, io::Error)>),
+}
+
+impl fmt::Display for SplitToWritersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SplitToWritersError::Invalid(reason) => write!(f, "{}", reason),
+            SplitToWritersError::Writers(errors) => {
+                write!(f, "Writing failed for writers")?;
+                for (i, e) in errors {
+                    write!(f, " {} ({})", i, e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SplitToWritersError {}
+
+// Error returned when a progress callback cancels the operation
+pub(crate) fn cancelled() -> io::Error {
+    io::Error::other(SharksError::Cancelled)
+}
+
+fn invalid_input(error: SharksError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+// Reads into `buf` until it is full or the reader reaches its end, returning the number of bytes read
+fn fill<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read)
+}
+
+impl Sharks {
+    /// Splits the secret read from `reader` into as many shares as `writers`, one written to each of them.
+    /// The secret is processed in buffered chunks, so its size is not limited by the available memory.
+    /// The bytes written to each writer can be parsed as a `Share`, or recovered with `recover_stream`.
+    /// An `Err` is returned if there are fewer writers than the threshold or more than 255,
+    /// or reading or writing fails.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// let sharks = Sharks(2);
+    /// let mut writers = vec![Vec::new(); 3];
+    /// sharks.split_stream(&[1, 2, 3, 4][..], &mut writers).unwrap();
+    ///
+    /// let mut secret = Vec::new();
+    /// sharks.recover_stream(&mut [&writers[0][..], &writers[2][..]], &mut secret).unwrap();
+    /// assert_eq!(secret, vec![1, 2, 3, 4]);
+    pub fn split_stream<R: Read, W: Write>(&self, reader: R, writers: &mut [W]) -> io::Result<()> {
+        self.split_stream_with_progress(reader, writers, |_| true)
+    }
+
+    /// Same as `split_stream`, calling `progress` after each chunk written to the writers, with the number of
+    /// secret bytes read so far. The operation is cancelled if the callback returns `false`, in which case
+    /// an `Err` holding `SharksError::Cancelled` is returned and the writers are left partially written.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, SharksError };
+    /// let sharks = Sharks(2);
+    /// let secret = vec![7u8; 100_000];
+    /// let mut writers = vec![Vec::new(); 3];
+    /// let mut reported = 0;
+    /// sharks
+    ///     .split_stream_with_progress(&secret[..], &mut writers, |p| {
+    ///         reported = p.processed;
+    ///         true
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(reported, 100_000);
+    ///
+    /// // Cancelled after the first chunk
+    /// let mut writers = vec![Vec::new(); 3];
+    /// let error = sharks.split_stream_with_progress(&secret[..], &mut writers, |_| false).unwrap_err();
+    /// assert_eq!(error.into_inner().unwrap().downcast_ref(), Some(&SharksError::Cancelled));
+    /// ```
+    pub fn split_stream_with_progress<R, W, P>(
+        &self,
+        mut reader: R,
+        writers: &mut [W],
+        mut progress: P,
+    ) -> io::Result<()>
+    where
+        R: Read,
+        W: Write,
+        P: FnMut(Progress) -> bool,
+    {
+        if writers.len() < self.0 as usize || writers.is_empty() {
+            return Err(invalid_input(SharksError::TooFewShares {
+                min: (self.0 as usize).max(1),
+            }));
+        } else if writers.len() > 255 {
+            return Err(invalid_input(SharksError::TooManyShares { max: 255 }));
+        }
+
+        for (x, writer) in (1..=255).zip(writers.iter_mut()) {
+            writer.write_all(&[x])?;
+        }
+
+        let mut buf = vec![0u8; BUFFER_SIZE];
+        let mut processed = 0;
+        let result = loop {
+            let read = match fill(&mut reader, &mut buf) {
+                Ok(0) => break Ok(()),
+                Ok(read) => read,
+                Err(e) => break Err(e),
+            };
+            let written = self
+                .dealer(&buf[..read])
+                .zip(writers.iter_mut())
+                .try_for_each(|(share, writer)| {
+                    let bytes: Vec<u8> = share.y.iter().map(|e| e.0).collect();
+                    writer.write_all(&bytes)
+                });
+            if let Err(e) = written {
+                break Err(e);
+            }
+            processed += read as u64;
+            if !progress(Progress {
+                processed,
+                total: None,
+            }) {
+                break Err(cancelled());
+            }
+        };
+
+        buf.zeroize();
+        result
+    }
+
+    /// Recovers the secret from the share streams written by `split_stream`, writing it to `writer`.
+    /// The shares are processed in buffered chunks, so the secret size is not limited by the available memory.
+    /// An `Err` is returned if there are not enough distinct shares, they have different lengths,
+    /// or reading or writing fails.
+    pub fn recover_stream<R: Read, W: Write>(
+        &self,
+        readers: &mut [R],
+        writer: W,
+    ) -> io::Result<()> {
+        self.recover_stream_with_progress(readers, writer, |_| true)
+    }
+
+    /// Same as `recover_stream`, calling `progress` after each chunk written to the writer, with the number of
+    /// secret bytes recovered so far. The operation is cancelled if the callback returns `false`, in which case
+    /// an `Err` holding `SharksError::Cancelled` is returned and the writer is left partially written.
+    pub fn recover_stream_with_progress<R, W, P>(
+        &self,
+        readers: &mut [R],
+        mut writer: W,
+        mut progress: P,
+    ) -> io::Result<()>
+    where
+        R: Read,
+        W: Write,
+        P: FnMut(Progress) -> bool,
+    {
+        let mut x = Vec::with_capacity(readers.len());
+        for reader in readers.iter_mut() {
+            let mut byte = [0u8];
+            if fill(reader, &mut byte)? == 0 {
+                return Err(invalid_input(SharksError::EmptyShare));
+            } else if byte[0] == 0 {
+                return Err(invalid_input(SharksError::ZeroShareX));
+            } else if x.contains(&GF256(byte[0])) {
+                return Err(invalid_input(SharksError::DuplicateShareX));
+            }
+            x.push(GF256(byte[0]));
+        }
+        if x.len() < self.0 as usize || x.is_empty() {
+            return Err(invalid_input(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: x.len(),
+                provided: x.len(),
+            }));
+        }
+
+        let mut bufs = vec![vec![0u8; BUFFER_SIZE]; readers.len()];
+        let mut processed = 0;
+        let result = loop {
+            let read = match readers
+                .iter_mut()
+                .zip(bufs.iter_mut())
+                .map(|(reader, buf)| fill(reader, buf))
+                .collect::<io::Result<Vec<usize>>>()
+            {
+                Ok(read) => read,
+                Err(e) => break Err(e),
+            };
+            if read.iter().any(|r| *r != read[0]) {
+                break Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    SharksError::InconsistentLengths,
+                ));
+            } else if read[0] == 0 {
+                break Ok(());
+            }
+
+            let shares: Vec<Share> = x
+                .iter()
+                .zip(bufs.iter())
+                .map(|(x, buf)| Share {
+                    x: *x,
+                    y: buf[..read[0]].iter().map(|b| GF256(*b)).collect(),
+                    threshold: None,
+                    group: None,
+                })
+                .collect();
+            let mut chunk: Vec<u8> = math::interpolate(&shares).iter().map(|e| e.0).collect();
+            let written = writer.write_all(&chunk);
+            chunk.zeroize();
+            if let Err(e) = written {
+                break Err(e);
+            }
+            processed += read[0] as u64;
+            if !progress(Progress {
+                processed,
+                total: None,
+            }) {
+                break Err(cancelled());
+            }
+        };
+
+        bufs.zeroize();
+        result
+    }
+
+    /// Splits the `secret` into as many shares as `writers`, writing each share to its own writer as soon as
+    /// it is dealt, so that the shares are never all held in memory together. The bytes written to each writer
+    /// can be parsed as a `Share`. A failing writer does not stop the others from receiving their share:
+    /// `SplitToWritersError::Writers` lists every failure with the index of its writer. An `Err` is also returned
+    /// if there are fewer writers than the threshold or more than 255, in which case nothing is written.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// # use std::convert::TryFrom;
+    /// let sharks = Sharks(2);
+    /// let mut writers = vec![Vec::new(); 3];
+    /// sharks.split_to_writers(&[1, 2, 3], &mut writers).unwrap();
+    ///
+    /// let shares: Vec<Share> = writers.iter().map(|w| Share::try_from(&w[..]).unwrap()).collect();
+    /// assert_eq!(sharks.recover(&shares[1..]).unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn split_to_writers<W: Write>(
+        &self,
+        secret: &[u8],
+        writers: &mut [W],
+    ) -> Result<(), SplitToWritersError> {
+        if writers.len() < self.0 as usize || writers.is_empty() {
+            return Err(SplitToWritersError::Invalid(SharksError::TooFewShares {
+                min: (self.0 as usize).max(1),
+            }));
+        } else if writers.len() > 255 {
+            return Err(SplitToWritersError::Invalid(SharksError::TooManyShares {
+                max: 255,
+            }));
+        }
+
+        let errors: Vec<(usize, io::Error)> = self
+            .dealer(secret)
+            .zip(writers.iter_mut())
+            .enumerate()
+            .filter_map(|(i, (share, writer))| {
+                let mut bytes = Vec::from(&share);
+                let written = writer.write_all(&bytes).and_then(|_| writer.flush());
+                bytes.zeroize();
+                written.err().map(|e| (i, e))
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SplitToWritersError::Writers(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Progress, SplitToWritersError, BUFFER_SIZE};
+    use crate::{Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+    use std::io::{self, Write};
+
+    #[test]
+    fn stream_integration_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..3 * BUFFER_SIZE + 10).map(|i| i as u8).collect();
+        let mut writers = vec![Vec::new(); 5];
+        sharks
+            .split_stream(secret.as_slice(), &mut writers)
+            .unwrap();
+        assert!(writers.iter().all(|w| w.len() == secret.len() + 1));
+
+        let mut recovered = Vec::new();
+        let mut readers: Vec<&[u8]> = writers[2..].iter().map(|w| w.as_slice()).collect();
+        sharks.recover_stream(&mut readers, &mut recovered).unwrap();
+        assert_eq!(recovered, secret);
+
+        // Streams are regular shares
+        let shares: Vec<Share> = writers[..3]
+            .iter()
+            .map(|w| Share::try_from(w.as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn split_stream_err() {
+        let mut writers = vec![Vec::new(); 2];
+        assert!(Sharks(3).split_stream(&[1, 2][..], &mut writers).is_err());
+        let mut writers = vec![Vec::new(); 256];
+        assert!(Sharks(3).split_stream(&[1, 2][..], &mut writers).is_err());
+    }
+
+    #[test]
+    fn recover_stream_err() {
+        let sharks = Sharks(2);
+        let mut writers = vec![Vec::new(); 3];
+        sharks.split_stream(&[1, 2, 3][..], &mut writers).unwrap();
+        let mut recovered = Vec::new();
+
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &writers[0]];
+        assert!(sharks.recover_stream(&mut readers, &mut recovered).is_err());
+        let mut readers: Vec<&[u8]> = vec![&writers[0]];
+        assert!(sharks.recover_stream(&mut readers, &mut recovered).is_err());
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &writers[1][..3]];
+        assert!(sharks.recover_stream(&mut readers, &mut recovered).is_err());
+        let mut readers: Vec<&[u8]> = vec![&writers[0], &[]];
+        assert!(sharks.recover_stream(&mut readers, &mut recovered).is_err());
+    }
+
+    #[test]
+    fn stream_progress_works() {
+        let sharks = Sharks(2);
+        let secret = vec![1u8; 2 * BUFFER_SIZE + 1];
+        let mut writers = vec![Vec::new(); 2];
+        let mut reports = Vec::new();
+        sharks
+            .split_stream_with_progress(secret.as_slice(), &mut writers, |p| {
+                reports.push(p);
+                true
+            })
+            .unwrap();
+        let processed: Vec<u64> = reports.iter().map(|p| p.processed).collect();
+        assert_eq!(
+            processed,
+            vec![
+                BUFFER_SIZE as u64,
+                2 * BUFFER_SIZE as u64,
+                secret.len() as u64
+            ]
+        );
+        assert_eq!(reports[0].fraction(), None);
+
+        let mut recovered = Vec::new();
+        let mut readers: Vec<&[u8]> = writers.iter().map(|w| w.as_slice()).collect();
+        let mut calls = 0;
+        let error = sharks
+            .recover_stream_with_progress(&mut readers, &mut recovered, |_| {
+                calls += 1;
+                calls < 2
+            })
+            .unwrap_err();
+        assert_eq!(
+            *error
+                .into_inner()
+                .unwrap()
+                .downcast::<SharksError>()
+                .unwrap(),
+            SharksError::Cancelled
+        );
+        assert_eq!(recovered.len(), 2 * BUFFER_SIZE);
+
+        let progress = Progress {
+            processed: 1,
+            total: Some(4),
+        };
+        assert_eq!(progress.fraction(), Some(0.25));
+    }
+
+    // Writer refusing every write, standing for a closed socket or a full disk
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn split_to_writers_works() {
+        let sharks = Sharks(3);
+        let secret: Vec<u8> = (0..100).collect();
+        let mut writers = vec![Vec::new(); 5];
+        sharks.split_to_writers(&secret, &mut writers).unwrap();
+        let shares: Vec<Share> = writers
+            .iter()
+            .map(|w| Share::try_from(w.as_slice()).unwrap())
+            .collect();
+        assert_eq!(shares[4].x.0, 5);
+        assert_eq!(sharks.recover(&shares[2..]).unwrap(), secret);
+
+        let mut writers: Vec<Box<dyn Write>> = vec![
+            Box::new(Vec::new()),
+            Box::new(Broken),
+            Box::new(Vec::new()),
+            Box::new(Broken),
+        ];
+        match sharks.split_to_writers(&secret, &mut writers) {
+            Err(SplitToWritersError::Writers(errors)) => {
+                assert_eq!(errors.iter().map(|e| e.0).collect::<Vec<_>>(), vec![1, 3]);
+            }
+            _ => panic!("writers should fail"),
+        }
+    }
+
+    #[test]
+    fn split_to_writers_err() {
+        let mut writers = vec![Vec::new(); 2];
+        match Sharks(3).split_to_writers(&[1, 2], &mut writers) {
+            Err(SplitToWritersError::Invalid(e)) => {
+                assert_eq!(e, SharksError::TooFewShares { min: 3 })
+            }
+            _ => panic!("too few writers"),
+        }
+        assert!(writers.iter().all(|w| w.is_empty()));
+        let mut writers = vec![Vec::new(); 256];
+        assert!(Sharks(3).split_to_writers(&[1, 2], &mut writers).is_err());
+        let mut writers = vec![Broken];
+        let error = Sharks(1).split_to_writers(&[1], &mut writers).unwrap_err();
+        assert_eq!(error.to_string(), "Writing failed for writers 0 (broken)");
+    }
+}
@@ -0,0 +1,119 @@
+// Sharing of structured values, serialized with serde as compact JSON before being split.
+// JSON keeps the fields in their declaration order and has a single compact form, so the same value always
+// results in the same secret bytes, readable by any language once recovered.
+// On recovery the bytes are deserialized into the expected type, which checks their structure.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::share::{AsShareRef, Share};
+use super::Sharks;
+
+impl Sharks {
+    /// Serializes the `value` as compact JSON and splits it into exactly `n` new shares, as `dealer_n`.
+    /// An `Err` is returned if the value does not serialize to JSON, such as maps with non string keys,
+    /// or `n` is out of range. The serialized bytes are wiped from memory.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use serde::{ Deserialize, Serialize };
+    /// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    /// struct Record {
+    ///     key: Vec<u8>,
+    ///     label: String,
+    /// }
+    ///
+    /// let sharks = Sharks(2);
+    /// let record = Record { key: vec![1, 2, 3], label: "backup".into() };
+    /// let shares = sharks.split_value(&record, 3).unwrap();
+    /// let recovered: Record = sharks.recover_value(&shares[1..]).unwrap();
+    /// assert_eq!(recovered, record);
+    /// ```
+    pub fn split_value<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        n: usize,
+    ) -> Result<Vec<Share>, SharksError> {
+        let mut bytes = serde_json::to_vec(value).map_err(|_| SharksError::InvalidValue)?;
+        let shares = self.dealer_n(&bytes, n);
+        bytes.zeroize();
+        shares
+    }
+
+    /// Recovers a value split with `split_value`, deserializing it into the type `T`.
+    /// An `Err` is returned for the same reasons as `recover`, or if the secret does not deserialize into `T`,
+    /// for instance when the shares were split from a value of another type.
+    /// The recovered bytes are wiped from memory.
+    pub fn recover_value<'a, V, S, T>(&self, shares: T) -> Result<V, SharksError>
+    where
+        V: DeserializeOwned,
+        S: AsShareRef + 'a,
+        T: IntoIterator<Item = &'a S>,
+        T::IntoIter: Iterator<Item = &'a S>,
+    {
+        let mut bytes = self.recover(shares)?;
+        let value = serde_json::from_slice(&bytes).map_err(|_| SharksError::InvalidValue);
+        bytes.zeroize();
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Sharks, SharksError};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn value_integration_works() {
+        let sharks = Sharks(3);
+        let mut value = BTreeMap::new();
+        value.insert("key".to_string(), vec![1u8, 2, 3]);
+        value.insert("iv".to_string(), vec![4u8; 12]);
+        let shares = sharks.split_value(&value, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(
+            sharks.recover(&shares).unwrap(),
+            br#"{"iv":[4,4,4,4,4,4,4,4,4,4,4,4],"key":[1,2,3]}"#.to_vec()
+        );
+        let recovered: BTreeMap<String, Vec<u8>> = sharks.recover_value(&shares[2..]).unwrap();
+        assert_eq!(recovered, value);
+
+        let shares = sharks.split_value("text", 3).unwrap();
+        assert_eq!(
+            sharks.recover_value::<String, _, _>(&shares).unwrap(),
+            "text"
+        );
+    }
+
+    #[test]
+    fn value_err() {
+        let sharks = Sharks(2);
+        let shares = sharks.split_value(&(1u8, true), 2).unwrap();
+        assert_eq!(
+            sharks.recover_value::<String, _, _>(&shares).err(),
+            Some(SharksError::InvalidValue)
+        );
+        assert_eq!(
+            sharks.recover_value::<(u8, bool), _, _>(&shares[..1]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 2,
+                got: 1,
+                provided: 1
+            })
+        );
+
+        let mut map = HashMap::new();
+        map.insert((1, 2), 3);
+        assert_eq!(
+            sharks.split_value(&map, 2).err(),
+            Some(SharksError::InvalidValue)
+        );
+        assert_eq!(
+            sharks.split_value(&1, 1).err(),
+            Some(SharksError::TooFewShares { min: 2 })
+        );
+    }
+}
@@ -0,0 +1,220 @@
+// Known-answer test vectors, for other implementations to check their compatibility with this crate.
+// A vector holds a secret, a threshold and the shares dealt from a fixed seed, all hexadecimal encoded.
+// Shares are in the regular `x || y` byte format over `GF256` with the AES polynomial, and their coefficients
+// are drawn from the ChaCha20 RNG seeded with the vector seed, see `Sharks::dealer_from_seed`.
+// Implementations without this RNG can still check that they recover the secret from the shares,
+// and that the shares they deal are recovered by this crate.
+
+use super::encoding::{from_hex, to_hex};
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+use std::convert::TryFrom;
+
+/// Known-answer test vector: the shares dealt for `secret` at `threshold` with the coefficients drawn from `seed`.
+///
+/// Example:
+/// ```
+/// # use sharks::TestVector;
+/// let vector = TestVector::generate([0; 32], b"sharks", 2, 3).unwrap();
+/// assert_eq!(vector.secret, "736861726b73");
+/// assert_eq!(vector.shares.len(), 3);
+/// assert!(vector.shares[0].starts_with("01"));
+/// assert!(vector.verify().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestVector {
+    /// Hexadecimal seed of the ChaCha20 RNG the coefficients are drawn from,
+    /// or `None` for vectors dealt with another RNG, whose shares are only checked to recover the secret.
+    pub seed: Option<String>,
+    /// Hexadecimal secret.
+    pub secret: String,
+    /// Minimum number of shares needed to recover the secret.
+    pub threshold: u8,
+    /// Hexadecimal bytes of the shares, at the `x` coordinates 1 to their number.
+    pub shares: Vec<String>,
+}
+
+impl TestVector {
+    /// Deals `count` shares of the `secret` at `threshold` from the `seed`, returning them as a test vector.
+    /// An `Err` is returned if the threshold is zero, or `count` is lower than it or higher than 255.
+    pub fn generate(
+        seed: [u8; 32],
+        secret: &[u8],
+        threshold: u8,
+        count: usize,
+    ) -> Result<TestVector, SharksError> {
+        if threshold == 0 {
+            return Err(SharksError::InvalidThreshold);
+        }
+        let sharks = Sharks(threshold);
+        // Validates `count` before dealing from the seed
+        sharks.dealer_n(secret, count)?;
+        Ok(TestVector {
+            seed: Some(to_hex(&seed)),
+            secret: to_hex(secret),
+            threshold,
+            shares: sharks
+                .dealer_from_seed(seed, secret)
+                .take(count)
+                .map(|s| to_hex(&Vec::from(&s)))
+                .collect(),
+        })
+    }
+
+    /// Checks the vector against this crate: every run of `threshold` consecutive shares must recover the secret,
+    /// and the shares must be the ones dealt from the seed, if any.
+    /// An `Err` holding `SharksError::VectorMismatch` is returned if they are not, `InvalidEncoding` if a field is not
+    /// hexadecimal or the seed is not 32 bytes long, or the error of `recover` if the shares are invalid.
+    pub fn verify(&self) -> Result<(), SharksError> {
+        let secret = from_hex(&self.secret).ok_or(SharksError::InvalidEncoding)?;
+        let shares = self
+            .shares
+            .iter()
+            .map(|s| {
+                let bytes = from_hex(s).ok_or(SharksError::InvalidEncoding)?;
+                Share::try_from(bytes.as_slice())
+            })
+            .collect::<Result<Vec<Share>, SharksError>>()?;
+
+        let sharks = Sharks(self.threshold);
+        let t = (self.threshold as usize).max(1);
+        if shares.len() < t {
+            sharks.recover(&shares)?;
+        }
+        for window in shares.windows(t) {
+            if sharks.recover(window)? != secret {
+                return Err(SharksError::VectorMismatch);
+            }
+        }
+
+        if let Some(seed) = &self.seed {
+            let seed = from_hex(seed)
+                .and_then(|s| <[u8; 32]>::try_from(s.as_slice()).ok())
+                .ok_or(SharksError::InvalidEncoding)?;
+            let expected = TestVector::generate(seed, &secret, self.threshold, shares.len())?;
+            let dealt = self.shares.iter().map(|s| s.to_lowercase());
+            if !dealt.eq(expected.shares) {
+                return Err(SharksError::VectorMismatch);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the reference test vectors of this crate, which it is guaranteed to reproduce across versions.
+/// They cover a single share, thresholds 1 to 255 and secrets of all the byte values,
+/// each dealt from the seed filled with its index.
+///
+/// Example:
+/// ```
+/// let vectors = sharks::test_vectors();
+/// assert!(sharks::verify_vectors(&vectors).is_ok());
+/// ```
+pub fn test_vectors() -> Vec<TestVector> {
+    let all: Vec<u8> = (0..=255).collect();
+    let cases: [(&[u8], u8, usize); 7] = [
+        (&[0x01], 1, 1),
+        (&[0x00], 1, 3),
+        (&[0xff], 2, 2),
+        (b"sharks", 2, 5),
+        (b"Shamir's secret sharing", 3, 5),
+        (&all, 5, 10),
+        (&all[..32], 255, 255),
+    ];
+    cases
+        .iter()
+        .enumerate()
+        .map(|(i, (secret, threshold, count))| {
+            TestVector::generate([i as u8; 32], secret, *threshold, *count)
+                .expect("reference vectors are valid")
+        })
+        .collect()
+}
+
+/// Verifies all the `vectors`, see `TestVector::verify`.
+/// An `Err` is returned with the index and error of each vector which does not verify.
+pub fn verify_vectors(vectors: &[TestVector]) -> Result<(), Vec<(usize, SharksError)>> {
+    let errors: Vec<(usize, SharksError)> = vectors
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| v.verify().err().map(|e| (i, e)))
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_vectors, verify_vectors, TestVector};
+    use crate::SharksError;
+
+    #[test]
+    fn vectors_integration_works() {
+        let vectors = test_vectors();
+        assert_eq!(vectors.len(), 7);
+        assert_eq!(verify_vectors(&vectors), Ok(()));
+        assert_eq!(vectors, test_vectors());
+        assert_eq!(vectors[3].shares[0].len(), 2 * 7);
+
+        // Pinned bytes, which must never change
+        let vector = &vectors[1];
+        assert_eq!(vector.shares, vec!["0100", "0200", "0300"]);
+        let vector = TestVector::generate([7; 32], &[0x2a], 2, 2).unwrap();
+        assert_eq!(
+            vector,
+            TestVector::generate([7; 32], &[0x2a], 2, 2).unwrap()
+        );
+
+        // Vectors of other RNGs only need to recover the secret
+        let mut foreign = vector.clone();
+        foreign.seed = None;
+        foreign.shares = TestVector::generate([8; 32], &[0x2a], 2, 2).unwrap().shares;
+        assert_eq!(foreign.verify(), Ok(()));
+        foreign.shares = foreign.shares.iter().map(|s| s.to_uppercase()).collect();
+        assert_eq!(foreign.verify(), Ok(()));
+    }
+
+    #[test]
+    fn vectors_err() {
+        assert_eq!(
+            TestVector::generate([0; 32], &[1], 0, 1).err(),
+            Some(SharksError::InvalidThreshold)
+        );
+        assert_eq!(
+            TestVector::generate([0; 32], &[1], 2, 256).err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+
+        let vector = TestVector::generate([1; 32], b"secret", 2, 3).unwrap();
+        let mut other = vector.clone();
+        other.seed = Some("00".repeat(32));
+        assert_eq!(other.verify(), Err(SharksError::VectorMismatch));
+        other.seed = Some("00".into());
+        assert_eq!(other.verify(), Err(SharksError::InvalidEncoding));
+        let mut other = vector.clone();
+        other.secret = "736563726574ff".into();
+        assert_eq!(other.verify(), Err(SharksError::VectorMismatch));
+        other.secret = "zz".into();
+        assert_eq!(other.verify(), Err(SharksError::InvalidEncoding));
+        let mut other = vector.clone();
+        other.shares.truncate(1);
+        assert!(other.verify().is_err());
+
+        assert_eq!(
+            verify_vectors(&[vector.clone(), other, vector]),
+            Err(vec![(
+                1,
+                SharksError::NotEnoughShares {
+                    needed: 2,
+                    got: 1,
+                    provided: 1
+                }
+            )])
+        );
+    }
+}
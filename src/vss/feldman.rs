@@ -0,0 +1,289 @@
+//! [Feldman's verifiable secret sharing](https://en.wikipedia.org/wiki/Verifiable_secret_sharing#Feldman%E2%80%99s_scheme).
+//!
+//! Along with the shares, the dealer publishes `Commitments` to the coefficients of every
+//! polynomial. Each participant can then verify its share is consistent before storing it.
+//! Note the commitment to the constant term reveals `g^s` for each secret chunk `s`,
+//! so secrets must be unpredictable (e.g. keys) for this scheme to be hiding.
+//!
+//! Usage example:
+//! ```
+//! use sharks::Sharks;
+//! use sharks::vss::feldman::Share;
+//!
+//! let sharks = Sharks(3);
+//! let (commitments, dealer) = sharks.dealer_feldman(&[1, 2, 3, 4]);
+//! let shares: Vec<Share> = dealer.take(5).collect();
+//! // Every participant checks its share against the published commitments
+//! assert!(shares.iter().all(|s| s.verify(&commitments)));
+//! // Recover the original secret!
+//! let secret = sharks.recover_feldman(&shares[..3]).unwrap();
+//! assert_eq!(secret, vec![1, 2, 3, 4]);
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use super::{
+    commitments_from_bytes, commitments_to_bytes, encode_secret, evaluate, evaluate_commitments,
+    random_polynomial, recover, share_from_bytes, share_to_bytes,
+};
+use crate::{Sharks, SharksError};
+
+/// Commitments to the coefficients of the polynomials used to generate a set of shares.
+/// Can be serialized to and from a byte array, to be published by the dealer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Commitments {
+    threshold: u8,
+    // One commitment vector per secret chunk, coefficient degrees going from higher to lower
+    points: Vec<Vec<RistrettoPoint>>,
+}
+
+impl Commitments {
+    /// Returns the minimum threshold of shares the commitments were generated for.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+}
+
+/// Obtains a byte vector from a `Commitments` instance: `threshold || points..`
+impl From<&Commitments> for Vec<u8> {
+    fn from(c: &Commitments) -> Vec<u8> {
+        commitments_to_bytes(c.threshold, &c.points)
+    }
+}
+
+/// Obtains a `Commitments` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for Commitments {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<Commitments, Self::Error> {
+        let (threshold, points) = commitments_from_bytes(s)?;
+        Ok(Commitments { threshold, points })
+    }
+}
+
+/// A share of a Feldman verifiable secret sharing. Can be serialized to and from a byte array.
+#[derive(Clone, PartialEq)]
+pub struct Share {
+    pub x: u8,
+    pub y: Vec<Scalar>,
+}
+
+/// Prints the share `x` coordinate and length, the `y` values being redacted so that shares do not leak into logs.
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", self.y.len()))
+            .finish()
+    }
+}
+
+impl Share {
+    /// Checks the share is consistent with the `commitments` published by the dealer,
+    /// that is, it lies on the committed polynomials.
+    pub fn verify(&self, commitments: &Commitments) -> bool {
+        let x = Scalar::from(self.x);
+        self.x != 0
+            && self.y.len() == commitments.points.len()
+            && self
+                .y
+                .iter()
+                .zip(commitments.points.iter())
+                .all(|(y, poly)| RistrettoPoint::mul_base(y) == evaluate_commitments(poly, x))
+    }
+}
+
+/// Obtains a byte vector from a `Share` instance: `x || y..`
+impl From<&Share> for Vec<u8> {
+    fn from(s: &Share) -> Vec<u8> {
+        share_to_bytes(s.x, s.y.iter())
+    }
+}
+
+/// Obtains a `Share` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for Share {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<Share, Self::Error> {
+        let (x, y) = share_from_bytes(s)?;
+        Ok(Share { x, y })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns the `Commitments` to publish along with an `Iterator`
+    /// over new Feldman verifiable shares. The maximum number of shares that can be generated is 255.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use sharks::vss::feldman::Share;
+    /// # let sharks = Sharks(3);
+    /// // Obtain the commitments and an iterator over the shares for secret [1, 2]
+    /// let (commitments, dealer) = sharks.dealer_feldman(&[1, 2]);
+    /// // Get 3 shares
+    /// let shares: Vec<Share> = dealer.take(3).collect();
+    pub fn dealer_feldman(&self, secret: &[u8]) -> (Commitments, impl Iterator<Item = Share>) {
+        let polys: Vec<Vec<Scalar>> = encode_secret(secret)
+            .into_iter()
+            .map(|s| random_polynomial(s, self.0))
+            .collect();
+
+        let commitments = Commitments {
+            threshold: polys[0].len() as u8,
+            points: polys
+                .iter()
+                .map(|p| p.iter().map(RistrettoPoint::mul_base).collect())
+                .collect(),
+        };
+
+        let shares = (1..=u8::MAX).map(move |x| Share {
+            x,
+            y: polys.iter().map(|p| evaluate(p, Scalar::from(x))).collect(),
+        });
+
+        (commitments, shares)
+    }
+
+    /// Given an iterable collection of Feldman verifiable shares, recovers the original secret.
+    /// If the number of distinct shares is less than the minimum threshold,
+    /// or the shares are inconsistent, an `Err` is returned.
+    /// Shares are not verified, use `Share::verify` for that purpose beforehand.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use sharks::vss::feldman::Share;
+    /// # let sharks = Sharks(3);
+    /// # let (_, dealer) = sharks.dealer_feldman(&[1]);
+    /// # let mut shares: Vec<Share> = dealer.take(3).collect();
+    /// // Recover original secret from shares
+    /// let mut secret = sharks.recover_feldman(&shares);
+    /// // Secret correctly recovered
+    /// assert!(secret.is_ok());
+    /// // Remove shares for demonstration purposes
+    /// shares.clear();
+    /// secret = sharks.recover_feldman(&shares);
+    /// // Not enough shares to recover secret
+    /// assert!(secret.is_err());
+    pub fn recover_feldman<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        recover(self.0, shares.into_iter().map(|s| (s.x, s.y.as_slice())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Commitments, Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn verify_works() {
+        let (commitments, dealer) = Sharks(3).dealer_feldman(&[1, 2, 3]);
+        for share in dealer.take(5) {
+            assert!(share.verify(&commitments));
+        }
+    }
+
+    #[test]
+    fn verify_tampered_share_fails() {
+        let (commitments, mut dealer) = Sharks(3).dealer_feldman(&[1, 2, 3]);
+        let share = dealer.next().unwrap();
+
+        let mut tampered = share.clone();
+        tampered.x = 2;
+        assert!(!tampered.verify(&commitments));
+
+        let mut tampered = share;
+        tampered.y[0] += curve25519_dalek::scalar::Scalar::ONE;
+        assert!(!tampered.verify(&commitments));
+
+        let (other, _) = Sharks(3).dealer_feldman(&[1, 2, 3]);
+        assert!(!dealer.next().unwrap().verify(&other));
+    }
+
+    #[test]
+    fn serialization_roundtrip_works() {
+        let (commitments, mut dealer) = Sharks(3).dealer_feldman(&[1; 40]);
+        let share = dealer.next().unwrap();
+
+        let bytes = Vec::from(&commitments);
+        assert_eq!(bytes.len(), 1 + 2 * 3 * 32);
+        let commitments = Commitments::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(commitments.threshold(), 3);
+
+        let bytes = Vec::from(&share);
+        assert_eq!(bytes.len(), 1 + 2 * 32);
+        let share = Share::try_from(bytes.as_slice()).unwrap();
+        assert!(share.verify(&commitments));
+    }
+
+    #[test]
+    fn invalid_bytes_err() {
+        assert!(Commitments::try_from(&[][..]).is_err());
+        assert!(Commitments::try_from(&[0][..]).is_err());
+        assert!(Commitments::try_from(&[1, 2, 3][..]).is_err());
+        assert!(Commitments::try_from(&[1; 33][..]).is_err());
+        assert!(Share::try_from(&[][..]).is_err());
+        assert_eq!(
+            Share::try_from(&[0; 33][..]).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert!(Share::try_from(&[1][..]).is_err());
+        assert_eq!(
+            Share::try_from(&[1, 2, 3][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert_eq!(
+            Share::try_from(&[0xff; 33][..]).err(),
+            Some(SharksError::InvalidElement)
+        );
+    }
+
+    #[test]
+    fn integration_works() {
+        let sharks = Sharks(5);
+        let secret: Vec<u8> = (0..100).collect();
+        let (_, dealer) = sharks.dealer_feldman(&secret);
+        let shares: Vec<Share> = dealer.take(5).collect();
+        assert_eq!(sharks.recover_feldman(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn insufficient_shares_err() {
+        let sharks = Sharks(5);
+        let (_, dealer) = sharks.dealer_feldman(&[1]);
+        let mut shares: Vec<Share> = dealer.take(5).collect();
+        shares[1] = shares[0].clone();
+        assert_eq!(
+            sharks.recover_feldman(&shares),
+            Err(SharksError::NotEnoughShares {
+                needed: 5,
+                got: 4,
+                provided: 5
+            })
+        );
+
+        // Conflicting shares at the same `x` are rejected rather than dropped
+        let (_, dealer) = sharks.dealer_feldman(&[1]);
+        let mut shares: Vec<Share> = dealer.take(6).collect();
+        shares[5].x = 1;
+        assert_eq!(
+            sharks.recover_feldman(&shares),
+            Err(SharksError::DuplicateShareX)
+        );
+        let extra = shares[5].y[0];
+        shares[5].y.push(extra);
+        assert_eq!(
+            sharks.recover_feldman(&shares),
+            Err(SharksError::InconsistentLengths)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Verifiable secret sharing schemes over the [Ristretto255](https://ristretto.group) prime order group.
+//!
+//! Contrary to the `Sharks` byte-wise shares over GF256, secrets are split in chunks of 31 bytes
+//! which are shared as scalars of the group, so that the dealer can publish commitments to the
+//! polynomial coefficients and participants can check their shares against them.
+//! The `pvss` module goes further, shares being encrypted to their recipients so that anyone can verify them.
+
+pub mod feldman;
+pub mod pedersen;
+pub mod pvss;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+
+use crate::SharksError;
+
+// Number of secret bytes packed in each scalar, small enough to never overflow the group order
+const CHUNK_SIZE: usize = 31;
+
+// Size in bytes of encoded scalars and compressed group elements
+const ELEMENT_SIZE: usize = 32;
+
+// Splits the secret in scalar chunks. The secret is padded with a `0x80` byte followed by zeroes
+// up to a multiple of the chunk size, so that its exact length can be restored on recovery.
+fn encode_secret(secret: &[u8]) -> Vec<Scalar> {
+    let mut padded = secret.to_vec();
+    padded.push(0x80);
+    padded.resize(padded.len().div_ceil(CHUNK_SIZE) * CHUNK_SIZE, 0);
+
+    padded
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            let mut bytes = [0u8; ELEMENT_SIZE];
+            bytes[..CHUNK_SIZE].copy_from_slice(chunk);
+            Scalar::from_bytes_mod_order(bytes)
+        })
+        .collect()
+}
+
+// Inverse of `encode_secret`, fails if the scalars do not hold a correctly padded secret.
+fn decode_secret(chunks: &[Scalar]) -> Result<Vec<u8>, SharksError> {
+    let mut secret = Vec::with_capacity(chunks.len() * CHUNK_SIZE);
+    for chunk in chunks {
+        let bytes = chunk.to_bytes();
+        if bytes[CHUNK_SIZE..].iter().any(|b| *b != 0) {
+            return Err(SharksError::InvalidPadding);
+        }
+        secret.extend_from_slice(&bytes[..CHUNK_SIZE]);
+    }
+
+    while let Some(0) = secret.last() {
+        secret.pop();
+    }
+    match secret.pop() {
+        Some(0x80) => Ok(secret),
+        _ => Err(SharksError::InvalidPadding),
+    }
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 2 * ELEMENT_SIZE];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+// Generates `k` polynomial coefficients, being the last one `s` and the others random scalars.
+// Coefficient degrees go from higher to lower in the returned vector order.
+fn random_polynomial(s: Scalar, k: u8) -> Vec<Scalar> {
+    let mut poly: Vec<Scalar> = (1..k).map(|_| random_scalar()).collect();
+    poly.push(s);
+    poly
+}
+
+fn evaluate(poly: &[Scalar], x: Scalar) -> Scalar {
+    poly.iter().fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+// Evaluates the polynomial committed to by `commitments` in the exponent, using Horner's method.
+fn evaluate_commitments(commitments: &[RistrettoPoint], x: Scalar) -> RistrettoPoint {
+    commitments
+        .iter()
+        .fold(RistrettoPoint::default(), |acc, c| acc * x + c)
+}
+
+// Computes the Lagrange coefficients of the points at `xs` for the evaluation at zero. All `x` must be distinct.
+fn lagrange_weights(xs: &[Scalar]) -> Vec<Scalar> {
+    xs.iter()
+        .map(|x_i| {
+            let (num, den) = xs
+                .iter()
+                .filter(|x_j| *x_j != x_i)
+                .fold((Scalar::ONE, Scalar::ONE), |(num, den), x_j| {
+                    (num * x_j, den * (x_j - x_i))
+                });
+            num * den.invert()
+        })
+        .collect()
+}
+
+// Finds the root of the Lagrange polynomials defined by `points`, given as `(x, [y_1, y_2..])` pairs
+// where each `y_i` belongs to the ith polynomial. All `x` must be distinct.
+fn interpolate(points: &[(Scalar, &[Scalar])]) -> Vec<Scalar> {
+    let xs: Vec<Scalar> = points.iter().map(|(x, _)| *x).collect();
+    let weights = lagrange_weights(&xs);
+
+    (0..points[0].1.len())
+        .map(|c| {
+            points
+                .iter()
+                .zip(weights.iter())
+                .map(|((_, y), w)| y[c] * w)
+                .sum()
+        })
+        .collect()
+}
+
+// Recovers the secret from `(x, [y_1, y_2..])` share points. Identical copies of a point are ignored,
+// while different points at the same `x` return an `Err`, as with `Sharks::recover`.
+fn recover<'a>(
+    threshold: u8,
+    shares: impl Iterator<Item = (u8, &'a [Scalar])>,
+) -> Result<Vec<u8>, SharksError> {
+    let mut distinct: Vec<(u8, &[Scalar])> = Vec::new();
+    let mut provided = 0;
+    for (x, y) in shares {
+        provided += 1;
+        match distinct.iter().find(|(x_i, _)| *x_i == x) {
+            Some((_, y_i)) if y_i.len() != y.len() => return Err(SharksError::InconsistentLengths),
+            Some((_, y_i)) if *y_i != y => return Err(SharksError::DuplicateShareX),
+            Some(_) => {}
+            None => distinct.push((x, y)),
+        }
+    }
+    let points: Vec<(Scalar, &[Scalar])> = distinct
+        .into_iter()
+        .map(|(x, y)| (Scalar::from(x), y))
+        .collect();
+
+    if points.len() < (threshold as usize).max(1) {
+        Err(SharksError::NotEnoughShares {
+            needed: (threshold as usize).max(1),
+            got: points.len(),
+            provided,
+        })
+    } else if points.iter().any(|(_, y)| y.len() != points[0].1.len()) {
+        Err(SharksError::InconsistentLengths)
+    } else {
+        decode_secret(&interpolate(&points))
+    }
+}
+
+// Second generator of the group used by Pedersen commitments, whose discrete logarithm
+// with respect to the basepoint is unknown as it is obtained by hashing the latter to the group.
+fn blinding_generator() -> RistrettoPoint {
+    let mut bytes = [0u8; 2 * ELEMENT_SIZE];
+    bytes.copy_from_slice(&Sha512::digest(RISTRETTO_BASEPOINT_COMPRESSED.as_bytes()));
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+fn scalar_from_bytes(bytes: &[u8]) -> Option<Scalar> {
+    let mut array = [0u8; ELEMENT_SIZE];
+    array.copy_from_slice(bytes);
+    Scalar::from_canonical_bytes(array).into()
+}
+
+fn point_from_bytes(bytes: &[u8]) -> Option<RistrettoPoint> {
+    CompressedRistretto::from_slice(bytes).ok()?.decompress()
+}
+
+// Serializes commitment vectors as `threshold || points..`
+fn commitments_to_bytes(threshold: u8, points: &[Vec<RistrettoPoint>]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(points.len() * threshold as usize * ELEMENT_SIZE + 1);
+    bytes.push(threshold);
+    for point in points.iter().flatten() {
+        bytes.extend_from_slice(point.compress().as_bytes());
+    }
+    bytes
+}
+
+// Inverse of `commitments_to_bytes`, validating every point is a canonical group element.
+fn commitments_from_bytes(s: &[u8]) -> Result<(u8, Vec<Vec<RistrettoPoint>>), SharksError> {
+    let (threshold, points) = match s {
+        [] => return Err(SharksError::InvalidLength),
+        [0, ..] => return Err(SharksError::InvalidThreshold),
+        [threshold, points @ ..] => (*threshold, points),
+    };
+
+    let poly_size = threshold as usize * ELEMENT_SIZE;
+    if points.is_empty() || points.len() % poly_size != 0 {
+        return Err(SharksError::InvalidLength);
+    }
+
+    let points = points
+        .chunks(poly_size)
+        .map(|poly| {
+            poly.chunks(ELEMENT_SIZE)
+                .map(point_from_bytes)
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(SharksError::InvalidElement)?;
+
+    Ok((threshold, points))
+}
+
+// Serializes share points as `x || scalars..`
+fn share_to_bytes<'a>(x: u8, scalars: impl Iterator<Item = &'a Scalar>) -> Vec<u8> {
+    let mut bytes = vec![x];
+    for scalar in scalars {
+        bytes.extend_from_slice(scalar.as_bytes());
+    }
+    bytes
+}
+
+// Inverse of `share_to_bytes`, validating `x` is not zero and every scalar is canonical.
+fn share_from_bytes(s: &[u8]) -> Result<(u8, Vec<Scalar>), SharksError> {
+    let (x, y) = match s {
+        [] | [_] => return Err(SharksError::EmptyShare),
+        [0, ..] => return Err(SharksError::ZeroShareX),
+        [x, y @ ..] => (*x, y),
+    };
+
+    if y.len() % ELEMENT_SIZE != 0 {
+        return Err(SharksError::InvalidLength);
+    }
+    let y = y
+        .chunks(ELEMENT_SIZE)
+        .map(scalar_from_bytes)
+        .collect::<Option<Vec<_>>>()
+        .ok_or(SharksError::InvalidElement)?;
+
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_secret, encode_secret, evaluate, interpolate, random_polynomial, Scalar};
+
+    #[test]
+    fn secret_encoding_roundtrip_works() {
+        for len in [0, 1, 30, 31, 32, 100].iter() {
+            let secret: Vec<u8> = (0..*len as u8).collect();
+            let chunks = encode_secret(&secret);
+            assert_eq!(chunks.len(), len / 31 + 1);
+            assert_eq!(decode_secret(&chunks).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn secret_decoding_invalid_err() {
+        assert!(decode_secret(&[Scalar::ZERO]).is_err());
+        assert!(decode_secret(&[-Scalar::ONE]).is_err());
+    }
+
+    #[test]
+    fn interpolate_works() {
+        let poly = random_polynomial(Scalar::from(185u64), 10);
+        let points: Vec<(Scalar, Vec<Scalar>)> = (1..=10u64)
+            .map(Scalar::from)
+            .map(|x| (x, vec![evaluate(&poly, x)]))
+            .collect();
+        let points: Vec<(Scalar, &[Scalar])> =
+            points.iter().map(|(x, y)| (*x, y.as_slice())).collect();
+        assert_eq!(interpolate(&points), vec![Scalar::from(185u64)]);
+    }
+}
@@ -0,0 +1,274 @@
+//! [Pedersen's verifiable secret sharing](https://link.springer.com/chapter/10.1007/3-540-46766-1_9).
+//!
+//! Works like the Feldman scheme, but every coefficient commitment is blinded by a random value
+//! shared through a companion polynomial. Commitments are then perfectly hiding and reveal
+//! nothing about the secret, while participants can still verify their shares.
+//!
+//! Usage example:
+//! ```
+//! use sharks::Sharks;
+//! use sharks::vss::pedersen::Share;
+//!
+//! let sharks = Sharks(3);
+//! let (commitments, dealer) = sharks.dealer_pedersen(&[1, 2, 3, 4]);
+//! let shares: Vec<Share> = dealer.take(5).collect();
+//! // Every participant checks its share against the published commitments
+//! assert!(shares.iter().all(|s| s.verify(&commitments)));
+//! // Recover the original secret!
+//! let secret = sharks.recover_pedersen(&shares[..3]).unwrap();
+//! assert_eq!(secret, vec![1, 2, 3, 4]);
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use super::{
+    blinding_generator, commitments_from_bytes, commitments_to_bytes, encode_secret, evaluate,
+    evaluate_commitments, random_polynomial, random_scalar, recover, share_from_bytes,
+    share_to_bytes,
+};
+use crate::{Sharks, SharksError};
+
+/// Blinded commitments to the coefficients of the polynomials used to generate a set of shares.
+/// Can be serialized to and from a byte array, to be published by the dealer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Commitments {
+    threshold: u8,
+    // One commitment vector per secret chunk, coefficient degrees going from higher to lower
+    points: Vec<Vec<RistrettoPoint>>,
+}
+
+impl Commitments {
+    /// Returns the minimum threshold of shares the commitments were generated for.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+}
+
+/// Obtains a byte vector from a `Commitments` instance: `threshold || points..`
+impl From<&Commitments> for Vec<u8> {
+    fn from(c: &Commitments) -> Vec<u8> {
+        commitments_to_bytes(c.threshold, &c.points)
+    }
+}
+
+/// Obtains a `Commitments` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for Commitments {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<Commitments, Self::Error> {
+        let (threshold, points) = commitments_from_bytes(s)?;
+        Ok(Commitments { threshold, points })
+    }
+}
+
+/// A share of a Pedersen verifiable secret sharing, made of the secret share `y`
+/// and its companion `blinding` share. Can be serialized to and from a byte array.
+#[derive(Clone, PartialEq)]
+pub struct Share {
+    pub x: u8,
+    pub y: Vec<Scalar>,
+    pub blinding: Vec<Scalar>,
+}
+
+/// Prints the share `x` coordinate and length, the `y` and `blinding` values being redacted.
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("x", &self.x)
+            .field("y", &format_args!("<{} redacted>", self.y.len()))
+            .field(
+                "blinding",
+                &format_args!("<{} redacted>", self.blinding.len()),
+            )
+            .finish()
+    }
+}
+
+impl Share {
+    /// Checks the share is consistent with the `commitments` published by the dealer,
+    /// that is, both the secret and blinding shares lie on the committed polynomials.
+    pub fn verify(&self, commitments: &Commitments) -> bool {
+        let x = Scalar::from(self.x);
+        let h = blinding_generator();
+
+        self.x != 0
+            && self.y.len() == commitments.points.len()
+            && self.blinding.len() == commitments.points.len()
+            && self
+                .y
+                .iter()
+                .zip(self.blinding.iter())
+                .zip(commitments.points.iter())
+                .all(|((y, t), poly)| {
+                    RistrettoPoint::mul_base(y) + h * t == evaluate_commitments(poly, x)
+                })
+    }
+}
+
+/// Obtains a byte vector from a `Share` instance: `x || y.. || blinding..`
+impl From<&Share> for Vec<u8> {
+    fn from(s: &Share) -> Vec<u8> {
+        share_to_bytes(s.x, s.y.iter().chain(s.blinding.iter()))
+    }
+}
+
+/// Obtains a `Share` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for Share {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<Share, Self::Error> {
+        let (x, mut y) = share_from_bytes(s)?;
+        if y.len() % 2 != 0 {
+            return Err(SharksError::InconsistentLengths);
+        }
+
+        let blinding = y.split_off(y.len() / 2);
+        Ok(Share { x, y, blinding })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns the blinded `Commitments` to publish along with
+    /// an `Iterator` over new Pedersen verifiable shares.
+    /// The maximum number of shares that can be generated is 255.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use sharks::vss::pedersen::Share;
+    /// # let sharks = Sharks(3);
+    /// // Obtain the commitments and an iterator over the shares for secret [1, 2]
+    /// let (commitments, dealer) = sharks.dealer_pedersen(&[1, 2]);
+    /// // Get 3 shares
+    /// let shares: Vec<Share> = dealer.take(3).collect();
+    pub fn dealer_pedersen(&self, secret: &[u8]) -> (Commitments, impl Iterator<Item = Share>) {
+        let polys: Vec<(Vec<Scalar>, Vec<Scalar>)> = encode_secret(secret)
+            .into_iter()
+            .map(|s| {
+                (
+                    random_polynomial(s, self.0),
+                    random_polynomial(random_scalar(), self.0),
+                )
+            })
+            .collect();
+
+        let h = blinding_generator();
+        let commitments = Commitments {
+            threshold: polys[0].0.len() as u8,
+            points: polys
+                .iter()
+                .map(|(p, b)| {
+                    p.iter()
+                        .zip(b.iter())
+                        .map(|(a, t)| RistrettoPoint::mul_base(a) + h * t)
+                        .collect()
+                })
+                .collect(),
+        };
+
+        let shares = (1..=u8::MAX).map(move |x| {
+            let x_s = Scalar::from(x);
+            Share {
+                x,
+                y: polys.iter().map(|(p, _)| evaluate(p, x_s)).collect(),
+                blinding: polys.iter().map(|(_, b)| evaluate(b, x_s)).collect(),
+            }
+        });
+
+        (commitments, shares)
+    }
+
+    /// Given an iterable collection of Pedersen verifiable shares, recovers the original secret.
+    /// If the number of distinct shares is less than the minimum threshold,
+    /// or the shares are inconsistent, an `Err` is returned.
+    /// Shares are not verified, use `Share::verify` for that purpose beforehand.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::Sharks;
+    /// # use sharks::vss::pedersen::Share;
+    /// # let sharks = Sharks(3);
+    /// # let (_, dealer) = sharks.dealer_pedersen(&[1]);
+    /// # let mut shares: Vec<Share> = dealer.take(3).collect();
+    /// // Recover original secret from shares
+    /// let mut secret = sharks.recover_pedersen(&shares);
+    /// // Secret correctly recovered
+    /// assert!(secret.is_ok());
+    /// // Remove shares for demonstration purposes
+    /// shares.clear();
+    /// secret = sharks.recover_pedersen(&shares);
+    /// // Not enough shares to recover secret
+    /// assert!(secret.is_err());
+    pub fn recover_pedersen<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        recover(self.0, shares.into_iter().map(|s| (s.x, s.y.as_slice())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Commitments, Scalar, Share, Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn verify_works() {
+        let (commitments, dealer) = Sharks(3).dealer_pedersen(&[1, 2, 3]);
+        for share in dealer.take(5) {
+            assert!(share.verify(&commitments));
+        }
+    }
+
+    #[test]
+    fn verify_tampered_share_fails() {
+        let (commitments, mut dealer) = Sharks(3).dealer_pedersen(&[1, 2, 3]);
+        let share = dealer.next().unwrap();
+
+        let mut tampered = share.clone();
+        tampered.y[0] += Scalar::ONE;
+        assert!(!tampered.verify(&commitments));
+
+        let mut tampered = share;
+        tampered.blinding[0] += Scalar::ONE;
+        assert!(!tampered.verify(&commitments));
+    }
+
+    #[test]
+    fn serialization_roundtrip_works() {
+        let (commitments, mut dealer) = Sharks(2).dealer_pedersen(&[1; 40]);
+        let share = dealer.next().unwrap();
+
+        let commitments = Commitments::try_from(Vec::from(&commitments).as_slice()).unwrap();
+        let bytes = Vec::from(&share);
+        assert_eq!(bytes.len(), 1 + 2 * 2 * 32);
+        let share = Share::try_from(bytes.as_slice()).unwrap();
+        assert!(share.verify(&commitments));
+        assert!(Share::try_from(&bytes[..33]).is_err());
+    }
+
+    #[test]
+    fn integration_works() {
+        let sharks = Sharks(5);
+        let secret: Vec<u8> = (0..100).collect();
+        let (_, dealer) = sharks.dealer_pedersen(&secret);
+        let shares: Vec<Share> = dealer.take(5).collect();
+        assert_eq!(sharks.recover_pedersen(&shares).unwrap(), secret);
+        assert!(sharks.recover_pedersen(&shares[..4]).is_err());
+
+        let mut conflicting = shares[4].clone();
+        conflicting.x = shares[0].x;
+        let mut pool = shares[1..].to_vec();
+        pool.push(shares[0].clone());
+        pool.push(conflicting);
+        assert_eq!(
+            sharks.recover_pedersen(&pool),
+            Err(SharksError::DuplicateShareX)
+        );
+    }
+}
@@ -0,0 +1,536 @@
+//! [Schoenmakers' publicly verifiable secret sharing](https://www.win.tue.nl/~berry/papers/crypto99.pdf).
+//!
+//! The dealer encrypts each share to the public key of its recipient and publishes them in a `Transcript`,
+//! along with commitments to the polynomial coefficients and non-interactive proofs that each encrypted share
+//! matches the commitments. Anyone can then verify the dealer distributed consistent shares to the right
+//! recipients, without learning them. Recipients decrypt their share and publish it with a proof of correct
+//! decryption, so that anyone can recover the secret from enough verified decrypted shares.
+//!
+//! Commitments are made over the Ristretto basepoint `g`, while keys and shares live over a second
+//! generator `G`, the shared secret being `G^s` for a random `s` chosen by the dealer, hashed to 32 bytes.
+//! Secrets are thus random values, such as beacon outputs or keys, rather than chosen byte strings.
+//!
+//! Usage example:
+//! ```
+//! use sharks::Sharks;
+//! use sharks::vss::pvss::{self, DecryptedShare};
+//! # use rand::RngCore;
+//! # let random_key = || {
+//! #     let mut bytes = [0u8; 64];
+//! #     rand::thread_rng().fill_bytes(&mut bytes);
+//! #     curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&bytes)
+//! # };
+//!
+//! let secret_keys: Vec<_> = (0..5).map(|_| random_key()).collect();
+//! let public_keys: Vec<_> = secret_keys.iter().map(pvss::public_key).collect();
+//!
+//! let sharks = Sharks(3);
+//! let (secret, transcript) = sharks.dealer_pvss(&public_keys).unwrap();
+//! // Anyone checks the dealer distributed consistent shares to the recipients
+//! assert!(transcript.verify(&public_keys));
+//! // Recipients decrypt their shares, whose decryption anyone can check as well
+//! let shares: Vec<DecryptedShare> = (1..=3)
+//!     .map(|x| transcript.decrypt(x, &secret_keys[x as usize - 1]).unwrap())
+//!     .collect();
+//! assert!(shares.iter().all(|s| s.verify(&transcript, &public_keys)));
+//! // Recover the secret!
+//! assert_eq!(sharks.recover_pvss(&shares).unwrap(), secret);
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+
+use super::{
+    blinding_generator, evaluate, evaluate_commitments, lagrange_weights, point_from_bytes,
+    random_polynomial, random_scalar, scalar_from_bytes, ELEMENT_SIZE,
+};
+use crate::{Sharks, SharksError};
+
+// Domain separation of the proofs challenges and the secret
+const PROOF_DOMAIN: &[u8] = b"sharks pvss proof";
+const SECRET_DOMAIN: &[u8] = b"sharks pvss secret";
+
+// Size in bytes of a serialized proof
+const PROOF_SIZE: usize = 2 * ELEMENT_SIZE;
+
+/// Returns the public key of a recipient, shares being encrypted to it, given its `secret_key`.
+pub fn public_key(secret_key: &Scalar) -> RistrettoPoint {
+    blinding_generator() * secret_key
+}
+
+// Hashes the shared group element to the secret
+fn secret(point: &RistrettoPoint) -> [u8; 32] {
+    let hash = Sha512::new()
+        .chain_update(SECRET_DOMAIN)
+        .chain_update(point.compress().as_bytes())
+        .finalize();
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&hash[..32]);
+    secret
+}
+
+// Chaum-Pedersen proof that `log_g1(h1) == log_g2(h2)`, made non-interactive with the Fiat-Shamir heuristic
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Proof {
+    challenge: Scalar,
+    response: Scalar,
+}
+
+impl Proof {
+    fn challenge(points: [&RistrettoPoint; 6]) -> Scalar {
+        let mut hash = Sha512::new().chain_update(PROOF_DOMAIN);
+        for point in points.iter() {
+            hash.update(point.compress().as_bytes());
+        }
+        let mut bytes = [0u8; 2 * ELEMENT_SIZE];
+        bytes.copy_from_slice(&hash.finalize());
+        Scalar::from_bytes_mod_order_wide(&bytes)
+    }
+
+    // Proves knowledge of `alpha`, being `h1 = g1^alpha` and `h2 = g2^alpha`
+    fn prove(
+        alpha: &Scalar,
+        g1: &RistrettoPoint,
+        h1: &RistrettoPoint,
+        g2: &RistrettoPoint,
+        h2: &RistrettoPoint,
+    ) -> Proof {
+        let w = random_scalar();
+        let (a1, a2) = (g1 * w, g2 * w);
+        let challenge = Proof::challenge([g1, h1, g2, h2, &a1, &a2]);
+        Proof {
+            challenge,
+            response: w - challenge * alpha,
+        }
+    }
+
+    fn verify(
+        &self,
+        g1: &RistrettoPoint,
+        h1: &RistrettoPoint,
+        g2: &RistrettoPoint,
+        h2: &RistrettoPoint,
+    ) -> bool {
+        let a1 = g1 * self.response + h1 * self.challenge;
+        let a2 = g2 * self.response + h2 * self.challenge;
+        Proof::challenge([g1, h1, g2, h2, &a1, &a2]) == self.challenge
+    }
+
+    fn to_bytes(self) -> [u8; PROOF_SIZE] {
+        let mut bytes = [0u8; PROOF_SIZE];
+        bytes[..ELEMENT_SIZE].copy_from_slice(self.challenge.as_bytes());
+        bytes[ELEMENT_SIZE..].copy_from_slice(self.response.as_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Proof> {
+        Some(Proof {
+            challenge: scalar_from_bytes(&bytes[..ELEMENT_SIZE])?,
+            response: scalar_from_bytes(&bytes[ELEMENT_SIZE..])?,
+        })
+    }
+}
+
+/// Encrypted shares published by the dealer, along with the commitments to the polynomial coefficients
+/// and the proofs of their consistency. Can be serialized to and from a byte array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transcript {
+    // Commitments to the coefficients, degrees going from higher to lower
+    commitments: Vec<RistrettoPoint>,
+    // Encrypted share and its proof of every recipient, the `n`th one at `x = n + 1`
+    shares: Vec<(RistrettoPoint, Proof)>,
+}
+
+impl Transcript {
+    /// Returns the minimum threshold of shares the transcript was generated for.
+    pub fn threshold(&self) -> u8 {
+        self.commitments.len() as u8
+    }
+
+    /// Returns the number of recipients, the share of the `n`th one being at `x = n`.
+    pub fn recipients(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Checks the encrypted shares are consistent with the commitments, each of them being encrypted
+    /// to the corresponding public key, in the order they were given to `Sharks::dealer_pvss`.
+    pub fn verify(&self, public_keys: &[RistrettoPoint]) -> bool {
+        self.shares.len() == public_keys.len()
+            && (1..=u8::MAX)
+                .zip(self.shares.iter().zip(public_keys.iter()))
+                .all(|(x, ((encrypted, proof), public_key))| {
+                    let committed = evaluate_commitments(&self.commitments, Scalar::from(x));
+                    proof.verify(
+                        &RISTRETTO_BASEPOINT_POINT,
+                        &committed,
+                        public_key,
+                        encrypted,
+                    )
+                })
+    }
+
+    /// Decrypts the share at `x` with the `secret_key` of its recipient, proving it was correctly decrypted.
+    /// An `Err` is returned if there is no share at `x`.
+    pub fn decrypt(&self, x: u8, secret_key: &Scalar) -> Result<DecryptedShare, SharksError> {
+        let (encrypted, _) = match x {
+            0 => return Err(SharksError::ZeroShareX),
+            x => self
+                .shares
+                .get(x as usize - 1)
+                .ok_or(SharksError::InvalidLength)?,
+        };
+        let point = encrypted * secret_key.invert();
+        let proof = Proof::prove(
+            secret_key,
+            &blinding_generator(),
+            &public_key(secret_key),
+            &point,
+            encrypted,
+        );
+        Ok(DecryptedShare { x, point, proof })
+    }
+}
+
+/// Obtains a byte vector from a `Transcript` instance: `threshold || commitments.. || (share || proof)..`
+impl From<&Transcript> for Vec<u8> {
+    fn from(t: &Transcript) -> Vec<u8> {
+        let mut bytes = vec![t.threshold()];
+        for point in t.commitments.iter() {
+            bytes.extend_from_slice(point.compress().as_bytes());
+        }
+        for (share, proof) in t.shares.iter() {
+            bytes.extend_from_slice(share.compress().as_bytes());
+            bytes.extend_from_slice(&proof.to_bytes());
+        }
+        bytes
+    }
+}
+
+/// Obtains a `Transcript` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for Transcript {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<Transcript, Self::Error> {
+        let (threshold, s) = match s {
+            [] => return Err(SharksError::InvalidLength),
+            [0, ..] => return Err(SharksError::InvalidThreshold),
+            [threshold, s @ ..] => (*threshold as usize, s),
+        };
+        let share_size = ELEMENT_SIZE + PROOF_SIZE;
+        if s.len() < threshold * ELEMENT_SIZE
+            || !(s.len() - threshold * ELEMENT_SIZE).is_multiple_of(share_size)
+        {
+            return Err(SharksError::InvalidLength);
+        }
+
+        let (commitments, shares) = s.split_at(threshold * ELEMENT_SIZE);
+        let commitments = commitments
+            .chunks(ELEMENT_SIZE)
+            .map(point_from_bytes)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SharksError::InvalidElement)?;
+        let shares = shares
+            .chunks(share_size)
+            .map(|s| {
+                Some((
+                    point_from_bytes(&s[..ELEMENT_SIZE])?,
+                    Proof::from_bytes(&s[ELEMENT_SIZE..])?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SharksError::InvalidElement)?;
+        if shares.len() < threshold || shares.len() > 255 {
+            return Err(SharksError::InvalidLength);
+        }
+
+        Ok(Transcript {
+            commitments,
+            shares,
+        })
+    }
+}
+
+/// A share decrypted by its recipient, along with the proof it was correctly decrypted.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, PartialEq)]
+pub struct DecryptedShare {
+    pub x: u8,
+    point: RistrettoPoint,
+    proof: Proof,
+}
+
+/// Prints the share `x` coordinate, the decrypted share being redacted so that shares do not leak into logs.
+impl fmt::Debug for DecryptedShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecryptedShare")
+            .field("x", &self.x)
+            .field("point", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
+impl DecryptedShare {
+    /// Checks the share was correctly decrypted from the `transcript` by its recipient, given the `public_keys`
+    /// of the recipients in the order they were given to `Sharks::dealer_pvss`.
+    pub fn verify(&self, transcript: &Transcript, public_keys: &[RistrettoPoint]) -> bool {
+        let i = match self.x.checked_sub(1) {
+            Some(i) => i as usize,
+            None => return false,
+        };
+        match (transcript.shares.get(i), public_keys.get(i)) {
+            (Some((encrypted, _)), Some(public_key)) => {
+                self.proof
+                    .verify(&blinding_generator(), public_key, &self.point, encrypted)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Obtains a byte vector from a `DecryptedShare` instance: `x || share || proof`
+impl From<&DecryptedShare> for Vec<u8> {
+    fn from(s: &DecryptedShare) -> Vec<u8> {
+        let mut bytes = vec![s.x];
+        bytes.extend_from_slice(s.point.compress().as_bytes());
+        bytes.extend_from_slice(&s.proof.to_bytes());
+        bytes
+    }
+}
+
+/// Obtains a `DecryptedShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for DecryptedShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<DecryptedShare, Self::Error> {
+        let (x, s) = match s {
+            [] => return Err(SharksError::EmptyShare),
+            [0, ..] => return Err(SharksError::ZeroShareX),
+            [x, s @ ..] => (*x, s),
+        };
+        if s.len() != ELEMENT_SIZE + PROOF_SIZE {
+            return Err(SharksError::InvalidLength);
+        }
+        let point = point_from_bytes(&s[..ELEMENT_SIZE]).ok_or(SharksError::InvalidElement)?;
+        let proof = Proof::from_bytes(&s[ELEMENT_SIZE..]).ok_or(SharksError::InvalidElement)?;
+        Ok(DecryptedShare { x, point, proof })
+    }
+}
+
+impl Sharks {
+    /// Given the `public_keys` of the recipients, returns a new random secret along with the `Transcript`
+    /// to publish, holding one encrypted share for each recipient, the `n`th one at `x = n`.
+    /// An `Err` is returned if there are fewer recipients than the threshold or more than 255,
+    /// or if a public key is the identity element, to which shares cannot be encrypted.
+    pub fn dealer_pvss(
+        &self,
+        public_keys: &[RistrettoPoint],
+    ) -> Result<([u8; 32], Transcript), SharksError> {
+        if public_keys.len() < self.0 as usize || public_keys.is_empty() {
+            return Err(SharksError::TooFewShares {
+                min: (self.0 as usize).max(1),
+            });
+        } else if public_keys.len() > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        } else if public_keys.contains(&RistrettoPoint::identity()) {
+            return Err(SharksError::InvalidElement);
+        }
+
+        let s = random_scalar();
+        let poly = random_polynomial(s, self.0.max(1));
+        let commitments: Vec<RistrettoPoint> = poly.iter().map(RistrettoPoint::mul_base).collect();
+        let shares = (1..=u8::MAX)
+            .zip(public_keys.iter())
+            .map(|(x, public_key)| {
+                let y = evaluate(&poly, Scalar::from(x));
+                let encrypted = public_key * y;
+                let committed = RistrettoPoint::mul_base(&y);
+                (
+                    encrypted,
+                    Proof::prove(
+                        &y,
+                        &RISTRETTO_BASEPOINT_POINT,
+                        &committed,
+                        public_key,
+                        &encrypted,
+                    ),
+                )
+            })
+            .collect();
+
+        Ok((
+            secret(&(blinding_generator() * s)),
+            Transcript {
+                commitments,
+                shares,
+            },
+        ))
+    }
+
+    /// Given an iterable collection of decrypted shares, recovers the secret returned by `dealer_pvss`.
+    /// If the number of distinct shares is less than the minimum threshold, an `Err` is returned.
+    /// Identical copies of a share are ignored, but different shares at the same `x` return an `Err`.
+    /// Shares are not verified, use `DecryptedShare::verify` for that purpose beforehand.
+    pub fn recover_pvss<'a, T>(&self, shares: T) -> Result<[u8; 32], SharksError>
+    where
+        T: IntoIterator<Item = &'a DecryptedShare>,
+        T::IntoIter: Iterator<Item = &'a DecryptedShare>,
+    {
+        let mut distinct: Vec<&DecryptedShare> = Vec::new();
+        let mut provided = 0;
+        for s in shares {
+            provided += 1;
+            match distinct.iter().find(|d| d.x == s.x) {
+                Some(d) if d.point != s.point => return Err(SharksError::DuplicateShareX),
+                Some(_) => {}
+                None => distinct.push(s),
+            }
+        }
+        let shares = distinct;
+        if shares.len() < (self.0 as usize).max(1) {
+            return Err(SharksError::NotEnoughShares {
+                needed: (self.0 as usize).max(1),
+                got: shares.len(),
+                provided,
+            });
+        }
+
+        let xs: Vec<Scalar> = shares.iter().map(|s| Scalar::from(s.x)).collect();
+        let point: RistrettoPoint = shares
+            .iter()
+            .zip(lagrange_weights(&xs).iter())
+            .map(|(s, w)| s.point * w)
+            .sum();
+        Ok(secret(&point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{public_key, random_scalar, DecryptedShare, Transcript};
+    use crate::{Sharks, SharksError};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use curve25519_dalek::traits::Identity;
+    use std::convert::TryFrom;
+
+    fn keys(n: usize) -> (Vec<Scalar>, Vec<RistrettoPoint>) {
+        let secret_keys: Vec<Scalar> = (0..n).map(|_| random_scalar()).collect();
+        let public_keys = secret_keys.iter().map(public_key).collect();
+        (secret_keys, public_keys)
+    }
+
+    #[test]
+    fn pvss_integration_works() {
+        let sharks = Sharks(3);
+        let (secret_keys, public_keys) = keys(5);
+        let (secret, transcript) = sharks.dealer_pvss(&public_keys).unwrap();
+        assert_eq!(transcript.threshold(), 3);
+        assert_eq!(transcript.recipients(), 5);
+        assert!(transcript.verify(&public_keys));
+
+        let shares: Vec<DecryptedShare> = (1..=5)
+            .map(|x| transcript.decrypt(x, &secret_keys[x as usize - 1]).unwrap())
+            .collect();
+        for share in shares.iter() {
+            assert!(share.verify(&transcript, &public_keys));
+        }
+        assert_eq!(sharks.recover_pvss(&shares[2..]), Ok(secret));
+        assert_eq!(sharks.recover_pvss(&shares[..3]), Ok(secret));
+
+        // Serialization roundtrip
+        let bytes = Vec::from(&transcript);
+        assert_eq!(bytes.len(), 1 + 3 * 32 + 5 * 96);
+        let parsed = Transcript::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(parsed, transcript);
+        assert!(parsed.verify(&public_keys));
+        let parsed: Vec<DecryptedShare> = shares
+            .iter()
+            .map(|s| DecryptedShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert!(parsed == shares);
+        assert_eq!(sharks.recover_pvss(&parsed[1..4]), Ok(secret));
+    }
+
+    #[test]
+    fn pvss_verify_fails() {
+        let sharks = Sharks(2);
+        let (secret_keys, public_keys) = keys(3);
+        let (_, transcript) = sharks.dealer_pvss(&public_keys).unwrap();
+
+        // Shares encrypted to other recipients
+        let (_, others) = keys(3);
+        assert!(!transcript.verify(&others));
+        let swapped = [public_keys[1], public_keys[0], public_keys[2]];
+        assert!(!transcript.verify(&swapped));
+        assert!(!transcript.verify(&public_keys[..2]));
+
+        // Tampered encrypted share
+        let mut tampered = transcript.clone();
+        tampered.shares[1].0 += public_keys[1];
+        assert!(!tampered.verify(&public_keys));
+
+        // Wrongly decrypted shares
+        let share = transcript.decrypt(1, &secret_keys[1]).unwrap();
+        assert!(!share.verify(&transcript, &public_keys));
+        let mut share = transcript.decrypt(1, &secret_keys[0]).unwrap();
+        assert!(share.verify(&transcript, &public_keys));
+        assert!(!share.verify(&transcript, &swapped));
+        share.x = 2;
+        assert!(!share.verify(&transcript, &public_keys));
+        share.x = 4;
+        assert!(!share.verify(&transcript, &public_keys));
+    }
+
+    #[test]
+    fn pvss_err() {
+        let sharks = Sharks(3);
+        let (secret_keys, public_keys) = keys(3);
+        assert_eq!(
+            sharks.dealer_pvss(&public_keys[..2]).err(),
+            Some(SharksError::TooFewShares { min: 3 })
+        );
+        assert_eq!(
+            sharks
+                .dealer_pvss(&[public_keys[0], public_keys[1], RistrettoPoint::identity()])
+                .err(),
+            Some(SharksError::InvalidElement)
+        );
+
+        let (_, transcript) = sharks.dealer_pvss(&public_keys).unwrap();
+        assert_eq!(
+            transcript.decrypt(0, &secret_keys[0]).err(),
+            Some(SharksError::ZeroShareX)
+        );
+        assert!(transcript.decrypt(4, &secret_keys[0]).is_err());
+        let share = transcript.decrypt(1, &secret_keys[0]).unwrap();
+        let mut conflicting = transcript.decrypt(2, &secret_keys[1]).unwrap();
+        conflicting.x = 1;
+        assert_eq!(
+            sharks.recover_pvss(&[share.clone(), conflicting]).err(),
+            Some(SharksError::DuplicateShareX)
+        );
+        assert_eq!(
+            sharks.recover_pvss(&[share.clone(), share]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 1,
+                provided: 2
+            })
+        );
+
+        assert!(Transcript::try_from(&[][..]).is_err());
+        assert!(Transcript::try_from(&[0][..]).is_err());
+        assert!(Transcript::try_from(&[1; 33][..]).is_err());
+        assert!(DecryptedShare::try_from(&[0; 97][..]).is_err());
+        assert_eq!(
+            DecryptedShare::try_from(&[1; 10][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+    }
+}
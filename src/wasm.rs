@@ -0,0 +1,115 @@
+// JavaScript bindings through `wasm-bindgen`, exchanging secrets and shares as `Uint8Array`s.
+// They are exported by any WebAssembly module built from a crate depending on this one with the `wasm` feature,
+// for instance with `wasm-pack`. The random number generator is seeded through the Web Crypto API.
+
+use std::convert::TryFrom;
+
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// Splits `secret` into `count` shares, any `threshold` of them recovering it.
+/// Returns an `Array` of `Uint8Array`s, each holding the bytes of a share, and throws if `count` is lower than `threshold`.
+#[wasm_bindgen]
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Array, JsError> {
+    Ok(split_bytes(secret, threshold, count)?
+        .iter()
+        .map(|s| Uint8Array::from(s.as_slice()))
+        .collect())
+}
+
+/// Recovers the secret from an `Array` of `Uint8Array` shares obtained from `split`.
+/// Throws if the shares are invalid or less than `threshold`.
+#[wasm_bindgen]
+pub fn recover(shares: Array, threshold: u8) -> Result<Vec<u8>, JsError> {
+    let shares: Vec<Vec<u8>> = shares
+        .iter()
+        .map(|s| Uint8Array::new(&s).to_vec())
+        .collect();
+    Ok(recover_bytes(&shares, threshold)?)
+}
+
+/// A share, parsed and validated from its bytes.
+#[wasm_bindgen(js_name = Share)]
+pub struct JsShare(Share);
+
+#[wasm_bindgen(js_class = Share)]
+impl JsShare {
+    /// Parses a share from its bytes, throwing if they are not valid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8]) -> Result<JsShare, JsError> {
+        Ok(JsShare(Share::try_from(bytes)?))
+    }
+
+    /// The `x` coordinate of the share.
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> u8 {
+        self.0.x.0
+    }
+
+    /// The `y` values of the share, one per secret byte.
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> Vec<u8> {
+        self.0.y.iter().map(|y| y.0).collect()
+    }
+
+    /// Returns the bytes of the share, as accepted by `recover`.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Vec::from(&self.0)
+    }
+}
+
+fn split_bytes(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Vec<u8>>, SharksError> {
+    if count < threshold {
+        return Err(SharksError::TooFewShares {
+            min: threshold as usize,
+        });
+    }
+    Ok(Sharks(threshold)
+        .dealer(secret)
+        .take(count as usize)
+        .map(|s| Vec::from(&s))
+        .collect())
+}
+
+fn recover_bytes(shares: &[Vec<u8>], threshold: u8) -> Result<Vec<u8>, SharksError> {
+    let shares = shares
+        .iter()
+        .map(|s| Share::try_from(s.as_slice()))
+        .collect::<Result<Vec<Share>, SharksError>>()?;
+    Sharks(threshold).recover(&shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recover_bytes, split_bytes, JsShare};
+    use crate::SharksError;
+
+    #[test]
+    fn bytes_integration_works() {
+        let shares = split_bytes(&[1, 2, 3], 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(recover_bytes(&shares[2..], 3).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            recover_bytes(&shares[3..], 3).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        assert!(split_bytes(&[1], 3, 2).is_err());
+    }
+
+    #[test]
+    fn share_accessors_work() {
+        let share = JsShare::new(&[4, 5, 6]).unwrap();
+        assert_eq!(share.x(), 4);
+        assert_eq!(share.y(), vec![5, 6]);
+        assert_eq!(share.to_bytes(), vec![4, 5, 6]);
+    }
+}
@@ -0,0 +1,147 @@
+// Weighted participants, each one holding as many regular shares as its weight, bundled together.
+// A participant of weight `w` counts as `w` participants towards the threshold.
+
+use std::convert::TryFrom;
+
+use super::error::SharksError;
+use super::share::Share;
+use super::Sharks;
+
+/// A bundle of shares held by a single weighted participant, see `Sharks::dealer_weighted`.
+/// Can be serialized to and from a byte array.
+#[derive(Clone, Debug)]
+pub struct WeightedShare {
+    /// Shares of the participant, as many as its weight.
+    pub shares: Vec<Share>,
+}
+
+impl WeightedShare {
+    /// Returns the weight of the participant, the number of shares it holds.
+    pub fn weight(&self) -> usize {
+        self.shares.len()
+    }
+}
+
+/// Obtains a byte vector from a `WeightedShare` instance: `weight || shares`,
+/// being the shares serialized one after another.
+impl From<&WeightedShare> for Vec<u8> {
+    fn from(s: &WeightedShare) -> Vec<u8> {
+        let mut bytes = vec![s.shares.len() as u8];
+        s.shares.iter().for_each(|s| bytes.extend(Vec::from(s)));
+        bytes
+    }
+}
+
+/// Obtains a `WeightedShare` instance from a byte slice, validating its contents.
+impl TryFrom<&[u8]> for WeightedShare {
+    type Error = SharksError;
+
+    fn try_from(s: &[u8]) -> Result<WeightedShare, Self::Error> {
+        let (weight, s) = s.split_first().ok_or(SharksError::EmptyShare)?;
+        if *weight == 0 || s.is_empty() || !s.len().is_multiple_of(*weight as usize) {
+            return Err(SharksError::InvalidLength);
+        }
+        let shares = s
+            .chunks(s.len() / *weight as usize)
+            .map(Share::try_from)
+            .collect::<Result<Vec<Share>, SharksError>>()?;
+        Ok(WeightedShare { shares })
+    }
+}
+
+impl Sharks {
+    /// Given a `secret` byte slice and the `weights` of the participants, returns their bundles of shares,
+    /// each participant counting as many times as its weight towards the threshold.
+    /// An `Err` is returned if a weight is zero, or the weights add up to more than 255 shares.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, WeightedShare };
+    /// let sharks = Sharks(5);
+    /// // The first participant counts as 3, the others as 1
+    /// let shares = sharks.dealer_weighted(&[1, 2, 3], &[3, 1, 1, 1]).unwrap();
+    /// assert_eq!(sharks.recover_weighted(&shares[..3]).unwrap(), vec![1, 2, 3]);
+    /// assert!(sharks.recover_weighted(&shares[1..]).is_err());
+    /// ```
+    pub fn dealer_weighted(
+        &self,
+        secret: &[u8],
+        weights: &[u8],
+    ) -> Result<Vec<WeightedShare>, SharksError> {
+        if weights.contains(&0) {
+            return Err(SharksError::InvalidWeight);
+        }
+        let total: usize = weights.iter().map(|w| *w as usize).sum();
+        if total > 255 {
+            return Err(SharksError::TooManyShares { max: 255 });
+        }
+
+        let mut dealer = self.dealer(secret);
+        Ok(weights
+            .iter()
+            .map(|w| WeightedShare {
+                shares: dealer.by_ref().take(*w as usize).collect(),
+            })
+            .collect())
+    }
+
+    /// Given an iterable collection of weighted shares, recovers the original secret.
+    /// Works the same way as `recover`, with the threshold applying to the sum of the weights.
+    pub fn recover_weighted<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a WeightedShare>,
+        T::IntoIter: Iterator<Item = &'a WeightedShare>,
+    {
+        self.recover(shares.into_iter().flat_map(|s| s.shares.iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeightedShare;
+    use crate::{Sharks, SharksError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn weighted_integration_works() {
+        let sharks = Sharks(5);
+        let secret: Vec<u8> = (0..20).collect();
+        let shares = sharks.dealer_weighted(&secret, &[3, 2, 1, 1, 1]).unwrap();
+        assert_eq!(shares[1].weight(), 2);
+
+        assert_eq!(sharks.recover_weighted(&shares[..2]).unwrap(), secret);
+        assert_eq!(sharks.recover_weighted(&shares[1..]).unwrap(), secret);
+        assert_eq!(
+            sharks.recover_weighted(&shares[2..]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 5,
+                got: 3,
+                provided: 3
+            })
+        );
+
+        let shares: Vec<WeightedShare> = shares
+            .iter()
+            .map(|s| WeightedShare::try_from(Vec::from(s).as_slice()).unwrap())
+            .collect();
+        assert_eq!(sharks.recover_weighted(&shares[..2]).unwrap(), secret);
+    }
+
+    #[test]
+    fn weighted_errors_work() {
+        let sharks = Sharks(2);
+        assert_eq!(
+            sharks.dealer_weighted(&[1], &[1, 0]).err(),
+            Some(SharksError::InvalidWeight)
+        );
+        assert_eq!(
+            sharks.dealer_weighted(&[1], &[200, 56]).err(),
+            Some(SharksError::TooManyShares { max: 255 })
+        );
+        assert_eq!(
+            WeightedShare::try_from(&[2, 1, 2, 3][..]).err(),
+            Some(SharksError::InvalidLength)
+        );
+        assert!(WeightedShare::try_from(&[2, 1, 2, 3, 4][..]).is_ok());
+    }
+}
@@ -0,0 +1,174 @@
+// Trivial n-of-n secret sharing, when every share is needed to recover the secret.
+// All the shares but the last one hold uniformly random pads, and the last one the secret XORed with all of them,
+// so that splitting and recovering only take XORs instead of evaluating and interpolating polynomials.
+// Shares keep the regular format, at the `x` coordinates 1 to n, and their `y` values are the pads themselves,
+// interoperating with simple XOR splitters. Over the binary fields used for secrets, XOR is the field addition.
+
+use rand::{CryptoRng, Rng, RngCore};
+use zeroize::Zeroize;
+
+use super::error::SharksError;
+use super::field::{Field, GF256};
+use super::math;
+use super::share::{FieldShare, Share};
+use super::Sharks;
+
+impl Sharks {
+    /// Given a `secret` byte slice, returns exactly threshold new shares, all of them being needed to recover it
+    /// with `recover_xor`. Each share is as large as the secret, and is dealt with XORs only,
+    /// without the overhead of Shamir polynomials.
+    ///
+    /// Example:
+    /// ```
+    /// # use sharks::{ Sharks, Share };
+    /// let sharks = Sharks(3);
+    /// let shares: Vec<Share> = sharks.dealer_xor(&[1, 2, 3]);
+    /// assert_eq!(shares.len(), 3);
+    /// assert_eq!(sharks.recover_xor(&shares).unwrap(), vec![1, 2, 3]);
+    ///
+    /// // The secret is the XOR of the `y` values
+    /// let xored: Vec<u8> = (0..3).map(|i| shares.iter().fold(0, |acc, s| acc ^ s.y[i].0)).collect();
+    /// assert_eq!(xored, vec![1, 2, 3]);
+    /// assert!(sharks.recover_xor(&shares[1..]).is_err());
+    /// ```
+    pub fn dealer_xor(&self, secret: &[u8]) -> Vec<Share> {
+        self.dealer_xor_with_rng(&mut rand::thread_rng(), secret)
+    }
+
+    /// Same as `dealer_xor`, generating the pads with the `rng` random number generator
+    /// instead of the thread local one.
+    pub fn dealer_xor_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        secret: &[u8],
+    ) -> Vec<Share> {
+        self.deal_xor(math::encode_secret::<GF256>(secret), rng)
+    }
+
+    /// Given the shares generated by `dealer_xor`, recovers the original secret by XORing them.
+    /// An `Err` is returned if there are fewer distinct shares than the threshold, or more of them,
+    /// and in the same cases as `recover` if they are inconsistent.
+    pub fn recover_xor<'a, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        T: IntoIterator<Item = &'a Share>,
+        T::IntoIter: Iterator<Item = &'a Share>,
+    {
+        self.recover_xor_in(shares)
+    }
+
+    // Deals one pad per share but the last one, over any binary field `F`
+    pub(crate) fn deal_xor<F: Field, R: Rng + ?Sized>(
+        &self,
+        chunks: Vec<F>,
+        rng: &mut R,
+    ) -> Vec<FieldShare<F>> {
+        let n = self.0 as usize;
+        let mut last = chunks;
+        let mut shares: Vec<FieldShare<F>> = (1..n)
+            .map(|i| {
+                let y: Vec<F> = (0..last.len()).map(|_| math::random_element(rng)).collect();
+                last.iter_mut()
+                    .zip(y.iter())
+                    .for_each(|(l, p)| *l = *l + *p);
+                FieldShare {
+                    x: F::from_index(i).expect("up to 255 shares are dealt"),
+                    y,
+                    threshold: Some(self.0),
+                    group: None,
+                }
+            })
+            .collect();
+        if n > 0 {
+            shares.push(FieldShare {
+                x: F::from_index(n).expect("up to 255 shares are dealt"),
+                y: last,
+                threshold: Some(self.0),
+                group: None,
+            });
+        }
+        shares
+    }
+
+    // Recovers a secret split by `deal_xor` over any binary field `F`
+    pub(crate) fn recover_xor_in<'a, F, T>(&self, shares: T) -> Result<Vec<u8>, SharksError>
+    where
+        F: Field + 'a,
+        T: IntoIterator<Item = &'a FieldShare<F>>,
+    {
+        let shares = self.distinct(shares, self.0 as usize)?;
+        if shares.len() > self.0 as usize {
+            return Err(SharksError::TooManyShares {
+                max: self.0 as usize,
+            });
+        }
+        let mut sum = vec![F::ZERO; shares[0].y.len()];
+        for s in shares {
+            sum.iter_mut()
+                .zip(s.y.iter())
+                .for_each(|(a, y)| *a = *a + *y);
+        }
+        let secret = math::decode_secret(&sum);
+        sum.zeroize();
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldShare, Share, Sharks, SharksError, GF256, GF2_64};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn xor_integration_works() {
+        let mut rng = ChaCha8Rng::from_seed([9; 32]);
+        for threshold in 1..6 {
+            let sharks = Sharks(threshold);
+            let secret: Vec<u8> = (0..50).collect();
+            let shares = sharks.dealer_xor_with_rng(&mut rng, &secret);
+            assert_eq!(shares.len(), threshold as usize);
+            assert_eq!(shares[threshold as usize - 1].x, GF256(threshold));
+            assert_eq!(shares[0].threshold, Some(threshold));
+            assert_eq!(sharks.recover_xor(shares.iter().rev()).unwrap(), secret);
+        }
+
+        // A single share is the secret itself, as with Shamir
+        let shares = Sharks(1).dealer_xor(&[1, 2, 3]);
+        assert_eq!(Sharks(1).recover(&shares).unwrap(), vec![1, 2, 3]);
+
+        let sharks = Sharks(4);
+        let wide: Vec<FieldShare<GF2_64>> =
+            sharks.deal_xor(crate::math::encode_secret(&[5; 20]), &mut rng);
+        assert_eq!(wide[0].y.len(), 3);
+        assert_eq!(sharks.recover_xor_in(&wide).unwrap(), vec![5; 20]);
+    }
+
+    #[test]
+    fn xor_err() {
+        let sharks = Sharks(3);
+        let mut shares: Vec<Share> = sharks.dealer_xor(&[1, 2, 3]);
+        assert_eq!(
+            sharks.recover_xor(&shares[..2]).err(),
+            Some(SharksError::NotEnoughShares {
+                needed: 3,
+                got: 2,
+                provided: 2
+            })
+        );
+        let mut extra = shares[0].clone();
+        extra.x = GF256(4);
+        shares.push(extra);
+        assert_eq!(
+            sharks.recover_xor(&shares).err(),
+            Some(SharksError::TooManyShares { max: 3 })
+        );
+        assert_eq!(
+            Sharks(2).recover_xor(&shares[..2]).err(),
+            Some(SharksError::ThresholdMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert!(Sharks(0).dealer_xor(&[1]).is_empty());
+    }
+}